@@ -7,6 +7,13 @@ use crate::commands::acp::run_acp_agent;
 use crate::commands::bench::agent_generator;
 use crate::commands::configure::handle_configure;
 use crate::commands::info::handle_info;
+use crate::commands::memory::{
+    handle_memory_export, handle_memory_import, handle_memory_list, handle_memory_prune,
+};
+use crate::commands::permissions::{
+    handle_permissions_clear, handle_permissions_list, handle_permissions_revoke,
+    handle_permissions_set, handle_permissions_suggest,
+};
 use crate::commands::project::{handle_project_default, handle_projects_interactive};
 use crate::commands::recipe::{handle_deeplink, handle_list, handle_open, handle_validate};
 // Import the new handlers from commands::schedule
@@ -218,15 +225,32 @@ enum SessionCommand {
             short,
             long,
             help = "Output file path (default: stdout)",
-            long_help = "Path to save the exported Markdown. If not provided, output will be sent to stdout"
+            long_help = "Path to save the exported session. If not provided, output will be sent to stdout"
         )]
         output: Option<PathBuf>,
 
         #[arg(
             long = "format",
             value_name = "FORMAT",
-            help = "Output format (markdown, json, yaml)",
-            default_value = "markdown"
+            help = "Output format (markdown, json, yaml, html, jsonl)",
+            long_help = "Output format. 'html' writes a self-contained page with collapsible tool calls; 'jsonl' writes the conversation as an OpenAI chat-completions transcript, e.g. for fine-tuning datasets.",
+            default_value = "markdown",
+            value_parser = clap::builder::PossibleValuesParser::new(["markdown", "json", "yaml", "html", "jsonl"])
+        )]
+        format: String,
+    },
+    #[command(about = "Show the plan made with /plan in a session and its progress")]
+    Plan {
+        /// Session ID to show the plan of
+        #[arg(value_name = "SESSION_ID")]
+        session_id: String,
+
+        #[arg(
+            long = "format",
+            value_name = "FORMAT",
+            help = "Output format (text, markdown, json)",
+            default_value = "text",
+            value_parser = clap::builder::PossibleValuesParser::new(["text", "markdown", "json"])
         )]
         format: String,
     },
@@ -347,6 +371,180 @@ pub enum BenchCommand {
     },
 }
 
+#[derive(Subcommand)]
+enum MemoryCommand {
+    /// List stored memories
+    #[command(about = "List stored memories")]
+    List {
+        #[arg(
+            short,
+            long,
+            help = "Only list global memories",
+            conflicts_with = "local"
+        )]
+        global: bool,
+
+        #[arg(short, long, help = "Only list memories for the current directory")]
+        local: bool,
+
+        #[arg(long, help = "Only list memories in this category")]
+        category: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(
+            long = "format",
+            value_name = "FORMAT",
+            help = "Output format (text, json)",
+            default_value = "text"
+        )]
+        format: String,
+    },
+
+    /// Export memories to a file that can be imported on another machine
+    #[command(about = "Export memories to a shareable file")]
+    Export {
+        #[arg(
+            short,
+            long,
+            help = "Export global memories instead of the current directory's"
+        )]
+        global: bool,
+
+        #[arg(long, help = "Only export memories in this category")]
+        category: Option<String>,
+
+        #[arg(
+            long = "tag",
+            value_name = "TAG",
+            help = "Only export memories with this tag (can be repeated)",
+            action = clap::ArgAction::Append
+        )]
+        tags: Vec<String>,
+
+        #[arg(short, long, help = "Output file path (default: stdout)")]
+        output: Option<PathBuf>,
+    },
+
+    /// Import memories from an exported file
+    #[command(about = "Import memories from an exported file")]
+    Import {
+        #[arg(help = "Exported memory file, or - for stdin")]
+        input: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            help = "Import into global memories instead of the current directory's"
+        )]
+        global: bool,
+    },
+
+    /// Remove stale or duplicate memories
+    #[command(about = "Remove stale or duplicate memories")]
+    Prune {
+        #[arg(
+            short,
+            long,
+            help = "Prune global memories instead of the current directory's"
+        )]
+        global: bool,
+
+        #[arg(
+            long = "older-than",
+            value_name = "DAYS",
+            help = "Remove memories not updated in this many days"
+        )]
+        older_than: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Remove duplicate memories, keeping the most recently updated copy"
+        )]
+        duplicates: bool,
+
+        #[arg(long, help = "Only prune memories in this category")]
+        category: Option<String>,
+
+        #[arg(long, help = "Show what would be removed without removing it")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PermissionsCommand {
+    /// List stored tool permissions and recorded decisions
+    #[command(about = "List stored tool permission levels, rules and recorded decisions")]
+    List {
+        /// Only show this tool
+        #[arg(
+            value_name = "TOOL",
+            help = "Only show this tool, with all of its decisions"
+        )]
+        tool: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(
+            long = "format",
+            value_name = "FORMAT",
+            help = "Output format (text, json)",
+            default_value = "text"
+        )]
+        format: String,
+    },
+
+    /// Forget everything stored for a tool
+    #[command(about = "Forget the permission level, rules and recorded decisions for a tool")]
+    Revoke {
+        #[arg(value_name = "TOOL", help = "Tool name, e.g. developer__shell")]
+        tool: String,
+    },
+
+    /// Set the permission level for a tool
+    #[command(about = "Set the permission level for a tool")]
+    Set {
+        #[arg(value_name = "TOOL", help = "Tool name, e.g. developer__shell")]
+        tool: String,
+
+        #[arg(
+            value_name = "LEVEL",
+            help = "Permission level",
+            value_parser = clap::builder::PossibleValuesParser::new(["always_allow", "ask_before", "never_allow"])
+        )]
+        level: String,
+    },
+
+    /// Forget every stored permission
+    #[command(about = "Forget every stored tool permission level, rule and recorded decision")]
+    Clear {
+        #[arg(short, long, help = "Clear without asking for confirmation")]
+        yes: bool,
+    },
+
+    /// Suggest allow rules learned from the tool calls you approved
+    #[command(about = "Suggest allow rules learned from the tool calls you approved")]
+    Suggest {
+        #[arg(
+            long = "min-approvals",
+            value_name = "COUNT",
+            help = "Approvals of similar calls needed before a rule is suggested",
+            default_value_t = goose::permission::permission_suggestions::DEFAULT_MIN_APPROVALS
+        )]
+        min_approvals: usize,
+
+        #[arg(short, long, help = "Save every suggested rule without asking")]
+        yes: bool,
+
+        /// Output format (text, json)
+        #[arg(
+            long = "format",
+            value_name = "FORMAT",
+            help = "Output format (text, json); json lists the suggestions without saving them",
+            default_value = "text"
+        )]
+        format: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum RecipeCommand {
     /// Validate a recipe file
@@ -720,13 +918,14 @@ enum Command {
         )]
         additional_sub_recipes: Vec<String>,
 
-        /// Output format (text, json)
+        /// Output format (text, json, stream-json)
         #[arg(
             long = "output-format",
             value_name = "FORMAT",
-            help = "Output format (text, json)",
+            help = "Output format (text, json, stream-json)",
+            long_help = "Output format. 'json' prints the whole conversation once the run finishes; 'stream-json' prints one JSON event per line as the run progresses.",
             default_value = "text",
-            value_parser = clap::builder::PossibleValuesParser::new(["text", "json"])
+            value_parser = clap::builder::PossibleValuesParser::new(["text", "json", "stream-json"])
         )]
         output_format: String,
 
@@ -756,6 +955,20 @@ enum Command {
         command: RecipeCommand,
     },
 
+    /// Manage memories saved by the memory extension
+    #[command(about = "Manage memories saved by the memory extension")]
+    Memory {
+        #[command(subcommand)]
+        command: MemoryCommand,
+    },
+
+    /// Manage stored tool permissions
+    #[command(about = "Manage stored tool permissions")]
+    Permissions {
+        #[command(subcommand)]
+        command: PermissionsCommand,
+    },
+
     /// Manage scheduled jobs
     #[command(about = "Manage scheduled jobs", visible_alias = "sched")]
     Schedule {
@@ -859,6 +1072,8 @@ pub async fn cli() -> anyhow::Result<()> {
         Some(Command::Update { .. }) => "update",
         Some(Command::Bench { .. }) => "bench",
         Some(Command::Recipe { .. }) => "recipe",
+        Some(Command::Memory { .. }) => "memory",
+        Some(Command::Permissions { .. }) => "permissions",
         Some(Command::Web { .. }) => "web",
         None => "default_session",
     };
@@ -933,6 +1148,10 @@ pub async fn cli() -> anyhow::Result<()> {
                     .await?;
                     Ok(())
                 }
+                Some(SessionCommand::Plan { session_id, format }) => {
+                    crate::commands::session::handle_session_plan(&session_id, &format).await?;
+                    Ok(())
+                }
                 Some(SessionCommand::Diagnostics { session_id, output }) => {
                     crate::commands::session::handle_diagnostics(&session_id, output).await?;
                     Ok(())
@@ -1340,6 +1559,63 @@ pub async fn cli() -> anyhow::Result<()> {
             }
             return Ok(());
         }
+        Some(Command::Memory { command }) => {
+            match command {
+                MemoryCommand::List {
+                    global,
+                    local,
+                    category,
+                    format,
+                } => {
+                    handle_memory_list(global, local, category, &format)?;
+                }
+                MemoryCommand::Export {
+                    global,
+                    category,
+                    tags,
+                    output,
+                } => {
+                    handle_memory_export(global, category, tags, output)?;
+                }
+                MemoryCommand::Import { input, global } => {
+                    handle_memory_import(input, global)?;
+                }
+                MemoryCommand::Prune {
+                    global,
+                    older_than,
+                    duplicates,
+                    category,
+                    dry_run,
+                } => {
+                    handle_memory_prune(global, older_than, duplicates, category, dry_run)?;
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Permissions { command }) => {
+            match command {
+                PermissionsCommand::List { tool, format } => {
+                    handle_permissions_list(tool, &format)?;
+                }
+                PermissionsCommand::Revoke { tool } => {
+                    handle_permissions_revoke(&tool)?;
+                }
+                PermissionsCommand::Set { tool, level } => {
+                    handle_permissions_set(&tool, &level)?;
+                }
+                PermissionsCommand::Clear { yes } => {
+                    handle_permissions_clear(yes)?;
+                }
+                PermissionsCommand::Suggest {
+                    min_approvals,
+                    yes,
+                    format,
+                } => {
+                    handle_permissions_suggest(min_approvals, yes, &format)?;
+                }
+            }
+            return Ok(());
+        }
         Some(Command::Web {
             port,
             host,
@@ -557,6 +557,7 @@ impl acp::Agent for GooseAcpAgent {
             schedule_id: None,
             max_turns: None,
             retry_config: None,
+            budget: None,
         };
 
         // Get agent's reply through the Goose agent
@@ -758,6 +758,11 @@ pub fn configure_extensions_dialog() -> anyhow::Result<()> {
                     "Computer Controller",
                     "controls for webscraping, file caching, and automations",
                 ),
+                (
+                    "data",
+                    "Data",
+                    "Load CSV, JSON and Parquet files and query them with SQL",
+                ),
                 (
                     "developer",
                     "Developer Tools",
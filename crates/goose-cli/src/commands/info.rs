@@ -2,6 +2,7 @@ use anyhow::Result;
 use console::style;
 use goose::config::paths::Paths;
 use goose::config::Config;
+use goose::prompt_template;
 use serde_yaml;
 
 fn print_aligned(label: &str, value: &str, width: usize) {
@@ -18,11 +19,14 @@ pub fn handle_info(verbose: bool) -> Result<()> {
     let config_dir = Paths::config_dir().display().to_string();
 
     // Define the labels and their corresponding path values once.
-    let paths = [
+    let mut paths = vec![
         ("Config dir:", config_dir),
         ("Sessions DB (sqlite):", sessions_db.display().to_string()),
         ("Logs dir:", logs_dir.display().to_string()),
     ];
+    if let Some(project_config) = config.project_path() {
+        paths.push(("Project config:", project_config));
+    }
 
     // Calculate padding: use the max length of the label plus extra space.
     let basic_padding = paths.iter().map(|(l, _)| l.len()).max().unwrap_or(0) + 4;
@@ -41,7 +45,7 @@ pub fn handle_info(verbose: bool) -> Result<()> {
     // Print verbose info if requested
     if verbose {
         println!("\n{}", style("goose Configuration:").cyan().bold());
-        let values = config.all_values()?;
+        let values = config.all_values_with_layers()?;
         if values.is_empty() {
             println!("  No configuration values set");
             println!(
@@ -49,15 +53,39 @@ pub fn handle_info(verbose: bool) -> Result<()> {
                 style("goose configure").cyan()
             );
         } else {
-            let sorted_values: std::collections::BTreeMap<_, _> =
-                values.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-
-            if let Ok(yaml) = serde_yaml::to_string(&sorted_values) {
-                for line in yaml.lines() {
-                    println!("  {}", line);
+            // Values are already sorted by key; annotate each with the layer it came from.
+            for (key, (value, layer)) in &values {
+                let entry = std::collections::BTreeMap::from([(key, value)]);
+                if let Ok(yaml) = serde_yaml::to_string(&entry) {
+                    for (i, line) in yaml.lines().enumerate() {
+                        if i == 0 {
+                            println!("  {}  {}", line, style(format!("[{}]", layer)).dim());
+                        } else {
+                            println!("  {}", line);
+                        }
+                    }
                 }
             }
         }
+
+        println!("\n{}", style("goose Prompt Templates:").cyan().bold());
+        let overrides =
+            prompt_template::template_overrides(&std::env::current_dir().unwrap_or_default());
+        if overrides.is_empty() {
+            println!("  Using built-in templates");
+        }
+        for template in overrides {
+            let status = match &template.error {
+                Some(error) => style(format!("[ignored: {}]", error)).yellow(),
+                None => style("[overridden]".to_string()).dim(),
+            };
+            println!(
+                "  {}  {}  {}",
+                template.name,
+                template.path.display(),
+                status
+            );
+        }
     }
 
     Ok(())
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use goose_mcp::memory::store::{Memory, MemoryFile, MemoryStore, PruneOptions};
+use goose_mcp::memory::{global_memory_dir, local_memory_dir};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+fn memory_dir(global: bool) -> Result<PathBuf> {
+    if global {
+        Ok(global_memory_dir())
+    } else {
+        Ok(local_memory_dir(&std::env::current_dir()?))
+    }
+}
+
+fn scope_name(global: bool) -> &'static str {
+    if global {
+        "global"
+    } else {
+        "local"
+    }
+}
+
+fn open_store(global: bool) -> Result<MemoryStore> {
+    let dir = memory_dir(global)?;
+    MemoryStore::open(&dir)
+        .with_context(|| format!("Failed to open memory store in {}", dir.display()))
+}
+
+fn print_memory(memory: &Memory) {
+    let tags = if memory.tags.is_empty() {
+        String::new()
+    } else {
+        format!(" #{}", memory.tags.join(" #"))
+    };
+    println!(
+        "  {} [{}] {}{} (updated {})",
+        memory.id,
+        memory.category,
+        memory.content.replace('\n', " "),
+        tags,
+        memory.updated_at.format("%Y-%m-%d")
+    );
+}
+
+/// List memories from the local store, the global store, or both.
+pub fn handle_memory_list(
+    global: bool,
+    local: bool,
+    category: Option<String>,
+    format: &str,
+) -> Result<()> {
+    let scopes: Vec<bool> = match (global, local) {
+        (true, false) => vec![true],
+        (false, true) => vec![false],
+        _ => vec![true, false],
+    };
+
+    let mut listed = Vec::new();
+    for is_global in scopes {
+        let store = open_store(is_global)?;
+        let memories: Vec<Memory> = store
+            .memories()
+            .iter()
+            .filter(|m| category.as_deref().is_none_or(|c| m.category == c))
+            .cloned()
+            .collect();
+        listed.push((is_global, memories));
+    }
+
+    if format == "json" {
+        let json: serde_json::Map<String, serde_json::Value> = listed
+            .into_iter()
+            .map(|(is_global, memories)| {
+                Ok((
+                    scope_name(is_global).to_string(),
+                    serde_json::to_value(memories)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    for (is_global, memories) in listed {
+        println!(
+            "{} memories ({}):",
+            if is_global { "Global" } else { "Local" },
+            memory_dir(is_global)?.display()
+        );
+        if memories.is_empty() {
+            println!("  (none)");
+        }
+        for memory in &memories {
+            print_memory(memory);
+        }
+    }
+    Ok(())
+}
+
+/// Write memories in the versioned store format so they can be imported elsewhere.
+pub fn handle_memory_export(
+    global: bool,
+    category: Option<String>,
+    tags: Vec<String>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let store = open_store(global)?;
+    let memories: Vec<Memory> = store
+        .memories()
+        .iter()
+        .filter(|m| category.as_deref().is_none_or(|c| m.category == c))
+        .filter(|m| tags.iter().all(|tag| m.has_tag(tag)))
+        .cloned()
+        .collect();
+    let count = memories.len();
+    let json = MemoryFile::new(memories).to_json()?;
+
+    if let Some(output) = output {
+        fs::write(&output, json)
+            .with_context(|| format!("Failed to write to output file: {}", output.display()))?;
+        println!(
+            "Exported {} {} memories to {}",
+            count,
+            scope_name(global),
+            output.display()
+        );
+    } else {
+        println!("{}", json);
+    }
+    Ok(())
+}
+
+/// Merge an exported memory file into the local or global store. `-` reads stdin.
+pub fn handle_memory_import(input: PathBuf, global: bool) -> Result<()> {
+    let json = if input.as_os_str() == "-" {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        fs::read_to_string(&input).with_context(|| format!("Failed to read {}", input.display()))?
+    };
+    let file = MemoryFile::from_json(&json).context("Invalid memory export")?;
+
+    let summary = open_store(global)?.import(file.memories)?;
+    println!(
+        "Imported into {} memories: {} added, {} updated, {} skipped",
+        scope_name(global),
+        summary.added,
+        summary.updated,
+        summary.skipped
+    );
+    Ok(())
+}
+
+/// Remove stale or duplicate memories.
+pub fn handle_memory_prune(
+    global: bool,
+    older_than_days: Option<u32>,
+    duplicates: bool,
+    category: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    if older_than_days.is_none() && !duplicates {
+        return Err(anyhow::anyhow!(
+            "Nothing to prune: pass --older-than and/or --duplicates"
+        ));
+    }
+
+    let options = PruneOptions {
+        updated_before: older_than_days
+            .map(|days| Utc::now() - chrono::Duration::days(i64::from(days))),
+        duplicates,
+        category: category.as_deref(),
+    };
+    let pruned = open_store(global)?.prune(&options, dry_run)?;
+
+    if pruned.is_empty() {
+        println!("No {} memories to prune", scope_name(global));
+        return Ok(());
+    }
+    println!(
+        "{} {} {} memories:",
+        if dry_run { "Would remove" } else { "Removed" },
+        pruned.len(),
+        scope_name(global)
+    );
+    for memory in &pruned {
+        print_memory(memory);
+    }
+    Ok(())
+}
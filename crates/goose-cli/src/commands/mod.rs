@@ -2,6 +2,8 @@ pub mod acp;
 pub mod bench;
 pub mod configure;
 pub mod info;
+pub mod memory;
+pub mod permissions;
 pub mod project;
 pub mod recipe;
 pub mod schedule;
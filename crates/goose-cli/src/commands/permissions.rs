@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use goose::config::permission::PermissionLevel;
+use goose::config::PermissionManager;
+use goose::permission::permission_store::ToolPermissionRecord;
+use goose::permission::{
+    suggest_permissions, summarize_tool_permissions, PermissionSuggestion, ToolPermissionStore,
+};
+use goose::utils::safe_truncate;
+
+/// Recent decisions shown per tool when listing every tool
+const RECENT_DECISIONS: usize = 5;
+const MAX_ARGUMENTS_LENGTH: usize = 80;
+
+fn level_name(level: &PermissionLevel) -> &'static str {
+    match level {
+        PermissionLevel::AlwaysAllow => "always_allow",
+        PermissionLevel::AskBefore => "ask_before",
+        PermissionLevel::NeverAllow => "never_allow",
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn format_expiry(expiry: Option<i64>, now: i64) -> String {
+    let Some(expiry) = expiry else {
+        return "never expires".to_string();
+    };
+    let remaining = expiry - now;
+    if remaining >= 86400 {
+        format!(
+            "expires in {}d {}h",
+            remaining / 86400,
+            remaining % 86400 / 3600
+        )
+    } else if remaining >= 3600 {
+        format!(
+            "expires in {}h {}m",
+            remaining / 3600,
+            remaining % 3600 / 60
+        )
+    } else {
+        format!("expires in {}m", (remaining / 60).max(1))
+    }
+}
+
+fn format_decision(record: &ToolPermissionRecord, now: i64) -> String {
+    let arguments = record
+        .arguments()
+        .and_then(|arguments| serde_json::to_string(&arguments).ok())
+        .unwrap_or_default();
+    format!(
+        "{}  {:<7}  {:<19}  {}",
+        format_timestamp(record.timestamp),
+        if record.allowed { "allowed" } else { "denied" },
+        format_expiry(record.expiry, now),
+        safe_truncate(&arguments, MAX_ARGUMENTS_LENGTH)
+    )
+}
+
+/// Show the stored levels, allow rules and recorded decisions, for one tool or all of them
+pub fn handle_permissions_list(tool: Option<String>, format: &str) -> Result<()> {
+    let store = ToolPermissionStore::load()?;
+    let permission_manager = PermissionManager::default();
+    let mut summaries = summarize_tool_permissions(&store, &permission_manager);
+    if let Some(tool) = &tool {
+        summaries.retain(|summary| &summary.tool == tool);
+    }
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
+
+    if summaries.is_empty() {
+        match tool {
+            Some(tool) => println!("No permissions stored for {}", tool),
+            None => println!("No tool permissions stored yet"),
+        }
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    for summary in &summaries {
+        println!("{}", summary.tool);
+        if let Some(level) = &summary.user_level {
+            println!("  level: {}", level_name(level));
+        }
+        if let Some(level) = &summary.smart_approve_level {
+            println!("  smart approve: {}", level_name(level));
+        }
+        for rule in &summary.allow_rules {
+            println!("  allow when {} matches {}", rule.argument, rule.pattern);
+        }
+        if !summary.decisions.is_empty() {
+            let allowed = summary.decisions.iter().filter(|r| r.allowed).count();
+            println!(
+                "  decisions: {} allowed, {} denied",
+                allowed,
+                summary.decisions.len() - allowed
+            );
+            let shown = if tool.is_some() {
+                summary.decisions.len()
+            } else {
+                RECENT_DECISIONS
+            };
+            let skipped = summary.decisions.len().saturating_sub(shown);
+            for record in summary.decisions.iter().skip(skipped) {
+                println!("    {}", format_decision(record, now));
+            }
+            if skipped > 0 {
+                println!(
+                    "    ... and {} earlier; run `goose permissions list {}` to see them",
+                    skipped, summary.tool
+                );
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Forget the level, allow rules and recorded decisions stored for a tool
+pub fn handle_permissions_revoke(tool: &str) -> Result<()> {
+    let mut permission_manager = PermissionManager::default();
+    let removed_level = permission_manager.remove_tool(tool);
+    let removed_decisions = ToolPermissionStore::load()?.remove_tool(tool)?;
+
+    if !removed_level && removed_decisions == 0 {
+        println!("No permissions stored for {}", tool);
+        return Ok(());
+    }
+    if removed_level {
+        println!("Removed the permission level and rules for {}", tool);
+    }
+    if removed_decisions > 0 {
+        println!(
+            "Forgot {} recorded decisions for {}",
+            removed_decisions, tool
+        );
+    }
+    Ok(())
+}
+
+/// Set the user permission level for a tool
+pub fn handle_permissions_set(tool: &str, level: &str) -> Result<()> {
+    let level: PermissionLevel =
+        serde_json::from_value(serde_json::Value::String(level.to_string())).map_err(|_| {
+            anyhow!(
+                "Unknown permission level '{}', expected always_allow, ask_before or never_allow",
+                level
+            )
+        })?;
+    let mut permission_manager = PermissionManager::default();
+    permission_manager.update_user_permission(tool, level.clone());
+    println!("Set {} to {}", tool, level_name(&level));
+    Ok(())
+}
+
+/// Forget every stored permission level, allow rule and recorded decision.
+/// `yes` skips the confirmation.
+pub fn handle_permissions_clear(yes: bool) -> Result<()> {
+    if !yes
+        && !cliclack::confirm("Forget every stored tool permission and recorded decision?")
+            .initial_value(false)
+            .interact()?
+    {
+        println!("Nothing was changed");
+        return Ok(());
+    }
+
+    let mut permission_manager = PermissionManager::default();
+    permission_manager.clear();
+    let removed_decisions = ToolPermissionStore::load()?.clear()?;
+    println!(
+        "Cleared all tool permissions and {} recorded decisions",
+        removed_decisions
+    );
+    Ok(())
+}
+
+/// Propose allow rules from the recorded approvals and save the ones the user picks.
+/// `yes` accepts all of them without asking.
+pub fn handle_permissions_suggest(min_approvals: usize, yes: bool, format: &str) -> Result<()> {
+    let store = ToolPermissionStore::load()?;
+    let records: Vec<ToolPermissionRecord> = store.records().cloned().collect();
+    let mut permission_manager = PermissionManager::default();
+    let suggestions = suggest_permissions(&records, &permission_manager, min_approvals);
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&suggestions)?);
+        return Ok(());
+    }
+
+    if suggestions.is_empty() {
+        println!(
+            "No suggestions yet. goose suggests a rule once you have approved similar tool calls {} times.",
+            min_approvals
+        );
+        return Ok(());
+    }
+
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        println!("{}. {}", index + 1, suggestion.describe());
+        for example in &suggestion.examples {
+            println!("     e.g. {}", example);
+        }
+    }
+    println!();
+
+    let accepted: Vec<&PermissionSuggestion> = if yes {
+        suggestions.iter().collect()
+    } else {
+        cliclack::multiselect(
+            "Select the rules to save (use \"space\" to toggle and \"enter\" to submit)",
+        )
+        .required(false)
+        .items(
+            &suggestions
+                .iter()
+                .enumerate()
+                .map(|(index, suggestion)| {
+                    (
+                        suggestion,
+                        format!("{}. {}", index + 1, suggestion.tool),
+                        suggestion
+                            .rule
+                            .as_ref()
+                            .map(|rule| format!("{} matches {}", rule.argument, rule.pattern))
+                            .unwrap_or_else(|| "always allow".to_string()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .interact()?
+    };
+
+    for suggestion in &accepted {
+        suggestion.accept(&mut permission_manager);
+    }
+    println!(
+        "Saved {} of {} suggested rules to {}",
+        accepted.len(),
+        suggestions.len(),
+        permission_manager.get_config_path().display()
+    );
+    Ok(())
+}
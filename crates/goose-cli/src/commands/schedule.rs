@@ -98,6 +98,8 @@ pub async fn handle_schedule_add(
         paused: false,
         current_session_id: None,
         process_start_time: None,
+        owner: None,
+        budget: None,
     };
 
     let scheduler_storage_path =
@@ -1,7 +1,9 @@
-use crate::session::message_to_markdown;
+use crate::session::{message_to_markdown, render_plan};
 use anyhow::{Context, Result};
 
 use cliclack::{confirm, multiselect, select};
+use goose::session::export::{self, SessionExportFormat};
+use goose::session::plan::load_plan;
 use goose::session::{generate_diagnostics, Session, SessionManager};
 use goose::utils::safe_truncate;
 use regex::Regex;
@@ -182,6 +184,8 @@ pub async fn handle_session_export(
     let output = match format.as_str() {
         "json" => serde_json::to_string_pretty(&session)?,
         "yaml" => serde_yaml::to_string(&session)?,
+        "html" => export::export_session(&session, SessionExportFormat::Html)?,
+        "jsonl" => export::export_session(&session, SessionExportFormat::Jsonl)?,
         "markdown" => {
             let conversation = session
                 .conversation
@@ -203,6 +207,27 @@ pub async fn handle_session_export(
     Ok(())
 }
 
+/// Show the plan made with `/plan` in a session, with the status of each step
+pub async fn handle_session_plan(session_id: &str, format: &str) -> Result<()> {
+    let plan = load_plan(session_id)
+        .await
+        .with_context(|| format!("Session '{}' not found or failed to read", session_id))?;
+    let Some(plan) = plan else {
+        println!(
+            "Session '{}' has no plan. Make one with /plan in the session.",
+            session_id
+        );
+        return Ok(());
+    };
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&plan)?),
+        "markdown" => print!("{}", plan.to_checklist()),
+        _ => render_plan(&plan),
+    }
+    Ok(())
+}
+
 pub async fn handle_diagnostics(session_id: &str, output_path: Option<PathBuf>) -> Result<()> {
     println!(
         "Generating diagnostics bundle for session '{}'...",
@@ -476,6 +476,7 @@ async fn process_message_streaming(
         schedule_id: None,
         max_turns: None,
         retry_config: None,
+        budget: None,
     };
 
     match agent.reply(user_message, session_config, None).await {
@@ -577,6 +578,9 @@ async fn process_message_streaming(
                     Ok(AgentEvent::ModelChange { model, mode }) => {
                         tracing::info!("Model changed to {} in {} mode", model, mode);
                     }
+                    Ok(AgentEvent::BudgetExceeded(limit)) => {
+                        tracing::info!("Reply stopped at budget limit: {}", limit);
+                    }
                     Err(e) => {
                         error!("Error in message stream: {}", e);
                         let mut sender = sender.lock().await;
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use goose_cli::cli::cli;
+use goose_cli::session::RunStatusError;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -33,5 +34,12 @@ async fn main() -> Result<()> {
         goose::tracing::shutdown_otlp();
     }
 
+    if let Err(e) = &result {
+        if let Some(status) = e.downcast_ref::<RunStatusError>() {
+            eprintln!("Error: {}", status);
+            std::process::exit(status.exit_code());
+        }
+    }
+
     result
 }
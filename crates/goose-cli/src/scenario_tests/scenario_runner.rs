@@ -233,6 +233,7 @@ where
         None,
         None,
         None,
+        None,
         "text".to_string(),
     )
     .await;
@@ -1,6 +1,7 @@
 use super::output;
 use super::CliSession;
 use console::style;
+use goose::agents::budget::Budget;
 use goose::agents::types::{RetryConfig, SessionConfig};
 use goose::agents::Agent;
 use goose::config::{
@@ -180,6 +181,7 @@ async fn offer_extension_debugging_help(
         None,
         None,
         None,
+        None,
         "text".to_string(),
     )
     .await;
@@ -243,6 +245,7 @@ pub struct SessionSettings {
     pub goose_model: Option<String>,
     pub goose_provider: Option<String>,
     pub temperature: Option<f32>,
+    pub budget: Option<Budget>,
 }
 
 pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
@@ -280,6 +283,22 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
         })
         .with_temperature(temperature);
 
+    // Report any prompt overrides for this directory that will be ignored
+    let cwd = std::env::current_dir().unwrap_or_default();
+    for template in goose::prompt_template::template_overrides(&cwd) {
+        if let Some(error) = &template.error {
+            eprintln!(
+                "{}",
+                style(format!(
+                    "Warning: Ignoring prompt template {} ({})",
+                    template.path.display(),
+                    error
+                ))
+                .yellow()
+            );
+        }
+    }
+
     // Create the agent
     let agent: Agent = Agent::new();
 
@@ -501,6 +520,10 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
         session_config.max_turns,
         edit_mode,
         session_config.retry_config.clone(),
+        session_config
+            .settings
+            .as_ref()
+            .and_then(|s| s.budget.clone()),
         session_config.output_format.clone(),
     )
     .await;
@@ -617,6 +640,7 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
         schedule_id: None,
         max_turns: None,
         retry_config: None,
+        budget: None,
     };
 
     if let Err(e) = session
@@ -645,8 +669,8 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
         session.agent.override_system_prompt(override_prompt).await;
     }
 
-    // Display session information unless in quiet mode
-    if !session_config.quiet {
+    // Display session information unless in quiet mode or writing machine readable output
+    if !session_config.quiet && session_config.output_format == "text" {
         output::display_session_info(
             session_config.resume,
             &provider_name,
@@ -119,10 +119,20 @@ impl GooseCompleter {
         Ok((line.len(), vec![]))
     }
 
+    /// Names and descriptions of the slash commands defined in prompt files
+    pub fn custom_commands(&self) -> Vec<(String, Option<String>)> {
+        let cache = self.completion_cache.read().unwrap();
+        cache
+            .commands
+            .iter()
+            .map(|command| (command.name.clone(), command.description.clone()))
+            .collect()
+    }
+
     /// Complete slash commands
     fn complete_slash_commands(&self, line: &str) -> Result<(usize, Vec<Pair>)> {
         // Define available slash commands
-        let commands = [
+        let builtin_commands = [
             "/exit",
             "/quit",
             "/help",
@@ -134,7 +144,19 @@ impl GooseCompleter {
             "/prompt",
             "/mode",
             "/recipe",
+            "/edit",
+            "/undo",
+            "/redo",
         ];
+        let custom_commands = self
+            .custom_commands()
+            .into_iter()
+            .map(|(name, _)| format!("/{}", name));
+        let commands: Vec<String> = builtin_commands
+            .iter()
+            .map(|cmd| cmd.to_string())
+            .chain(custom_commands)
+            .collect();
 
         // Find commands that match the prefix
         let matching_commands: Vec<Pair> = commands
@@ -275,6 +297,15 @@ impl GooseCompleter {
                 return Ok((line.len(), vec![]));
             }
 
+            // Complete the path in an @file mention after the '@'
+            if let Some(mention) = last_part.strip_prefix('@') {
+                let pos = line.len() - mention.len();
+                let (start, candidates) =
+                    self.filename_completer
+                        .complete(mention, mention.len(), ctx)?;
+                return Ok((pos + start, candidates));
+            }
+
             // Complete the partial path
             let pos = line.len() - last_part.len();
             let (start, candidates) =
@@ -21,6 +21,18 @@ pub enum InputResult {
     Clear,
     Recipe(Option<String>),
     Compact,
+    /// Edit the nth most recent prompt, counting the last one as 1
+    Edit(usize),
+    Undo,
+    Redo,
+    CustomCommand(CustomCommandOptions),
+}
+
+/// A slash command defined in a prompt file
+#[derive(Debug)]
+pub struct CustomCommandOptions {
+    pub name: String,
+    pub args: String,
 }
 
 #[derive(Debug)]
@@ -99,10 +111,54 @@ pub fn get_input(
         return Ok(InputResult::Message(trimmed.to_string()));
     }
 
+    let custom_commands = editor
+        .helper()
+        .map(|helper| helper.custom_commands())
+        .unwrap_or_default();
+
     // Handle slash commands
     match handle_slash_command(&input) {
-        Some(result) => Ok(result),
-        None => Ok(InputResult::Message(input.trim().to_string())),
+        Some(result) => {
+            if matches!(input.trim(), "/?" | "/help") {
+                print_custom_commands_help(&custom_commands);
+            }
+            Ok(result)
+        }
+        None => Ok(parse_custom_command(&input, &custom_commands)
+            .unwrap_or_else(|| InputResult::Message(input.trim().to_string()))),
+    }
+}
+
+/// `/name args` when `name` is one of the custom commands
+fn parse_custom_command(
+    input: &str,
+    custom_commands: &[(String, Option<String>)],
+) -> Option<InputResult> {
+    let command = input.trim().strip_prefix('/')?;
+    let (name, args) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+    custom_commands
+        .iter()
+        .any(|(custom, _)| custom == name)
+        .then(|| {
+            InputResult::CustomCommand(CustomCommandOptions {
+                name: name.to_string(),
+                args: args.trim().to_string(),
+            })
+        })
+}
+
+fn print_custom_commands_help(custom_commands: &[(String, Option<String>)]) {
+    if custom_commands.is_empty() {
+        return;
+    }
+    println!("\nCustom commands:");
+    for (name, description) in custom_commands {
+        match description {
+            Some(description) => println!("/{} [args] - {}", name, description),
+            None => println!("/{} [args]", name),
+        }
     }
 }
 
@@ -122,6 +178,9 @@ fn handle_slash_command(input: &str) -> Option<InputResult> {
     const CMD_RECIPE: &str = "/recipe";
     const CMD_COMPACT: &str = "/compact";
     const CMD_SUMMARIZE_DEPRECATED: &str = "/summarize";
+    const CMD_EDIT: &str = "/edit";
+    const CMD_UNDO: &str = "/undo";
+    const CMD_REDO: &str = "/redo";
 
     match input {
         "/exit" | "/quit" => Some(InputResult::Exit),
@@ -186,10 +245,31 @@ fn handle_slash_command(input: &str) -> Option<InputResult> {
             println!("{}", console::style("⚠️  Note: /summarize has been renamed to /compact and will be removed in a future release.").yellow());
             Some(InputResult::Compact)
         }
+        s if s == CMD_EDIT || s.starts_with("/edit ") => parse_edit_command(&s[CMD_EDIT.len()..]),
+        s if s == CMD_UNDO => Some(InputResult::Undo),
+        s if s == CMD_REDO => Some(InputResult::Redo),
         _ => None,
     }
 }
 
+fn parse_edit_command(args: &str) -> Option<InputResult> {
+    let args = args.trim();
+    if args.is_empty() {
+        return Some(InputResult::Edit(1));
+    }
+    match args.parse::<usize>() {
+        Ok(n) if n > 0 => Some(InputResult::Edit(n)),
+        _ => {
+            println!(
+                "{}",
+                console::style("Usage: /edit [n], where n counts back from your last message")
+                    .red()
+            );
+            Some(InputResult::Retry)
+        }
+    }
+}
+
 fn parse_recipe_command(s: &str) -> Option<InputResult> {
     const CMD_RECIPE: &str = "/recipe";
 
@@ -313,6 +393,9 @@ fn print_help() {
 /compact - Compact the current conversation to reduce context length while preserving key information.
 /? or /help - Display this help message
 /clear - Clears the current chat history
+/edit [n] - Edit your last message, or the nth most recent one, in $EDITOR and rerun from there
+/undo - Remove your last message and everything after it
+/redo - Restore the conversation as it was before the last /edit or /undo
 
 Navigation:
 Ctrl+C - Clear current line if text is entered, otherwise exit the session
@@ -546,6 +629,52 @@ mod tests {
         assert!(matches!(result, Some(InputResult::Retry)));
     }
 
+    #[test]
+    fn test_edit_and_undo_commands() {
+        assert!(matches!(
+            handle_slash_command("/edit"),
+            Some(InputResult::Edit(1))
+        ));
+        assert!(matches!(
+            handle_slash_command("/edit 3"),
+            Some(InputResult::Edit(3))
+        ));
+        assert!(matches!(
+            handle_slash_command("/edit zero"),
+            Some(InputResult::Retry)
+        ));
+        assert!(matches!(
+            handle_slash_command("/undo"),
+            Some(InputResult::Undo)
+        ));
+        assert!(matches!(
+            handle_slash_command("/redo"),
+            Some(InputResult::Redo)
+        ));
+        assert!(handle_slash_command("/editor").is_none());
+    }
+
+    #[test]
+    fn test_custom_command() {
+        let commands = vec![("review".to_string(), Some("Review a file".to_string()))];
+
+        if let Some(InputResult::CustomCommand(opts)) =
+            parse_custom_command("/review src/main.rs  carefully ", &commands)
+        {
+            assert_eq!(opts.name, "review");
+            assert_eq!(opts.args, "src/main.rs  carefully");
+        } else {
+            panic!("Expected CustomCommand");
+        }
+
+        assert!(matches!(
+            parse_custom_command("/review", &commands),
+            Some(InputResult::CustomCommand(_))
+        ));
+        assert!(parse_custom_command("/reviewer", &commands).is_none());
+        assert!(parse_custom_command("/usr/bin/env", &commands).is_none());
+    }
+
     #[test]
     fn test_get_input_prompt_string() {
         let prompt = get_input_prompt_string();
@@ -0,0 +1,312 @@
+use base64::Engine;
+use goose::conversation::message::Message;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+/// Text files longer than this are cut off
+pub const MAX_MENTION_TEXT_BYTES: usize = 100 * 1024;
+/// Images larger than this are not attached
+pub const MAX_MENTION_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+/// Entries shown for a mentioned directory
+pub const MAX_MENTION_DIR_ENTRIES: usize = 200;
+
+/// Characters that may follow a mention in prose without being part of the path
+const TRAILING_PUNCTUATION: &[char] = &[',', '.', ';', ':', '!', '?', ')', ']', '"', '\''];
+
+/// What happened to one `@path` in a prompt, for showing to the user
+#[derive(Debug, Clone, PartialEq)]
+pub enum MentionNote {
+    Attached(String),
+    Skipped { path: String, reason: String },
+}
+
+/// Words written as `@path` that name an existing file or directory under `cwd`.
+/// Anything else starting with `@`, such as a handle, is left alone.
+pub fn find_mentions(text: &str, cwd: &Path) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(mention) = word.strip_prefix('@') else {
+            continue;
+        };
+        let candidates = [mention, mention.trim_end_matches(TRAILING_PUNCTUATION)];
+        if let Some(found) = candidates
+            .into_iter()
+            .find(|path| !path.is_empty() && cwd.join(path).exists())
+        {
+            if !mentions.iter().any(|m| m == found) {
+                mentions.push(found.to_string());
+            }
+        }
+    }
+    mentions
+}
+
+/// Same rules as the developer extension: `.gooseignore`, else `.gitignore`, else a
+/// few patterns for secrets.
+fn ignore_patterns(cwd: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(cwd);
+    let gooseignore = cwd.join(".gooseignore");
+    let gitignore = cwd.join(".gitignore");
+    if gooseignore.is_file() {
+        let _ = builder.add(gooseignore);
+    } else if gitignore.is_file() {
+        let _ = builder.add(gitignore);
+    } else {
+        let _ = builder.add_line(None, "**/.env");
+        let _ = builder.add_line(None, "**/.env.*");
+        let _ = builder.add_line(None, "**/secrets.*");
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+fn list_directory(path: &Path, ignore: &Gitignore) -> std::io::Result<String> {
+    let mut entries: Vec<(String, bool)> = fs::read_dir(path)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let entry_path = entry.path();
+            let is_dir = entry_path.is_dir();
+            if ignore.matched(&entry_path, is_dir).is_ignore() {
+                return None;
+            }
+            Some((entry.file_name().to_string_lossy().to_string(), is_dir))
+        })
+        .collect();
+    entries.sort();
+
+    let total = entries.len();
+    let mut listing: Vec<String> = entries
+        .into_iter()
+        .take(MAX_MENTION_DIR_ENTRIES)
+        .map(|(name, is_dir)| if is_dir { format!("{}/", name) } else { name })
+        .collect();
+    if total > MAX_MENTION_DIR_ENTRIES {
+        listing.push(format!("... and {} more", total - MAX_MENTION_DIR_ENTRIES));
+    }
+    Ok(listing.join("\n"))
+}
+
+enum Attachment {
+    Text(String),
+    Image {
+        data: String,
+        mime_type: &'static str,
+    },
+}
+
+/// The content to attach for one mention, and a short description of it
+fn attach(mention: &str, cwd: &Path, ignore: &Gitignore) -> Result<(Attachment, String), String> {
+    // Resolve `..` and symlinks first so a mention can't reach outside the working directory
+    let root = cwd.canonicalize().map_err(|e| e.to_string())?;
+    let resolved = cwd
+        .join(mention)
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+    let relative = resolved
+        .strip_prefix(&root)
+        .map_err(|_| "outside the working directory".to_string())?;
+    let path = cwd.join(relative);
+    let is_dir = path.is_dir();
+    if ignore.matched(&path, is_dir).is_ignore() {
+        return Err("restricted by .gooseignore".to_string());
+    }
+
+    if is_dir {
+        let listing = list_directory(&path, ignore).map_err(|e| e.to_string())?;
+        let text = format!(
+            "<directory path=\"{}\">\n{}\n</directory>",
+            mention, listing
+        );
+        return Ok((Attachment::Text(text), "directory listing".to_string()));
+    }
+
+    let size = fs::metadata(&path).map_err(|e| e.to_string())?.len();
+    if let Some(mime_type) = image_mime_type(&path) {
+        if size > MAX_MENTION_IMAGE_BYTES {
+            return Err(format!(
+                "image is larger than {} MB",
+                MAX_MENTION_IMAGE_BYTES / (1024 * 1024)
+            ));
+        }
+        let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+        let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+        return Ok((Attachment::Image { data, mime_type }, "image".to_string()));
+    }
+
+    // Read one byte past the limit so a huge file is never loaded just to be cut off
+    let mut bytes = Vec::new();
+    File::open(&path)
+        .and_then(|file| {
+            file.take(MAX_MENTION_TEXT_BYTES as u64 + 1)
+                .read_to_end(&mut bytes)
+        })
+        .map_err(|e| e.to_string())?;
+    if bytes.iter().take(8192).any(|b| *b == 0) {
+        return Err("binary file".to_string());
+    }
+    let truncated = bytes.len() > MAX_MENTION_TEXT_BYTES;
+    if truncated {
+        bytes.truncate(MAX_MENTION_TEXT_BYTES);
+        // Drop a multi-byte character the cut split in two
+        if let Err(e) = std::str::from_utf8(&bytes) {
+            if e.error_len().is_none() {
+                bytes.truncate(e.valid_up_to());
+            }
+        }
+    }
+    let content = String::from_utf8_lossy(&bytes);
+    let content = if truncated {
+        format!(
+            "{}\n[truncated at {} KB]",
+            content,
+            MAX_MENTION_TEXT_BYTES / 1024
+        )
+    } else {
+        content.into_owned()
+    };
+    let text = format!("<file path=\"{}\">\n{}\n</file>", mention, content);
+    let note = if truncated {
+        format!("truncated to {} KB", MAX_MENTION_TEXT_BYTES / 1024)
+    } else {
+        format!("{} bytes", size)
+    };
+    Ok((Attachment::Text(text), note))
+}
+
+/// Build the user message for `text`, with every `@path` it mentions attached after
+/// the prompt as its own content.
+pub fn message_with_mentions(text: &str, cwd: &Path) -> (Message, Vec<MentionNote>) {
+    let mut message = Message::user().with_text(text);
+    let mentions = find_mentions(text, cwd);
+    if mentions.is_empty() {
+        return (message, Vec::new());
+    }
+
+    let ignore = ignore_patterns(cwd);
+    let mut notes = Vec::new();
+    for mention in mentions {
+        match attach(&mention, cwd, &ignore) {
+            Ok((attachment, detail)) => {
+                message = match attachment {
+                    Attachment::Text(text) => message.with_text(text),
+                    Attachment::Image { data, mime_type } => message.with_image(data, mime_type),
+                };
+                notes.push(MentionNote::Attached(format!("{} ({})", mention, detail)));
+            }
+            Err(reason) => notes.push(MentionNote::Skipped {
+                path: mention,
+                reason,
+            }),
+        }
+    }
+    (message, notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goose::conversation::message::MessageContent;
+
+    #[test]
+    fn test_find_mentions() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mentions = find_mentions(
+            "look at @src/main.rs, then @src. ask @alice or me@example.com about @src/main.rs",
+            dir.path(),
+        );
+        assert_eq!(mentions, vec!["src/main.rs", "src"]);
+    }
+
+    #[test]
+    fn test_message_with_mentions() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gooseignore"), "secret.txt\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "remember the milk").unwrap();
+        fs::write(dir.path().join("secret.txt"), "hunter2").unwrap();
+        fs::write(dir.path().join("logo.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        fs::write(dir.path().join("data.bin"), [1, 0, 2]).unwrap();
+
+        let (message, notes) = message_with_mentions(
+            "summarize @notes.txt @secret.txt @logo.png @data.bin @.",
+            dir.path(),
+        );
+
+        assert_eq!(message.content.len(), 4);
+        assert!(matches!(&message.content[1], MessageContent::Text(t)
+            if t.text == "<file path=\"notes.txt\">\nremember the milk\n</file>"));
+        assert!(matches!(&message.content[2], MessageContent::Image(i)
+            if i.mime_type == "image/png"));
+        let MessageContent::Text(listing) = &message.content[3] else {
+            panic!("expected a directory listing");
+        };
+        assert!(listing.text.contains("notes.txt"));
+        assert!(!listing.text.contains("secret.txt"));
+
+        assert!(notes.contains(&MentionNote::Skipped {
+            path: "secret.txt".to_string(),
+            reason: "restricted by .gooseignore".to_string(),
+        }));
+        assert!(notes.contains(&MentionNote::Skipped {
+            path: "data.bin".to_string(),
+            reason: "binary file".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_mentions_stay_inside_cwd() {
+        let parent = tempfile::tempdir().unwrap();
+        let cwd = parent.path().join("project");
+        fs::create_dir(&cwd).unwrap();
+        fs::write(parent.path().join("outside.txt"), "not yours").unwrap();
+        let absolute = parent.path().join("outside.txt");
+
+        let (message, notes) = message_with_mentions(
+            &format!("read @../outside.txt and @{}", absolute.display()),
+            &cwd,
+        );
+
+        assert_eq!(message.content.len(), 1);
+        assert_eq!(notes.len(), 2);
+        assert!(notes
+            .iter()
+            .all(|note| matches!(note, MentionNote::Skipped { reason, .. }
+            if reason == "outside the working directory")));
+    }
+
+    #[test]
+    fn test_large_text_mention_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        // The odd offset puts the cut in the middle of a two-byte character
+        let content = format!("a{}", "é".repeat(MAX_MENTION_TEXT_BYTES));
+        fs::write(dir.path().join("big.log"), content).unwrap();
+
+        let (message, notes) = message_with_mentions("check @big.log", dir.path());
+
+        let MessageContent::Text(text) = &message.content[1] else {
+            panic!("expected the file contents");
+        };
+        assert!(text.text.ends_with("[truncated at 100 KB]\n</file>"));
+        assert!(!text.text.contains('\u{FFFD}'));
+        assert_eq!(
+            notes,
+            vec![MentionNote::Attached(
+                "big.log (truncated to 100 KB)".to_string()
+            )]
+        );
+    }
+}
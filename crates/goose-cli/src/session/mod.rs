@@ -2,8 +2,11 @@ mod builder;
 mod completion;
 mod export;
 mod input;
+mod mentions;
 mod output;
 mod prompt;
+mod rewind;
+mod stream_json;
 mod task_execution_display;
 mod thinking;
 
@@ -15,6 +18,7 @@ use std::io::Write;
 use std::str::FromStr;
 
 pub use self::export::message_to_markdown;
+pub use self::output::render_plan;
 pub use builder::{build_session, SessionBuilderConfig, SessionSettings};
 use console::Color;
 use goose::agents::AgentEvent;
@@ -26,16 +30,23 @@ use goose::utils::safe_truncate;
 
 use anyhow::{Context, Result};
 use completion::GooseCompleter;
+use goose::agents::approval::ApprovalBroker;
+use goose::agents::budget::Budget;
 use goose::agents::extension::{Envs, ExtensionConfig};
 use goose::agents::types::RetryConfig;
 use goose::agents::{Agent, SessionConfig, MANUAL_COMPACT_TRIGGER};
 use goose::config::{Config, GooseMode};
 use goose::providers::pricing::initialize_pricing_cache;
+use goose::session::plan::{load_plan, save_plan, start_plan};
 use goose::session::SessionManager;
+use goose::session::{ExtensionState, Plan};
+use goose::slash_commands::{discover_commands, CommandSource, SlashCommand};
 use input::InputResult;
 use rmcp::model::PromptMessage;
 use rmcp::model::ServerNotification;
 use rmcp::model::{ErrorCode, ErrorData};
+pub use stream_json::RunStatusError;
+use stream_json::{RunStatus, StreamEvent};
 
 use goose::config::paths::Paths;
 use goose::conversation::message::{Message, MessageContent};
@@ -79,6 +90,7 @@ pub struct CliSession {
     max_turns: Option<u32>,
     edit_mode: Option<EditMode>,
     retry_config: Option<RetryConfig>,
+    budget: Option<Budget>,
     output_format: String,
 }
 
@@ -86,6 +98,7 @@ pub struct CliSession {
 struct CompletionCache {
     prompts: HashMap<String, Vec<String>>,
     prompt_info: HashMap<String, output::PromptInfo>,
+    commands: Vec<SlashCommand>,
     last_updated: Instant,
 }
 
@@ -94,6 +107,7 @@ impl CompletionCache {
         Self {
             prompts: HashMap::new(),
             prompt_info: HashMap::new(),
+            commands: Vec::new(),
             last_updated: Instant::now(),
         }
     }
@@ -143,6 +157,7 @@ impl CliSession {
         max_turns: Option<u32>,
         edit_mode: Option<EditMode>,
         retry_config: Option<RetryConfig>,
+        budget: Option<Budget>,
         output_format: String,
     ) -> Self {
         let messages = SessionManager::get_session(&session_id, true)
@@ -161,6 +176,7 @@ impl CliSession {
             max_turns,
             edit_mode,
             retry_config,
+            budget,
             output_format,
         }
     }
@@ -443,46 +459,19 @@ impl CliSession {
             self.display_context_usage().await?;
 
             match input::get_input(&mut editor)? {
-                InputResult::Message(content) => {
-                    match self.run_mode {
-                        RunMode::Normal => {
-                            save_history(&mut editor);
-
-                            self.push_message(Message::user().with_text(&content));
-
-                            // Track the current directory and last instruction in projects.json
-                            if let Err(e) = crate::project_tracker::update_project_tracker(
-                                Some(&content),
-                                Some(&self.session_id),
-                            ) {
-                                eprintln!("Warning: Failed to update project tracker with instruction: {}", e);
-                            }
-
-                            let _provider = self.agent.provider().await?;
-
-                            output::show_thinking();
-                            let start_time = Instant::now();
-                            self.process_agent_response(true, CancellationToken::default())
-                                .await?;
-                            output::hide_thinking();
-
-                            // Display elapsed time
-                            let elapsed = start_time.elapsed();
-                            let elapsed_str = format_elapsed_time(elapsed);
-                            println!(
-                                "\n{}",
-                                console::style(format!("⏱️  Elapsed time: {}", elapsed_str)).dim()
-                            );
-                        }
-                        RunMode::Plan => {
-                            let mut plan_messages = self.messages.clone();
-                            plan_messages.push(Message::user().with_text(&content));
-                            let reasoner = get_reasoner().await?;
-                            self.plan_with_reasoner_model(plan_messages, reasoner)
-                                .await?;
-                        }
+                InputResult::Message(content) => match self.run_mode {
+                    RunMode::Normal => {
+                        save_history(&mut editor);
+                        self.send_prompt(&content).await?;
                     }
-                }
+                    RunMode::Plan => {
+                        let mut plan_messages = self.messages.clone();
+                        plan_messages.push(Message::user().with_text(&content));
+                        let reasoner = get_reasoner().await?;
+                        self.plan_with_reasoner_model(plan_messages, reasoner)
+                            .await?;
+                    }
+                },
                 input::InputResult::Exit => break,
                 input::InputResult::AddExtension(cmd) => {
                     save_history(&mut editor);
@@ -649,6 +638,28 @@ impl CliSession {
 
                     continue;
                 }
+                InputResult::CustomCommand(opts) => {
+                    save_history(&mut editor);
+                    self.run_custom_command(opts).await?;
+                    continue;
+                }
+                InputResult::Edit(n) => {
+                    save_history(&mut editor);
+                    if let Some(content) = self.rewind_for_edit(n).await? {
+                        self.send_prompt(&content).await?;
+                    }
+                    continue;
+                }
+                InputResult::Undo => {
+                    save_history(&mut editor);
+                    self.undo().await?;
+                    continue;
+                }
+                InputResult::Redo => {
+                    save_history(&mut editor);
+                    self.redo().await?;
+                    continue;
+                }
                 InputResult::Compact => {
                     save_history(&mut editor);
 
@@ -679,6 +690,7 @@ impl CliSession {
             }
         }
 
+        self.agent.end_session(&self.session_id).await;
         println!(
             "Closing session. Session ID: {}",
             console::style(&self.session_id).cyan()
@@ -687,6 +699,358 @@ impl CliSession {
         Ok(())
     }
 
+    /// Send a prompt typed in the interactive session and show the response
+    async fn send_prompt(&mut self, content: &str) -> Result<()> {
+        let (message, notes) = mentions::message_with_mentions(content, &std::env::current_dir()?);
+        for note in notes {
+            match note {
+                mentions::MentionNote::Attached(detail) => {
+                    println!("{}", console::style(format!("📎 {}", detail)).dim())
+                }
+                mentions::MentionNote::Skipped { path, reason } => println!(
+                    "{}",
+                    console::style(format!("Not attaching {}: {}", path, reason)).yellow()
+                ),
+            }
+        }
+        self.push_message(message);
+
+        // Track the current directory and last instruction in projects.json
+        if let Err(e) =
+            crate::project_tracker::update_project_tracker(Some(content), Some(&self.session_id))
+        {
+            eprintln!(
+                "Warning: Failed to update project tracker with instruction: {}",
+                e
+            );
+        }
+
+        let _provider = self.agent.provider().await?;
+
+        output::show_thinking();
+        let start_time = Instant::now();
+        self.process_agent_response(true, CancellationToken::default())
+            .await?;
+        output::hide_thinking();
+
+        // Display elapsed time
+        let elapsed = start_time.elapsed();
+        let elapsed_str = format_elapsed_time(elapsed);
+        println!(
+            "\n{}",
+            console::style(format!("⏱️  Elapsed time: {}", elapsed_str)).dim()
+        );
+        Ok(())
+    }
+
+    /// Run a slash command defined in a prompt file, enabling its extensions and
+    /// using its model settings for this one prompt
+    async fn run_custom_command(&mut self, opts: input::CustomCommandOptions) -> Result<()> {
+        let command = {
+            let cache = self.completion_cache.read().unwrap();
+            cache.commands.iter().find(|c| c.name == opts.name).cloned()
+        };
+        let Some(command) = command else {
+            output::render_error(&format!("Unknown command /{}", opts.name));
+            return Ok(());
+        };
+        let prompt = match command.render(&opts.args) {
+            Ok(prompt) => prompt,
+            Err(e) => {
+                output::render_error(&format!("Failed to render /{}: {}", command.name, e));
+                return Ok(());
+            }
+        };
+
+        // Extensions the command needs that aren't running yet; they are stopped again
+        // once the command finishes
+        let enabled: Vec<String> = self
+            .agent
+            .get_extension_configs()
+            .await
+            .iter()
+            .map(|config| config.name())
+            .collect();
+        let missing: Vec<ExtensionConfig> = command
+            .extensions
+            .iter()
+            .flatten()
+            .filter(|extension| !enabled.contains(&extension.name()))
+            .cloned()
+            .collect();
+
+        // A project's command file can name any program, so ask before running it
+        if command.source == CommandSource::Project && !missing.is_empty() {
+            let list = missing
+                .iter()
+                .map(|extension| format!("  {}", extension))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let prompt = format!(
+                "/{} from {} starts these extensions:\n{}\nStart them?",
+                command.name,
+                command.path.display(),
+                list
+            );
+            let confirmed = match cliclack::confirm(prompt).initial_value(false).interact() {
+                Ok(choice) => choice,
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::Interrupted {
+                        false
+                    } else {
+                        return Err(e.into());
+                    }
+                }
+            };
+            if !confirmed {
+                println!(
+                    "{}",
+                    console::style(format!("/{} cancelled.", command.name)).yellow()
+                );
+                return Ok(());
+            }
+        }
+
+        let mut started = Vec::new();
+        for extension in missing {
+            if let Err(e) = self.agent.add_extension(extension.clone()).await {
+                output::render_error(&format!(
+                    "Failed to start extension '{}' for /{}: {}",
+                    extension.name(),
+                    command.name,
+                    e
+                ));
+                self.stop_command_extensions(&started).await;
+                return Ok(());
+            }
+            started.push(extension.name());
+        }
+        if !started.is_empty() {
+            self.invalidate_completion_cache().await;
+        }
+
+        // A bad provider or model in the command file only fails this command
+        let previous_provider = match &command.settings {
+            Some(settings) => match self.use_command_provider(settings).await {
+                Ok(previous) => Some(previous),
+                Err(e) => {
+                    output::render_error(&format!(
+                        "Failed to set up the model for /{}: {}",
+                        command.name, e
+                    ));
+                    self.stop_command_extensions(&started).await;
+                    return Ok(());
+                }
+            },
+            None => None,
+        };
+
+        let result = self.send_prompt(&prompt).await;
+        self.stop_command_extensions(&started).await;
+        if let Some(provider) = previous_provider {
+            self.agent.update_provider(provider).await?;
+        }
+        result
+    }
+
+    /// Switch to the provider and model a custom command asks for, returning the
+    /// provider to restore afterwards
+    async fn use_command_provider(
+        &mut self,
+        settings: &goose::recipe::Settings,
+    ) -> Result<Arc<dyn Provider>> {
+        let current = self.agent.provider().await?;
+        let provider_name = settings
+            .goose_provider
+            .clone()
+            .unwrap_or_else(|| current.get_name().to_string());
+        let model_name = settings
+            .goose_model
+            .clone()
+            .unwrap_or_else(|| current.get_model_config().model_name);
+        let model_config =
+            goose::model::ModelConfig::new(&model_name)?.with_temperature(settings.temperature);
+        let provider = goose::providers::create(&provider_name, model_config).await?;
+        self.agent.update_provider(provider).await?;
+        Ok(current)
+    }
+
+    /// Remove the extensions a custom command started for itself
+    async fn stop_command_extensions(&mut self, names: &[String]) {
+        if names.is_empty() {
+            return;
+        }
+        for name in names {
+            if let Err(e) = self.agent.remove_extension(name).await {
+                output::render_error(&format!("Failed to stop extension '{}': {}", name, e));
+            }
+        }
+        self.invalidate_completion_cache().await;
+    }
+
+    /// Cut the stored conversation back to `len` messages, keeping the removed ones
+    /// in the session for /redo
+    async fn truncate_conversation(
+        &mut self,
+        conversation: Conversation,
+        len: usize,
+    ) -> Result<()> {
+        let mut extension_data = SessionManager::get_session(&self.session_id, false)
+            .await?
+            .extension_data;
+        let mut state =
+            rewind::RewindState::from_extension_data(&extension_data).unwrap_or_default();
+        state.rewinds.push(rewind::Rewind {
+            index: len,
+            messages: conversation.messages()[len..].to_vec(),
+        });
+        state.to_extension_data(&mut extension_data)?;
+
+        let mut truncated = conversation;
+        truncated.truncate(len);
+        SessionManager::replace_conversation(&self.session_id, &truncated).await?;
+        SessionManager::update_session(&self.session_id)
+            .extension_data(extension_data)
+            .apply()
+            .await?;
+        self.messages = truncated;
+        Ok(())
+    }
+
+    /// Open the nth most recent prompt in an editor and, if it changed, drop it and
+    /// everything after it. Returns the edited prompt to send.
+    async fn rewind_for_edit(&mut self, n: usize) -> Result<Option<String>> {
+        let conversation = SessionManager::get_session(&self.session_id, true)
+            .await?
+            .conversation
+            .unwrap_or_default();
+        let Some(index) = rewind::nth_last_prompt(conversation.messages(), n) else {
+            output::render_error(&format!("There is no message {} back to edit", n));
+            return Ok(None);
+        };
+
+        let original = rewind::prompt_text(&conversation.messages()[index]);
+        let edited = match rewind::edit_in_editor(&original) {
+            Ok(Some(edited)) => edited,
+            Ok(None) => {
+                println!("{}", console::style("Message unchanged.").yellow());
+                return Ok(None);
+            }
+            Err(e) => {
+                output::render_error(&e.to_string());
+                return Ok(None);
+            }
+        };
+
+        let removed = conversation.len() - index;
+        self.truncate_conversation(conversation, index).await?;
+        println!(
+            "{}",
+            console::style(format!(
+                "Removed {} messages and resent the edited one. Use /redo to restore them.",
+                removed
+            ))
+            .dim()
+        );
+        Ok(Some(edited))
+    }
+
+    /// Drop the last prompt and everything after it
+    async fn undo(&mut self) -> Result<()> {
+        let conversation = SessionManager::get_session(&self.session_id, true)
+            .await?
+            .conversation
+            .unwrap_or_default();
+        let Some(index) = rewind::nth_last_prompt(conversation.messages(), 1) else {
+            output::render_error("There is nothing to undo");
+            return Ok(());
+        };
+
+        let removed = conversation.len() - index;
+        self.truncate_conversation(conversation, index).await?;
+        println!(
+            "{}",
+            console::style(format!(
+                "Removed {} messages. Use /redo to restore them.",
+                removed
+            ))
+            .dim()
+        );
+        Ok(())
+    }
+
+    /// Put back the conversation as it was before the last /edit or /undo
+    async fn redo(&mut self) -> Result<()> {
+        let session = SessionManager::get_session(&self.session_id, true).await?;
+        let mut extension_data = session.extension_data;
+        let mut state =
+            rewind::RewindState::from_extension_data(&extension_data).unwrap_or_default();
+        let current = session.conversation.unwrap_or_default();
+        let Some(messages) = state.redo(current.messages()) else {
+            output::render_error("There is nothing to redo");
+            return Ok(());
+        };
+        state.to_extension_data(&mut extension_data)?;
+
+        let conversation = Conversation::new_unvalidated(messages);
+        SessionManager::replace_conversation(&self.session_id, &conversation).await?;
+        SessionManager::update_session(&self.session_id)
+            .extension_data(extension_data)
+            .apply()
+            .await?;
+        println!(
+            "{}",
+            console::style(format!("Restored {} messages.", conversation.len())).dim()
+        );
+        self.messages = conversation;
+        Ok(())
+    }
+
+    /// Let the user act on a plan, edit its steps first or keep chatting. Edits are
+    /// saved to the session as they are made.
+    async fn review_plan(&self, plan: &mut Plan) -> Result<bool> {
+        loop {
+            output::render_plan(plan);
+            let choice = cliclack::select("What would you like to do with this plan?")
+                .item(
+                    "act",
+                    "Act on it",
+                    "Clear message history & act on this plan",
+                )
+                .item("edit", "Edit", "Edit the steps in $EDITOR first")
+                .item("chat", "Keep chatting", "Keep the plan as a draft")
+                .interact();
+            let choice = match choice {
+                Ok(choice) => choice,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => "chat",
+                Err(e) => return Err(e.into()),
+            };
+
+            match choice {
+                "act" => return Ok(true),
+                "edit" => match rewind::edit_in_editor(&plan.to_checklist()) {
+                    Ok(Some(edited)) => match plan.edit_steps(&edited) {
+                        Ok(()) => save_plan(&self.session_id, plan).await?,
+                        Err(e) => output::render_error(&e.to_string()),
+                    },
+                    Ok(None) => println!("{}", console::style("The plan is unchanged.").dim()),
+                    Err(e) => output::render_error(&e.to_string()),
+                },
+                _ => {
+                    println!(
+                        "{}",
+                        console::style(format!(
+                            "The plan is saved as a draft. View it with `goose session plan {}`.",
+                            self.session_id
+                        ))
+                        .dim()
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
     async fn plan_with_reasoner_model(
         &mut self,
         plan_messages: Conversation,
@@ -706,20 +1070,28 @@ impl CliSession {
         match planner_response_type {
             PlannerResponseType::Plan => {
                 println!();
-                let should_act = match cliclack::confirm(
-                    "Do you want to clear message history & act on this plan?",
-                )
-                .initial_value(true)
-                .interact()
-                {
-                    Ok(choice) => choice,
-                    Err(e) => {
-                        if e.kind() == std::io::ErrorKind::Interrupted {
-                            false // If interrupted, set should_act to false
-                        } else {
-                            return Err(e.into());
-                        }
+                let plan_text = plan_response.as_concat_text();
+                let mut plan = Plan::from_text(&plan_text);
+                let should_act = match plan.as_mut() {
+                    Some(plan) => {
+                        save_plan(&self.session_id, plan).await?;
+                        self.review_plan(plan).await?
                     }
+                    None => match cliclack::confirm(
+                        "Do you want to clear message history & act on this plan?",
+                    )
+                    .initial_value(true)
+                    .interact()
+                    {
+                        Ok(choice) => choice,
+                        Err(e) => {
+                            if e.kind() == std::io::ErrorKind::Interrupted {
+                                false // If interrupted, set should_act to false
+                            } else {
+                                return Err(e.into());
+                            }
+                        }
+                    },
                 };
                 if should_act {
                     output::render_act_on_plan();
@@ -733,8 +1105,15 @@ impl CliSession {
 
                     // clear the messages before acting on the plan
                     self.messages.clear();
-                    // add the plan response as a user message
-                    let plan_message = Message::user().with_text(plan_response.as_concat_text());
+                    // add the plan as a user message; a structured plan also becomes the
+                    // todo list the agent tracks its progress in
+                    let plan_message = match plan.as_mut() {
+                        Some(plan) => {
+                            start_plan(&self.session_id, plan).await?;
+                            Message::user().with_text(plan.to_prompt())
+                        }
+                        None => Message::user().with_text(plan_text),
+                    };
                     self.push_message(plan_message);
                     // act on the plan
                     output::show_thinking();
@@ -742,6 +1121,12 @@ impl CliSession {
                         .await?;
                     output::hide_thinking();
 
+                    if plan.is_some() {
+                        if let Some(plan) = load_plan(&self.session_id).await? {
+                            output::render_plan(&plan);
+                        }
+                    }
+
                     // Reset run & goose mode
                     if curr_goose_mode != GooseMode::Auto {
                         config.set_goose_mode(curr_goose_mode)?;
@@ -764,27 +1149,81 @@ impl CliSession {
 
     /// Process a single message and exit
     pub async fn headless(&mut self, prompt: String) -> Result<()> {
+        self.agent
+            .set_approval_broker(ApprovalBroker::from_config()?)
+            .await;
         let message = Message::user().with_text(&prompt);
-        self.process_message(message, CancellationToken::default())
-            .await?;
-        Ok(())
+        if self.output_format != "stream-json" {
+            self.push_message(message);
+            let status = self
+                .process_agent_response(false, CancellationToken::default())
+                .await;
+            self.agent.end_session(&self.session_id).await;
+            return match status? {
+                RunStatus::BudgetExceeded => Err(RunStatusError(RunStatus::BudgetExceeded).into()),
+                _ => Ok(()),
+            };
+        }
+
+        let start_time = Instant::now();
+        stream_json::emit(&StreamEvent::Start {
+            schema_version: stream_json::STREAM_JSON_SCHEMA_VERSION,
+            session_id: self.session_id.clone(),
+        });
+
+        self.push_message(message);
+        let status = match self
+            .process_agent_response(false, CancellationToken::default())
+            .await
+        {
+            Ok(status) => status,
+            Err(e) => {
+                stream_json::emit(&StreamEvent::Error {
+                    message: e.to_string(),
+                });
+                RunStatus::Error
+            }
+        };
+
+        if let Ok(session) = SessionManager::get_session(&self.session_id, false).await {
+            stream_json::emit(&StreamEvent::Usage {
+                input_tokens: session.accumulated_input_tokens,
+                output_tokens: session.accumulated_output_tokens,
+                total_tokens: session.accumulated_total_tokens,
+            });
+        }
+        self.agent.end_session(&self.session_id).await;
+        stream_json::emit(&StreamEvent::Result {
+            status,
+            exit_code: status.exit_code(),
+            session_id: self.session_id.clone(),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+        });
+
+        match status {
+            RunStatus::Success => Ok(()),
+            status => Err(RunStatusError(status).into()),
+        }
     }
 
     async fn process_agent_response(
         &mut self,
         interactive: bool,
         cancel_token: CancellationToken,
-    ) -> Result<()> {
+    ) -> Result<RunStatus> {
         let cancel_token_clone = cancel_token.clone();
 
         // Cache the output format check to avoid repeated string comparisons in the hot loop
-        let is_json_mode = self.output_format == "json";
+        let is_stream_json = self.output_format == "stream-json";
+        let is_json_mode = self.output_format == "json" || is_stream_json;
+        let mut status = RunStatus::Success;
 
         let session_config = SessionConfig {
             id: self.session_id.clone(),
             schedule_id: self.scheduled_job_id.clone(),
             max_turns: self.max_turns,
             retry_config: self.retry_config.clone(),
+            budget: self.budget.clone(),
         };
         let user_message = self
             .messages
@@ -805,12 +1244,31 @@ impl CliSession {
         loop {
             tokio::select! {
                 result = stream.next() => {
+                    if is_stream_json {
+                        if let Some(Ok(event)) = &result {
+                            for stream_event in stream_json::events_from_agent_event(event) {
+                                stream_json::emit(&stream_event);
+                            }
+                        }
+                    }
                     match result {
                         Some(Ok(AgentEvent::Message(message))) => {
                             // If it's a confirmation request, get approval but otherwise do not render/persist
                             if let Some(MessageContent::ToolConfirmationRequest(confirmation)) = message.content.first() {
                                 output::hide_thinking();
 
+                                // The agent hands the request to the broker itself
+                                if let Some(broker) = self.agent.approval_broker().await {
+                                    if !is_json_mode {
+                                        output::render_text(
+                                            &format!("Asking {} to approve {}", broker.backend, confirmation.tool_name),
+                                            Some(Color::Yellow),
+                                            true,
+                                        );
+                                    }
+                                    continue;
+                                }
+
                                 // Format the confirmation prompt - use security message if present, otherwise use generic message
                                 let prompt = if let Some(security_message) = &confirmation.prompt {
                                     println!("\n{}", security_message);
@@ -850,7 +1308,10 @@ impl CliSession {
                                 };
 
                                 if permission == Permission::Cancel {
-                                    output::render_text("Tool call cancelled. Returning to chat...", Some(Color::Yellow), true);
+                                    status = RunStatus::Cancelled;
+                                    if !is_json_mode {
+                                        output::render_text("Tool call cancelled. Returning to chat...", Some(Color::Yellow), true);
+                                    }
 
                                     let mut response_message = Message::user();
                                     response_message.content.push(MessageContent::tool_response(
@@ -1036,6 +1497,9 @@ impl CliSession {
                         Some(Ok(AgentEvent::HistoryReplaced(updated_conversation))) => {
                             self.messages = updated_conversation;
                         }
+                        Some(Ok(AgentEvent::BudgetExceeded(_))) => {
+                            status = RunStatus::BudgetExceeded;
+                        }
                         Some(Ok(AgentEvent::ModelChange { model, mode })) => {
                             // Log model change if in debug mode
                             if self.debug {
@@ -1058,11 +1522,15 @@ impl CliSession {
                                 warn!("Compaction requested. Should have happened in the agent!");
                             }
                             eprintln!("Error: {}", e);
+                            status = RunStatus::Error;
+                            if is_stream_json {
+                                stream_json::emit(&StreamEvent::Error { message: e.to_string() });
+                            }
                             cancel_token_clone.cancel();
                             drop(stream);
                             if let Err(e) = self.handle_interrupted_messages(false).await {
                                 eprintln!("Error handling interruption: {}", e);
-                            } else {
+                            } else if !is_json_mode {
                                 output::render_error(
                                     "The error above was an exception we were not able to handle.\n\
                                     These errors are often related to connection or authentication\n\
@@ -1076,6 +1544,7 @@ impl CliSession {
                     }
                 }
                 _ = tokio::signal::ctrl_c() => {
+                    status = RunStatus::Cancelled;
                     cancel_token_clone.cancel();
                     drop(stream);
                     if let Err(e) = self.handle_interrupted_messages(true).await {
@@ -1087,7 +1556,7 @@ impl CliSession {
         }
 
         // Output JSON if requested
-        if is_json_mode {
+        if self.output_format == "json" {
             let metadata = match SessionManager::get_session(&self.session_id, false).await {
                 Ok(session) => JsonMetadata {
                     total_tokens: session.total_tokens,
@@ -1105,11 +1574,11 @@ impl CliSession {
             };
 
             println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else {
+        } else if !is_stream_json {
             println!();
         }
 
-        Ok(())
+        Ok(status)
     }
 
     async fn handle_interrupted_messages(&mut self, interrupt: bool) -> Result<()> {
@@ -1204,11 +1673,13 @@ impl CliSession {
     pub async fn update_completion_cache(&mut self) -> Result<()> {
         // Get fresh data
         let prompts = self.agent.list_extension_prompts().await;
+        let commands = discover_commands(&std::env::current_dir()?);
 
         // Update the cache with write lock
         let mut cache = self.completion_cache.write().unwrap();
         cache.prompts.clear();
         cache.prompt_info.clear();
+        cache.commands = commands;
 
         for (extension, prompt_list) in prompts {
             let names: Vec<String> = prompt_list.iter().map(|p| p.name.clone()).collect();
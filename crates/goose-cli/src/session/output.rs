@@ -3,11 +3,10 @@ use bat::WrappingMode;
 use console::{measure_text_width, style, Color, Term};
 use goose::config::Config;
 use goose::conversation::message::{Message, MessageContent, ToolRequest, ToolResponse};
-use goose::providers::pricing::get_model_pricing;
-use goose::providers::pricing::parse_model_id;
+use goose::providers::pricing::estimate_cost_usd;
+use goose::session::{Plan, PlanStatus, PlanStepStatus};
 use goose::utils::safe_truncate;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use regex::Regex;
 use rmcp::model::{CallToolRequestParam, JsonObject, PromptArgument};
 use serde_json::Value;
 use std::cell::RefCell;
@@ -251,6 +250,45 @@ pub fn render_exit_plan_mode() {
     println!("\n{}\n", style("Exiting plan mode.").green().bold());
 }
 
+/// Show a plan's steps and how far along it is
+pub fn render_plan(plan: &Plan) {
+    let status = match plan.status {
+        PlanStatus::Draft => "draft",
+        PlanStatus::Executing => "executing",
+        PlanStatus::Completed => "completed",
+    };
+    println!(
+        "{} {}",
+        style("Plan").green().bold(),
+        style(format!(
+            "({}, {} of {} steps done{})",
+            status,
+            plan.done_steps(),
+            plan.steps.len(),
+            if plan.edited { ", edited" } else { "" }
+        ))
+        .dim()
+    );
+    for (index, step) in plan.steps.iter().enumerate() {
+        let (mark, title) = match step.status {
+            PlanStepStatus::Pending => (style("[ ]").dim(), style(step.title.as_str())),
+            PlanStepStatus::InProgress => {
+                (style("[~]").yellow(), style(step.title.as_str()).yellow())
+            }
+            PlanStepStatus::Completed => (style("[x]").green(), style(step.title.as_str()).dim()),
+            PlanStepStatus::Skipped => (
+                style("[-]").dim(),
+                style(step.title.as_str()).dim().strikethrough(),
+            ),
+        };
+        println!("{:>4}. {} {}", index + 1, mark, title);
+        for detail in &step.details {
+            println!("         {}", style(detail).dim());
+        }
+    }
+    println!();
+}
+
 pub fn goose_mode_message(text: &str) {
     println!("\n{}", style(text).yellow(),);
 }
@@ -813,61 +851,6 @@ pub fn display_context_usage(total_tokens: usize, context_limit: usize) {
     );
 }
 
-fn normalize_model_name(model: &str) -> String {
-    let mut result = model.to_string();
-
-    // Remove "-latest" suffix
-    if result.ends_with("-latest") {
-        result = result.strip_suffix("-latest").unwrap().to_string();
-    }
-
-    // Remove date-like suffixes: -YYYYMMDD
-    let re_date = Regex::new(r"-\d{8}$").unwrap();
-    if re_date.is_match(&result) {
-        result = re_date.replace(&result, "").to_string();
-    }
-
-    // Convert version numbers like -3-7- to -3.7- (e.g., claude-3-7-sonnet -> claude-3.7-sonnet)
-    let re_version = Regex::new(r"-(\d+)-(\d+)-").unwrap();
-    if re_version.is_match(&result) {
-        result = re_version.replace(&result, "-$1.$2-").to_string();
-    }
-
-    result
-}
-
-async fn estimate_cost_usd(
-    provider: &str,
-    model: &str,
-    input_tokens: usize,
-    output_tokens: usize,
-) -> Option<f64> {
-    // For OpenRouter, parse the model name to extract real provider/model
-    let openrouter_data = if provider == "openrouter" {
-        parse_model_id(model)
-    } else {
-        None
-    };
-
-    let (provider_to_use, model_to_use) = match &openrouter_data {
-        Some((real_provider, real_model)) => (real_provider.as_str(), real_model.as_str()),
-        None => (provider, model),
-    };
-
-    // Use the pricing module's get_model_pricing which handles model name mapping internally
-    let cleaned_model = normalize_model_name(model_to_use);
-    let pricing_info = get_model_pricing(provider_to_use, &cleaned_model).await;
-
-    match pricing_info {
-        Some(pricing) => {
-            let input_cost = pricing.input_cost * input_tokens as f64;
-            let output_cost = pricing.output_cost * output_tokens as f64;
-            Some(input_cost + output_cost)
-        }
-        None => None,
-    }
-}
-
 /// Display cost information, if price data is available.
 pub async fn display_cost_usage(
     provider: &str,
@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Context, Result};
+use goose::conversation::message::{Message, MessageContent};
+use goose::session::ExtensionState;
+use rmcp::model::Role;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Messages removed by /edit and /undo, most recent last. Kept in the session's
+/// extension data so /redo still works after the session is resumed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RewindState {
+    pub rewinds: Vec<Rewind>,
+}
+
+/// The messages cut from the end of the conversation and where they were cut
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rewind {
+    pub index: usize,
+    pub messages: Vec<Message>,
+}
+
+impl ExtensionState for RewindState {
+    const EXTENSION_NAME: &'static str = "rewind";
+    const VERSION: &'static str = "v0";
+}
+
+impl RewindState {
+    /// Undo the most recent rewind: keep `messages` up to where it cut and put the
+    /// removed messages back after them
+    pub fn redo(&mut self, messages: &[Message]) -> Option<Vec<Message>> {
+        let rewind = self.rewinds.pop()?;
+        let mut restored = messages[..rewind.index.min(messages.len())].to_vec();
+        restored.extend(rewind.messages);
+        Some(restored)
+    }
+}
+
+/// Indices of the messages the user typed, oldest first. Tool results and
+/// messages hidden from either the user or the agent are not prompts; attached
+/// files and images are part of the prompt they follow.
+pub fn user_prompt_indices(messages: &[Message]) -> Vec<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| {
+            message.role == Role::User
+                && message.is_user_visible()
+                && message.is_agent_visible()
+                && matches!(message.content.first(), Some(MessageContent::Text(_)))
+                && !message
+                    .content
+                    .iter()
+                    .any(|content| matches!(content, MessageContent::ToolResponse(_)))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The text the user typed, without any attachments
+pub fn prompt_text(message: &Message) -> String {
+    match message.content.first() {
+        Some(MessageContent::Text(text)) => text.text.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Index of the `n`th most recent prompt, counting the last one as 1
+pub fn nth_last_prompt(messages: &[Message], n: usize) -> Option<usize> {
+    let prompts = user_prompt_indices(messages);
+    n.checked_sub(1)
+        .and_then(|back| prompts.len().checked_sub(back + 1))
+        .map(|position| prompts[position])
+}
+
+fn editor_command() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// Open `text` in `$VISUAL` or `$EDITOR` and return what was saved, or None when
+/// it was left empty or unchanged.
+pub fn edit_in_editor(text: &str) -> Result<Option<String>> {
+    let file = tempfile::Builder::new()
+        .prefix("goose-prompt-")
+        .suffix(".md")
+        .tempfile()?;
+    std::fs::write(file.path(), text)?;
+
+    let command = editor_command();
+    let parts = shlex::split(&command).unwrap_or_default();
+    let (program, args) = parts
+        .split_first()
+        .ok_or_else(|| anyhow!("Invalid editor command: {}", command))?;
+    let status = Command::new(program)
+        .args(args)
+        .arg(file.path())
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'", command))?;
+    if !status.success() {
+        return Err(anyhow!("Editor '{}' exited with {}", command, status));
+    }
+
+    let edited = std::fs::read_to_string(file.path())?.trim().to_string();
+    if edited.is_empty() || edited == text.trim() {
+        Ok(None)
+    } else {
+        Ok(Some(edited))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_indices_skip_tool_results_and_hidden_messages() {
+        let messages = vec![
+            Message::user().with_text("first"),
+            Message::assistant().with_text("reply"),
+            Message::user().with_tool_response("call-1", Ok(vec![])),
+            Message::user().with_text("summary").agent_only(),
+            Message::user().with_text("second"),
+            Message::assistant().with_text("reply"),
+        ];
+
+        assert_eq!(user_prompt_indices(&messages), vec![0, 4]);
+        assert_eq!(nth_last_prompt(&messages, 1), Some(4));
+        assert_eq!(nth_last_prompt(&messages, 2), Some(0));
+        assert_eq!(nth_last_prompt(&messages, 3), None);
+        assert_eq!(nth_last_prompt(&messages, 0), None);
+    }
+
+    #[test]
+    fn test_redo_replaces_messages_sent_since_the_rewind() {
+        let text = |messages: &[Message]| messages.iter().map(prompt_text).collect::<Vec<_>>();
+        let mut state = RewindState {
+            rewinds: vec![Rewind {
+                index: 1,
+                messages: vec![Message::user().with_text("original")],
+            }],
+        };
+
+        // Round trip through the session's extension data, as across a restart
+        let mut extension_data = goose::session::ExtensionData::new();
+        state.to_extension_data(&mut extension_data).unwrap();
+        state = RewindState::from_extension_data(&extension_data).unwrap();
+
+        let current = vec![
+            Message::user().with_text("first"),
+            Message::user().with_text("edited"),
+        ];
+        let restored = state.redo(&current).unwrap();
+        assert_eq!(text(&restored), vec!["first", "original"]);
+        assert!(state.redo(&restored).is_none());
+    }
+}
@@ -0,0 +1,302 @@
+//! Newline-delimited JSON events for `goose run --output-format stream-json`.
+//!
+//! Every line written to stdout is one [`StreamEvent`], tagged by `type`. The schema is
+//! versioned through [`STREAM_JSON_SCHEMA_VERSION`] in the `start` event: fields may be
+//! added within a version, but never renamed or removed.
+
+use goose::agents::budget::BudgetKind;
+use goose::agents::AgentEvent;
+use goose::conversation::message::{Message, MessageContent, SystemNotificationType};
+use rmcp::model::{Content, Role, ServerNotification};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+
+pub const STREAM_JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// First event of every run
+    Start {
+        schema_version: u32,
+        session_id: String,
+    },
+    /// A chunk of assistant text; chunks sharing a `message_id` belong to one message
+    Text {
+        message_id: Option<String>,
+        text: String,
+    },
+    Thinking {
+        message_id: Option<String>,
+        text: String,
+    },
+    ToolRequest {
+        id: String,
+        name: Option<String>,
+        arguments: Option<Value>,
+        error: Option<String>,
+    },
+    ToolResult {
+        id: String,
+        is_error: bool,
+        content: Vec<Content>,
+        error: Option<String>,
+    },
+    /// The agent wants approval before running a tool
+    PermissionRequest {
+        id: String,
+        tool_name: String,
+        arguments: Value,
+        prompt: Option<String>,
+    },
+    /// Status lines from the agent such as compaction progress
+    Notice {
+        message: String,
+    },
+    /// Log output from an extension
+    Notification {
+        extension: String,
+        message: Value,
+    },
+    /// The conversation was compacted and replaced
+    Compaction {
+        message_count: usize,
+    },
+    ModelChange {
+        model: String,
+        mode: String,
+    },
+    Usage {
+        input_tokens: Option<i32>,
+        output_tokens: Option<i32>,
+        total_tokens: Option<i32>,
+    },
+    Error {
+        message: String,
+    },
+    /// The run was stopped because a budget limit was reached
+    BudgetExceeded {
+        limit: BudgetKind,
+        used: f64,
+        max: f64,
+    },
+    /// Last event of every run
+    Result {
+        status: RunStatus,
+        exit_code: i32,
+        session_id: String,
+        duration_ms: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Success,
+    Error,
+    Cancelled,
+    BudgetExceeded,
+}
+
+impl RunStatus {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            RunStatus::Success => 0,
+            RunStatus::Error | RunStatus::Cancelled => 1,
+            RunStatus::BudgetExceeded => 2,
+        }
+    }
+}
+
+/// Returned by `goose run` when it does not succeed, so the process can exit with
+/// the status's own code.
+#[derive(Debug)]
+pub struct RunStatusError(pub RunStatus);
+
+impl std::fmt::Display for RunStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            RunStatus::Success => write!(f, "goose run succeeded"),
+            RunStatus::Error => write!(f, "goose run failed"),
+            RunStatus::Cancelled => write!(f, "goose run was cancelled"),
+            RunStatus::BudgetExceeded => write!(f, "goose run stopped at its budget limit"),
+        }
+    }
+}
+
+impl RunStatusError {
+    pub fn exit_code(&self) -> i32 {
+        self.0.exit_code()
+    }
+}
+
+impl std::error::Error for RunStatusError {}
+
+pub fn emit(event: &StreamEvent) {
+    let mut stdout = std::io::stdout().lock();
+    if let Ok(line) = serde_json::to_string(event) {
+        let _ = writeln!(stdout, "{}", line);
+        let _ = stdout.flush();
+    }
+}
+
+/// Convert one agent event into the stream events describing it.
+pub fn events_from_agent_event(event: &AgentEvent) -> Vec<StreamEvent> {
+    match event {
+        AgentEvent::Message(message) => events_from_message(message),
+        AgentEvent::McpNotification((
+            extension,
+            ServerNotification::LoggingMessageNotification(n),
+        )) => {
+            vec![StreamEvent::Notification {
+                extension: extension.clone(),
+                message: n.params.data.clone(),
+            }]
+        }
+        AgentEvent::McpNotification(_) => Vec::new(),
+        AgentEvent::ModelChange { model, mode } => vec![StreamEvent::ModelChange {
+            model: model.clone(),
+            mode: mode.clone(),
+        }],
+        AgentEvent::HistoryReplaced(conversation) => vec![StreamEvent::Compaction {
+            message_count: conversation.messages().len(),
+        }],
+        AgentEvent::BudgetExceeded(limit) => vec![StreamEvent::BudgetExceeded {
+            limit: limit.kind,
+            used: limit.used,
+            max: limit.max,
+        }],
+    }
+}
+
+pub fn events_from_message(message: &Message) -> Vec<StreamEvent> {
+    message
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            MessageContent::Text(text) if message.role == Role::Assistant => {
+                Some(StreamEvent::Text {
+                    message_id: message.id.clone(),
+                    text: text.text.clone(),
+                })
+            }
+            MessageContent::Thinking(thinking) => Some(StreamEvent::Thinking {
+                message_id: message.id.clone(),
+                text: thinking.thinking.clone(),
+            }),
+            MessageContent::ToolRequest(request) => Some(match &request.tool_call {
+                Ok(call) => StreamEvent::ToolRequest {
+                    id: request.id.clone(),
+                    name: Some(call.name.to_string()),
+                    arguments: call.arguments.clone().map(Value::Object),
+                    error: None,
+                },
+                Err(e) => StreamEvent::ToolRequest {
+                    id: request.id.clone(),
+                    name: None,
+                    arguments: None,
+                    error: Some(e.message.to_string()),
+                },
+            }),
+            MessageContent::ToolResponse(response) => Some(match &response.tool_result {
+                Ok(content) => StreamEvent::ToolResult {
+                    id: response.id.clone(),
+                    is_error: false,
+                    content: content.clone(),
+                    error: None,
+                },
+                Err(e) => StreamEvent::ToolResult {
+                    id: response.id.clone(),
+                    is_error: true,
+                    content: Vec::new(),
+                    error: Some(e.message.to_string()),
+                },
+            }),
+            MessageContent::ToolConfirmationRequest(confirmation) => {
+                Some(StreamEvent::PermissionRequest {
+                    id: confirmation.id.clone(),
+                    tool_name: confirmation.tool_name.clone(),
+                    arguments: Value::Object(confirmation.arguments.clone()),
+                    prompt: confirmation.prompt.clone(),
+                })
+            }
+            MessageContent::SystemNotification(notification)
+                if notification.notification_type == SystemNotificationType::InlineMessage =>
+            {
+                Some(StreamEvent::Notice {
+                    message: notification.msg.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::object;
+
+    #[test]
+    fn test_tool_round_trip_events() {
+        let request = Message::assistant()
+            .with_text("Listing files")
+            .with_tool_request(
+                "call-1",
+                Ok(CallToolRequestParam {
+                    name: "developer__shell".into(),
+                    arguments: Some(object!({"command": "ls"})),
+                }),
+            );
+        let events = events_from_message(&request);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], StreamEvent::Text { text, .. } if text == "Listing files"));
+        let StreamEvent::ToolRequest { name, .. } = &events[1] else {
+            panic!("expected a tool request, got {:?}", events[1]);
+        };
+        assert_eq!(name.as_deref(), Some("developer__shell"));
+
+        let response =
+            Message::user().with_tool_response("call-1", Ok(vec![Content::text("Cargo.toml")]));
+        let line = serde_json::to_value(&events_from_message(&response)[0]).unwrap();
+        assert_eq!(line["type"], "tool_result");
+        assert_eq!(line["id"], "call-1");
+        assert_eq!(line["is_error"], false);
+        assert_eq!(line["content"][0]["text"], "Cargo.toml");
+    }
+
+    #[test]
+    fn test_user_text_is_not_echoed() {
+        let events = events_from_message(&Message::user().with_text("hello"));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_result_serialization() {
+        let event = StreamEvent::Result {
+            status: RunStatus::Error,
+            exit_code: RunStatus::Error.exit_code(),
+            session_id: "20250101_1".to_string(),
+            duration_ms: 12,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"result","status":"error","exit_code":1,"session_id":"20250101_1","duration_ms":12}"#
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded_event() {
+        let event = AgentEvent::BudgetExceeded(goose::agents::budget::BudgetLimit {
+            kind: BudgetKind::OutputTokens,
+            used: 1200.0,
+            max: 1000.0,
+        });
+        let line = serde_json::to_value(&events_from_agent_event(&event)[0]).unwrap();
+        assert_eq!(line["type"], "budget_exceeded");
+        assert_eq!(line["limit"], "output_tokens");
+        assert_eq!(RunStatus::BudgetExceeded.exit_code(), 2);
+    }
+}
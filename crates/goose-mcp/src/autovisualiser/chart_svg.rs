@@ -0,0 +1,384 @@
+use super::{ChartData, ChartDataValues, ChartType};
+use std::fmt::Write;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 450.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 60.0;
+const MARGIN_BOTTOM: f64 = 80.0;
+const PALETTE: [&str; 8] = [
+    "#36a2eb", "#ff6384", "#4bc0c0", "#ff9f40", "#9966ff", "#ffcd56", "#c9cbcf", "#2e7d32",
+];
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        let formatted = format!("{:.2}", value);
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+/// Tick positions covering `min..=max` at a 1, 2 or 5 step, about five of them
+fn ticks(min: f64, max: f64) -> Vec<f64> {
+    let raw_step = (max - min) / 5.0;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = match raw_step / magnitude {
+        n if n <= 1.0 => magnitude,
+        n if n <= 2.0 => 2.0 * magnitude,
+        n if n <= 5.0 => 5.0 * magnitude,
+        _ => 10.0 * magnitude,
+    };
+    let first = (min / step).floor() as i64;
+    let last = (max / step).ceil() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+fn range(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values
+        .filter(|v| v.is_finite())
+        .fold(None, |acc, v| match acc {
+            None => Some((v, v)),
+            Some((min, max)) => Some((min.min(v), max.max(v))),
+        })
+}
+
+/// Widen a degenerate range so it can be scaled
+fn padded((min, max): (f64, f64)) -> (f64, f64) {
+    if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+struct Scale {
+    domain: (f64, f64),
+    range: (f64, f64),
+}
+
+impl Scale {
+    fn apply(&self, value: f64) -> f64 {
+        let (d0, d1) = self.domain;
+        let (r0, r1) = self.range;
+        r0 + (value - d0) / (d1 - d0) * (r1 - r0)
+    }
+}
+
+/// Static SVG rendering of a chart, for places the interactive Chart.js version
+/// cannot run such as exported reports.
+///
+/// Bar charts and datasets given as plain numbers are drawn against categories
+/// (the labels, or the value index); x/y points use a numeric x axis.
+pub fn chart_to_svg(chart: &ChartData) -> String {
+    let categorical = matches!(chart.chart_type, ChartType::Bar)
+        || chart
+            .datasets
+            .iter()
+            .any(|d| matches!(d.data, ChartDataValues::Numbers(_)));
+
+    // Every dataset as (x, y) pairs, where x is the category index when categorical
+    let series: Vec<Vec<(f64, f64)>> = chart
+        .datasets
+        .iter()
+        .map(|dataset| match &dataset.data {
+            ChartDataValues::Numbers(values) => values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i as f64, *v))
+                .collect(),
+            ChartDataValues::Points(points) if categorical => points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i as f64, p.y))
+                .collect(),
+            ChartDataValues::Points(points) => points.iter().map(|p| (p.x, p.y)).collect(),
+        })
+        .collect();
+
+    let plot_left = MARGIN_LEFT;
+    let plot_right = WIDTH - MARGIN_RIGHT;
+    let plot_top = MARGIN_TOP;
+    let plot_bottom = HEIGHT - MARGIN_BOTTOM;
+
+    let category_count = chart
+        .labels
+        .as_ref()
+        .map(Vec::len)
+        .unwrap_or(0)
+        .max(series.iter().map(Vec::len).max().unwrap_or(0))
+        .max(1);
+    let band = (plot_right - plot_left) / category_count as f64;
+
+    let (mut y_min, y_max) =
+        padded(range(series.iter().flatten().map(|(_, y)| *y)).unwrap_or((0.0, 1.0)));
+    if matches!(chart.chart_type, ChartType::Bar) || y_min > 0.0 {
+        y_min = y_min.min(0.0);
+    }
+    let y_ticks = ticks(y_min, y_max);
+    let y_scale = Scale {
+        domain: (y_ticks[0], *y_ticks.last().unwrap_or(&y_max)),
+        range: (plot_bottom, plot_top),
+    };
+
+    let x_ticks = if categorical {
+        Vec::new()
+    } else {
+        let (x_min, x_max) =
+            padded(range(series.iter().flatten().map(|(x, _)| *x)).unwrap_or((0.0, 1.0)));
+        ticks(x_min, x_max)
+    };
+    let x_scale = Scale {
+        domain: if categorical {
+            (-0.5, category_count as f64 - 0.5)
+        } else {
+            (x_ticks[0], *x_ticks.last().unwrap_or(&1.0))
+        },
+        range: (plot_left, plot_right),
+    };
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{WIDTH}" height="{HEIGHT}" fill="white"/>"#
+    );
+
+    if let Some(title) = &chart.title {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="24" text-anchor="middle" font-size="18" font-weight="bold">{}</text>"#,
+            WIDTH / 2.0,
+            escape(title)
+        );
+    }
+    if let Some(subtitle) = &chart.subtitle {
+        let _ = writeln!(
+            svg,
+            r##"<text x="{}" y="44" text-anchor="middle" fill="#666">{}</text>"##,
+            WIDTH / 2.0,
+            escape(subtitle)
+        );
+    }
+
+    // Grid lines and axis ticks
+    for tick in &y_ticks {
+        let y = y_scale.apply(*tick);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{plot_left}" y1="{y:.1}" x2="{plot_right}" y2="{y:.1}" stroke="#e5e5e5"/><text x="{:.1}" y="{:.1}" text-anchor="end" fill="#666">{}</text>"##,
+            plot_left - 6.0,
+            y + 4.0,
+            format_number(*tick)
+        );
+    }
+    if categorical {
+        for i in 0..category_count {
+            let label = chart
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(i))
+                .cloned()
+                .unwrap_or_else(|| (i + 1).to_string());
+            let _ = writeln!(
+                svg,
+                r##"<text x="{:.1}" y="{:.1}" text-anchor="middle" fill="#666">{}</text>"##,
+                x_scale.apply(i as f64),
+                plot_bottom + 16.0,
+                escape(&label)
+            );
+        }
+    } else {
+        for tick in &x_ticks {
+            let _ = writeln!(
+                svg,
+                r##"<text x="{:.1}" y="{:.1}" text-anchor="middle" fill="#666">{}</text>"##,
+                x_scale.apply(*tick),
+                plot_bottom + 16.0,
+                format_number(*tick)
+            );
+        }
+    }
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{plot_left}" y1="{plot_bottom}" x2="{plot_right}" y2="{plot_bottom}" stroke="#999"/><line x1="{plot_left}" y1="{plot_top}" x2="{plot_left}" y2="{plot_bottom}" stroke="#999"/>"##
+    );
+
+    if let Some(label) = &chart.x_axis_label {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            (plot_left + plot_right) / 2.0,
+            plot_bottom + 36.0,
+            escape(label)
+        );
+    }
+    if let Some(label) = &chart.y_axis_label {
+        let x = 16.0;
+        let y = (plot_top + plot_bottom) / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{y:.1}" text-anchor="middle" transform="rotate(-90 {x} {y:.1})">{}</text>"#,
+            escape(label)
+        );
+    }
+
+    // Data
+    let dataset_count = series.len().max(1) as f64;
+    let bar_width = band * 0.8 / dataset_count;
+    let baseline = y_scale.apply(0f64.clamp(y_scale.domain.0, y_scale.domain.1));
+    for (index, (dataset, points)) in chart.datasets.iter().zip(&series).enumerate() {
+        let color = escape(
+            dataset
+                .border_color
+                .as_deref()
+                .or(dataset.background_color.as_deref())
+                .unwrap_or(PALETTE[index % PALETTE.len()]),
+        );
+        let fill = escape(dataset.background_color.as_deref().unwrap_or(&color));
+
+        match chart.chart_type {
+            ChartType::Bar => {
+                for (x, y) in points {
+                    let left = x_scale.apply(*x) - band * 0.4 + bar_width * index as f64;
+                    let top = y_scale.apply(*y);
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{left:.1}" y="{:.1}" width="{bar_width:.1}" height="{:.1}" fill="{fill}" stroke="{color}"/>"#,
+                        top.min(baseline),
+                        (top - baseline).abs()
+                    );
+                }
+            }
+            ChartType::Line => {
+                let path: Vec<String> = points
+                    .iter()
+                    .map(|(x, y)| format!("{:.1},{:.1}", x_scale.apply(*x), y_scale.apply(*y)))
+                    .collect();
+                let width = dataset.border_width.unwrap_or(2.0);
+                let _ = writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="{width}"/>"#,
+                    path.join(" ")
+                );
+                for (x, y) in points {
+                    let _ = writeln!(
+                        svg,
+                        r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{color}"/>"#,
+                        x_scale.apply(*x),
+                        y_scale.apply(*y)
+                    );
+                }
+            }
+            ChartType::Scatter => {
+                for (x, y) in points {
+                    let _ = writeln!(
+                        svg,
+                        r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="{fill}" stroke="{color}"/>"#,
+                        x_scale.apply(*x),
+                        y_scale.apply(*y)
+                    );
+                }
+            }
+        }
+
+        // Legend entries run along the bottom edge
+        let legend_x = plot_left + index as f64 * 150.0;
+        let legend_y = HEIGHT - 16.0;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{legend_x:.1}" y="{:.1}" width="12" height="12" fill="{color}"/><text x="{:.1}" y="{legend_y:.1}">{}</text>"#,
+            legend_y - 10.0,
+            legend_x + 18.0,
+            escape(&dataset.label)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autovisualiser::{ChartDataset, ChartPoint};
+
+    fn dataset(label: &str, data: ChartDataValues) -> ChartDataset {
+        ChartDataset {
+            label: label.to_string(),
+            data,
+            background_color: None,
+            border_color: None,
+            border_width: None,
+            tension: None,
+            fill: None,
+        }
+    }
+
+    #[test]
+    fn test_ticks_use_round_steps() {
+        assert_eq!(ticks(0.0, 80.0), vec![0.0, 20.0, 40.0, 60.0, 80.0]);
+        assert_eq!(ticks(-3.0, 7.0), vec![-4.0, -2.0, 0.0, 2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn test_bar_chart_svg() {
+        let chart = ChartData {
+            chart_type: ChartType::Bar,
+            datasets: vec![
+                dataset("2024", ChartDataValues::Numbers(vec![10.0, 20.0, 30.0])),
+                dataset("2025", ChartDataValues::Numbers(vec![15.0, 25.0, 35.0])),
+            ],
+            labels: Some(vec!["Q1".into(), "Q2".into(), "Q3 <est>".into()]),
+            title: Some("Revenue & costs".to_string()),
+            subtitle: None,
+            x_axis_label: None,
+            y_axis_label: Some("USD".to_string()),
+        };
+        let svg = chart_to_svg(&chart);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect x=").count(), 6 + 2);
+        assert!(svg.contains("Revenue &amp; costs"));
+        assert!(svg.contains("Q3 &lt;est&gt;"));
+        assert!(svg.contains(">USD</text>"));
+    }
+
+    #[test]
+    fn test_scatter_chart_svg() {
+        let chart = ChartData {
+            chart_type: ChartType::Scatter,
+            datasets: vec![dataset(
+                "Samples",
+                ChartDataValues::Points(vec![
+                    ChartPoint { x: 1.0, y: 2.0 },
+                    ChartPoint { x: 3.0, y: 5.0 },
+                ]),
+            )],
+            labels: None,
+            title: None,
+            subtitle: None,
+            x_axis_label: None,
+            y_axis_label: None,
+        };
+        let svg = chart_to_svg(&chart);
+
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert!(svg.contains(">Samples</text>"));
+    }
+}
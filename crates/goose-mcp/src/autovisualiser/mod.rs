@@ -1,11 +1,15 @@
+mod chart_svg;
+
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use etcetera::{choose_app_strategy, AppStrategy};
+use goose::session::artifacts::ArtifactStore;
+use goose::session_context::SESSION_ID_HEADER;
 use indoc::formatdoc;
 use rmcp::{
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::{
-        CallToolResult, Content, ErrorCode, ErrorData, Implementation, ResourceContents, Role,
-        ServerCapabilities, ServerInfo,
+        CallToolResult, Content, ErrorCode, ErrorData, Implementation, Meta, ResourceContents,
+        Role, ServerCapabilities, ServerInfo,
     },
     tool, tool_handler, tool_router, ServerHandler,
 };
@@ -13,6 +17,18 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
 
+/// Config key that turns on saving every visualization to the session's artifacts
+pub const SAVE_ARTIFACTS_KEY: &str = "GOOSE_AUTOVISUALISER_SAVE_ARTIFACTS";
+
+/// The goose session a tool call belongs to, sent by goose in the request metadata
+fn session_id(meta: &Meta) -> Option<String> {
+    meta.0
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(SESSION_ID_HEADER))
+        .and_then(|(_, value)| value.as_str())
+        .map(str::to_string)
+}
+
 /// Validates that the data parameter is a proper JSON value and not a string
 fn validate_data_param(params: &Value, allow_array: bool) -> Result<Value, ErrorData> {
     let data_value = params.get("data").ok_or_else(|| {
@@ -400,6 +416,8 @@ pub struct AutoVisualiserRouter {
     tool_router: ToolRouter<Self>,
     #[allow(dead_code)]
     cache_dir: PathBuf,
+    artifacts: ArtifactStore,
+    save_artifacts: bool,
     instructions: String,
 }
 
@@ -427,6 +445,45 @@ impl ServerHandler for AutoVisualiserRouter {
     }
 }
 
+impl AutoVisualiserRouter {
+    /// Save a rendered visualization to the session's artifacts when that is enabled,
+    /// returning a note for the model about where it was written.
+    fn save_artifact(
+        &self,
+        meta: &Meta,
+        kind: &str,
+        title: Option<&str>,
+        html: &str,
+        svg: Option<&str>,
+    ) -> Option<Content> {
+        if !self.save_artifacts {
+            return None;
+        }
+        let Some(session_id) = session_id(meta) else {
+            tracing::warn!("Not saving {} visualization: no session id", kind);
+            return None;
+        };
+
+        match self.artifacts.save(&session_id, kind, title, html, svg) {
+            Ok(artifact) => {
+                let path = self.artifacts.path_of(&session_id, &artifact.html_file);
+                Some(
+                    Content::text(format!(
+                        "Saved visualization {} to {}",
+                        artifact.id,
+                        path.display()
+                    ))
+                    .with_audience(vec![Role::Assistant]),
+                )
+            }
+            Err(e) => {
+                tracing::warn!("Failed to save {} visualization: {}", kind, e);
+                None
+            }
+        }
+    }
+}
+
 #[tool_router(router = tool_router)]
 impl AutoVisualiserRouter {
     pub fn new() -> Self {
@@ -457,11 +514,21 @@ impl AutoVisualiserRouter {
             - **render_map**: Creates interactive map visualizations with location markers
             - **render_mermaid**: Creates interactive Mermaid diagrams from Mermaid syntax
             - **show_chart**: Creates interactive line, scatter, or bar charts for data visualization
+            - **list_visualizations**: Lists the visualizations saved earlier in this session
+
+            When {SAVE_ARTIFACTS_KEY} is enabled every visualization is also saved as a standalone
+            HTML file (plus a static SVG for charts) and included when the session is exported.
         "#};
 
+        let save_artifacts = goose::config::Config::global()
+            .get_param::<bool>(SAVE_ARTIFACTS_KEY)
+            .unwrap_or(false);
+
         Self {
             tool_router: Self::tool_router(),
             cache_dir,
+            artifacts: ArtifactStore::default(),
+            save_artifacts,
             instructions,
         }
     }
@@ -488,6 +555,7 @@ Example:
     pub async fn render_sankey(
         &self,
         params: Parameters<RenderSankeyParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -539,10 +607,10 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "sankey", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show a radar chart (spider chart) for multi-dimensional data comparison
@@ -572,6 +640,7 @@ Example:
     pub async fn render_radar(
         &self,
         params: Parameters<RenderRadarParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -621,10 +690,10 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "radar", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show pie or donut charts for categorical data visualization
@@ -659,6 +728,7 @@ Example multiple charts:
     pub async fn render_donut(
         &self,
         params: Parameters<RenderDonutParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -708,10 +778,10 @@ Example multiple charts:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "donut", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show a treemap visualization for hierarchical data
@@ -743,6 +813,7 @@ Example:
     pub async fn render_treemap(
         &self,
         params: Parameters<RenderTreemapParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -792,10 +863,10 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "treemap", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// Show a chord diagram visualization for relationships and flows
@@ -821,6 +892,7 @@ Example:
     pub async fn render_chord(
         &self,
         params: Parameters<RenderChordParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -870,10 +942,10 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "chord", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show an interactive map visualization with location markers
@@ -913,6 +985,7 @@ Example:
     pub async fn render_map(
         &self,
         params: Parameters<RenderMapParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
@@ -979,10 +1052,10 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "map", Some(title), &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show a Mermaid diagram from Mermaid syntax
@@ -1003,6 +1076,7 @@ graph TD;
     pub async fn render_mermaid(
         &self,
         params: Parameters<RenderMermaidParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
         let mermaid_code = params.0.mermaid_code;
 
@@ -1034,10 +1108,10 @@ graph TD;
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(&meta, "mermaid", None, &html_content, None));
+        Ok(CallToolResult::success(content))
     }
 
     /// show interactive line, scatter, or bar charts
@@ -1061,7 +1135,12 @@ Example:
     pub async fn show_chart(
         &self,
         params: Parameters<ShowChartParams>,
+        meta: Meta,
     ) -> Result<CallToolResult, ErrorData> {
+        let title = params.0.data.title.clone();
+        let svg = self
+            .save_artifacts
+            .then(|| chart_svg::chart_to_svg(&params.0.data));
         let data = validate_data_param(
             &serde_json::to_value(params.0).map_err(|e| {
                 ErrorData::new(
@@ -1110,10 +1189,75 @@ Example:
             meta: None,
         };
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let mut content =
+            vec![Content::resource(resource_contents).with_audience(vec![Role::User])];
+        content.extend(self.save_artifact(
+            &meta,
+            "chart",
+            title.as_deref(),
+            &html_content,
+            svg.as_deref(),
+        ));
+        Ok(CallToolResult::success(content))
+    }
+
+    /// list the visualizations saved earlier in this session
+    #[tool(
+        name = "list_visualizations",
+        description = r#"list the visualizations saved earlier in this session
+
+Returns each saved visualization's id, type, title, creation time and the paths of its standalone HTML file and, for charts, static SVG. Visualizations are only saved when saving is enabled in the goose configuration."#
+    )]
+    pub async fn list_visualizations(&self, meta: Meta) -> Result<CallToolResult, ErrorData> {
+        let session_id = session_id(&meta).ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_REQUEST,
+                "Saved visualizations are only available within a goose session".to_string(),
+                None,
+            )
+        })?;
+
+        let artifacts = self.artifacts.list(&session_id);
+        if artifacts.is_empty() {
+            let hint = if self.save_artifacts {
+                String::new()
+            } else {
+                format!(
+                    " Saving is disabled; set {} to true to enable it.",
+                    SAVE_ARTIFACTS_KEY
+                )
+            };
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No visualizations have been saved in this session.{}",
+                hint
+            ))]));
+        }
+
+        let mut listing = format!("{} saved visualization(s):\n", artifacts.len());
+        for artifact in &artifacts {
+            listing.push_str(&format!(
+                "- {} ({}{}), created {}\n  html: {}\n",
+                artifact.id,
+                artifact.kind,
+                artifact
+                    .title
+                    .as_ref()
+                    .map(|title| format!(": {}", title))
+                    .unwrap_or_default(),
+                artifact.created_at.to_rfc3339(),
+                self.artifacts
+                    .path_of(&session_id, &artifact.html_file)
+                    .display()
+            ));
+            if let Some(svg_file) = &artifact.svg_file {
+                listing.push_str(&format!(
+                    "  svg: {}\n",
+                    self.artifacts.path_of(&session_id, svg_file).display()
+                ));
+            }
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(listing)]))
     }
 }
 
@@ -1281,7 +1425,7 @@ mod tests {
             },
         });
 
-        let result = router.render_sankey(params).await;
+        let result = router.render_sankey(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1326,7 +1470,7 @@ mod tests {
             },
         });
 
-        let result = router.render_radar(params).await;
+        let result = router.render_radar(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1377,7 +1521,7 @@ mod tests {
             },
         });
 
-        let result = router.render_donut(params).await;
+        let result = router.render_donut(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1415,7 +1559,7 @@ mod tests {
             },
         });
 
-        let result = router.render_treemap(params).await;
+        let result = router.render_treemap(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1442,7 +1586,7 @@ mod tests {
             },
         });
 
-        let result = router.render_chord(params).await;
+        let result = router.render_chord(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1481,7 +1625,7 @@ mod tests {
             },
         });
 
-        let result = router.render_map(params).await;
+        let result = router.render_map(params, Meta::default()).await;
         assert!(result.is_ok());
         let tool_result = result.unwrap();
         assert_eq!(tool_result.content.len(), 1);
@@ -1520,7 +1664,7 @@ mod tests {
             },
         });
 
-        let result = router.show_chart(params).await;
+        let result = router.show_chart(params, Meta::default()).await;
         if let Err(e) = &result {
             eprintln!("Error in test_show_chart: {:?}", e);
         }
@@ -1548,7 +1692,7 @@ mod tests {
                 .to_string(),
         });
 
-        let result = router.render_mermaid(params).await;
+        let result = router.render_mermaid(params, Meta::default()).await;
         if let Err(e) = &result {
             eprintln!("Error in test_render_mermaid: {:?}", e);
         }
@@ -1563,4 +1707,72 @@ mod tests {
             &vec![Role::User]
         );
     }
+
+    #[tokio::test]
+    async fn test_visualizations_saved_to_session_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let router = AutoVisualiserRouter {
+            artifacts: ArtifactStore::new(dir.path()),
+            save_artifacts: true,
+            ..AutoVisualiserRouter::new()
+        };
+        let mut meta = Meta::default();
+        meta.0
+            .insert(SESSION_ID_HEADER.to_string(), json!("20250101_1"));
+
+        let params = Parameters(ShowChartParams {
+            data: ChartData {
+                chart_type: ChartType::Bar,
+                datasets: vec![ChartDataset {
+                    label: "Sales".to_string(),
+                    data: ChartDataValues::Numbers(vec![3.0, 5.0]),
+                    background_color: None,
+                    border_color: None,
+                    border_width: None,
+                    tension: None,
+                    fill: None,
+                }],
+                labels: Some(vec!["Jan".to_string(), "Feb".to_string()]),
+                title: Some("Monthly Sales".to_string()),
+                subtitle: None,
+                x_axis_label: None,
+                y_axis_label: None,
+            },
+        });
+        let result = router.show_chart(params, meta.clone()).await.unwrap();
+        assert_eq!(result.content.len(), 2);
+        assert_eq!(
+            result.content[1].audience().unwrap(),
+            &vec![Role::Assistant]
+        );
+
+        let params = Parameters(RenderMermaidParams {
+            mermaid_code: "graph TD;\n    A-->B;".to_string(),
+        });
+        router.render_mermaid(params, meta.clone()).await.unwrap();
+
+        let saved = router.artifacts.list("20250101_1");
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0].kind, "chart");
+        assert_eq!(saved[0].title.as_deref(), Some("Monthly Sales"));
+        let svg = std::fs::read_to_string(
+            router
+                .artifacts
+                .path_of("20250101_1", saved[0].svg_file.as_ref().unwrap()),
+        )
+        .unwrap();
+        assert!(svg.contains("Monthly Sales"));
+        assert_eq!(saved[1].kind, "mermaid");
+        assert!(saved[1].svg_file.is_none());
+
+        let listing = router.list_visualizations(meta).await.unwrap();
+        let RawContent::Text(text) = &*listing.content[0] else {
+            panic!("Expected text content");
+        };
+        assert!(text.text.starts_with("2 saved visualization(s)"));
+        assert!(text.text.contains("(chart: Monthly Sales)"));
+        assert!(text.text.contains("svg: "));
+
+        assert!(router.list_visualizations(Meta::default()).await.is_err());
+    }
 }
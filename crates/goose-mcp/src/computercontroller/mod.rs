@@ -13,7 +13,13 @@ use rmcp::{
     tool, tool_handler, tool_router, RoleServer, ServerHandler,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::PathBuf, sync::Arc, sync::Mutex};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    sync::Mutex,
+};
 use tokio::process::Command;
 
 #[cfg(unix)]
@@ -21,6 +27,8 @@ use std::os::unix::fs::PermissionsExt;
 
 mod docx_tool;
 mod pdf_tool;
+mod readability;
+mod web_scrape;
 mod xlsx_tool;
 
 mod platform;
@@ -33,6 +41,8 @@ pub enum SaveAsFormat {
     /// Save as text (for HTML pages)
     #[default]
     Text,
+    /// Save the readable content of an HTML page as Markdown, without navigation and scripts
+    Markdown,
     /// Save as JSON (for API responses)
     Json,
     /// Save as binary (for images and other files)
@@ -47,6 +57,13 @@ pub struct WebScrapeParams {
     /// How to interpret and save the content
     #[serde(default)]
     pub save_as: SaveAsFormat,
+    /// Crawl up to this many pages on the same site, following links from the url (default 1, no crawling; at most 50)
+    pub max_pages: Option<usize>,
+    /// How many links away from the url to follow when crawling (default 2)
+    pub max_depth: Option<usize>,
+    /// Include the links found on the page in the response
+    #[serde(default)]
+    pub extract_links: bool,
 }
 
 /// Enum for language parameter in automation_script tool
@@ -114,10 +131,16 @@ pub struct CacheParams {
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum PdfOperation {
-    /// Extract all text content from the PDF
+    /// Extract text content from the PDF, page by page
     ExtractText,
+    /// Detect tables from the text layout and save them as CSV files
+    ExtractTables,
     /// Extract and save embedded images to PNG files
     ExtractImages,
+    /// List the outline (bookmarks) with page numbers
+    Outline,
+    /// Get document metadata such as title, author and page count
+    Metadata,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -126,6 +149,8 @@ pub struct PdfToolParams {
     pub path: String,
     /// Operation to perform on the PDF
     pub operation: PdfOperation,
+    /// Pages for extract_text and extract_tables, e.g. '3', '1-5' or '1-3,8,10-' (defaults to all pages)
+    pub pages: Option<String>,
 }
 
 /// Enum for operation parameter in docx_tool
@@ -250,6 +275,20 @@ pub enum XlsxOperation {
     GetCell,
     /// Save changes back to the file
     Save,
+    /// Create a new workbook at the given path
+    CreateWorkbook,
+    /// Add an empty worksheet
+    AddWorksheet,
+    /// Write rows of values and formulas starting at a cell
+    WriteRange,
+    /// Append rows after the last used row
+    AppendRows,
+    /// Export a worksheet as CSV
+    ToCsv,
+    /// Import a CSV file into a new worksheet
+    FromCsv,
+    /// Set width, number format and header styling for columns
+    FormatColumns,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -273,6 +312,21 @@ pub struct XlsxToolParams {
     pub col: Option<u64>,
     /// New value for update_cell operation
     pub value: Option<String>,
+    /// Top-left cell in A1 notation for write_range (defaults to 'A1')
+    pub start_cell: Option<String>,
+    /// Rows of cell values for write_range and append_rows; values starting with '=' are written as formulas
+    pub values: Option<Vec<Vec<String>>>,
+    /// CSV file to read for from_csv, or to write for to_csv (to_csv returns the CSV text if omitted)
+    pub csv_path: Option<String>,
+    /// Column or column range for format_columns (e.g., 'B' or 'B:D')
+    pub columns: Option<String>,
+    /// Column width for format_columns
+    pub width: Option<f64>,
+    /// Excel number format code for format_columns (e.g., '#,##0.00' or 'yyyy-mm-dd'), applied below the header row
+    pub number_format: Option<String>,
+    /// Make the header cell bold in format_columns
+    #[serde(default)]
+    pub bold_header: bool,
 }
 
 /// ComputerController MCP Server using official RMCP SDK
@@ -398,8 +452,9 @@ impl ComputerControllerServer {
 
             web_scrape
               - Fetch content from html websites and APIs
-              - Save as text, JSON, or binary files
-              - Content is cached locally for later use
+              - Save as text, markdown (readable page content), JSON, or binary files
+              - Crawl a few pages of the same site, honouring robots.txt
+              - Content is cached locally for later use, and unchanged pages are not downloaded again
               - This is not optimised for complex websites, so don't use this as the first tool.
             cache
               - Manage your cached files
@@ -477,10 +532,15 @@ impl ComputerControllerServer {
         description = "
             Fetch and save content from a web page. The content can be saved as:
             - text (for HTML pages)
+            - markdown (the readable content of HTML pages, without navigation, scripts and boilerplate)
             - json (for API responses)
             - binary (for images and other files)
             The content is cached locally and can be accessed later using the cache_path
-            returned in the response.
+            returned in the response. Pages fetched before are only downloaded again if they changed.
+
+            Set max_pages above 1 to crawl pages on the same site reachable from the url, up to
+            max_depth links away. Crawling honours robots.txt. Set extract_links to list the links
+            found on the page.
         "
     )]
     pub async fn web_scrape(
@@ -488,79 +548,83 @@ impl ComputerControllerServer {
         params: Parameters<WebScrapeParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let params = params.0;
-        let url = &params.url;
-        let save_as = params.save_as;
-
-        // Fetch the content
-        let response = self.http_client.get(url).send().await.map_err(|e| {
+        let url = Url::parse(&params.url).map_err(|e| {
             ErrorData::new(
-                ErrorCode::INTERNAL_ERROR,
-                format!("Failed to fetch URL: {}", e),
+                ErrorCode::INVALID_PARAMS,
+                format!("Invalid URL '{}': {}", params.url, e),
                 None,
             )
         })?;
+        let max_pages = params
+            .max_pages
+            .unwrap_or(1)
+            .clamp(1, web_scrape::MAX_CRAWL_PAGES);
 
-        let status = response.status();
-        if !status.is_success() {
-            return Err(ErrorData::new(
-                ErrorCode::INTERNAL_ERROR,
-                format!("HTTP request failed with status: {}", status),
-                None,
-            ));
+        if max_pages == 1 {
+            let page = web_scrape::fetch(&self.http_client, &self.cache_dir, &url, &params.save_as)
+                .await?;
+            self.register_as_resource(&page.path, page.mime_type)?;
+
+            let mut result = format!("Content saved to: {}", page.path.display());
+            if page.not_modified {
+                result.push_str("\nThe page has not changed since it was last fetched.");
+            }
+            if params.extract_links {
+                result.push_str(&format!("\n\nLinks ({}):", page.links.len()));
+                for link in &page.links {
+                    result.push_str(&format!("\n- {}", link));
+                }
+            }
+            return Ok(CallToolResult::success(vec![Content::text(result)]));
         }
 
-        // Process based on save_as parameter
-        let (content, extension, mime_type) = match save_as {
-            SaveAsFormat::Text => {
-                let text = response.text().await.map_err(|e| {
-                    ErrorData::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        format!("Failed to get text: {}", e),
-                        None,
-                    )
-                })?;
-                (text.into_bytes(), "txt", "text/plain")
+        let options = web_scrape::ScrapeOptions {
+            save_as: params.save_as,
+            max_pages,
+            max_depth: params.max_depth.unwrap_or(web_scrape::DEFAULT_CRAWL_DEPTH),
+        };
+        let report = web_scrape::crawl(&self.http_client, &self.cache_dir, &url, &options).await?;
+
+        let mut result = format!("Crawled {} pages from {}:", report.pages.len(), url);
+        for page in &report.pages {
+            self.register_as_resource(&page.path, page.mime_type)?;
+            let title = page
+                .title
+                .as_ref()
+                .map(|title| format!(" \"{}\"", title))
+                .unwrap_or_default();
+            let unchanged = if page.not_modified {
+                " (unchanged)"
+            } else {
+                ""
+            };
+            result.push_str(&format!(
+                "\n- {}{} saved to: {}{}",
+                page.url,
+                title,
+                page.path.display(),
+                unchanged
+            ));
+            if params.extract_links {
+                for link in &page.links {
+                    result.push_str(&format!("\n    - {}", link));
+                }
             }
-            SaveAsFormat::Json => {
-                let text = response.text().await.map_err(|e| {
-                    ErrorData::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        format!("Failed to get text: {}", e),
-                        None,
-                    )
-                })?;
-                // Verify it's valid JSON
-                serde_json::from_str::<serde_json::Value>(&text).map_err(|e| {
-                    ErrorData::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        format!("Invalid JSON response: {}", e),
-                        None,
-                    )
-                })?;
-                (text.into_bytes(), "json", "application/json")
+        }
+        if !report.disallowed.is_empty() {
+            result.push_str("\n\nSkipped (disallowed by robots.txt):");
+            for url in &report.disallowed {
+                result.push_str(&format!("\n- {}", url));
             }
-            SaveAsFormat::Binary => {
-                let bytes = response.bytes().await.map_err(|e| {
-                    ErrorData::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        format!("Failed to get bytes: {}", e),
-                        None,
-                    )
-                })?;
-                (bytes.to_vec(), "bin", "application/octet-stream")
+        }
+        if !report.failed.is_empty() {
+            result.push_str("\n\nFailed:");
+            for (url, error) in &report.failed {
+                result.push_str(&format!("\n- {}: {}", url, error));
             }
-        };
-
-        // Save to cache
-        let cache_path = self.save_to_cache(&content, "web", extension).await?;
-
-        // Register as a resource
-        self.register_as_resource(&cache_path, mime_type)?;
+        }
 
-        Ok(CallToolResult::success(vec![Content::text(format!(
-            "Content saved to: {}",
-            cache_path.display()
-        ))]))
+        Ok(CallToolResult::success(vec![Content::text(result)]))
     }
 
     /// Create and run small scripts for automation tasks
@@ -895,8 +959,17 @@ impl ComputerControllerServer {
             - update_cell: Update a single cell's value (returns confirmation message)
             - get_cell: Get value and formula from a specific cell (returns both value and formula if present)
             - save: Save changes back to the file (returns confirmation message)
+            - create_workbook: Create a new workbook at path with one worksheet (named by worksheet, default 'Sheet1')
+            - add_worksheet: Add an empty worksheet named by worksheet
+            - write_range: Write values (a 2D array of [row][column]) starting at start_cell; values starting with '=' are written as formulas
+            - append_rows: Write values as new rows after the last used row
+            - to_csv: Export a worksheet as CSV (written to csv_path if given, otherwise returned)
+            - from_csv: Import csv_path into a new worksheet (named by worksheet, default the CSV file name); creates the workbook if it does not exist
+            - format_columns: Set width, number_format and bold_header for columns (e.g., 'B' or 'B:D')
 
-            Use this when working with Excel spreadsheets to analyze or modify data.
+            Writing operations save the workbook immediately. Formulas are calculated when the file is opened in Excel.
+
+            Use this when working with Excel spreadsheets to analyze, modify or produce data.
         "
     )]
     pub async fn xlsx_tool(
@@ -1078,6 +1151,174 @@ impl ComputerControllerServer {
                     cell_value
                 ))]))
             }
+            XlsxOperation::CreateWorkbook => {
+                if Path::new(path).exists() {
+                    return Err(ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        format!("'{}' already exists", path),
+                        None,
+                    ));
+                }
+                let worksheet_name = params.worksheet.as_deref().unwrap_or("Sheet1");
+                let xlsx = xlsx_tool::XlsxTool::create(worksheet_name)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                xlsx.save(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Created workbook '{}' with worksheet '{}'",
+                    path, worksheet_name
+                ))]))
+            }
+            XlsxOperation::AddWorksheet => {
+                let worksheet_name = params.worksheet.as_ref().ok_or_else(|| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        "Missing 'worksheet' parameter".to_string(),
+                        None,
+                    )
+                })?;
+
+                let mut xlsx = xlsx_tool::XlsxTool::new(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                xlsx.add_worksheet(worksheet_name)
+                    .map_err(|e| ErrorData::new(ErrorCode::INVALID_PARAMS, e.to_string(), None))?;
+                xlsx.save(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Added worksheet '{}'",
+                    worksheet_name
+                ))]))
+            }
+            XlsxOperation::WriteRange | XlsxOperation::AppendRows => {
+                let values = params.values.as_ref().ok_or_else(|| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        "Missing 'values' parameter".to_string(),
+                        None,
+                    )
+                })?;
+
+                let mut xlsx = xlsx_tool::XlsxTool::new(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let worksheet_name = xlsx
+                    .worksheet_name_or_first(params.worksheet.as_deref())
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let written = if matches!(operation, XlsxOperation::WriteRange) {
+                    let start_cell = params.start_cell.as_deref().unwrap_or("A1");
+                    xlsx.write_range(&worksheet_name, start_cell, values)
+                } else {
+                    xlsx.append_rows(&worksheet_name, values)
+                }
+                .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                xlsx.save(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Wrote {} row(s) to {} in worksheet '{}'",
+                    values.len(),
+                    written,
+                    worksheet_name
+                ))]))
+            }
+            XlsxOperation::ToCsv => {
+                let xlsx = xlsx_tool::XlsxTool::new(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let worksheet = if let Some(name) = &params.worksheet {
+                    xlsx.get_worksheet_by_name(name).map_err(|e| {
+                        ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None)
+                    })?
+                } else {
+                    xlsx.get_worksheet_by_index(0).map_err(|e| {
+                        ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None)
+                    })?
+                };
+                let csv = xlsx
+                    .to_csv(worksheet)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+                match &params.csv_path {
+                    Some(csv_path) => {
+                        fs::write(csv_path, &csv).map_err(|e| {
+                            ErrorData::new(
+                                ErrorCode::INTERNAL_ERROR,
+                                format!("Failed to write CSV file: {}", e),
+                                None,
+                            )
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(format!(
+                            "Exported worksheet '{}' to {}",
+                            worksheet.get_name(),
+                            csv_path
+                        ))]))
+                    }
+                    None => Ok(CallToolResult::success(vec![Content::text(csv)])),
+                }
+            }
+            XlsxOperation::FromCsv => {
+                let csv_path = params.csv_path.as_ref().ok_or_else(|| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        "Missing 'csv_path' parameter".to_string(),
+                        None,
+                    )
+                })?;
+                let csv = fs::read_to_string(csv_path).map_err(|e| {
+                    ErrorData::new(
+                        ErrorCode::INTERNAL_ERROR,
+                        format!("Failed to read CSV file: {}", e),
+                        None,
+                    )
+                })?;
+
+                let worksheet_name = params.worksheet.clone().unwrap_or_else(|| {
+                    Path::new(csv_path)
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "Sheet1".to_string())
+                });
+                let mut xlsx = if Path::new(path).exists() {
+                    xlsx_tool::XlsxTool::new(path)
+                } else {
+                    Ok(xlsx_tool::XlsxTool::empty())
+                }
+                .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let written = xlsx
+                    .import_csv(&worksheet_name, &csv)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                xlsx.save(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Imported {} into {} in worksheet '{}'",
+                    csv_path, written, worksheet_name
+                ))]))
+            }
+            XlsxOperation::FormatColumns => {
+                let columns = params.columns.as_ref().ok_or_else(|| {
+                    ErrorData::new(
+                        ErrorCode::INVALID_PARAMS,
+                        "Missing 'columns' parameter".to_string(),
+                        None,
+                    )
+                })?;
+
+                let mut xlsx = xlsx_tool::XlsxTool::new(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let worksheet_name = xlsx
+                    .worksheet_name_or_first(params.worksheet.as_deref())
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let format = xlsx_tool::ColumnFormat {
+                    width: params.width,
+                    number_format: params.number_format.clone(),
+                    bold_header: params.bold_header,
+                };
+                xlsx.format_columns(&worksheet_name, columns, &format)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                xlsx.save(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Formatted columns {} in worksheet '{}'",
+                    columns, worksheet_name
+                ))]))
+            }
         }
     }
 
@@ -1134,10 +1375,16 @@ impl ComputerControllerServer {
     #[tool(
         name = "pdf_tool",
         description = "
-            Process PDF files to extract text and images.
+            Process PDF files to extract text, tables, images, bookmarks and metadata.
             Supports operations:
-            - extract_text: Extract all text content from the PDF
+            - extract_text: Extract text content, labelled with page numbers (use pages to limit the range, e.g. '1-5')
+            - extract_tables: Detect tables from the text layout and save each as a CSV file (use pages to limit the range)
             - extract_images: Extract and save embedded images to PNG files
+            - outline: List the outline (bookmarks) with the page each one points to
+            - metadata: Get the title, author, dates, producer and page count
+
+            Results too large to return are saved to the cache with a summary and preview; use pages to
+            work through long documents a section at a time.
 
             Use this when there is a .pdf file or files that need to be processed.
         "
@@ -1153,13 +1400,20 @@ impl ComputerControllerServer {
         // Convert enum to string for the existing implementation
         let operation_str = match operation {
             PdfOperation::ExtractText => "extract_text",
+            PdfOperation::ExtractTables => "extract_tables",
             PdfOperation::ExtractImages => "extract_images",
+            PdfOperation::Outline => "outline",
+            PdfOperation::Metadata => "metadata",
         };
 
-        let result =
-            crate::computercontroller::pdf_tool::pdf_tool(path, operation_str, &self.cache_dir)
-                .await
-                .map_err(|e| ErrorData::new(e.code, e.message, e.data))?;
+        let result = crate::computercontroller::pdf_tool::pdf_tool(
+            path,
+            operation_str,
+            params.pages.as_deref(),
+            &self.cache_dir,
+        )
+        .await
+        .map_err(|e| ErrorData::new(e.code, e.message, e.data))?;
 
         Ok(CallToolResult::success(result))
     }
@@ -1,10 +1,31 @@
-use lopdf::{content::Content as PdfContent, Document, Object};
+use lopdf::{content::Content as PdfContent, Dictionary, Document, Object, ObjectId};
 use rmcp::model::{Content, ErrorCode, ErrorData};
-use std::{fs, path::Path};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// Results longer than this many characters are written to the cache instead of returned inline
+const INLINE_RESULT_CHAR_LIMIT: usize = 50_000;
+/// Characters of a cached result included in the summary
+const CACHED_RESULT_PREVIEW_CHARS: usize = 2_000;
+/// Guards against malformed, cyclic bookmark trees
+const MAX_OUTLINE_DEPTH: usize = 32;
+/// Fragments whose baselines are within this many points are on the same line
+const LINE_TOLERANCE: f32 = 2.0;
+/// Without font metrics, glyphs are assumed to be half an em wide on average
+const AVERAGE_CHAR_WIDTH_EMS: f32 = 0.5;
+/// Whitespace wider than this starts a new table cell
+const CELL_GAP_EMS: f32 = 1.5;
+/// Cells starting within this many points of each other are in the same column
+const COLUMN_TOLERANCE: f32 = 10.0;
+const MIN_TABLE_ROWS: usize = 2;
 
 pub async fn pdf_tool(
     path: &str,
     operation: &str,
+    pages: Option<&str>,
     cache_dir: &Path,
 ) -> Result<Vec<Content>, ErrorData> {
     // Open and parse the PDF file
@@ -19,103 +40,87 @@ pub async fn pdf_tool(
     let result = match operation {
         "extract_text" => {
             let mut text = String::new();
+            let mut found_text = false;
 
-            // Iterate over each page in the document
-            for (page_num, page_id) in doc.get_pages() {
-                text.push_str(&format!("Page {}:\n", page_num));
-
-                // Try to get text from page contents
-                if let Ok(page_obj) = doc.get_object(page_id) {
-                    if let Ok(page_dict) = page_obj.as_dict() {
-                        // Try to get text from Contents stream
-                        if let Ok(contents) =
-                            page_dict.get(b"Contents").and_then(|c| c.as_reference())
-                        {
-                            if let Ok(content_obj) = doc.get_object(contents) {
-                                if let Ok(stream) = content_obj.as_stream() {
-                                    if let Ok(content_data) = stream.get_plain_content() {
-                                        if let Ok(content) = PdfContent::decode(&content_data) {
-                                            // Process each operation in the content stream
-                                            for operation in content.operations {
-                                                match operation.operator.as_ref() {
-                                                    // "Tj" operator: show text
-                                                    "Tj" => {
-                                                        for operand in operation.operands {
-                                                            if let Object::String(ref bytes, _) =
-                                                                operand
-                                                            {
-                                                                if let Ok(s) =
-                                                                    std::str::from_utf8(bytes)
-                                                                {
-                                                                    text.push_str(s);
-                                                                }
-                                                            }
-                                                        }
-                                                        text.push(' ');
-                                                    }
-                                                    // "TJ" operator: show text with positioning
-                                                    "TJ" => {
-                                                        if let Some(Object::Array(ref arr)) =
-                                                            operation.operands.first()
-                                                        {
-                                                            let mut last_was_text = false;
-                                                            for element in arr {
-                                                                match element {
-                                                                    Object::String(
-                                                                        ref bytes,
-                                                                        _,
-                                                                    ) => {
-                                                                        if let Ok(s) =
-                                                                            std::str::from_utf8(
-                                                                                bytes,
-                                                                            )
-                                                                        {
-                                                                            if last_was_text {
-                                                                                text.push(' ');
-                                                                            }
-                                                                            text.push_str(s);
-                                                                            last_was_text = true;
-                                                                        }
-                                                                    }
-                                                                    Object::Integer(offset) => {
-                                                                        // Large negative offsets often indicate word spacing
-                                                                        if *offset < -100 {
-                                                                            text.push(' ');
-                                                                            last_was_text = false;
-                                                                        }
-                                                                    }
-                                                                    Object::Real(offset) => {
-                                                                        if *offset < -100.0 {
-                                                                            text.push(' ');
-                                                                            last_was_text = false;
-                                                                        }
-                                                                    }
-                                                                    _ => {}
-                                                                }
-                                                            }
-                                                            text.push(' ');
-                                                        }
-                                                    }
-                                                    _ => (), // Ignore other operators
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                text.push('\n');
+            for (page_num, page_id) in select_pages(&doc, pages)? {
+                let page_text = fragments_to_text(&page_fragments(&doc, page_id));
+                found_text |= !page_text.trim().is_empty();
+                text.push_str(&format!("Page {}:\n{}\n\n", page_num, page_text));
             }
 
-            if text.trim().is_empty() {
+            if found_text {
+                format!("Extracted text from PDF:\n\n{}", text)
+            } else {
                 "No text found in PDF".to_string()
+            }
+        }
+
+        "extract_tables" => {
+            let tables_dir = cache_dir.join("pdf_tables");
+            fs::create_dir_all(&tables_dir).map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to create table cache directory: {}", e),
+                    None,
+                )
+            })?;
+            let stem = Path::new(path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "pdf".to_string());
+
+            let mut tables = Vec::new();
+            for (page_num, page_id) in select_pages(&doc, pages)? {
+                for (index, table) in detect_tables(&page_fragments(&doc, page_id))
+                    .iter()
+                    .enumerate()
+                {
+                    let csv = table_to_csv(table)?;
+                    let csv_path =
+                        tables_dir.join(format!("{}_page{}_table{}.csv", stem, page_num, index + 1));
+                    fs::write(&csv_path, &csv).map_err(|e| {
+                        ErrorData::new(
+                            ErrorCode::INTERNAL_ERROR,
+                            format!("Failed to write table: {}", e),
+                            None,
+                        )
+                    })?;
+
+                    let preview: Vec<&str> = csv.lines().take(5).collect();
+                    tables.push(format!(
+                        "Page {} table {} ({} rows x {} columns) saved to: {}\n{}",
+                        page_num,
+                        index + 1,
+                        table.len(),
+                        table.first().map_or(0, Vec::len),
+                        csv_path.display(),
+                        preview.join("\n")
+                    ));
+                }
+            }
+
+            if tables.is_empty() {
+                "No tables found in PDF".to_string()
             } else {
-                format!("Extracted text from PDF:\n\n{}", text)
+                format!(
+                    "Found {} tables (detected from text layout, check the CSV before relying on it):\n\n{}",
+                    tables.len(),
+                    tables.join("\n\n")
+                )
             }
         }
 
+        "outline" => {
+            let entries = outline_entries(&doc);
+            if entries.is_empty() {
+                "No outline found in PDF".to_string()
+            } else {
+                format!("Outline:\n{}", entries.join("\n"))
+            }
+        }
+
+        "metadata" => format_metadata(&doc),
+
         "extract_images" => {
             let cache_dir = cache_dir.join("pdf_images");
             fs::create_dir_all(&cache_dir).map_err(|e| {
@@ -348,7 +353,7 @@ pub async fn pdf_tool(
             return Err(ErrorData::new(
                 ErrorCode::INVALID_PARAMS,
                 format!(
-                    "Invalid operation: {}. Valid operations are: 'extract_text', 'extract_images'",
+                    "Invalid operation: {}. Valid operations are: 'extract_text', 'extract_tables', 'extract_images', 'outline', 'metadata'",
                     operation
                 ),
                 None,
@@ -356,14 +361,653 @@ pub async fn pdf_tool(
         }
     };
 
+    let result = cache_large_result(result, operation, cache_dir)?;
     Ok(vec![Content::text(result)])
 }
 
+/// Write results too large for the context to the cache and return a summary instead
+fn cache_large_result(
+    result: String,
+    operation: &str,
+    cache_dir: &Path,
+) -> Result<String, ErrorData> {
+    let char_count = result.chars().count();
+    if char_count <= INLINE_RESULT_CHAR_LIMIT {
+        return Ok(result);
+    }
+
+    fs::create_dir_all(cache_dir).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to create cache directory: {}", e),
+            None,
+        )
+    })?;
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let cache_path = cache_dir.join(format!("pdf_{}_{}.txt", operation, timestamp));
+    fs::write(&cache_path, &result).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to write to cache: {}", e),
+            None,
+        )
+    })?;
+
+    let preview: String = result.chars().take(CACHED_RESULT_PREVIEW_CHARS).collect();
+    Ok(format!(
+        "The result is {} characters, too large to return inline, and was saved to: {}\n\
+         Use the 'pages' parameter to work through a smaller page range, or read the cached file in parts.\n\n\
+         Preview:\n{}...",
+        char_count,
+        cache_path.display(),
+        preview
+    ))
+}
+
+/// The pages selected by a page range such as "1-3,7,10-", in document order
+fn select_pages(doc: &Document, pages: Option<&str>) -> Result<Vec<(u32, ObjectId)>, ErrorData> {
+    let all_pages = doc.get_pages();
+    let Some(pages) = pages else {
+        return Ok(all_pages.into_iter().collect());
+    };
+    let selected = parse_page_ranges(pages, all_pages.len() as u32)?;
+    Ok(all_pages
+        .into_iter()
+        .filter(|(page_num, _)| selected.contains(page_num))
+        .collect())
+}
+
+fn parse_page_ranges(spec: &str, page_count: u32) -> Result<BTreeSet<u32>, ErrorData> {
+    let invalid = |detail: String| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Invalid page range '{}': {}", spec, detail),
+            None,
+        )
+    };
+    let parse = |value: &str, default: u32| {
+        if value.is_empty() {
+            Ok(default)
+        } else {
+            value
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("'{}' is not a page number", value)))
+        }
+    };
+
+    let mut pages = BTreeSet::new();
+    for part in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let start = parse(start.trim(), 1)?;
+        let end = parse(end.trim(), page_count)?;
+        if start == 0 || start > end || end > page_count {
+            return Err(invalid(format!("the document has {} pages", page_count)));
+        }
+        pages.extend(start..=end);
+    }
+
+    if pages.is_empty() {
+        return Err(invalid("no pages selected".to_string()));
+    }
+    Ok(pages)
+}
+
+/// A run of text shown by a single text operator, positioned in page space
+struct TextFragment {
+    x: f32,
+    y: f32,
+    end_x: f32,
+    em: f32,
+    text: String,
+}
+
+/// Just enough of the PDF text state to place fragments on the page. Rotation,
+/// skew and the graphics transformation matrix are ignored.
+struct TextState {
+    line_x: f32,
+    line_y: f32,
+    x: f32,
+    y: f32,
+    scale: f32,
+    font_size: f32,
+    leading: f32,
+}
+
+impl TextState {
+    fn new() -> Self {
+        Self {
+            line_x: 0.0,
+            line_y: 0.0,
+            x: 0.0,
+            y: 0.0,
+            scale: 1.0,
+            font_size: 12.0,
+            leading: 0.0,
+        }
+    }
+
+    fn begin_text(&mut self) {
+        self.set_matrix(1.0, 1.0, 0.0, 0.0);
+    }
+
+    fn set_matrix(&mut self, a: f32, d: f32, e: f32, f: f32) {
+        let scale = if d != 0.0 { d.abs() } else { a.abs() };
+        self.scale = if scale > 0.0 { scale } else { 1.0 };
+        self.line_x = e;
+        self.line_y = f;
+        self.x = e;
+        self.y = f;
+    }
+
+    fn move_line(&mut self, tx: f32, ty: f32) {
+        self.line_x += tx * self.scale;
+        self.line_y += ty * self.scale;
+        self.x = self.line_x;
+        self.y = self.line_y;
+    }
+
+    fn next_line(&mut self) {
+        self.move_line(0.0, -self.leading);
+    }
+
+    fn show(&mut self, text: String, fragments: &mut Vec<TextFragment>) {
+        if text.is_empty() {
+            return;
+        }
+        let em = self.font_size * self.scale;
+        let width = text.chars().count() as f32 * em * AVERAGE_CHAR_WIDTH_EMS;
+        fragments.push(TextFragment {
+            x: self.x,
+            y: self.y,
+            end_x: self.x + width,
+            em,
+            text,
+        });
+        self.x += width;
+    }
+}
+
+fn string_operand(operand: &Object) -> Option<&str> {
+    match operand {
+        Object::String(bytes, _) => std::str::from_utf8(bytes).ok(),
+        _ => None,
+    }
+}
+
+/// Text fragments of a page in content stream order
+fn page_fragments(doc: &Document, page_id: ObjectId) -> Vec<TextFragment> {
+    let Some(content) = doc
+        .get_page_content(page_id)
+        .ok()
+        .and_then(|data| PdfContent::decode(&data).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut state = TextState::new();
+    let mut fragments = Vec::new();
+    for operation in &content.operations {
+        let numbers: Vec<f32> = operation
+            .operands
+            .iter()
+            .filter_map(|operand| operand.as_float().ok())
+            .collect();
+
+        match operation.operator.as_ref() {
+            "BT" => state.begin_text(),
+            "Tf" => {
+                if let [size] = numbers[..] {
+                    state.font_size = size.abs();
+                }
+            }
+            "TL" => {
+                if let [leading] = numbers[..] {
+                    state.leading = leading;
+                }
+            }
+            "Td" => {
+                if let [tx, ty] = numbers[..] {
+                    state.move_line(tx, ty);
+                }
+            }
+            "TD" => {
+                if let [tx, ty] = numbers[..] {
+                    state.leading = -ty;
+                    state.move_line(tx, ty);
+                }
+            }
+            "Tm" => {
+                if let [a, _, _, d, e, f] = numbers[..] {
+                    state.set_matrix(a, d, e, f);
+                }
+            }
+            "T*" => state.next_line(),
+            // "Tj" operator: show text
+            "Tj" => {
+                let text: String = operation
+                    .operands
+                    .iter()
+                    .filter_map(string_operand)
+                    .collect();
+                state.show(text, &mut fragments);
+            }
+            // "'" and "\"" operators: move to the next line and show text
+            "'" | "\"" => {
+                state.next_line();
+                if let Some(text) = operation.operands.last().and_then(string_operand) {
+                    state.show(text.to_string(), &mut fragments);
+                }
+            }
+            // "TJ" operator: show text with positioning
+            "TJ" => {
+                if let Some(Object::Array(elements)) = operation.operands.first() {
+                    let mut text = String::new();
+                    for element in elements {
+                        match element.as_float() {
+                            // Large negative offsets often indicate word spacing
+                            Ok(offset) if offset < -100.0 => {
+                                if !text.is_empty() && !text.ends_with(' ') {
+                                    text.push(' ');
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(_) => {
+                                if let Some(s) = string_operand(element) {
+                                    text.push_str(s);
+                                }
+                            }
+                        }
+                    }
+                    state.show(text, &mut fragments);
+                }
+            }
+            _ => (), // Ignore other operators
+        }
+    }
+    fragments
+}
+
+fn fragments_to_text(fragments: &[TextFragment]) -> String {
+    let mut text = String::new();
+    let mut previous_y: Option<f32> = None;
+    for fragment in fragments {
+        match previous_y {
+            Some(y) if (y - fragment.y).abs() > LINE_TOLERANCE => text.push('\n'),
+            Some(_) if !text.ends_with(char::is_whitespace) => text.push(' '),
+            _ => {}
+        }
+        text.push_str(&fragment.text);
+        previous_y = Some(fragment.y);
+    }
+    text
+}
+
+struct TableCell {
+    x: f32,
+    text: String,
+}
+
+/// Split each line of text into cells wherever there is a wide gap between fragments
+fn line_cells(fragments: &[TextFragment]) -> Vec<Vec<TableCell>> {
+    let mut lines: Vec<Vec<TableCell>> = Vec::new();
+    let mut previous: Option<&TextFragment> = None;
+    for fragment in fragments {
+        let text = fragment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let same_line = previous.is_some_and(|prev| (prev.y - fragment.y).abs() <= LINE_TOLERANCE);
+        match (previous, lines.last_mut()) {
+            (Some(prev), Some(line)) if same_line => {
+                let gap = fragment.x - prev.end_x;
+                match line.last_mut() {
+                    Some(cell) if gap <= prev.em * CELL_GAP_EMS => {
+                        cell.text.push(' ');
+                        cell.text.push_str(text);
+                    }
+                    _ => line.push(TableCell {
+                        x: fragment.x,
+                        text: text.to_string(),
+                    }),
+                }
+            }
+            _ => lines.push(vec![TableCell {
+                x: fragment.x,
+                text: text.to_string(),
+            }]),
+        }
+        previous = Some(fragment);
+    }
+    lines
+}
+
+/// Heuristic table detection: consecutive lines that each split into two or more
+/// cells form a table, with columns aligned on the cells' starting positions.
+fn detect_tables(fragments: &[TextFragment]) -> Vec<Vec<Vec<String>>> {
+    let mut tables = Vec::new();
+    let mut rows: Vec<Vec<TableCell>> = Vec::new();
+    for line in line_cells(fragments)
+        .into_iter()
+        .chain(std::iter::once(Vec::new()))
+    {
+        if line.len() >= 2 {
+            rows.push(line);
+            continue;
+        }
+        if rows.len() >= MIN_TABLE_ROWS {
+            tables.push(align_columns(&rows));
+        }
+        rows.clear();
+    }
+    tables
+}
+
+fn align_columns(rows: &[Vec<TableCell>]) -> Vec<Vec<String>> {
+    let mut columns: Vec<f32> = rows.iter().flatten().map(|cell| cell.x).collect();
+    columns.sort_by(|a, b| a.total_cmp(b));
+    columns.dedup_by(|x, column| (*x - *column).abs() <= COLUMN_TOLERANCE);
+
+    rows.iter()
+        .map(|row| {
+            let mut values = vec![String::new(); columns.len()];
+            for cell in row {
+                let column = columns
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| (*a - cell.x).abs().total_cmp(&(*b - cell.x).abs()))
+                    .map_or(0, |(index, _)| index);
+                if !values[column].is_empty() {
+                    values[column].push(' ');
+                }
+                values[column].push_str(&cell.text);
+            }
+            values
+        })
+        .collect()
+}
+
+fn table_to_csv(table: &[Vec<String>]) -> Result<String, ErrorData> {
+    let to_error = |e: String| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to write table CSV: {}", e),
+            None,
+        )
+    };
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in table {
+        writer
+            .write_record(row)
+            .map_err(|e| to_error(e.to_string()))?;
+    }
+    let bytes = writer.into_inner().map_err(|e| to_error(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| to_error(e.to_string()))
+}
+
+fn resolve<'a>(doc: &'a Document, object: &'a Object) -> &'a Object {
+    match object {
+        Object::Reference(id) => doc.get_object(*id).unwrap_or(object),
+        _ => object,
+    }
+}
+
+fn resolve_dict<'a>(doc: &'a Document, object: &'a Object) -> Option<&'a Dictionary> {
+    resolve(doc, object).as_dict().ok()
+}
+
+/// Decode a PDF text string, which is either UTF-16BE with a byte order mark or
+/// (approximately) Latin-1
+fn decode_pdf_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(b"\xFE\xFF") {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn text_value(doc: &Document, dict: &Dictionary, key: &[u8]) -> Option<String> {
+    let value = resolve(doc, dict.get(key).ok()?).as_str().ok()?;
+    let value = decode_pdf_string(value).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+fn format_metadata(doc: &Document) -> String {
+    let mut lines = vec![
+        format!("PDF version: {}", doc.version),
+        format!("Pages: {}", doc.get_pages().len()),
+    ];
+    if doc.trailer.get(b"Encrypt").is_ok() {
+        lines.push("Encrypted: yes".to_string());
+    }
+    if let Some(info) = doc
+        .trailer
+        .get(b"Info")
+        .ok()
+        .and_then(|info| resolve_dict(doc, info))
+    {
+        for key in [
+            "Title",
+            "Author",
+            "Subject",
+            "Keywords",
+            "Creator",
+            "Producer",
+            "CreationDate",
+            "ModDate",
+        ] {
+            if let Some(value) = text_value(doc, info, key.as_bytes()) {
+                lines.push(format!("{}: {}", key, value));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Bookmarks as an indented list, with the page each one points to when it has an
+/// explicit destination
+fn outline_entries(doc: &Document) -> Vec<String> {
+    let page_numbers: HashMap<ObjectId, u32> = doc
+        .get_pages()
+        .into_iter()
+        .map(|(page_num, page_id)| (page_id, page_num))
+        .collect();
+    let first = doc
+        .trailer
+        .get(b"Root")
+        .ok()
+        .and_then(|root| resolve_dict(doc, root))
+        .and_then(|catalog| catalog.get(b"Outlines").ok())
+        .and_then(|outlines| resolve_dict(doc, outlines))
+        .and_then(|outlines| outlines.get(b"First").ok());
+
+    let mut entries = Vec::new();
+    if let Some(first) = first {
+        let mut visited = HashSet::new();
+        collect_outline(doc, first, 0, &page_numbers, &mut visited, &mut entries);
+    }
+    entries
+}
+
+fn collect_outline(
+    doc: &Document,
+    first: &Object,
+    depth: usize,
+    page_numbers: &HashMap<ObjectId, u32>,
+    visited: &mut HashSet<ObjectId>,
+    entries: &mut Vec<String>,
+) {
+    if depth >= MAX_OUTLINE_DEPTH {
+        return;
+    }
+    let mut current = Some(first);
+    while let Some(Object::Reference(id)) = current {
+        if !visited.insert(*id) {
+            return;
+        }
+        let Ok(item) = doc.get_dictionary(*id) else {
+            return;
+        };
+
+        let title = text_value(doc, item, b"Title").unwrap_or_else(|| "(untitled)".to_string());
+        let indent = "  ".repeat(depth);
+        match outline_destination(doc, item).and_then(|page_id| page_numbers.get(&page_id)) {
+            Some(page_num) => entries.push(format!("{}- {} (page {})", indent, title, page_num)),
+            None => entries.push(format!("{}- {}", indent, title)),
+        }
+
+        if let Ok(child) = item.get(b"First") {
+            collect_outline(doc, child, depth + 1, page_numbers, visited, entries);
+        }
+        current = item.get(b"Next").ok();
+    }
+}
+
+/// The page an outline item points to. Named destinations are not resolved.
+fn outline_destination(doc: &Document, item: &Dictionary) -> Option<ObjectId> {
+    let destination = match item.get(b"Dest") {
+        Ok(destination) => resolve(doc, destination),
+        Err(_) => {
+            let action = resolve_dict(doc, item.get(b"A").ok()?)?;
+            resolve(doc, action.get(b"D").ok()?)
+        }
+    };
+    match destination {
+        Object::Array(destination) => destination.first()?.as_reference().ok(),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lopdf::{content::Operation, dictionary, Stream};
     use std::path::PathBuf;
 
+    /// A two page report: a heading on page 1, a price table on page 2, an outline and document info
+    fn build_report_pdf(path: &Path) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let page_texts: [&[(i64, i64, &str)]; 2] = [
+            &[
+                (72, 720, "Quarterly report"),
+                (72, 700, "Summary of results"),
+            ],
+            &[
+                (72, 720, "Item"),
+                (250, 720, "Price"),
+                (72, 700, "Tea"),
+                (250, 700, "3.50"),
+                (72, 680, "Coffee"),
+                (250, 680, "4.25"),
+                (72, 640, "Prices include tax"),
+            ],
+        ];
+        let mut page_ids = Vec::new();
+        for texts in page_texts {
+            let mut operations = vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+            ];
+            for (x, y, text) in texts {
+                operations.push(Operation::new(
+                    "Tm",
+                    vec![
+                        1.into(),
+                        0.into(),
+                        0.into(),
+                        1.into(),
+                        (*x).into(),
+                        (*y).into(),
+                    ],
+                ));
+                operations.push(Operation::new("Tj", vec![Object::string_literal(*text)]));
+            }
+            operations.push(Operation::new("ET", vec![]));
+            let content = PdfContent { operations };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            page_ids.push(doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            }));
+        }
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => page_ids.iter().map(|id| Object::Reference(*id)).collect::<Vec<_>>(),
+                "Count" => page_ids.len() as i64,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+
+        let outlines_id = doc.new_object_id();
+        let summary_id = doc.new_object_id();
+        let pricing_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal("Pricing"),
+            "Parent" => summary_id,
+            "Dest" => vec![page_ids[1].into(), "Fit".into()],
+        });
+        doc.objects.insert(
+            summary_id,
+            Object::Dictionary(dictionary! {
+                "Title" => Object::string_literal("Summary"),
+                "Parent" => outlines_id,
+                "Dest" => vec![page_ids[0].into(), "Fit".into()],
+                "First" => pricing_id,
+                "Last" => pricing_id,
+                "Count" => 1,
+            }),
+        );
+        doc.objects.insert(
+            outlines_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Outlines",
+                "First" => summary_id,
+                "Last" => summary_id,
+                "Count" => 2,
+            }),
+        );
+
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "Outlines" => outlines_id,
+        });
+        let info_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal("Quarterly Report"),
+            "Author" => Object::string_literal("Finance"),
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.trailer.set("Info", info_id);
+        doc.save(path).unwrap();
+    }
+
+    fn result_text(content: Vec<Content>) -> String {
+        content[0].as_text().unwrap().text.clone()
+    }
+
     #[tokio::test]
     async fn test_pdf_text_extraction() {
         let test_pdf_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -372,7 +1016,13 @@ mod tests {
 
         println!("Testing text extraction from: {}", test_pdf_path.display());
 
-        let result = pdf_tool(test_pdf_path.to_str().unwrap(), "extract_text", &cache_dir).await;
+        let result = pdf_tool(
+            test_pdf_path.to_str().unwrap(),
+            "extract_text",
+            None,
+            &cache_dir,
+        )
+        .await;
 
         assert!(result.is_ok(), "PDF text extraction should succeed");
         let content = result.unwrap();
@@ -398,6 +1048,7 @@ mod tests {
         let result = pdf_tool(
             test_pdf_path.to_str().unwrap(),
             "extract_images",
+            None,
             &cache_dir,
         )
         .await;
@@ -437,7 +1088,7 @@ mod tests {
     #[tokio::test]
     async fn test_pdf_invalid_path() {
         let cache_dir = tempfile::tempdir().unwrap().into_path();
-        let result = pdf_tool("nonexistent.pdf", "extract_text", &cache_dir).await;
+        let result = pdf_tool("nonexistent.pdf", "extract_text", None, &cache_dir).await;
 
         assert!(result.is_err(), "Should fail with invalid path");
     }
@@ -451,10 +1102,132 @@ mod tests {
         let result = pdf_tool(
             test_pdf_path.to_str().unwrap(),
             "invalid_operation",
+            None,
             &cache_dir,
         )
         .await;
 
         assert!(result.is_err(), "Should fail with invalid operation");
     }
+
+    #[tokio::test]
+    async fn test_pdf_page_range_extraction() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pdf_path = temp_dir.path().join("report.pdf");
+        build_report_pdf(&pdf_path);
+        let pdf_path = pdf_path.to_str().unwrap();
+
+        let text = result_text(
+            pdf_tool(pdf_path, "extract_text", Some("2"), temp_dir.path())
+                .await
+                .unwrap(),
+        );
+        assert!(text.contains("Page 2:\nItem Price\nTea 3.50"));
+        assert!(!text.contains("Page 1:"));
+        assert!(!text.contains("Quarterly report"));
+
+        let text = result_text(
+            pdf_tool(pdf_path, "extract_text", None, temp_dir.path())
+                .await
+                .unwrap(),
+        );
+        assert!(text.contains("Page 1:\nQuarterly report\nSummary of results"));
+
+        let result = pdf_tool(pdf_path, "extract_text", Some("2-3"), temp_dir.path()).await;
+        assert_eq!(result.unwrap_err().code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_parse_page_ranges() {
+        let pages = |spec| {
+            parse_page_ranges(spec, 10)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pages("3"), vec![3]);
+        assert_eq!(pages("1-3, 7"), vec![1, 2, 3, 7]);
+        assert_eq!(pages("9-"), vec![9, 10]);
+        assert_eq!(pages("-2,2"), vec![1, 2]);
+        assert!(parse_page_ranges("0", 10).is_err());
+        assert!(parse_page_ranges("4-2", 10).is_err());
+        assert!(parse_page_ranges("11", 10).is_err());
+        assert!(parse_page_ranges("one", 10).is_err());
+        assert!(parse_page_ranges(",", 10).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pdf_table_extraction() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pdf_path = temp_dir.path().join("report.pdf");
+        build_report_pdf(&pdf_path);
+
+        let text = result_text(
+            pdf_tool(
+                pdf_path.to_str().unwrap(),
+                "extract_tables",
+                None,
+                temp_dir.path(),
+            )
+            .await
+            .unwrap(),
+        );
+        assert!(text.contains("Found 1 tables"));
+        assert!(text.contains("Page 2 table 1 (3 rows x 2 columns)"));
+
+        let csv_path = temp_dir.path().join("pdf_tables/report_page2_table1.csv");
+        assert_eq!(
+            fs::read_to_string(csv_path).unwrap(),
+            "Item,Price\nTea,3.50\nCoffee,4.25\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pdf_outline_and_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pdf_path = temp_dir.path().join("report.pdf");
+        build_report_pdf(&pdf_path);
+        let pdf_path = pdf_path.to_str().unwrap();
+
+        let outline = result_text(
+            pdf_tool(pdf_path, "outline", None, temp_dir.path())
+                .await
+                .unwrap(),
+        );
+        assert!(outline.contains("- Summary (page 1)\n  - Pricing (page 2)"));
+
+        let metadata = result_text(
+            pdf_tool(pdf_path, "metadata", None, temp_dir.path())
+                .await
+                .unwrap(),
+        );
+        assert!(metadata.contains("Pages: 2"));
+        assert!(metadata.contains("Title: Quarterly Report"));
+        assert!(metadata.contains("Author: Finance"));
+    }
+
+    #[test]
+    fn test_large_results_are_cached() {
+        let cache_dir = tempfile::tempdir().unwrap();
+
+        let small = cache_large_result("short".to_string(), "extract_text", cache_dir.path());
+        assert_eq!(small.unwrap(), "short");
+
+        let large = "x".repeat(INLINE_RESULT_CHAR_LIMIT + 1);
+        let summary = cache_large_result(large.clone(), "extract_text", cache_dir.path()).unwrap();
+        assert!(summary.len() < large.len());
+        let cached_path = summary
+            .lines()
+            .next()
+            .and_then(|line| line.split("saved to: ").nth(1))
+            .unwrap();
+        assert_eq!(fs::read_to_string(cached_path).unwrap(), large);
+    }
+
+    #[test]
+    fn test_decode_pdf_string() {
+        assert_eq!(decode_pdf_string(b"Report"), "Report");
+        assert_eq!(decode_pdf_string(b"\xFE\xFF\x00H\x00i"), "Hi");
+        assert_eq!(decode_pdf_string(b"caf\xE9"), "café");
+    }
 }
@@ -0,0 +1,517 @@
+use scraper::{ElementRef, Html, Node, Selector};
+use url::Url;
+
+/// Elements that never hold the main content of a page
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "form", "button",
+    "input", "select", "textarea", "nav", "header", "footer", "aside", "dialog",
+];
+
+/// ARIA landmarks used for site chrome rather than content
+const SKIPPED_ROLES: &[&str] = &[
+    "navigation",
+    "banner",
+    "contentinfo",
+    "complementary",
+    "search",
+    "dialog",
+];
+
+/// Class and id words that mark boilerplate blocks (menus, cookie banners, share buttons)
+const BOILERPLATE_WORDS: &[&str] = &[
+    "nav",
+    "navbar",
+    "navigation",
+    "menu",
+    "sidebar",
+    "breadcrumb",
+    "breadcrumbs",
+    "footer",
+    "cookie",
+    "cookies",
+    "consent",
+    "advert",
+    "advertisement",
+    "ads",
+    "share",
+    "social",
+    "related",
+    "newsletter",
+    "skip",
+];
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "body",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// The readable part of an HTML page
+pub struct Readable {
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+/// Convert the main content of an HTML page to Markdown, dropping navigation,
+/// scripts and other page chrome. Relative links and images are resolved against `base`.
+pub fn html_to_markdown(html: &str, base: &Url) -> Readable {
+    let document = Html::parse_document(html);
+    let title = select_first(&document, "title")
+        .map(|title| collapse_whitespace(&title.text().collect::<String>()))
+        .filter(|title| !title.is_empty());
+
+    let root = ["main", "[role=main]", "article", "body"]
+        .iter()
+        .find_map(|selector| select_first(&document, selector))
+        .unwrap_or_else(|| document.root_element());
+
+    let mut converter = Converter {
+        base,
+        blocks: Vec::new(),
+    };
+    converter.block_children(root, &mut String::new());
+
+    let mut markdown = String::new();
+    if let Some(title) = &title {
+        if !converter
+            .blocks
+            .first()
+            .is_some_and(|block| block.starts_with("# "))
+        {
+            markdown.push_str(&format!("# {}\n\n", title));
+        }
+    }
+    markdown.push_str(&converter.blocks.join("\n\n"));
+    markdown.push('\n');
+
+    Readable { title, markdown }
+}
+
+/// Absolute http(s) links on a page, without fragments, in document order
+pub fn extract_links(html: &str, base: &Url) -> Vec<Url> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[href]").expect("valid selector");
+    let mut links: Vec<Url> = Vec::new();
+    for anchor in document.select(&selector) {
+        let Some(mut link) = anchor.attr("href").and_then(|href| base.join(href).ok()) else {
+            continue;
+        };
+        if !matches!(link.scheme(), "http" | "https") {
+            continue;
+        }
+        link.set_fragment(None);
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+    links
+}
+
+fn select_first<'a>(document: &'a Html, selector: &str) -> Option<ElementRef<'a>> {
+    let selector = Selector::parse(selector).ok()?;
+    document.select(&selector).next()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_boilerplate(element: ElementRef) -> bool {
+    let value = element.value();
+    if SKIPPED_ELEMENTS.contains(&value.name())
+        || value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value
+            .attr("role")
+            .is_some_and(|role| SKIPPED_ROLES.contains(&role))
+    {
+        return true;
+    }
+
+    ["class", "id"].iter().any(|attr| {
+        value.attr(attr).is_some_and(|names| {
+            names
+                .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+                .any(|word| BOILERPLATE_WORDS.contains(&word.to_ascii_lowercase().as_str()))
+        })
+    })
+}
+
+struct Converter<'a> {
+    base: &'a Url,
+    blocks: Vec<String>,
+}
+
+impl Converter<'_> {
+    fn resolve(&self, href: &str) -> String {
+        self.base
+            .join(href)
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| href.to_string())
+    }
+
+    fn push_block(&mut self, block: String) {
+        let block = block.trim_end().to_string();
+        if !block.trim().is_empty() {
+            self.blocks.push(block);
+        }
+    }
+
+    fn flush_paragraph(&mut self, paragraph: &mut String) {
+        let text = std::mem::take(paragraph);
+        self.push_block(text.trim().to_string());
+    }
+
+    /// Render the children of a block element, gathering runs of inline content into paragraphs
+    fn block_children(&mut self, element: ElementRef, paragraph: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => append_text(paragraph, text),
+                Node::Element(_) => {
+                    let Some(child) = ElementRef::wrap(child) else {
+                        continue;
+                    };
+                    if is_boilerplate(child) {
+                        continue;
+                    }
+                    if BLOCK_ELEMENTS.contains(&child.value().name()) {
+                        self.flush_paragraph(paragraph);
+                        self.block(child);
+                    } else if child.value().name() == "br" {
+                        paragraph.push('\n');
+                    } else {
+                        paragraph.push_str(&self.inline(child));
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.flush_paragraph(paragraph);
+    }
+
+    fn block(&mut self, element: ElementRef) {
+        match element.value().name() {
+            name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                let text = self.inline_children(element);
+                if !text.is_empty() {
+                    self.push_block(format!("{} {}", "#".repeat(level), text));
+                }
+            }
+            "p" | "dt" | "dd" | "figcaption" | "summary" | "address" => {
+                let text = self.inline_children(element);
+                self.push_block(text);
+            }
+            "hr" => self.push_block("---".to_string()),
+            "pre" => {
+                let code: String = element.text().collect();
+                self.push_block(format!("```\n{}\n```", code.trim_end_matches('\n')));
+            }
+            "ul" | "ol" => {
+                let list = self.list(element, 0);
+                self.push_block(list);
+            }
+            "blockquote" => {
+                let mut inner = Converter {
+                    base: self.base,
+                    blocks: Vec::new(),
+                };
+                inner.block_children(element, &mut String::new());
+                let quoted: Vec<String> = inner
+                    .blocks
+                    .join("\n\n")
+                    .lines()
+                    .map(|line| format!("> {}", line).trim_end().to_string())
+                    .collect();
+                self.push_block(quoted.join("\n"));
+            }
+            "table" => {
+                let table = self.table(element);
+                self.push_block(table);
+            }
+            _ => self.block_children(element, &mut String::new()),
+        }
+    }
+
+    fn list(&mut self, element: ElementRef, depth: usize) -> String {
+        let ordered = element.value().name() == "ol";
+        let indent = "  ".repeat(depth);
+        let mut lines = Vec::new();
+        let mut number = 1;
+        for item in element.children().filter_map(ElementRef::wrap) {
+            if item.value().name() != "li" || is_boilerplate(item) {
+                continue;
+            }
+            let marker = if ordered {
+                format!("{}.", number)
+            } else {
+                "-".to_string()
+            };
+            number += 1;
+
+            let mut text = String::new();
+            let mut nested = Vec::new();
+            for child in item.children() {
+                match child.value() {
+                    Node::Text(t) => append_text(&mut text, t),
+                    Node::Element(_) => {
+                        let Some(child) = ElementRef::wrap(child) else {
+                            continue;
+                        };
+                        match child.value().name() {
+                            "ul" | "ol" => nested.push(self.list(child, depth + 1)),
+                            _ if is_boilerplate(child) => {}
+                            _ if BLOCK_ELEMENTS.contains(&child.value().name()) => {
+                                append_text(&mut text, &self.inline_children(child));
+                            }
+                            _ => text.push_str(&self.inline(child)),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            lines.push(format!("{}{} {}", indent, marker, text.trim()));
+            lines.extend(nested.into_iter().filter(|list| !list.is_empty()));
+        }
+        lines.join("\n")
+    }
+
+    fn table(&mut self, element: ElementRef) -> String {
+        let row_selector = Selector::parse("tr").expect("valid selector");
+        let rows: Vec<Vec<String>> = element
+            .select(&row_selector)
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                    .map(|cell| self.inline_children(cell).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+        let Some(columns) = rows.iter().map(Vec::len).max() else {
+            return String::new();
+        };
+
+        let mut lines = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            let mut cells = row.clone();
+            cells.resize(columns, String::new());
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if index == 0 {
+                lines.push(format!("|{}", " --- |".repeat(columns)));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn inline_children(&self, element: ElementRef) -> String {
+        let mut text = String::new();
+        for child in element.children() {
+            match child.value() {
+                Node::Text(t) => append_text(&mut text, t),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        if !is_boilerplate(child) {
+                            text.push_str(&self.inline(child));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        collapse_whitespace(&text)
+    }
+
+    fn inline(&self, element: ElementRef) -> String {
+        match element.value().name() {
+            "br" => "\n".to_string(),
+            "img" => match element.attr("src") {
+                Some(src) => format!(
+                    "![{}]({})",
+                    element.attr("alt").unwrap_or_default(),
+                    self.resolve(src)
+                ),
+                None => String::new(),
+            },
+            name => {
+                let text = self.inline_children(element);
+                if text.is_empty() {
+                    return text;
+                }
+                // Keep the whitespace around the element so words do not run together
+                let leading = if starts_with_space(element) { " " } else { "" };
+                let trailing = if ends_with_space(element) { " " } else { "" };
+                let formatted = match name {
+                    "a" => match element.attr("href") {
+                        Some(href) if !href.starts_with("javascript:") => {
+                            format!("[{}]({})", text, self.resolve(href))
+                        }
+                        _ => text,
+                    },
+                    "strong" | "b" => format!("**{}**", text),
+                    "em" | "i" => format!("*{}*", text),
+                    "code" | "kbd" | "samp" => format!("`{}`", text),
+                    _ => text,
+                };
+                format!("{}{}{}", leading, formatted, trailing)
+            }
+        }
+    }
+}
+
+fn starts_with_space(element: ElementRef) -> bool {
+    element
+        .text()
+        .next()
+        .is_some_and(|text| text.starts_with(char::is_whitespace))
+}
+
+fn ends_with_space(element: ElementRef) -> bool {
+    element
+        .text()
+        .last()
+        .is_some_and(|text| text.ends_with(char::is_whitespace))
+}
+
+/// Append a text node, collapsing whitespace but keeping word boundaries
+fn append_text(out: &mut String, text: &str) {
+    if text.trim().is_empty() {
+        if !text.is_empty() && !out.is_empty() && !out.ends_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        return;
+    }
+    if text.starts_with(char::is_whitespace)
+        && !out.is_empty()
+        && !out.ends_with(char::is_whitespace)
+    {
+        out.push(' ');
+    }
+    out.push_str(&collapse_whitespace(text));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("https://example.com/docs/guide.html").unwrap()
+    }
+
+    #[test]
+    fn test_strips_page_chrome() {
+        let html = r#"
+            <html>
+              <head><title>Guide</title><script>track()</script></head>
+              <body>
+                <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+                <div class="cookie-banner">We use cookies</div>
+                <main>
+                  <h1>Getting started</h1>
+                  <p>Install the <strong>CLI</strong> and run <code>goose</code>.</p>
+                  <aside>Related posts</aside>
+                </main>
+                <footer>Copyright</footer>
+              </body>
+            </html>
+        "#;
+
+        let readable = html_to_markdown(html, &base());
+        assert_eq!(readable.title.as_deref(), Some("Guide"));
+        assert_eq!(
+            readable.markdown,
+            "# Getting started\n\nInstall the **CLI** and run `goose`.\n"
+        );
+    }
+
+    #[test]
+    fn test_converts_structure_to_markdown() {
+        let html = r#"
+            <body>
+              <h2>Steps</h2>
+              <ol>
+                <li>Read the <a href="setup.html">setup notes</a></li>
+                <li>Configure
+                  <ul><li>providers</li><li>extensions</li></ul>
+                </li>
+              </ol>
+              <blockquote><p>Keep it simple.</p></blockquote>
+              <table>
+                <tr><th>Name</th><th>Value</th></tr>
+                <tr><td>mode</td><td>auto</td></tr>
+              </table>
+              <pre>cargo build
+cargo test</pre>
+              <img src="/logo.png" alt="Logo">
+            </body>
+        "#;
+
+        let markdown = html_to_markdown(html, &base()).markdown;
+        assert_eq!(
+            markdown,
+            "## Steps\n\n\
+             1. Read the [setup notes](https://example.com/docs/setup.html)\n\
+             2. Configure\n  \
+             - providers\n  \
+             - extensions\n\n\
+             > Keep it simple.\n\n\
+             | Name | Value |\n\
+             | --- | --- |\n\
+             | mode | auto |\n\n\
+             ```\ncargo build\ncargo test\n```\n\n\
+             ![Logo](https://example.com/logo.png)\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_links() {
+        let html = r##"
+            <a href="/a">A</a>
+            <a href="b.html#section">B</a>
+            <a href="b.html">B again</a>
+            <a href="mailto:team@example.com">Mail</a>
+            <a href="https://other.org/">Other</a>
+        "##;
+
+        let links: Vec<String> = extract_links(html, &base())
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/a",
+                "https://example.com/docs/b.html",
+                "https://other.org/",
+            ]
+        );
+    }
+}
@@ -0,0 +1,602 @@
+use reqwest::{
+    header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Client, StatusCode,
+};
+use rmcp::model::{ErrorCode, ErrorData};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+use super::readability;
+use super::SaveAsFormat;
+
+/// Upper bound on pages fetched by a single crawl
+pub const MAX_CRAWL_PAGES: usize = 50;
+pub const DEFAULT_CRAWL_DEPTH: usize = 2;
+/// Product token matched against robots.txt user-agent lines
+const ROBOTS_USER_AGENT: &str = "goose";
+const ETAG_INDEX_FILE: &str = "web_etags.json";
+
+pub struct ScrapeOptions {
+    pub save_as: SaveAsFormat,
+    pub max_pages: usize,
+    pub max_depth: usize,
+}
+
+/// A fetched page saved to the cache
+pub struct SavedPage {
+    pub url: Url,
+    pub path: PathBuf,
+    pub mime_type: &'static str,
+    /// The server reported the cached copy is still current
+    pub not_modified: bool,
+    pub title: Option<String>,
+    pub links: Vec<Url>,
+}
+
+#[derive(Default)]
+pub struct CrawlReport {
+    pub pages: Vec<SavedPage>,
+    pub disallowed: Vec<Url>,
+    pub failed: Vec<(Url, String)>,
+}
+
+/// Validators from the last successful fetch of a URL, used for conditional requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    path: PathBuf,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    links: Vec<String>,
+}
+
+/// ETags and Last-Modified dates of cached pages, stored alongside them in the cache directory
+struct EtagIndex {
+    path: PathBuf,
+    entries: HashMap<String, CachedResponse>,
+}
+
+impl EtagIndex {
+    fn load(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(ETAG_INDEX_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    fn save(&self) -> Result<(), ErrorData> {
+        let content = serde_json::to_string_pretty(&self.entries).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to serialize ETag cache: {}", e),
+                None,
+            )
+        })?;
+        fs::write(&self.path, content).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to write ETag cache: {}", e),
+                None,
+            )
+        })
+    }
+}
+
+fn format_details(save_as: &SaveAsFormat) -> (&'static str, &'static str, &'static str) {
+    // (cache key prefix, file extension, mime type)
+    match save_as {
+        SaveAsFormat::Text => ("text", "txt", "text/plain"),
+        SaveAsFormat::Markdown => ("markdown", "md", "text/markdown"),
+        SaveAsFormat::Json => ("json", "json", "application/json"),
+        SaveAsFormat::Binary => ("binary", "bin", "application/octet-stream"),
+    }
+}
+
+/// A stable cache file name for a URL, so refetching a page replaces its previous copy
+fn page_cache_path(cache_dir: &Path, url: &Url, extension: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.as_str().hash(&mut hasher);
+    let host: String = url
+        .host_str()
+        .unwrap_or("page")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    cache_dir.join(format!(
+        "web_{}_{:016x}.{}",
+        host,
+        hasher.finish(),
+        extension
+    ))
+}
+
+fn fetch_error(url: &Url, message: String) -> ErrorData {
+    ErrorData::new(
+        ErrorCode::INTERNAL_ERROR,
+        format!("Failed to fetch {}: {}", url, message),
+        None,
+    )
+}
+
+/// Fetch a URL and save it to the cache, sending the validators from the previous fetch
+/// so unchanged pages are not downloaded again
+async fn fetch_page(
+    client: &Client,
+    cache_dir: &Path,
+    index: &mut EtagIndex,
+    url: &Url,
+    save_as: &SaveAsFormat,
+) -> Result<SavedPage, ErrorData> {
+    let (key_prefix, extension, mime_type) = format_details(save_as);
+    let key = format!("{}:{}", key_prefix, url);
+    let cached = index
+        .entries
+        .get(&key)
+        .filter(|cached| cached.path.exists())
+        .cloned();
+
+    let mut request = client.get(url.clone());
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| fetch_error(url, e.to_string()))?;
+    let status = response.status();
+
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+            return Ok(SavedPage {
+                url: url.clone(),
+                path: cached.path,
+                mime_type,
+                not_modified: true,
+                title: cached.title,
+                links: cached
+                    .links
+                    .iter()
+                    .filter_map(|link| Url::parse(link).ok())
+                    .collect(),
+            });
+        }
+    }
+    if !status.is_success() {
+        return Err(fetch_error(
+            url,
+            format!("HTTP request failed with status: {}", status),
+        ));
+    }
+
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let is_html = header(CONTENT_TYPE).is_some_and(|content_type| content_type.contains("html"));
+    // Relative links resolve against the final URL after redirects
+    let base = response.url().clone();
+
+    let mut title = None;
+    let mut links = Vec::new();
+    let content = match save_as {
+        SaveAsFormat::Binary => response
+            .bytes()
+            .await
+            .map_err(|e| fetch_error(url, format!("Failed to get bytes: {}", e)))?
+            .to_vec(),
+        _ => {
+            let text = response
+                .text()
+                .await
+                .map_err(|e| fetch_error(url, format!("Failed to get text: {}", e)))?;
+            if is_html {
+                links = readability::extract_links(&text, &base);
+            }
+            match save_as {
+                SaveAsFormat::Json => {
+                    // Verify it's valid JSON
+                    serde_json::from_str::<serde_json::Value>(&text).map_err(|e| {
+                        ErrorData::new(
+                            ErrorCode::INTERNAL_ERROR,
+                            format!("Invalid JSON response: {}", e),
+                            None,
+                        )
+                    })?;
+                    text.into_bytes()
+                }
+                SaveAsFormat::Markdown if is_html => {
+                    let readable = readability::html_to_markdown(&text, &base);
+                    title = readable.title;
+                    readable.markdown.into_bytes()
+                }
+                _ => text.into_bytes(),
+            }
+        }
+    };
+
+    let path = page_cache_path(cache_dir, url, extension);
+    fs::write(&path, &content).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to write to cache: {}", e),
+            None,
+        )
+    })?;
+
+    if etag.is_some() || last_modified.is_some() {
+        index.entries.insert(
+            key,
+            CachedResponse {
+                etag,
+                last_modified,
+                path: path.clone(),
+                title: title.clone(),
+                links: links.iter().map(Url::to_string).collect(),
+            },
+        );
+    } else {
+        index.entries.remove(&key);
+    }
+
+    Ok(SavedPage {
+        url: url.clone(),
+        path,
+        mime_type,
+        not_modified: false,
+        title,
+        links,
+    })
+}
+
+/// Fetch a single page
+pub async fn fetch(
+    client: &Client,
+    cache_dir: &Path,
+    url: &Url,
+    save_as: &SaveAsFormat,
+) -> Result<SavedPage, ErrorData> {
+    let mut index = EtagIndex::load(cache_dir);
+    let page = fetch_page(client, cache_dir, &mut index, url, save_as).await?;
+    index.save()?;
+    Ok(page)
+}
+
+/// Breadth-first crawl of same-origin pages reachable from `start`, honouring robots.txt
+pub async fn crawl(
+    client: &Client,
+    cache_dir: &Path,
+    start: &Url,
+    options: &ScrapeOptions,
+) -> Result<CrawlReport, ErrorData> {
+    let robots = RobotsRules::fetch(client, start).await;
+    let mut index = EtagIndex::load(cache_dir);
+    let mut report = CrawlReport::default();
+
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    let mut seen = HashSet::from([start.clone()]);
+    while let Some((url, depth)) = queue.pop_front() {
+        if report.pages.len() >= options.max_pages {
+            break;
+        }
+        if !robots.allows(&url) {
+            report.disallowed.push(url);
+            continue;
+        }
+
+        match fetch_page(client, cache_dir, &mut index, &url, &options.save_as).await {
+            Ok(page) => {
+                if depth < options.max_depth {
+                    for link in &page.links {
+                        if link.origin() == start.origin() && seen.insert(link.clone()) {
+                            queue.push_back((link.clone(), depth + 1));
+                        }
+                    }
+                }
+                report.pages.push(page);
+            }
+            Err(e) => report.failed.push((url, e.message.to_string())),
+        }
+    }
+
+    index.save()?;
+    Ok(report)
+}
+
+/// The Allow and Disallow rules of a robots.txt that apply to goose
+#[derive(Debug, Default)]
+pub struct RobotsRules {
+    // (allow, path pattern)
+    rules: Vec<(bool, String)>,
+}
+
+impl RobotsRules {
+    /// Fetch the robots.txt for a URL's origin. A missing file allows everything; an
+    /// unreachable or failing server disallows everything, as RFC 9309 recommends.
+    pub async fn fetch(client: &Client, url: &Url) -> Self {
+        let disallow_all = Self {
+            rules: vec![(false, "/".to_string())],
+        };
+        let Ok(robots_url) = url.join("/robots.txt") else {
+            return Self::default();
+        };
+        let Ok(response) = client.get(robots_url).send().await else {
+            return disallow_all;
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            match response.text().await {
+                Ok(text) => Self::parse(&text, ROBOTS_USER_AGENT),
+                Err(_) => disallow_all,
+            }
+        } else if status.is_server_error() {
+            disallow_all
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Parse the rules for `user_agent`, falling back to the `*` group
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_ascii_lowercase();
+        let mut specific = Vec::new();
+        let mut wildcard = Vec::new();
+
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match field.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_ascii_lowercase());
+                }
+                field @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let rule = (field == "allow", value.to_string());
+                    if agents.contains(&user_agent) {
+                        specific.push(rule.clone());
+                    }
+                    if agents.iter().any(|agent| agent == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if specific.is_empty() {
+                wildcard
+            } else {
+                specific
+            },
+        }
+    }
+
+    /// The longest matching rule wins, and Allow wins a tie
+    pub fn allows(&self, url: &Url) -> bool {
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, &path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+/// Match a robots.txt path pattern, where `*` matches any characters and a trailing `$`
+/// anchors the end of the path
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn html_page(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(
+            format!(
+                "<html><head><title>Test</title></head><body>{}</body></html>",
+                body
+            ),
+            "text/html; charset=utf-8",
+        )
+    }
+
+    async fn mount_page(server: &MockServer, route: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(html_page(body))
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn test_robots_rules() {
+        let robots = RobotsRules::parse(
+            "User-agent: *\n\
+             Disallow: /private\n\
+             Allow: /private/public\n\
+             Disallow: /*.pdf$\n\
+             \n\
+             User-agent: otherbot\n\
+             Disallow: /\n",
+            "goose",
+        );
+        let allows = |path: &str| {
+            robots.allows(
+                &Url::parse("https://example.com")
+                    .unwrap()
+                    .join(path)
+                    .unwrap(),
+            )
+        };
+        assert!(allows("/"));
+        assert!(allows("/docs"));
+        assert!(!allows("/private/notes"));
+        assert!(allows("/private/public/page"));
+        assert!(!allows("/files/report.pdf"));
+        assert!(allows("/files/report.pdf?download=1"));
+
+        let robots = RobotsRules::parse(
+            "User-agent: *\nDisallow: /\n\nUser-agent: Goose\nDisallow: /admin\n",
+            "goose",
+        );
+        assert!(robots.allows(&Url::parse("https://example.com/docs").unwrap()));
+        assert!(!robots.allows(&Url::parse("https://example.com/admin").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_request_reuses_cached_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(
+                html_page("<nav>Menu</nav><main><h1>News</h1><p>Body text</p></main>")
+                    .insert_header("etag", "\"v1\""),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = Client::new();
+        let url = Url::parse(&format!("{}/article", server.uri())).unwrap();
+
+        let first = fetch(&client, cache_dir.path(), &url, &SaveAsFormat::Markdown)
+            .await
+            .unwrap();
+        assert!(!first.not_modified);
+        assert_eq!(first.title.as_deref(), Some("Test"));
+        assert_eq!(
+            fs::read_to_string(&first.path).unwrap(),
+            "# News\n\nBody text\n"
+        );
+
+        let second = fetch(&client, cache_dir.path(), &url, &SaveAsFormat::Markdown)
+            .await
+            .unwrap();
+        assert!(second.not_modified);
+        assert_eq!(second.path, first.path);
+        assert_eq!(second.title.as_deref(), Some("Test"));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_respects_limits_and_robots() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .mount(&server)
+            .await;
+        mount_page(
+            &server,
+            "/",
+            r#"<a href="/a">A</a> <a href="/private">P</a> <a href="https://other.example/">O</a>"#,
+        )
+        .await;
+        mount_page(&server, "/a", r#"<a href="/b">B</a> <a href="/">Home</a>"#).await;
+        mount_page(&server, "/b", r#"<a href="/c">C</a>"#).await;
+        mount_page(&server, "/c", "Too deep").await;
+        mount_page(&server, "/private", "Secret").await;
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let client = Client::new();
+        let start = Url::parse(&format!("{}/", server.uri())).unwrap();
+        let options = ScrapeOptions {
+            save_as: SaveAsFormat::Markdown,
+            max_pages: 10,
+            max_depth: 2,
+        };
+
+        let report = crawl(&client, cache_dir.path(), &start, &options)
+            .await
+            .unwrap();
+        let crawled: Vec<&str> = report.pages.iter().map(|page| page.url.path()).collect();
+        assert_eq!(crawled, vec!["/", "/a", "/b"]);
+        let disallowed: Vec<&str> = report.disallowed.iter().map(Url::path).collect();
+        assert_eq!(disallowed, vec!["/private"]);
+        assert!(report.failed.is_empty());
+
+        let options = ScrapeOptions {
+            max_pages: 2,
+            ..options
+        };
+        let report = crawl(&client, cache_dir.path(), &start, &options)
+            .await
+            .unwrap();
+        assert_eq!(report.pages.len(), 2);
+    }
+}
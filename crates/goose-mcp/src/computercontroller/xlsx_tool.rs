@@ -27,6 +27,15 @@ pub struct RangeData {
     values: Vec<Vec<CellValue>>,
 }
 
+/// Basic formatting applied to a range of columns
+#[derive(Debug, Default)]
+pub struct ColumnFormat {
+    pub width: Option<f64>,
+    /// Excel number format code (e.g. '#,##0.00' or 'yyyy-mm-dd'), applied below the header row
+    pub number_format: Option<String>,
+    pub bold_header: bool,
+}
+
 pub struct XlsxTool {
     workbook: Spreadsheet,
 }
@@ -38,6 +47,38 @@ impl XlsxTool {
         Ok(Self { workbook })
     }
 
+    /// Create a workbook without any worksheets
+    pub fn empty() -> Self {
+        Self {
+            workbook: umya_spreadsheet::new_file_empty_worksheet(),
+        }
+    }
+
+    /// Create an empty workbook with a single worksheet
+    pub fn create(worksheet_name: &str) -> Result<Self> {
+        let mut xlsx = Self::empty();
+        xlsx.add_worksheet(worksheet_name)?;
+        Ok(xlsx)
+    }
+
+    pub fn add_worksheet(&mut self, name: &str) -> Result<()> {
+        if self.workbook.get_sheet_by_name(name).is_some() {
+            anyhow::bail!("Worksheet '{}' already exists", name);
+        }
+        self.workbook
+            .new_sheet(name)
+            .map_err(|e| anyhow::anyhow!("Failed to add worksheet '{}': {}", name, e))?;
+        Ok(())
+    }
+
+    /// Resolve an optional worksheet name, defaulting to the first worksheet
+    pub fn worksheet_name_or_first(&self, name: Option<&str>) -> Result<String> {
+        match name {
+            Some(name) => Ok(self.get_worksheet_by_name(name)?.get_name().to_string()),
+            None => Ok(self.get_worksheet_by_index(0)?.get_name().to_string()),
+        }
+    }
+
     pub fn list_worksheets(&self) -> Result<Vec<WorksheetInfo>> {
         let mut worksheets = Vec::new();
         for (index, worksheet) in self.workbook.get_sheet_collection().iter().enumerate() {
@@ -151,6 +192,142 @@ impl XlsxTool {
         Ok(())
     }
 
+    /// Write rows of values starting at `start_cell` (e.g. "B2") and return the range written.
+    /// Values starting with '=' are written as formulas.
+    pub fn write_range(
+        &mut self,
+        worksheet_name: &str,
+        start_cell: &str,
+        values: &[Vec<String>],
+    ) -> Result<String> {
+        let (start_row, start_col) = parse_cell_reference(start_cell)?;
+        if start_row == 0 || start_col == 0 {
+            anyhow::bail!("Invalid cell reference '{}'", start_cell);
+        }
+        let worksheet = self
+            .workbook
+            .get_sheet_by_name_mut(worksheet_name)
+            .context("Worksheet not found")?;
+
+        let mut end_col = start_col;
+        for (row_offset, row_values) in values.iter().enumerate() {
+            let row = start_row + row_offset as u32;
+            for (col_offset, value) in row_values.iter().enumerate() {
+                let col = start_col + col_offset as u32;
+                end_col = end_col.max(col);
+                let cell = worksheet.get_cell_mut((col, row));
+                match value.strip_prefix('=') {
+                    Some(formula) => {
+                        cell.set_formula(formula);
+                    }
+                    None => {
+                        cell.set_value(value.as_str());
+                    }
+                }
+            }
+        }
+
+        let end_row = start_row + values.len().saturating_sub(1) as u32;
+        Ok(format!(
+            "{}{}:{}{}",
+            column_number_to_letter(start_col),
+            start_row,
+            column_number_to_letter(end_col),
+            end_row
+        ))
+    }
+
+    /// Write rows below the last used row of a worksheet and return the range written
+    pub fn append_rows(&mut self, worksheet_name: &str, rows: &[Vec<String>]) -> Result<String> {
+        let next_row = self
+            .get_worksheet_by_name(worksheet_name)?
+            .get_highest_row()
+            + 1;
+        self.write_range(worksheet_name, &format!("A{}", next_row), rows)
+    }
+
+    /// Apply width, number format and header styling to the columns in `columns`
+    /// (e.g. "B" or "B:D")
+    pub fn format_columns(
+        &mut self,
+        worksheet_name: &str,
+        columns: &str,
+        format: &ColumnFormat,
+    ) -> Result<()> {
+        let (start_col, end_col) = parse_column_range(columns)?;
+        let worksheet = self
+            .workbook
+            .get_sheet_by_name_mut(worksheet_name)
+            .context("Worksheet not found")?;
+        let highest_row = worksheet.get_highest_row();
+
+        for col in start_col..=end_col {
+            if let Some(width) = format.width {
+                worksheet
+                    .get_column_dimension_by_number_mut(&col)
+                    .set_width(width);
+            }
+            if let Some(number_format) = &format.number_format {
+                for row in 2..=highest_row {
+                    worksheet
+                        .get_style_mut((col, row))
+                        .get_number_format_mut()
+                        .set_format_code(number_format.as_str());
+                }
+            }
+            if format.bold_header {
+                worksheet
+                    .get_style_mut((col, 1))
+                    .get_font_mut()
+                    .set_bold(true);
+            }
+        }
+        Ok(())
+    }
+
+    /// Render a worksheet as CSV. Formula cells are exported with their last calculated value.
+    pub fn to_csv(&self, worksheet: &Worksheet) -> Result<String> {
+        let mut writer = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(Vec::new());
+        for row_num in 1..=worksheet.get_highest_row() {
+            let record: Vec<String> = (1..=worksheet.get_highest_column())
+                .map(|col_num| {
+                    worksheet
+                        .get_cell((col_num, row_num))
+                        .map(|cell| cell.get_value().into_owned())
+                        .unwrap_or_default()
+                })
+                .collect();
+            writer
+                .write_record(&record)
+                .context("Failed to write CSV record")?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| anyhow::anyhow!("Failed to write CSV: {}", e))?;
+        String::from_utf8(bytes).context("CSV output is not valid UTF-8")
+    }
+
+    /// Import CSV text into a new worksheet and return the range written
+    pub fn import_csv(&mut self, worksheet_name: &str, csv_text: &str) -> Result<String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(csv_text.as_bytes());
+        let rows = reader
+            .records()
+            .map(|record| {
+                record
+                    .map(|record| record.iter().map(str::to_string).collect::<Vec<_>>())
+                    .context("Failed to parse CSV")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.add_worksheet(worksheet_name)?;
+        self.write_range(worksheet_name, "A1", &rows)
+    }
+
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         umya_spreadsheet::writer::xlsx::write(&self.workbook, path)
             .context("Failed to save Excel file")?;
@@ -245,6 +422,27 @@ fn parse_cell_reference(reference: &str) -> Result<(u32, u32)> {
     Ok((row, col))
 }
 
+fn parse_column_range(columns: &str) -> Result<(u32, u32)> {
+    // Handle "B" or "B:D" and return (start_col, end_col)
+    let (start, end) = columns.split_once(':').unwrap_or((columns, columns));
+    let start = column_letter_to_number(start.trim())?;
+    let end = column_letter_to_number(end.trim())?;
+    if start == 0 || end < start {
+        anyhow::bail!("Invalid column range. Expected format: 'B' or 'B:D'");
+    }
+    Ok((start, end))
+}
+
+fn column_number_to_letter(mut column: u32) -> String {
+    let mut letters = Vec::new();
+    while column > 0 {
+        let remainder = (column - 1) % 26;
+        letters.push((b'A' + remainder as u8) as char);
+        column = (column - 1) / 26;
+    }
+    letters.iter().rev().collect()
+}
+
 fn column_letter_to_number(column: &str) -> Result<u32> {
     let mut result = 0u32;
     for c in column.chars() {
@@ -413,4 +611,112 @@ mod tests {
 
         Ok(())
     }
+
+    fn rows(values: &[&[&str]]) -> Vec<Vec<String>> {
+        values
+            .iter()
+            .map(|row| row.iter().map(|v| v.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_create_write_and_append() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("report.xlsx");
+
+        let mut xlsx = XlsxTool::create("Sales")?;
+        let written = xlsx.write_range(
+            "Sales",
+            "A1",
+            &rows(&[&["Region", "Units"], &["North", "10"], &["South", "15"]]),
+        )?;
+        assert_eq!(written, "A1:B3");
+        let appended = xlsx.append_rows("Sales", &rows(&[&["Total", "=SUM(B2:B3)"]]))?;
+        assert_eq!(appended, "A4:B4");
+        xlsx.add_worksheet("Notes")?;
+        assert!(xlsx.add_worksheet("Notes").is_err());
+        xlsx.save(&path)?;
+
+        let xlsx = XlsxTool::new(&path)?;
+        let names: Vec<String> = xlsx
+            .list_worksheets()?
+            .into_iter()
+            .map(|info| info.name)
+            .collect();
+        assert_eq!(names, vec!["Sales", "Notes"]);
+
+        let worksheet = xlsx.get_worksheet_by_name("Sales")?;
+        assert_eq!(xlsx.get_cell_value(worksheet, 3, 1)?.value, "South");
+        assert_eq!(xlsx.get_cell_value(worksheet, 3, 2)?.value, "15");
+        assert_eq!(
+            xlsx.get_cell_value(worksheet, 4, 2)?.formula.as_deref(),
+            Some("SUM(B2:B3)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_round_trip() -> Result<()> {
+        let csv_text = "name,notes\nWidget,\"small, blue\"\nGadget,\"says \"\"hi\"\"\"\n";
+
+        let mut xlsx = XlsxTool::create("Sheet1")?;
+        let written = xlsx.import_csv("Imported", csv_text)?;
+        assert_eq!(written, "A1:B3");
+        assert!(xlsx.import_csv("Imported", csv_text).is_err());
+
+        let worksheet = xlsx.get_worksheet_by_name("Imported")?;
+        assert_eq!(xlsx.get_cell_value(worksheet, 2, 2)?.value, "small, blue");
+        assert_eq!(xlsx.get_cell_value(worksheet, 3, 2)?.value, "says \"hi\"");
+        assert_eq!(xlsx.to_csv(worksheet)?, csv_text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_columns() -> Result<()> {
+        let mut xlsx = XlsxTool::create("Sheet1")?;
+        xlsx.write_range(
+            "Sheet1",
+            "A1",
+            &rows(&[&["Item", "Price"], &["Tea", "3.5"]]),
+        )?;
+        xlsx.format_columns(
+            "Sheet1",
+            "A:B",
+            &ColumnFormat {
+                width: Some(18.0),
+                number_format: Some("#,##0.00".to_string()),
+                bold_header: true,
+            },
+        )?;
+        assert!(xlsx
+            .format_columns("Sheet1", "C:A", &ColumnFormat::default())
+            .is_err());
+
+        let worksheet = xlsx.get_worksheet_by_name("Sheet1")?;
+        assert_eq!(
+            *worksheet
+                .get_column_dimension_by_number(&2)
+                .unwrap()
+                .get_width(),
+            18.0
+        );
+        let header_style = worksheet.get_style((2, 1));
+        assert!(*header_style.get_font().unwrap().get_bold());
+        let value_style = worksheet.get_style((2, 2));
+        assert_eq!(
+            value_style.get_number_format().unwrap().get_format_code(),
+            "#,##0.00"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_letters() -> Result<()> {
+        assert_eq!(column_number_to_letter(1), "A");
+        assert_eq!(column_number_to_letter(26), "Z");
+        assert_eq!(column_number_to_letter(28), "AB");
+        assert_eq!(parse_column_range("C")?, (3, 3));
+        assert_eq!(parse_column_range("b:d")?, (2, 4));
+        Ok(())
+    }
 }
@@ -0,0 +1,353 @@
+use anyhow::{anyhow, bail, Context, Result};
+use parquet::file::reader::SerializedFileReader;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    Tsv,
+    Json,
+    Parquet,
+}
+
+impl FileFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "json" | "jsonl" | "ndjson" => Some(Self::Json),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::parse)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Real,
+    Text,
+}
+
+impl ColumnType {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Integer => "INTEGER",
+            Self::Real => "REAL",
+            Self::Text => "TEXT",
+        }
+    }
+}
+
+/// Rows read from a file, with column names already made safe to use unquoted in SQL
+#[derive(Debug, Default)]
+pub struct Table {
+    pub columns: Vec<String>,
+    /// Source names that had to change, as (original, column)
+    pub renamed: Vec<(String, String)>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    fn new(source_columns: Vec<String>) -> Self {
+        let mut table = Table::default();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (index, source) in source_columns.into_iter().enumerate() {
+            let mut column = sql_identifier(&source, index);
+            let count = seen.entry(column.to_ascii_lowercase()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                column = format!("{}_{}", column, count);
+            }
+            if column != source {
+                table.renamed.push((source, column.clone()));
+            }
+            table.columns.push(column);
+        }
+        table
+    }
+
+    /// The narrowest column type that holds every non-null value
+    pub fn column_types(&self) -> Vec<ColumnType> {
+        (0..self.columns.len())
+            .map(|index| {
+                let mut column_type = ColumnType::Integer;
+                for value in self.rows.iter().filter_map(|row| row.get(index)) {
+                    match value {
+                        Value::Null => {}
+                        Value::Bool(_) => {}
+                        Value::Number(n) if n.is_i64() || n.is_u64() => {}
+                        Value::Number(_) => column_type = ColumnType::Real,
+                        _ => return ColumnType::Text,
+                    }
+                }
+                column_type
+            })
+            .collect()
+    }
+}
+
+/// A column name made of letters, digits and underscores that does not start with a digit
+pub fn sql_identifier(name: &str, index: usize) -> String {
+    let mut identifier: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    identifier = identifier.trim_matches('_').to_string();
+    if identifier.is_empty() {
+        return format!("column_{}", index + 1);
+    }
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert_str(0, "c_");
+    }
+    identifier
+}
+
+pub fn read_table(path: &Path, format: FileFormat) -> Result<Table> {
+    match format {
+        FileFormat::Csv => read_delimited(path, b','),
+        FileFormat::Tsv => read_delimited(path, b'\t'),
+        FileFormat::Json => read_json(path),
+        FileFormat::Parquet => read_parquet(path),
+    }
+}
+
+/// Numbers are only recognized when they survive a round trip, so identifiers such
+/// as zip codes with leading zeros stay text.
+fn delimited_cell(cell: &str) -> Value {
+    let cell = cell.trim();
+    if cell.is_empty() {
+        return Value::Null;
+    }
+    let leading_zero = cell.len() > 1 && cell.starts_with('0') && !cell.starts_with("0.");
+    if !leading_zero {
+        if let Ok(n) = cell.parse::<i64>() {
+            return Value::from(n);
+        }
+        if let Ok(n) = cell.parse::<f64>() {
+            if n.is_finite() {
+                return Value::from(n);
+            }
+        }
+    }
+    Value::String(cell.to_string())
+}
+
+fn read_delimited(path: &Path, delimiter: u8) -> Result<Table> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let headers = reader.headers()?.iter().map(str::to_string).collect();
+    let mut table = Table::new(headers);
+    let width = table.columns.len();
+    for (line, record) in reader.records().enumerate() {
+        let record = record.with_context(|| format!("Invalid row {}", line + 2))?;
+        let mut row: Vec<Value> = record.iter().take(width).map(delimited_cell).collect();
+        row.resize(width, Value::Null);
+        table.rows.push(row);
+    }
+    Ok(table)
+}
+
+/// Either an array of objects or one object per line (JSON Lines)
+fn read_json(path: &Path) -> Result<Table> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let objects: Vec<Value> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(&content).context("Invalid JSON array")?
+    } else {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("Invalid JSON on line {}", index + 1))
+            })
+            .collect::<Result<_>>()?
+    };
+
+    let objects = objects
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| match value {
+            Value::Object(object) => Ok(object),
+            _ => Err(anyhow!("Record {} is not a JSON object", index + 1)),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(table_from_objects(objects))
+}
+
+fn read_parquet(path: &Path) -> Result<Table> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader = SerializedFileReader::new(file).context("Invalid Parquet file")?;
+
+    let objects = reader
+        .into_iter()
+        .map(|row| match row?.to_json_value() {
+            Value::Object(object) => Ok(object),
+            _ => bail!("Parquet row is not a record"),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(table_from_objects(objects))
+}
+
+/// Columns are the union of keys, in the order they are first seen. Nested values
+/// are kept as JSON text, which SQLite's json functions can still query.
+fn table_from_objects(objects: Vec<Map<String, Value>>) -> Table {
+    let mut keys: Vec<String> = Vec::new();
+    for object in &objects {
+        for key in object.keys() {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+    }
+
+    let mut table = Table::new(keys.clone());
+    table.rows = objects
+        .into_iter()
+        .map(|mut object| {
+            keys.iter()
+                .map(|key| match object.remove(key) {
+                    Some(value @ (Value::Array(_) | Value::Object(_))) => {
+                        Value::String(value.to_string())
+                    }
+                    Some(value) => value,
+                    None => Value::Null,
+                })
+                .collect()
+        })
+        .collect();
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_read_csv_infers_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sales.csv");
+        fs::write(
+            &path,
+            "Order Date,region,amount,zip,amount\n2024-01-02,west,10,02134,1.5\n2024-01-03,east,,10001,2\n",
+        )
+        .unwrap();
+
+        let table = read_table(&path, FileFormat::from_path(&path).unwrap()).unwrap();
+        assert_eq!(
+            table.columns,
+            vec!["Order_Date", "region", "amount", "zip", "amount_2"]
+        );
+        assert_eq!(
+            table.renamed,
+            vec![
+                ("Order Date".to_string(), "Order_Date".to_string()),
+                ("amount".to_string(), "amount_2".to_string())
+            ]
+        );
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[1][2], Value::Null);
+        assert_eq!(
+            table.column_types(),
+            vec![
+                ColumnType::Text,
+                ColumnType::Text,
+                ColumnType::Integer,
+                ColumnType::Text,
+                ColumnType::Real
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_json_array_and_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let array = dir.path().join("users.json");
+        fs::write(
+            &array,
+            r#"[{"name": "ada", "age": 36}, {"name": "alan", "tags": ["x"]}]"#,
+        )
+        .unwrap();
+        let table = read_table(&array, FileFormat::Json).unwrap();
+        assert_eq!(table.columns, vec!["name", "age", "tags"]);
+        assert_eq!(table.rows[0][2], Value::Null);
+        assert_eq!(table.rows[1][2], Value::String(r#"["x"]"#.to_string()));
+
+        let lines = dir.path().join("events.jsonl");
+        fs::write(&lines, "{\"id\": 1}\n\n{\"id\": 2.5}\n").unwrap();
+        let table = read_table(&lines, FileFormat::from_path(&lines).unwrap()).unwrap();
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.column_types(), vec![ColumnType::Real]);
+
+        fs::write(&lines, "[1, 2]").unwrap();
+        assert!(read_table(&lines, FileFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_read_parquet() {
+        use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cities.parquet");
+        let schema = Arc::new(
+            parse_message_type(
+                "message cities { required binary city (UTF8); required int64 population; }",
+            )
+            .unwrap(),
+        );
+        let mut writer =
+            SerializedFileWriter::new(File::create(&path).unwrap(), schema, Default::default())
+                .unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(
+                &[ByteArray::from("Oslo"), ByteArray::from("Lima")],
+                None,
+                None,
+            )
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[700_000, 10_000_000], None, None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        writer.close().unwrap();
+
+        let table = read_table(&path, FileFormat::from_path(&path).unwrap()).unwrap();
+        assert_eq!(table.columns, vec!["city", "population"]);
+        assert_eq!(
+            table.rows[1],
+            vec![Value::from("Lima"), Value::from(10_000_000)]
+        );
+        assert_eq!(
+            table.column_types(),
+            vec![ColumnType::Text, ColumnType::Integer]
+        );
+    }
+}
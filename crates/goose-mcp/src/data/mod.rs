@@ -0,0 +1,879 @@
+mod loader;
+
+use crate::autovisualiser::{
+    AutoVisualiserRouter, ChartData, ChartDataValues, ChartDataset, ChartPoint, ChartType,
+    ShowChartParams,
+};
+use indoc::formatdoc;
+use loader::{read_table, sql_identifier, ColumnType, FileFormat};
+use rmcp::{
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::{
+        CallToolResult, Content, ErrorCode, ErrorData, Implementation, Meta, ServerCapabilities,
+        ServerInfo,
+    },
+    schemars::JsonSchema,
+    tool, tool_handler, tool_router, ServerHandler,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+const DEFAULT_MAX_ROWS: usize = 50;
+const MAX_ROWS_LIMIT: usize = 1000;
+/// Longer cell values are cut in query output so one wide column cannot flood the context
+const MAX_CELL_CHARS: usize = 200;
+const SAMPLE_ROWS: usize = 5;
+
+/// Parameters for the load_table tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LoadTableParams {
+    /// Path to a CSV, TSV, JSON (array of objects or JSON Lines) or Parquet file
+    pub path: String,
+    /// Name of the table to create; defaults to the file name without its extension.
+    /// An existing table with the same name is replaced.
+    pub table_name: Option<String>,
+    /// File format: csv, tsv, json or parquet. Detected from the extension when omitted
+    pub format: Option<String>,
+}
+
+/// Parameters for the describe_table tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DescribeTableParams {
+    /// The table to describe; lists all loaded tables when omitted
+    pub table_name: Option<String>,
+}
+
+/// Chart to render from query results with the autovisualiser
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct QueryChart {
+    /// Chart type: line, bar or scatter
+    #[serde(rename = "type")]
+    pub chart_type: ChartType,
+    /// Column for the x axis. Line and bar charts use its values as labels; scatter
+    /// charts need it to be numeric
+    pub x: String,
+    /// Numeric columns to plot, one dataset each
+    pub y: Vec<String>,
+    /// Optional chart title
+    pub title: Option<String>,
+}
+
+/// Parameters for the query tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct QueryParams {
+    /// A single SQLite SELECT (or WITH ... SELECT) statement over the loaded tables
+    pub sql: String,
+    /// Maximum number of rows to return (default 50, at most 1000)
+    pub max_rows: Option<usize>,
+    /// Also show the returned rows as a chart
+    pub chart: Option<QueryChart>,
+}
+
+struct LoadedTable {
+    source: PathBuf,
+    rows: usize,
+    columns: Vec<(String, ColumnType)>,
+}
+
+/// Rows returned by a query, cut off after the requested maximum
+struct ResultSet {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+    truncated: bool,
+}
+
+fn invalid_params(message: impl Into<String>) -> ErrorData {
+    ErrorData::new(ErrorCode::INVALID_PARAMS, message.into(), None)
+}
+
+fn internal_error(error: impl std::fmt::Display) -> ErrorData {
+    ErrorData::new(ErrorCode::INTERNAL_ERROR, error.to_string(), None)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The statement without trailing semicolons, or an error when there is more than one.
+/// Quoted strings, identifiers and comments are skipped while looking for separators.
+fn single_statement(sql: &str) -> Result<&str, String> {
+    let statement = sql
+        .trim()
+        .trim_end_matches(|c: char| c == ';' || c.is_whitespace());
+    if statement.is_empty() {
+        return Err("The query is empty".to_string());
+    }
+
+    let mut chars = statement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                for next in chars.by_ref() {
+                    if next == close {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ';' => {
+                return Err("Only a single SELECT statement can be run at a time".to_string());
+            }
+            _ => {}
+        }
+    }
+    Ok(statement)
+}
+
+fn cell_value(row: &SqliteRow, index: usize) -> Value {
+    let Ok(raw) = row.try_get_raw(index) else {
+        return Value::Null;
+    };
+    if raw.is_null() {
+        return Value::Null;
+    }
+    match raw.type_info().name() {
+        "INTEGER" => row.try_get::<i64, _>(index).map(Value::from).ok(),
+        "REAL" => row
+            .try_get::<f64, _>(index)
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        "BLOB" => row
+            .try_get::<Vec<u8>, _>(index)
+            .map(|blob| Value::String(format!("<{} bytes>", blob.len())))
+            .ok(),
+        _ => row.try_get::<String, _>(index).map(Value::String).ok(),
+    }
+    .unwrap_or(Value::Null)
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn markdown_table(columns: &[String], rows: &[Vec<Value>]) -> String {
+    let cell = |value: &Value| {
+        let text = display_value(value).replace('|', "\\|").replace('\n', " ");
+        if text.chars().count() > MAX_CELL_CHARS {
+            format!("{}…", text.chars().take(MAX_CELL_CHARS).collect::<String>())
+        } else {
+            text
+        }
+    };
+
+    let mut table = format!(
+        "| {} |\n|{}|\n",
+        columns.join(" | "),
+        vec!["---"; columns.len()].join("|")
+    );
+    for row in rows {
+        table.push_str(&format!(
+            "| {} |\n",
+            row.iter().map(cell).collect::<Vec<_>>().join(" | ")
+        ));
+    }
+    table
+}
+
+/// Chart data for the autovisualiser from the returned rows
+fn chart_data(chart: QueryChart, result: &ResultSet) -> Result<ChartData, String> {
+    let column_index = |name: &str| {
+        result
+            .columns
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                format!(
+                    "Chart column '{}' is not in the query result (columns: {})",
+                    name,
+                    result.columns.join(", ")
+                )
+            })
+    };
+    let numbers = |index: usize| -> Result<Vec<f64>, String> {
+        result
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row, values)| {
+                values[index].as_f64().ok_or_else(|| {
+                    format!(
+                        "Column '{}' has a non-numeric value in row {}",
+                        result.columns[index],
+                        row + 1
+                    )
+                })
+            })
+            .collect()
+    };
+
+    if chart.y.is_empty() {
+        return Err("A chart needs at least one y column".to_string());
+    }
+    let x = column_index(&chart.x)?;
+    let y = chart
+        .y
+        .iter()
+        .map(|name| column_index(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let scatter = matches!(chart.chart_type, ChartType::Scatter);
+    let x_values = if scatter { numbers(x)? } else { Vec::new() };
+    let datasets = y
+        .into_iter()
+        .map(|index| {
+            let values = numbers(index)?;
+            let data = if scatter {
+                ChartDataValues::Points(
+                    x_values
+                        .iter()
+                        .zip(values)
+                        .map(|(x, y)| ChartPoint { x: *x, y })
+                        .collect(),
+                )
+            } else {
+                ChartDataValues::Numbers(values)
+            };
+            Ok(ChartDataset {
+                label: result.columns[index].clone(),
+                data,
+                background_color: None,
+                border_color: None,
+                border_width: None,
+                tension: None,
+                fill: None,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(ChartData {
+        chart_type: chart.chart_type,
+        datasets,
+        labels: (!scatter).then(|| {
+            result
+                .rows
+                .iter()
+                .map(|row| display_value(&row[x]))
+                .collect()
+        }),
+        title: chart.title,
+        subtitle: None,
+        x_axis_label: Some(result.columns[x].clone()),
+        y_axis_label: None,
+    })
+}
+
+/// Data MCP server: loads local tabular files into an in-memory SQLite database and
+/// answers SQL queries over them
+#[derive(Clone)]
+pub struct DataServer {
+    tool_router: ToolRouter<Self>,
+    instructions: String,
+    db: Arc<OnceCell<SqlitePool>>,
+    tables: Arc<Mutex<BTreeMap<String, LoadedTable>>>,
+    visualiser: AutoVisualiserRouter,
+}
+
+impl Default for DataServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tool_router(router = tool_router)]
+impl DataServer {
+    pub fn new() -> Self {
+        let instructions = formatdoc! {r#"
+            This extension loads local data files into tables and answers SQL queries over them,
+            so there is no need to write scripts to analyse CSV, JSON or Parquet files.
+
+            ## Available Tools:
+            - **load_table**: Loads a CSV, TSV, JSON (array or JSON Lines) or Parquet file into a table
+            - **describe_table**: Lists loaded tables, or shows a table's columns, statistics and sample rows
+            - **query**: Runs a SQLite SELECT statement over the loaded tables, optionally rendering a chart
+
+            Queries use the SQLite dialect, including its date and json functions. Column names are
+            made SQL-friendly when loading, so check describe_table for the exact names. Results are
+            truncated to max_rows; aggregate in SQL rather than fetching raw rows where possible.
+        "#};
+
+        Self {
+            tool_router: Self::tool_router(),
+            instructions,
+            db: Arc::new(OnceCell::new()),
+            tables: Arc::new(Mutex::new(BTreeMap::new())),
+            visualiser: AutoVisualiserRouter::new(),
+        }
+    }
+
+    /// A single connection keeps the in-memory database alive for the whole session
+    async fn pool(&self) -> Result<&SqlitePool, ErrorData> {
+        self.db
+            .get_or_try_init(|| async {
+                SqlitePoolOptions::new()
+                    .max_connections(1)
+                    .min_connections(1)
+                    .idle_timeout(None)
+                    .max_lifetime(None)
+                    .connect("sqlite::memory:")
+                    .await
+            })
+            .await
+            .map_err(internal_error)
+    }
+
+    fn loaded_table_name(&self, name: &str) -> Option<String> {
+        let tables = self.tables.lock().unwrap_or_else(|p| p.into_inner());
+        tables
+            .keys()
+            .find(|table| table.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    async fn fetch(&self, sql: &str, max_rows: usize) -> Result<ResultSet, ErrorData> {
+        let statement = single_statement(sql).map_err(invalid_params)?;
+        // The newline keeps a trailing line comment from swallowing the closing paren
+        let wrapped = format!(
+            "SELECT * FROM (\n{}\n) LIMIT {}",
+            statement,
+            max_rows.saturating_add(1)
+        );
+        let mut rows = sqlx::query(&wrapped)
+            .fetch_all(self.pool().await?)
+            .await
+            .map_err(|e| invalid_params(format!("Query failed: {}", e)))?;
+
+        let truncated = rows.len() > max_rows;
+        rows.truncate(max_rows);
+        let columns = rows
+            .first()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let rows = rows
+            .iter()
+            .map(|row| (0..row.len()).map(|index| cell_value(row, index)).collect())
+            .collect();
+
+        Ok(ResultSet {
+            columns,
+            rows,
+            truncated,
+        })
+    }
+
+    /// Load a CSV, TSV, JSON or Parquet file into a table that can be queried with SQL
+    #[tool(
+        name = "load_table",
+        description = "Load a local CSV, TSV, JSON (array of objects or JSON Lines) or Parquet file into a table that can be queried with SQL. Column types are inferred and column names are made SQL-friendly. Replaces any table with the same name."
+    )]
+    pub async fn load_table(
+        &self,
+        params: Parameters<LoadTableParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let path = PathBuf::from(shellexpand::tilde(&params.path).into_owned());
+        if !path.is_file() {
+            return Err(invalid_params(format!(
+                "File not found: {}",
+                path.display()
+            )));
+        }
+
+        let format = match &params.format {
+            Some(format) => FileFormat::parse(format).ok_or_else(|| {
+                invalid_params(format!(
+                    "Unsupported format '{}'. Use csv, tsv, json or parquet",
+                    format
+                ))
+            })?,
+            None => FileFormat::from_path(&path).ok_or_else(|| {
+                invalid_params(format!(
+                    "Cannot tell the format of {} from its extension; pass format",
+                    path.display()
+                ))
+            })?,
+        };
+
+        let table_name = params
+            .table_name
+            .as_deref()
+            .or_else(|| path.file_stem().and_then(|stem| stem.to_str()))
+            .map(|name| sql_identifier(name, 0))
+            .unwrap_or_else(|| "data".to_string());
+
+        let read_path = path.clone();
+        let table = tokio::task::spawn_blocking(move || read_table(&read_path, format))
+            .await
+            .map_err(internal_error)?
+            .map_err(|e| invalid_params(format!("Failed to read {}: {:#}", path.display(), e)))?;
+        if table.columns.is_empty() {
+            return Err(invalid_params(format!("{} has no columns", path.display())));
+        }
+        let types = table.column_types();
+
+        let quoted_table = quote_identifier(&table_name);
+        let column_definitions = table
+            .columns
+            .iter()
+            .zip(&types)
+            .map(|(column, column_type)| {
+                format!("{} {}", quote_identifier(column), column_type.as_sql())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let insert = format!(
+            "INSERT INTO {} VALUES ({})",
+            quoted_table,
+            vec!["?"; table.columns.len()].join(", ")
+        );
+
+        let mut tx = self.pool().await?.begin().await.map_err(internal_error)?;
+        sqlx::query(&format!("DROP TABLE IF EXISTS {}", quoted_table))
+            .execute(&mut *tx)
+            .await
+            .map_err(internal_error)?;
+        sqlx::query(&format!(
+            "CREATE TABLE {} ({})",
+            quoted_table, column_definitions
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(internal_error)?;
+        for row in &table.rows {
+            let mut query = sqlx::query(&insert);
+            for value in row {
+                query = match value {
+                    Value::Null => query.bind(None::<String>),
+                    Value::Bool(b) => query.bind(*b as i64),
+                    Value::Number(n) => match n.as_i64() {
+                        Some(i) => query.bind(i),
+                        None => query.bind(n.as_f64()),
+                    },
+                    Value::String(s) => query.bind(s.clone()),
+                    other => query.bind(other.to_string()),
+                };
+            }
+            query.execute(&mut *tx).await.map_err(internal_error)?;
+        }
+        tx.commit().await.map_err(internal_error)?;
+
+        let columns: Vec<(String, ColumnType)> = table.columns.iter().cloned().zip(types).collect();
+        let mut summary = format!(
+            "Loaded {} rows from {} into table {}\nColumns:\n",
+            table.rows.len(),
+            path.display(),
+            table_name
+        );
+        for (column, column_type) in &columns {
+            let original = table
+                .renamed
+                .iter()
+                .find(|(_, renamed)| renamed == column)
+                .map(|(original, _)| format!(" (from \"{}\")", original))
+                .unwrap_or_default();
+            summary.push_str(&format!(
+                "- {} {}{}\n",
+                column,
+                column_type.as_sql(),
+                original
+            ));
+        }
+        let sample: Vec<Vec<Value>> = table.rows.iter().take(SAMPLE_ROWS).cloned().collect();
+        if !sample.is_empty() {
+            summary.push_str(&format!(
+                "\nFirst rows:\n{}",
+                markdown_table(&table.columns, &sample)
+            ));
+        }
+
+        let mut tables = self.tables.lock().unwrap_or_else(|p| p.into_inner());
+        tables.retain(|name, _| !name.eq_ignore_ascii_case(&table_name));
+        tables.insert(
+            table_name,
+            LoadedTable {
+                source: path,
+                rows: table.rows.len(),
+                columns,
+            },
+        );
+
+        Ok(CallToolResult::success(vec![Content::text(summary)]))
+    }
+
+    /// List loaded tables, or describe one table's columns with statistics and sample rows
+    #[tool(
+        name = "describe_table",
+        description = "List the loaded tables, or describe one table: its columns and types, null and distinct counts, min/max/mean of numeric columns and a few sample rows."
+    )]
+    pub async fn describe_table(
+        &self,
+        params: Parameters<DescribeTableParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(requested) = params.0.table_name else {
+            let tables = self.tables.lock().unwrap_or_else(|p| p.into_inner());
+            if tables.is_empty() {
+                return Ok(CallToolResult::success(vec![Content::text(
+                    "No tables loaded yet. Use load_table to load a file.",
+                )]));
+            }
+            let listing = tables
+                .iter()
+                .map(|(name, table)| {
+                    format!(
+                        "- {}: {} rows, {} columns, from {}",
+                        name,
+                        table.rows,
+                        table.columns.len(),
+                        table.source.display()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "Loaded tables:\n{}",
+                listing
+            ))]));
+        };
+
+        let name = self.loaded_table_name(&requested).ok_or_else(|| {
+            invalid_params(format!(
+                "No table named '{}'. Use describe_table without a name to list tables",
+                requested
+            ))
+        })?;
+        let (source, rows, columns) = {
+            let tables = self.tables.lock().unwrap_or_else(|p| p.into_inner());
+            let table = &tables[&name];
+            (table.source.clone(), table.rows, table.columns.clone())
+        };
+
+        let quoted_table = quote_identifier(&name);
+        let mut description = format!(
+            "Table {} ({} rows, from {})\nColumns:\n",
+            name,
+            rows,
+            source.display()
+        );
+        for (column, column_type) in &columns {
+            let quoted = quote_identifier(column);
+            let numeric = *column_type != ColumnType::Text;
+            let stats = self
+                .fetch(
+                    &format!(
+                        "SELECT COUNT({quoted}) AS non_null, COUNT(DISTINCT {quoted}) AS distinct_values, \
+                         MIN({quoted}) AS min, MAX({quoted}) AS max, {} AS mean FROM {quoted_table}",
+                        if numeric {
+                            format!("AVG({})", quoted)
+                        } else {
+                            "NULL".to_string()
+                        }
+                    ),
+                    1,
+                )
+                .await?;
+            let Some(values) = stats.rows.first() else {
+                continue;
+            };
+            let non_null = values[0].as_u64().unwrap_or_default() as usize;
+            let mut line = format!(
+                "- {} {}: {} nulls, {} distinct",
+                column,
+                column_type.as_sql(),
+                rows.saturating_sub(non_null),
+                display_value(&values[1])
+            );
+            if non_null > 0 {
+                line.push_str(&format!(
+                    ", min {}, max {}",
+                    display_value(&values[2]),
+                    display_value(&values[3])
+                ));
+                if let Some(mean) = values[4].as_f64() {
+                    line.push_str(&format!(", mean {:.2}", mean));
+                }
+            }
+            description.push_str(&line);
+            description.push('\n');
+        }
+
+        let sample = self
+            .fetch(&format!("SELECT * FROM {}", quoted_table), SAMPLE_ROWS)
+            .await?;
+        if !sample.rows.is_empty() {
+            description.push_str(&format!(
+                "\nSample rows:\n{}",
+                markdown_table(&sample.columns, &sample.rows)
+            ));
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(description)]))
+    }
+
+    /// Run a SQL query over the loaded tables
+    #[tool(
+        name = "query",
+        description = "Run a single SQLite SELECT statement over the loaded tables. Returns at most max_rows rows (default 50, up to 1000) as a table and says when more were cut off. Set chart to also render the returned rows as a line, bar or scatter chart."
+    )]
+    pub async fn query(
+        &self,
+        params: Parameters<QueryParams>,
+        meta: Meta,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let max_rows = params
+            .max_rows
+            .unwrap_or(DEFAULT_MAX_ROWS)
+            .clamp(1, MAX_ROWS_LIMIT);
+        let result = self.fetch(&params.sql, max_rows).await?;
+
+        if result.rows.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "The query returned no rows.",
+            )]));
+        }
+
+        let mut output = markdown_table(&result.columns, &result.rows);
+        if result.truncated {
+            output.push_str(&format!(
+                "\nShowing the first {} rows; the query returned more. Aggregate or filter in SQL, or raise max_rows (at most {}).",
+                result.rows.len(),
+                MAX_ROWS_LIMIT
+            ));
+        } else {
+            output.push_str(&format!("\n{} rows", result.rows.len()));
+        }
+        let mut content = vec![Content::text(output)];
+
+        if let Some(chart) = params.chart {
+            let data = chart_data(chart, &result).map_err(invalid_params)?;
+            let rendered = self
+                .visualiser
+                .show_chart(Parameters(ShowChartParams { data }), meta)
+                .await?;
+            content.extend(rendered.content);
+        }
+
+        Ok(CallToolResult::success(content))
+    }
+}
+
+#[tool_handler(router = self.tool_router)]
+impl ServerHandler for DataServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            server_info: Implementation {
+                name: "goose-data".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+                title: None,
+                icons: None,
+                website_url: None,
+            },
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            instructions: Some(self.instructions.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::{RawContent, ResourceContents};
+    use std::fs;
+
+    fn text(result: &CallToolResult) -> String {
+        match &*result.content[0] {
+            RawContent::Text(text) => text.text.clone(),
+            _ => panic!("Expected text content"),
+        }
+    }
+
+    async fn server_with_sales() -> (DataServer, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sales.csv");
+        fs::write(
+            &path,
+            "month,region,Units Sold\nJan,west,10\nJan,east,4\nFeb,west,12\nFeb,east,\nMar,west,7\n",
+        )
+        .unwrap();
+
+        let server = DataServer::new();
+        server
+            .load_table(Parameters(LoadTableParams {
+                path: path.to_string_lossy().to_string(),
+                table_name: None,
+                format: None,
+            }))
+            .await
+            .unwrap();
+        (server, dir)
+    }
+
+    #[test]
+    fn test_single_statement() {
+        assert_eq!(single_statement(" SELECT 1; ").unwrap(), "SELECT 1");
+        assert_eq!(
+            single_statement("SELECT ';' AS a -- trailing; comment").unwrap(),
+            "SELECT ';' AS a -- trailing; comment"
+        );
+        assert!(single_statement("SELECT 1; ATTACH 'x.db' AS x").is_err());
+        assert!(single_statement(" ; ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_and_describe_table() {
+        let (server, _dir) = server_with_sales().await;
+
+        let tables = server
+            .describe_table(Parameters(DescribeTableParams { table_name: None }))
+            .await
+            .unwrap();
+        assert!(text(&tables).contains("- sales: 5 rows, 3 columns"));
+
+        let description = text(
+            &server
+                .describe_table(Parameters(DescribeTableParams {
+                    table_name: Some("SALES".to_string()),
+                }))
+                .await
+                .unwrap(),
+        );
+        assert!(description.contains("- region TEXT: 0 nulls, 2 distinct"));
+        assert!(description
+            .contains("- Units_Sold INTEGER: 1 nulls, 4 distinct, min 4, max 12, mean 8.25"));
+        assert!(description.contains("| month | region | Units_Sold |"));
+
+        assert!(server
+            .describe_table(Parameters(DescribeTableParams {
+                table_name: Some("missing".to_string()),
+            }))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_truncates_and_rejects_writes() {
+        let (server, _dir) = server_with_sales().await;
+
+        let result = server
+            .query(
+                Parameters(QueryParams {
+                    sql: "SELECT month, SUM(Units_Sold) AS units FROM sales GROUP BY month ORDER BY units DESC".to_string(),
+                    max_rows: None,
+                    chart: None,
+                }),
+                Meta::default(),
+            )
+            .await
+            .unwrap();
+        let output = text(&result);
+        assert!(output.starts_with("| month | units |\n|---|---|\n| Jan | 14 |\n| Feb | 12 |"));
+        assert!(output.ends_with("3 rows"));
+
+        let result = server
+            .query(
+                Parameters(QueryParams {
+                    sql: "SELECT * FROM sales".to_string(),
+                    max_rows: Some(2),
+                    chart: None,
+                }),
+                Meta::default(),
+            )
+            .await
+            .unwrap();
+        let output = text(&result);
+        assert_eq!(output.matches("\n| ").count(), 2);
+        assert!(output.contains("Showing the first 2 rows"));
+
+        for sql in ["DELETE FROM sales", "SELECT 1; DROP TABLE sales"] {
+            assert!(server
+                .query(
+                    Parameters(QueryParams {
+                        sql: sql.to_string(),
+                        max_rows: None,
+                        chart: None,
+                    }),
+                    Meta::default(),
+                )
+                .await
+                .is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_renders_chart() {
+        let (server, _dir) = server_with_sales().await;
+
+        let chart = |y: &str| QueryChart {
+            chart_type: ChartType::Bar,
+            x: "month".to_string(),
+            y: vec![y.to_string()],
+            title: Some("Units by month".to_string()),
+        };
+        let result = server
+            .query(
+                Parameters(QueryParams {
+                    sql: "SELECT month, SUM(Units_Sold) AS units FROM sales GROUP BY month"
+                        .to_string(),
+                    max_rows: None,
+                    chart: Some(chart("units")),
+                }),
+                Meta::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.content.len(), 2);
+        let RawContent::Resource(resource) = &*result.content[1] else {
+            panic!("Expected the chart resource");
+        };
+        assert!(matches!(
+            &resource.resource,
+            ResourceContents::BlobResourceContents { uri, .. } if uri == "ui://chart/interactive"
+        ));
+
+        let err = server
+            .query(
+                Parameters(QueryParams {
+                    sql: "SELECT month, region FROM sales".to_string(),
+                    max_rows: None,
+                    chart: Some(chart("region")),
+                }),
+                Meta::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("non-numeric"));
+    }
+}
@@ -114,6 +114,14 @@ impl CallGraph {
         graph
     }
 
+    /// Every call site or type reference of `symbol` as (file, line, enclosing function).
+    pub fn references(&self, symbol: &str) -> &[(PathBuf, usize, String)] {
+        self.callers
+            .get(symbol)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     pub fn find_incoming_chains(&self, symbol: &str, max_depth: u32) -> Vec<CallChain> {
         tracing::trace!(
             "Finding incoming chains for {} with depth {}",
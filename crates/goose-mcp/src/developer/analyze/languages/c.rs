@@ -0,0 +1,89 @@
+/// Tree-sitter query for extracting C code elements
+pub const ELEMENT_QUERY: &str = r#"
+    (function_definition
+      declarator: (function_declarator
+        declarator: (identifier) @func))
+
+    ; Functions returning pointers
+    (function_definition
+      declarator: (pointer_declarator
+        declarator: (function_declarator
+          declarator: (identifier) @func)))
+
+    ; Only definitions with a body, not every `struct Foo` type use
+    (struct_specifier name: (type_identifier) @struct body: (field_declaration_list))
+    (union_specifier name: (type_identifier) @struct body: (field_declaration_list))
+    (enum_specifier name: (type_identifier) @struct body: (enumerator_list))
+    (type_definition declarator: (type_identifier) @struct)
+
+    (preproc_include) @import
+"#;
+
+/// Tree-sitter query for extracting C function calls
+pub const CALL_QUERY: &str = r#"
+    ; Function calls
+    (call_expression
+      function: (identifier) @function.call)
+
+    ; Calls through struct members (function pointers)
+    (call_expression
+      function: (field_expression
+        field: (field_identifier) @method.call))
+"#;
+
+/// Tree-sitter query for extracting C type references
+pub const REFERENCE_QUERY: &str = r#"
+    ; Struct fields - typedef'd and struct types
+    (field_declaration
+      type: (type_identifier) @field.type)
+    (field_declaration
+      type: (struct_specifier
+        name: (type_identifier) @field.type))
+
+    ; Function parameters
+    (parameter_declaration
+      type: (type_identifier) @param.type)
+    (parameter_declaration
+      type: (struct_specifier
+        name: (type_identifier) @param.type))
+
+    ; Variable declarations
+    (declaration
+      type: (type_identifier) @var.type)
+    (declaration
+      type: (struct_specifier
+        name: (type_identifier) @var.type))
+
+    ; Compound literals, e.g. (struct point){1, 2}
+    (compound_literal_expression
+      type: (type_descriptor
+        type: (type_identifier) @struct.literal))
+"#;
+
+/// Extract the name of a C or C++ function definition
+///
+/// The name is nested inside the declarator chain (pointer, reference and function
+/// declarators, and C++ qualified names), so it is not a direct child of the
+/// function_definition node. Qualified names like `Widget::draw` resolve to `draw`
+/// to match the names recorded as definitions.
+pub fn extract_function_name_for_kind(
+    node: &tree_sitter::Node,
+    source: &str,
+    kind: &str,
+) -> Option<String> {
+    if kind != "function_definition" {
+        return None;
+    }
+
+    let mut declarator = node.child_by_field_name("declarator")?;
+    loop {
+        declarator = match declarator.kind() {
+            "function_declarator" | "pointer_declarator" => {
+                declarator.child_by_field_name("declarator")?
+            }
+            "reference_declarator" | "parenthesized_declarator" => declarator.named_child(0)?,
+            "qualified_identifier" => declarator.child_by_field_name("name")?,
+            _ => return Some(source[declarator.byte_range()].to_string()),
+        };
+    }
+}
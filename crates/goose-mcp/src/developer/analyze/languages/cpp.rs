@@ -0,0 +1,115 @@
+/// Tree-sitter query for extracting C++ code elements
+pub const ELEMENT_QUERY: &str = r#"
+    ; Free functions
+    (function_definition
+      declarator: (function_declarator
+        declarator: (identifier) @func))
+
+    ; Methods defined inside a class body
+    (function_definition
+      declarator: (function_declarator
+        declarator: (field_identifier) @func))
+
+    ; Methods defined outside the class, e.g. Widget::draw
+    (function_definition
+      declarator: (function_declarator
+        declarator: (qualified_identifier
+          name: (identifier) @func)))
+
+    ; Functions returning pointers
+    (function_definition
+      declarator: (pointer_declarator
+        declarator: (function_declarator
+          declarator: (identifier) @func)))
+
+    (class_specifier name: (type_identifier) @class body: (field_declaration_list))
+    (struct_specifier name: (type_identifier) @struct body: (field_declaration_list))
+    (enum_specifier name: (type_identifier) @struct body: (enumerator_list))
+
+    (preproc_include) @import
+    (using_declaration) @import
+"#;
+
+/// Tree-sitter query for extracting C++ function calls
+pub const CALL_QUERY: &str = r#"
+    ; Function calls
+    (call_expression
+      function: (identifier) @function.call)
+
+    ; Method calls (obj.method() and ptr->method())
+    (call_expression
+      function: (field_expression
+        field: (field_identifier) @method.call))
+
+    ; Qualified calls (e.g., std::move(), Widget::create())
+    (call_expression
+      function: (qualified_identifier) @scoped.call)
+
+    ; Heap allocations
+    (new_expression
+      type: (type_identifier) @constructor.call)
+"#;
+
+/// Tree-sitter query for extracting C++ type references and method ownership
+pub const REFERENCE_QUERY: &str = r#"
+    ; Methods defined inside a class or struct body
+    (field_declaration_list
+      (function_definition
+        declarator: (function_declarator
+          declarator: (field_identifier) @method.receiver)))
+
+    ; Methods defined outside the class - the scope names the owning type
+    (function_definition
+      declarator: (function_declarator
+        declarator: (qualified_identifier
+          scope: (namespace_identifier) @method.receiver)))
+
+    ; Member types
+    (field_declaration
+      type: (type_identifier) @field.type)
+
+    ; Parameter types
+    (parameter_declaration
+      type: (type_identifier) @param.type)
+
+    ; Local and global variable types
+    (declaration
+      type: (type_identifier) @var.type)
+"#;
+
+/// Find the method name for a method receiver node in C++
+///
+/// The receiver is either the method name itself (in-class definitions) or the
+/// scope of a qualified name (out-of-class definitions); both resolve to the
+/// enclosing function_definition.
+pub fn find_method_for_receiver(
+    receiver_node: &tree_sitter::Node,
+    source: &str,
+    _ast_recursion_limit: Option<usize>,
+) -> Option<String> {
+    let mut current = *receiver_node;
+    while let Some(parent) = current.parent() {
+        if parent.kind() == "function_definition" {
+            return super::c::extract_function_name_for_kind(&parent, source, parent.kind());
+        }
+        current = parent;
+    }
+    None
+}
+
+/// Find the class a method receiver belongs to in C++
+///
+/// For in-class definitions this is the enclosing class or struct. Out-of-class
+/// definitions have no enclosing class, so the scope text is used instead.
+pub fn find_receiver_type(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        if matches!(parent.kind(), "class_specifier" | "struct_specifier") {
+            return parent
+                .child_by_field_name("name")
+                .map(|name| source[name.byte_range()].to_string());
+        }
+        current = parent;
+    }
+    None
+}
@@ -0,0 +1,97 @@
+/// Tree-sitter query for extracting C# code elements
+pub const ELEMENT_QUERY: &str = r#"
+    (method_declaration name: (identifier) @func)
+    (constructor_declaration name: (identifier) @func)
+    (local_function_statement name: (identifier) @func)
+    (class_declaration name: (identifier) @class)
+    (interface_declaration name: (identifier) @class)
+    (record_declaration name: (identifier) @class)
+    (struct_declaration name: (identifier) @struct)
+    (enum_declaration name: (identifier) @struct)
+    (using_directive) @import
+"#;
+
+/// Tree-sitter query for extracting C# function calls
+pub const CALL_QUERY: &str = r#"
+    ; Function calls
+    (invocation_expression
+      function: (identifier) @function.call)
+
+    ; Method calls
+    (invocation_expression
+      function: (member_access_expression
+        name: (identifier) @method.call))
+
+    ; Constructor calls
+    (object_creation_expression
+      type: (identifier) @constructor.call)
+"#;
+
+/// Tree-sitter query for extracting C# type references and method ownership
+pub const REFERENCE_QUERY: &str = r#"
+    ; Method names, associated with their containing type
+    (method_declaration
+      name: (identifier) @method.receiver)
+
+    ; Field types
+    (field_declaration
+      (variable_declaration
+        type: (identifier) @field.type))
+
+    ; Property types
+    (property_declaration
+      type: (identifier) @field.type)
+
+    ; Parameter types
+    (parameter
+      type: (identifier) @param.type)
+
+    ; Local variable types
+    (local_declaration_statement
+      (variable_declaration
+        type: (identifier) @var.type))
+"#;
+
+/// Extract function name for C# method-like nodes
+///
+/// The return type of a method can be an identifier too, so the name is taken
+/// from the `name` field rather than the first identifier child.
+pub fn extract_function_name_for_kind(
+    node: &tree_sitter::Node,
+    source: &str,
+    _kind: &str,
+) -> Option<String> {
+    node.child_by_field_name("name")
+        .map(|name| source[name.byte_range()].to_string())
+}
+
+/// Find the method name for a method receiver node in C#
+///
+/// The receiver node is the method name itself.
+pub fn find_method_for_receiver(
+    receiver_node: &tree_sitter::Node,
+    source: &str,
+    _ast_recursion_limit: Option<usize>,
+) -> Option<String> {
+    Some(source[receiver_node.byte_range()].to_string())
+}
+
+/// Find the type declaring a method in C#
+pub fn find_receiver_type(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        if matches!(
+            parent.kind(),
+            "class_declaration"
+                | "struct_declaration"
+                | "record_declaration"
+                | "interface_declaration"
+        ) {
+            return parent
+                .child_by_field_name("name")
+                .map(|name| source[name.byte_range()].to_string());
+        }
+        current = parent;
+    }
+    None
+}
@@ -1,11 +1,11 @@
-/// Tree-sitter query for extracting JavaScript/TypeScript code elements
+/// Tree-sitter query for extracting JavaScript code elements
 pub const ELEMENT_QUERY: &str = r#"
     (function_declaration name: (identifier) @func)
     (class_declaration name: (identifier) @class)
     (import_statement) @import
 "#;
 
-/// Tree-sitter query for extracting JavaScript/TypeScript function calls
+/// Tree-sitter query for extracting JavaScript function calls
 pub const CALL_QUERY: &str = r#"
     ; Function calls
     (call_expression
@@ -19,20 +19,25 @@
 //! Languages can opt into additional features by implementing:
 //!
 //! - Reference tracking: Define `REFERENCE_QUERY` to track type instantiation,
-//!   field types, and method-to-type associations (see Go and Ruby)
+//!   field types, and method-to-type associations (see Go, Ruby and C#)
 //! - Custom function naming: Implement `extract_function_name_for_kind()` for
 //!   special cases like Swift's init/deinit or Rust's impl blocks
 //! - Method receiver lookup: Implement `find_method_for_receiver()` to associate
 //!   methods with their containing types (see Go and Ruby)
 
+pub mod c;
+pub mod cpp;
+pub mod csharp;
 pub mod go;
 pub mod java;
 pub mod javascript;
 pub mod kotlin;
+pub mod php;
 pub mod python;
 pub mod ruby;
 pub mod rust;
 pub mod swift;
+pub mod typescript;
 
 /// Handler for extracting function names from special node kinds
 type ExtractFunctionNameHandler = fn(&tree_sitter::Node, &str, &str) -> Option<String>;
@@ -94,7 +99,7 @@ pub fn get_language_info(language: &str) -> Option<LanguageInfo> {
             find_method_for_receiver_handler: Some(rust::find_method_for_receiver),
             find_receiver_type_handler: Some(rust::find_receiver_type),
         }),
-        "javascript" | "typescript" => Some(LanguageInfo {
+        "javascript" => Some(LanguageInfo {
             element_query: javascript::ELEMENT_QUERY,
             call_query: javascript::CALL_QUERY,
             reference_query: "",
@@ -108,6 +113,21 @@ pub fn get_language_info(language: &str) -> Option<LanguageInfo> {
             find_method_for_receiver_handler: None,
             find_receiver_type_handler: None,
         }),
+        "typescript" | "tsx" => Some(LanguageInfo {
+            element_query: typescript::ELEMENT_QUERY,
+            call_query: typescript::CALL_QUERY,
+            reference_query: typescript::REFERENCE_QUERY,
+            function_node_kinds: &[
+                "function_declaration",
+                "generator_function_declaration",
+                "method_definition",
+                "arrow_function",
+            ],
+            function_name_kinds: &["identifier", "property_identifier"],
+            extract_function_name_handler: Some(typescript::extract_function_name_for_kind),
+            find_method_for_receiver_handler: Some(typescript::find_method_for_receiver),
+            find_receiver_type_handler: Some(typescript::find_receiver_type),
+        }),
         "go" => Some(LanguageInfo {
             element_query: go::ELEMENT_QUERY,
             call_query: go::CALL_QUERY,
@@ -163,6 +183,50 @@ pub fn get_language_info(language: &str) -> Option<LanguageInfo> {
             find_method_for_receiver_handler: Some(ruby::find_method_for_receiver),
             find_receiver_type_handler: None,
         }),
+        "c" => Some(LanguageInfo {
+            element_query: c::ELEMENT_QUERY,
+            call_query: c::CALL_QUERY,
+            reference_query: c::REFERENCE_QUERY,
+            function_node_kinds: &["function_definition"],
+            function_name_kinds: &["identifier"],
+            extract_function_name_handler: Some(c::extract_function_name_for_kind),
+            find_method_for_receiver_handler: None,
+            find_receiver_type_handler: None,
+        }),
+        "cpp" => Some(LanguageInfo {
+            element_query: cpp::ELEMENT_QUERY,
+            call_query: cpp::CALL_QUERY,
+            reference_query: cpp::REFERENCE_QUERY,
+            function_node_kinds: &["function_definition"],
+            function_name_kinds: &["identifier", "field_identifier"],
+            extract_function_name_handler: Some(c::extract_function_name_for_kind),
+            find_method_for_receiver_handler: Some(cpp::find_method_for_receiver),
+            find_receiver_type_handler: Some(cpp::find_receiver_type),
+        }),
+        "csharp" => Some(LanguageInfo {
+            element_query: csharp::ELEMENT_QUERY,
+            call_query: csharp::CALL_QUERY,
+            reference_query: csharp::REFERENCE_QUERY,
+            function_node_kinds: &[
+                "method_declaration",
+                "constructor_declaration",
+                "local_function_statement",
+            ],
+            function_name_kinds: &["identifier"],
+            extract_function_name_handler: Some(csharp::extract_function_name_for_kind),
+            find_method_for_receiver_handler: Some(csharp::find_method_for_receiver),
+            find_receiver_type_handler: Some(csharp::find_receiver_type),
+        }),
+        "php" => Some(LanguageInfo {
+            element_query: php::ELEMENT_QUERY,
+            call_query: php::CALL_QUERY,
+            reference_query: php::REFERENCE_QUERY,
+            function_node_kinds: &["function_definition", "method_declaration"],
+            function_name_kinds: &["name"],
+            extract_function_name_handler: None,
+            find_method_for_receiver_handler: Some(php::find_method_for_receiver),
+            find_receiver_type_handler: Some(php::find_receiver_type),
+        }),
         _ => None,
     }
 }
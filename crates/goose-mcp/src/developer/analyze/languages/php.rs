@@ -0,0 +1,81 @@
+/// Tree-sitter query for extracting PHP code elements
+pub const ELEMENT_QUERY: &str = r#"
+    (function_definition name: (name) @func)
+    (method_declaration name: (name) @func)
+    (class_declaration name: (name) @class)
+    (interface_declaration name: (name) @class)
+    (trait_declaration name: (name) @class)
+    (enum_declaration name: (name) @struct)
+    (namespace_use_declaration) @import
+"#;
+
+/// Tree-sitter query for extracting PHP function calls
+pub const CALL_QUERY: &str = r#"
+    ; Function calls
+    (function_call_expression
+      function: (name) @function.call)
+
+    ; Namespaced function calls (e.g., \App\helper())
+    (function_call_expression
+      function: (qualified_name) @function.call)
+
+    ; Method calls ($obj->method() and $obj?->method())
+    (member_call_expression
+      name: (name) @method.call)
+    (nullsafe_member_call_expression
+      name: (name) @method.call)
+
+    ; Static calls (Class::method())
+    (scoped_call_expression
+      name: (name) @method.call)
+
+    ; Constructor calls
+    (object_creation_expression
+      (name) @constructor.call)
+"#;
+
+/// Tree-sitter query for extracting PHP type references and method ownership
+pub const REFERENCE_QUERY: &str = r#"
+    ; Method names, associated with their containing class, trait or interface
+    (method_declaration
+      name: (name) @method.receiver)
+
+    ; Typed properties
+    (property_declaration
+      type: (named_type (name) @field.type))
+
+    ; Parameter types
+    (simple_parameter
+      type: (named_type (name) @param.type))
+"#;
+
+/// Find the method name for a method receiver node in PHP
+///
+/// The receiver node is the method name itself.
+pub fn find_method_for_receiver(
+    receiver_node: &tree_sitter::Node,
+    source: &str,
+    _ast_recursion_limit: Option<usize>,
+) -> Option<String> {
+    Some(source[receiver_node.byte_range()].to_string())
+}
+
+/// Find the class, trait, interface or enum declaring a method in PHP
+pub fn find_receiver_type(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        if matches!(
+            parent.kind(),
+            "class_declaration"
+                | "trait_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+        ) {
+            return parent
+                .child_by_field_name("name")
+                .map(|name| source[name.byte_range()].to_string());
+        }
+        current = parent;
+    }
+    None
+}
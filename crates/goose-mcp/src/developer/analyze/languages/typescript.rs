@@ -0,0 +1,129 @@
+/// Tree-sitter query for extracting TypeScript/TSX code elements
+pub const ELEMENT_QUERY: &str = r#"
+    (function_declaration name: (identifier) @func)
+    (generator_function_declaration name: (identifier) @func)
+    (method_definition name: (property_identifier) @func)
+
+    ; Arrow functions assigned to variables, e.g. const handler = () => {}
+    (lexical_declaration
+      (variable_declarator
+        name: (identifier) @func
+        value: (arrow_function)))
+
+    (class_declaration name: (type_identifier) @class)
+    (abstract_class_declaration name: (type_identifier) @class)
+    (interface_declaration name: (type_identifier) @class)
+    (type_alias_declaration name: (type_identifier) @struct)
+    (enum_declaration name: (identifier) @struct)
+    (import_statement) @import
+"#;
+
+/// Tree-sitter query for extracting TypeScript/TSX function calls
+pub const CALL_QUERY: &str = r#"
+    ; Function calls
+    (call_expression
+      function: (identifier) @function.call)
+
+    ; Method calls
+    (call_expression
+      function: (member_expression
+        property: (property_identifier) @method.call))
+
+    ; Constructor calls
+    (new_expression
+      constructor: (identifier) @constructor.call)
+
+    ; Decorators without arguments (@Injectable); decorator factories
+    ; such as @Component({...}) are matched as call expressions above
+    (decorator
+      (identifier) @function.call)
+    (decorator
+      (member_expression
+        property: (property_identifier) @method.call))
+"#;
+
+/// Tree-sitter query for extracting TypeScript type references and method ownership
+pub const REFERENCE_QUERY: &str = r#"
+    ; Method names, associated with their containing class
+    (method_definition
+      name: (property_identifier) @method.receiver)
+
+    ; Class fields and interface properties
+    (public_field_definition
+      type: (type_annotation (type_identifier) @field.type))
+    (property_signature
+      type: (type_annotation (type_identifier) @field.type))
+
+    ; Parameter types
+    (required_parameter
+      type: (type_annotation (type_identifier) @param.type))
+    (optional_parameter
+      type: (type_annotation (type_identifier) @param.type))
+
+    ; Variable type annotations
+    (variable_declarator
+      type: (type_annotation (type_identifier) @var.type))
+"#;
+
+/// Extract function name for TypeScript arrow functions
+///
+/// Arrow functions are anonymous; one assigned to a variable takes the variable's
+/// name, and any other (callbacks, IIFEs) is attributed to the nearest named
+/// function around it.
+pub fn extract_function_name_for_kind(
+    node: &tree_sitter::Node,
+    source: &str,
+    kind: &str,
+) -> Option<String> {
+    if kind != "arrow_function" {
+        return None;
+    }
+
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        let named = match parent.kind() {
+            "variable_declarator" if current.kind() == "arrow_function" => true,
+            "function_declaration" | "generator_function_declaration" | "method_definition" => true,
+            _ => false,
+        };
+        if named {
+            return parent
+                .child_by_field_name("name")
+                .map(|name| source[name.byte_range()].to_string());
+        }
+        current = parent;
+    }
+    None
+}
+
+/// Find the method name for a method receiver node in TypeScript
+///
+/// The receiver node is the method name itself. Methods of object literals and
+/// anonymous classes are skipped since there is no type to associate them with.
+pub fn find_method_for_receiver(
+    receiver_node: &tree_sitter::Node,
+    source: &str,
+    _ast_recursion_limit: Option<usize>,
+) -> Option<String> {
+    find_receiver_type(receiver_node, source)?;
+    Some(source[receiver_node.byte_range()].to_string())
+}
+
+/// Find the class declaring a method in TypeScript
+///
+/// Methods of anonymous class expressions have no owning type.
+pub fn find_receiver_type(node: &tree_sitter::Node, source: &str) -> Option<String> {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "class_declaration" | "abstract_class_declaration" => {
+                return parent
+                    .child_by_field_name("name")
+                    .map(|name| source[name.byte_range()].to_string());
+            }
+            "class" => return None,
+            _ => current = parent,
+        }
+    }
+    None
+}
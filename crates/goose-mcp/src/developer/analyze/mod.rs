@@ -138,6 +138,43 @@ impl CodeAnalyzer {
         Ok(CallToolResult::success(Formatter::format_results(output)))
     }
 
+    /// Build a call graph over the files under `path`, for symbol lookups when no
+    /// language server is available.
+    pub fn call_graph(
+        &self,
+        path: &Path,
+        max_depth: u32,
+        ignore_patterns: &Gitignore,
+    ) -> Result<CallGraph, ErrorData> {
+        let traverser = FileTraverser::new(ignore_patterns);
+        traverser.validate_path(path)?;
+
+        let params = AnalyzeParams {
+            path: path.display().to_string(),
+            focus: None,
+            follow_depth: 0,
+            max_depth,
+            ast_recursion_limit: None,
+            force: false,
+        };
+        let files = if path.is_file() {
+            vec![path.to_path_buf()]
+        } else {
+            traverser.collect_files_for_focused(path, max_depth)?
+        };
+
+        use rayon::prelude::*;
+        let results = files
+            .par_iter()
+            .map(|file_path| {
+                self.analyze_file(file_path, &AnalysisMode::Semantic, &params)
+                    .map(|result| (file_path.clone(), result))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CallGraph::build_from_results(&results))
+    }
+
     fn determine_mode(&self, params: &AnalyzeParams, path: &Path) -> AnalysisMode {
         if params.focus.is_some() {
             return AnalysisMode::Focused;
@@ -35,12 +35,18 @@ impl ParserManager {
         let language_config: Language = match language {
             "python" => tree_sitter_python::language(),
             "rust" => tree_sitter_rust::language(),
-            "javascript" | "typescript" => tree_sitter_javascript::language(),
+            "javascript" => tree_sitter_javascript::language(),
+            "typescript" => tree_sitter_typescript::language_typescript(),
+            "tsx" => tree_sitter_typescript::language_tsx(),
             "go" => tree_sitter_go::language(),
             "java" => tree_sitter_java::language(),
             "kotlin" => tree_sitter_kotlin::language(),
             "swift" => devgen_tree_sitter_swift::language(),
             "ruby" => tree_sitter_ruby::language(),
+            "c" => tree_sitter_c::language(),
+            "cpp" => tree_sitter_cpp::language(),
+            "csharp" => tree_sitter_c_sharp::language(),
+            "php" => tree_sitter_php::language_php(),
             _ => {
                 tracing::warn!("Unsupported language: {}", language);
                 return Err(ErrorData::new(
@@ -217,7 +223,7 @@ impl ElementExtractor {
             for capture in match_.captures {
                 let node = capture.node;
                 let text = &source[node.byte_range()];
-                let line = source[..node.start_byte()].lines().count() + 1;
+                let line = node.start_position().row + 1;
 
                 match query.capture_names()[capture.index as usize] {
                     "func" | "const" => {
@@ -0,0 +1,107 @@
+use crate::developer::analyze::parser::{ElementExtractor, ParserManager};
+use crate::developer::analyze::types::{AnalysisResult, ReferenceType};
+use std::collections::HashSet;
+
+fn parse_and_extract(code: &str) -> AnalysisResult {
+    let manager = ParserManager::new();
+    let tree = manager.parse(code, "c").unwrap();
+    ElementExtractor::extract_with_depth(&tree, code, "c", "semantic", None).unwrap()
+}
+
+fn has_reference(result: &AnalysisResult, ref_type: ReferenceType, symbol: &str) -> bool {
+    result
+        .references
+        .iter()
+        .any(|r| r.ref_type == ref_type && r.symbol == symbol)
+}
+
+#[test]
+fn test_c_elements_calls_and_references() {
+    let code = r#"
+#include <stdio.h>
+#include "widget.h"
+
+typedef struct point {
+    int x;
+    int y;
+} Point;
+
+struct widget {
+    Point origin;
+    struct widget *next;
+};
+
+static int area(struct widget *w) {
+    return w->origin.x * w->origin.y;
+}
+
+char *widget_name(Point p) {
+    return "widget";
+}
+
+int main(void) {
+    struct widget w = {0};
+    Point p = (Point){1, 2};
+    printf("%d\n", area(&w));
+    widget_name(p);
+    return 0;
+}
+"#;
+
+    let result = parse_and_extract(code);
+
+    let functions: HashSet<_> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(functions, HashSet::from(["area", "widget_name", "main"]));
+    assert!(result.main_line.is_some());
+
+    // Definitions with a body and typedefs, not every `struct widget` use
+    let types: HashSet<_> = result.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(types, HashSet::from(["point", "Point", "widget"]));
+    assert_eq!(result.import_count, 2);
+
+    // Calls are attributed to the function whose declarator names them
+    let area_call = result
+        .calls
+        .iter()
+        .find(|c| c.callee_name == "area")
+        .expect("area call");
+    assert_eq!(area_call.caller_name.as_deref(), Some("main"));
+    assert!(result.calls.iter().any(|c| c.callee_name == "printf"));
+
+    assert!(has_reference(&result, ReferenceType::FieldType, "Point"));
+    assert!(has_reference(
+        &result,
+        ReferenceType::ParameterType,
+        "Point"
+    ));
+    assert!(has_reference(
+        &result,
+        ReferenceType::ParameterType,
+        "widget"
+    ));
+    assert!(has_reference(&result, ReferenceType::VariableType, "Point"));
+    assert!(has_reference(
+        &result,
+        ReferenceType::TypeInstantiation,
+        "Point"
+    ));
+}
+
+#[test]
+fn test_c_pointer_returning_function_is_caller() {
+    let code = r#"
+char *copy_name(const char *name) {
+    return strdup(name);
+}
+"#;
+
+    let result = parse_and_extract(code);
+
+    assert_eq!(result.function_count, 1);
+    let call = result
+        .calls
+        .iter()
+        .find(|c| c.callee_name == "strdup")
+        .expect("strdup call");
+    assert_eq!(call.caller_name.as_deref(), Some("copy_name"));
+}
@@ -0,0 +1,127 @@
+use crate::developer::analyze::graph::CallGraph;
+use crate::developer::analyze::parser::{ElementExtractor, ParserManager};
+use crate::developer::analyze::types::{AnalysisResult, ReferenceType};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+fn parse_and_extract(code: &str) -> AnalysisResult {
+    let manager = ParserManager::new();
+    let tree = manager.parse(code, "cpp").unwrap();
+    ElementExtractor::extract_with_depth(&tree, code, "cpp", "semantic", None).unwrap()
+}
+
+const SHAPES: &str = r#"
+#include <vector>
+#include "shape.h"
+
+using std::vector;
+
+class Shape {
+public:
+    virtual double area() const { return 0.0; }
+    void describe();
+};
+
+struct Point {
+    int x;
+    int y;
+};
+
+void Shape::describe() {
+    log_area(area());
+}
+
+double total_area(const vector<Shape*>& shapes) {
+    double total = 0;
+    for (auto* shape : shapes) {
+        total += shape->area();
+    }
+    return total;
+}
+
+int main() {
+    Shape* shape = new Shape();
+    Point origin{0, 0};
+    shape->describe();
+    std::vector<Shape*> shapes;
+    total_area(shapes);
+    return 0;
+}
+"#;
+
+#[test]
+fn test_cpp_elements() {
+    let result = parse_and_extract(SHAPES);
+
+    let functions: HashSet<_> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        functions,
+        HashSet::from(["area", "describe", "total_area", "main"])
+    );
+
+    let classes: HashSet<_> = result.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(classes, HashSet::from(["Shape", "Point"]));
+
+    // Two includes and one using declaration
+    assert_eq!(result.import_count, 3);
+}
+
+#[test]
+fn test_cpp_calls_use_unqualified_caller_names() {
+    let result = parse_and_extract(SHAPES);
+
+    let caller_of = |callee: &str| {
+        result
+            .calls
+            .iter()
+            .find(|c| c.callee_name == callee)
+            .and_then(|c| c.caller_name.clone())
+    };
+
+    // Shape::describe is recorded as `describe`, matching its definition
+    assert_eq!(caller_of("log_area").as_deref(), Some("describe"));
+    assert_eq!(caller_of("total_area").as_deref(), Some("main"));
+    assert!(result
+        .calls
+        .iter()
+        .any(|c| c.callee_name == "Shape" && c.caller_name.as_deref() == Some("main")));
+}
+
+#[test]
+fn test_cpp_methods_are_linked_to_their_class() {
+    let result = parse_and_extract(SHAPES);
+
+    let shape_methods: HashSet<_> = result
+        .references
+        .iter()
+        .filter(|r| {
+            r.ref_type == ReferenceType::MethodDefinition
+                && r.associated_type.as_deref() == Some("Shape")
+        })
+        .map(|r| r.symbol.as_str())
+        .collect();
+    assert_eq!(shape_methods, HashSet::from(["area", "describe"]));
+
+    let graph = CallGraph::build_from_results(&[(PathBuf::from("shapes.cpp"), result)]);
+    let chains = graph.find_outgoing_chains("Shape", 1);
+    let targets: HashSet<_> = chains
+        .iter()
+        .filter_map(|c| c.path.first().map(|(_, _, _, to)| to.as_str()))
+        .collect();
+    assert!(targets.contains("area"));
+    assert!(targets.contains("describe"));
+}
+
+#[test]
+fn test_cpp_type_references() {
+    let result = parse_and_extract(SHAPES);
+
+    let var_types: HashSet<_> = result
+        .references
+        .iter()
+        .filter(|r| r.ref_type == ReferenceType::VariableType)
+        .map(|r| r.symbol.as_str())
+        .collect();
+    assert!(var_types.contains("Shape"));
+    assert!(var_types.contains("Point"));
+}
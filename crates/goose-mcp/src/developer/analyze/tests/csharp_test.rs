@@ -0,0 +1,128 @@
+use crate::developer::analyze::parser::{ElementExtractor, ParserManager};
+use crate::developer::analyze::types::{AnalysisResult, ReferenceType};
+use std::collections::HashSet;
+
+fn parse_and_extract(code: &str) -> AnalysisResult {
+    let manager = ParserManager::new();
+    let tree = manager.parse(code, "csharp").unwrap();
+    ElementExtractor::extract_with_depth(&tree, code, "csharp", "semantic", None).unwrap()
+}
+
+const SHOP: &str = r#"
+using System;
+using System.Collections.Generic;
+
+namespace Shop
+{
+    public interface IRepository
+    {
+        Order Find(int id);
+    }
+
+    public record Customer(string Name);
+
+    public enum Status { Open, Closed }
+
+    public class Order
+    {
+        private Customer owner;
+        public Status State { get; set; }
+
+        public Order(Customer customer)
+        {
+            owner = customer;
+        }
+
+        public decimal Total()
+        {
+            return Compute(owner);
+        }
+
+        private static decimal Compute(Customer customer)
+        {
+            Log(customer.ToString());
+            return 0m;
+        }
+
+        private static void Log(string message)
+        {
+            Console.WriteLine(message);
+        }
+    }
+
+    public static class Program
+    {
+        public static void Main()
+        {
+            Order order = new Order(new Customer("Ada"));
+            order.Total();
+        }
+    }
+}
+"#;
+
+#[test]
+fn test_csharp_elements() {
+    let result = parse_and_extract(SHOP);
+
+    let functions: HashSet<_> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    for name in ["Find", "Order", "Total", "Compute", "Log", "Main"] {
+        assert!(functions.contains(name), "missing function {}", name);
+    }
+
+    let types: HashSet<_> = result.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        types,
+        HashSet::from(["IRepository", "Customer", "Status", "Order", "Program"])
+    );
+    assert_eq!(result.import_count, 2);
+}
+
+#[test]
+fn test_csharp_callers_ignore_return_types() {
+    let result = parse_and_extract(SHOP);
+
+    let caller_of = |callee: &str| {
+        result
+            .calls
+            .iter()
+            .find(|c| c.callee_name == callee)
+            .and_then(|c| c.caller_name.clone())
+    };
+
+    // `decimal Total()` and `static decimal Compute(...)` resolve to the method name
+    assert_eq!(caller_of("Compute").as_deref(), Some("Total"));
+    assert_eq!(caller_of("Log").as_deref(), Some("Compute"));
+    assert_eq!(caller_of("WriteLine").as_deref(), Some("Log"));
+    assert_eq!(caller_of("Customer").as_deref(), Some("Main"));
+}
+
+#[test]
+fn test_csharp_type_references() {
+    let result = parse_and_extract(SHOP);
+
+    let order_methods: HashSet<_> = result
+        .references
+        .iter()
+        .filter(|r| {
+            r.ref_type == ReferenceType::MethodDefinition
+                && r.associated_type.as_deref() == Some("Order")
+        })
+        .map(|r| r.symbol.as_str())
+        .collect();
+    assert_eq!(order_methods, HashSet::from(["Total", "Compute", "Log"]));
+
+    let references_of = |ref_type: ReferenceType| -> HashSet<String> {
+        result
+            .references
+            .iter()
+            .filter(|r| r.ref_type == ref_type)
+            .map(|r| r.symbol.clone())
+            .collect()
+    };
+    let fields = references_of(ReferenceType::FieldType);
+    assert!(fields.contains("Customer"));
+    assert!(fields.contains("Status"));
+    assert!(references_of(ReferenceType::ParameterType).contains("Customer"));
+    assert!(references_of(ReferenceType::VariableType).contains("Order"));
+}
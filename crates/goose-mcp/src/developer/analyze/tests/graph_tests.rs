@@ -114,3 +114,24 @@ fn test_deep_chain() {
     assert_eq!(chains.len(), 1);
     assert_eq!(chains[0].path.len(), 4); // Full chain a->b->c->d->e
 }
+
+#[test]
+fn test_references_lists_call_sites() {
+    let results = vec![(
+        PathBuf::from("test.rs"),
+        create_test_result_with_calls(
+            vec!["a", "b", "target"],
+            vec![("a", "target"), ("b", "target")],
+        ),
+    )];
+
+    let graph = CallGraph::build_from_results(&results);
+
+    let callers: Vec<&str> = graph
+        .references("target")
+        .iter()
+        .map(|(_, _, caller)| caller.as_str())
+        .collect();
+    assert_eq!(callers, vec!["a", "b"]);
+    assert!(graph.references("missing").is_empty());
+}
@@ -241,6 +241,11 @@ fn test_language_registry() {
         "kotlin",
         "swift",
         "ruby",
+        "c",
+        "cpp",
+        "csharp",
+        "php",
+        "tsx",
     ];
 
     for lang in supported {
@@ -268,9 +273,14 @@ fn test_language_registry() {
 
     let js = languages::get_language_info("javascript").unwrap();
     let ts = languages::get_language_info("typescript").unwrap();
-    assert_eq!(
+    let tsx = languages::get_language_info("tsx").unwrap();
+    assert_ne!(
         js.element_query, ts.element_query,
-        "JS/TS should share config"
+        "TypeScript should have its own queries"
+    );
+    assert_eq!(
+        ts.element_query, tsx.element_query,
+        "TS/TSX should share config"
     );
 
     let go = languages::get_language_info("go").unwrap();
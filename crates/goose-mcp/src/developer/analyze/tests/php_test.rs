@@ -0,0 +1,137 @@
+use crate::developer::analyze::parser::{ElementExtractor, ParserManager};
+use crate::developer::analyze::types::{AnalysisResult, ReferenceType};
+use std::collections::HashSet;
+
+fn parse_and_extract(code: &str) -> AnalysisResult {
+    let manager = ParserManager::new();
+    let tree = manager.parse(code, "php").unwrap();
+    ElementExtractor::extract_with_depth(&tree, code, "php", "semantic", None).unwrap()
+}
+
+const NOTIFIER: &str = r#"<?php
+
+namespace App;
+
+use App\Models\User;
+use App\Services\Mailer;
+
+interface Notifier
+{
+    public function notify(User $user): void;
+}
+
+trait Loggable
+{
+    public function log(string $message): void
+    {
+        error_log($message);
+    }
+}
+
+class WelcomeNotifier implements Notifier
+{
+    use Loggable;
+
+    private Mailer $mailer;
+
+    public function __construct(Mailer $mailer)
+    {
+        $this->mailer = $mailer;
+    }
+
+    public function notify(User $user): void
+    {
+        $this->log("notifying");
+        $this->mailer->send(format_greeting($user));
+        Mailer::flush();
+    }
+}
+
+function format_greeting(User $user): string
+{
+    return sprintf("Welcome %s", $user->name);
+}
+
+$notifier = new WelcomeNotifier(new Mailer());
+$notifier->notify(new User());
+"#;
+
+#[test]
+fn test_php_elements() {
+    let result = parse_and_extract(NOTIFIER);
+
+    let functions: HashSet<_> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        functions,
+        HashSet::from(["notify", "log", "__construct", "format_greeting"])
+    );
+
+    let classes: HashSet<_> = result.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        classes,
+        HashSet::from(["Notifier", "Loggable", "WelcomeNotifier"])
+    );
+
+    // Namespace imports only; `use Loggable;` inside the class is a trait use
+    assert_eq!(result.import_count, 2);
+}
+
+#[test]
+fn test_php_calls() {
+    let result = parse_and_extract(NOTIFIER);
+
+    let callers = |callee: &str| -> HashSet<Option<String>> {
+        result
+            .calls
+            .iter()
+            .filter(|c| c.callee_name == callee)
+            .map(|c| c.caller_name.clone())
+            .collect()
+    };
+
+    assert_eq!(callers("error_log"), HashSet::from([Some("log".into())]));
+    assert_eq!(
+        callers("format_greeting"),
+        HashSet::from([Some("notify".into())])
+    );
+    assert_eq!(callers("flush"), HashSet::from([Some("notify".into())]));
+    assert_eq!(callers("send"), HashSet::from([Some("notify".into())]));
+    // Top-level statements have no caller
+    assert_eq!(callers("WelcomeNotifier"), HashSet::from([None]));
+    assert!(callers("notify").contains(&None));
+}
+
+#[test]
+fn test_php_type_references() {
+    let result = parse_and_extract(NOTIFIER);
+
+    let methods_of = |owner: &str| -> HashSet<String> {
+        result
+            .references
+            .iter()
+            .filter(|r| {
+                r.ref_type == ReferenceType::MethodDefinition
+                    && r.associated_type.as_deref() == Some(owner)
+            })
+            .map(|r| r.symbol.clone())
+            .collect()
+    };
+    assert_eq!(
+        methods_of("WelcomeNotifier"),
+        HashSet::from(["__construct".to_string(), "notify".to_string()])
+    );
+    assert_eq!(methods_of("Loggable"), HashSet::from(["log".to_string()]));
+    assert_eq!(
+        methods_of("Notifier"),
+        HashSet::from(["notify".to_string()])
+    );
+
+    assert!(result
+        .references
+        .iter()
+        .any(|r| r.ref_type == ReferenceType::FieldType && r.symbol == "Mailer"));
+    assert!(result
+        .references
+        .iter()
+        .any(|r| r.ref_type == ReferenceType::ParameterType && r.symbol == "User"));
+}
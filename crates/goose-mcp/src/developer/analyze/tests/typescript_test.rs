@@ -0,0 +1,165 @@
+use crate::developer::analyze::parser::{ElementExtractor, ParserManager};
+use crate::developer::analyze::types::{AnalysisResult, ReferenceType};
+use std::collections::HashSet;
+
+fn parse_and_extract(code: &str, language: &str) -> AnalysisResult {
+    let manager = ParserManager::new();
+    let tree = manager.parse(code, language).unwrap();
+    ElementExtractor::extract_with_depth(&tree, code, language, "semantic", None).unwrap()
+}
+
+const SERVICE: &str = r#"
+import { Injectable, Log } from "@angular/core";
+import type { Request } from "./http";
+
+export interface User {
+    id: number;
+    profile: Profile;
+}
+
+export type Profile = {
+    name: string;
+};
+
+type Handler = (req: Request) => void;
+
+enum Role {
+    Admin,
+    Member,
+}
+
+@Injectable
+export class UserService {
+    private repository: Repository;
+
+    constructor(repository: Repository) {
+        this.repository = repository;
+    }
+
+    @Log()
+    find(id: number): User {
+        return this.repository.load(id);
+    }
+}
+
+export const handle = (req: Request): void => {
+    const service = new UserService(createRepository());
+    service.find(1);
+};
+
+function createRepository(): Repository {
+    return new Repository();
+}
+"#;
+
+#[test]
+fn test_typescript_interfaces_and_type_aliases() {
+    let result = parse_and_extract(SERVICE, "typescript");
+
+    let functions: HashSet<_> = result.functions.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        functions,
+        HashSet::from(["constructor", "find", "handle", "createRepository"])
+    );
+
+    let types: HashSet<_> = result.classes.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        types,
+        HashSet::from(["User", "Profile", "Handler", "Role", "UserService"])
+    );
+    assert_eq!(result.import_count, 2);
+}
+
+#[test]
+fn test_typescript_decorators_and_arrow_function_callers() {
+    let result = parse_and_extract(SERVICE, "typescript");
+
+    let callers = |callee: &str| -> HashSet<Option<String>> {
+        result
+            .calls
+            .iter()
+            .filter(|c| c.callee_name == callee)
+            .map(|c| c.caller_name.clone())
+            .collect()
+    };
+
+    // Bare and factory decorators are both recorded as calls
+    assert!(!callers("Injectable").is_empty());
+    assert!(!callers("Log").is_empty());
+
+    // Arrow functions assigned to a const take the variable's name
+    assert_eq!(
+        callers("createRepository"),
+        HashSet::from([Some("handle".into())])
+    );
+    assert_eq!(
+        callers("UserService"),
+        HashSet::from([Some("handle".into())])
+    );
+    assert_eq!(callers("load"), HashSet::from([Some("find".into())]));
+    assert_eq!(
+        callers("Repository"),
+        HashSet::from([Some("createRepository".into())])
+    );
+}
+
+#[test]
+fn test_typescript_type_references() {
+    let result = parse_and_extract(SERVICE, "typescript");
+
+    let service_methods: HashSet<_> = result
+        .references
+        .iter()
+        .filter(|r| {
+            r.ref_type == ReferenceType::MethodDefinition
+                && r.associated_type.as_deref() == Some("UserService")
+        })
+        .map(|r| r.symbol.as_str())
+        .collect();
+    assert_eq!(service_methods, HashSet::from(["constructor", "find"]));
+
+    let references_of = |ref_type: ReferenceType| -> HashSet<String> {
+        result
+            .references
+            .iter()
+            .filter(|r| r.ref_type == ref_type)
+            .map(|r| r.symbol.clone())
+            .collect()
+    };
+    let fields = references_of(ReferenceType::FieldType);
+    assert!(fields.contains("Repository"));
+    assert!(fields.contains("Profile"));
+    let params = references_of(ReferenceType::ParameterType);
+    assert!(params.contains("Repository"));
+    assert!(params.contains("Request"));
+}
+
+#[test]
+fn test_tsx_components() {
+    let code = r#"
+import React from "react";
+
+interface Props {
+    title: string;
+}
+
+export function Header({ title }: Props) {
+    return <h1 onClick={() => track(title)}>{format(title)}</h1>;
+}
+"#;
+
+    let result = parse_and_extract(code, "tsx");
+
+    assert_eq!(result.function_count, 1);
+    assert_eq!(result.classes[0].name, "Props");
+
+    // Callbacks inside JSX are attributed to the component
+    for callee in ["track", "format"] {
+        let call = result
+            .calls
+            .iter()
+            .find(|c| c.callee_name == callee)
+            .unwrap_or_else(|| panic!("missing call to {}", callee));
+        assert_eq!(call.caller_name.as_deref(), Some("Header"));
+    }
+}
@@ -0,0 +1,201 @@
+use ignore::gitignore::Gitignore;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::developer::goose_hints::import_files::read_referenced_files;
+use crate::developer::goose_hints::load_hints::find_git_root;
+
+/// Config key for the number of tokens of subdirectory hints injected per session. 0 disables them.
+pub const DIRECTORY_HINTS_TOKEN_BUDGET_KEY: &str = "GOOSE_DIRECTORY_HINTS_TOKEN_BUDGET";
+pub const DEFAULT_DIRECTORY_HINTS_TOKEN_BUDGET: usize = 4000;
+
+/// Rough token estimate, good enough for budgeting hint text
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[derive(Default)]
+struct DirectoryHintsState {
+    visited_dirs: HashSet<PathBuf>,
+    seen_contents: HashSet<String>,
+    tokens_used: usize,
+}
+
+/// Hints files in subdirectories of the working directory, loaded the first time a file
+/// under them is viewed or edited.
+///
+/// Hints in the working directory and its parents are loaded at startup by
+/// `load_hint_files`, so only directories strictly below `root` are considered here.
+pub struct DirectoryHints {
+    root: PathBuf,
+    import_boundary: PathBuf,
+    hints_filenames: Vec<String>,
+    token_budget: usize,
+    state: Mutex<DirectoryHintsState>,
+}
+
+impl DirectoryHints {
+    pub fn new(root: &Path, hints_filenames: Vec<String>, token_budget: usize) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            import_boundary: find_git_root(root).unwrap_or(root).to_path_buf(),
+            hints_filenames,
+            token_budget,
+            state: Mutex::new(DirectoryHintsState::default()),
+        }
+    }
+
+    /// Hints from the directories between the root and `path` that have not been
+    /// returned before, outermost first. Identical hint contents are only returned once,
+    /// and hints that would exceed the token budget are replaced by a pointer to the file.
+    pub fn hints_for(&self, path: &Path, ignore_patterns: &Gitignore) -> Option<String> {
+        if self.token_budget == 0 {
+            return None;
+        }
+        let dir = if path.is_dir() { path } else { path.parent()? };
+        let relative = dir.strip_prefix(&self.root).ok()?;
+
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        let mut sections = Vec::new();
+        let mut current = self.root.clone();
+        for component in relative.components() {
+            current.push(component);
+            if !state.visited_dirs.insert(current.clone()) {
+                continue;
+            }
+
+            for hints_filename in &self.hints_filenames {
+                let hints_path = current.join(hints_filename);
+                if !hints_path.is_file() || ignore_patterns.matched(&hints_path, false).is_ignore()
+                {
+                    continue;
+                }
+
+                let mut visited = HashSet::new();
+                let content = read_referenced_files(
+                    &hints_path,
+                    &self.import_boundary,
+                    &mut visited,
+                    0,
+                    ignore_patterns,
+                );
+                let content = content.trim();
+                if content.is_empty() || !state.seen_contents.insert(content.to_string()) {
+                    continue;
+                }
+
+                let display_path = hints_path.strip_prefix(&self.root).unwrap_or(&hints_path);
+                let tokens = estimate_tokens(content);
+                if state.tokens_used + tokens > self.token_budget {
+                    sections.push(format!(
+                        "--- {} (not loaded: hints token budget exhausted; view the file if it is relevant) ---",
+                        display_path.display()
+                    ));
+                    continue;
+                }
+                state.tokens_used += tokens;
+                sections.push(format!("--- {} ---\n{}", display_path.display(), content));
+            }
+        }
+
+        if sections.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "### Directory Hints\nThe following hints apply to files under {}:\n{}",
+            dir.display(),
+            sections.join("\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ignore::gitignore::GitignoreBuilder;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Gitignore) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".goosehints"), "root hints").unwrap();
+        fs::create_dir_all(dir.path().join("packages/api/src")).unwrap();
+        fs::create_dir_all(dir.path().join("packages/web")).unwrap();
+        let ignore = GitignoreBuilder::new(dir.path()).build().unwrap();
+        (dir, ignore)
+    }
+
+    fn hints(root: &Path, budget: usize) -> DirectoryHints {
+        DirectoryHints::new(root, vec![".goosehints".to_string()], budget)
+    }
+
+    #[test]
+    fn test_subdirectory_hints_load_once_outermost_first() {
+        let (dir, ignore) = setup();
+        let root = dir.path();
+        fs::write(root.join("packages/.goosehints"), "shared package hints").unwrap();
+        fs::write(root.join("packages/api/.goosehints"), "api hints").unwrap();
+
+        let directory_hints = hints(root, DEFAULT_DIRECTORY_HINTS_TOKEN_BUDGET);
+        let loaded = directory_hints
+            .hints_for(&root.join("packages/api/src/main.rs"), &ignore)
+            .unwrap();
+
+        // Root hints are loaded at startup and not repeated
+        assert!(!loaded.contains("root hints"));
+        let shared = loaded.find("shared package hints").unwrap();
+        let api = loaded.find("api hints").unwrap();
+        assert!(shared < api);
+
+        assert!(directory_hints
+            .hints_for(&root.join("packages/api/lib.rs"), &ignore)
+            .is_none());
+        assert!(directory_hints
+            .hints_for(&root.join("README.md"), &ignore)
+            .is_none());
+    }
+
+    #[test]
+    fn test_identical_hints_are_deduplicated() {
+        let (dir, ignore) = setup();
+        let root = dir.path();
+        fs::write(root.join("packages/api/.goosehints"), "use pnpm").unwrap();
+        fs::write(root.join("packages/web/.goosehints"), "use pnpm\n").unwrap();
+
+        let directory_hints = hints(root, DEFAULT_DIRECTORY_HINTS_TOKEN_BUDGET);
+        assert!(directory_hints
+            .hints_for(&root.join("packages/api/index.ts"), &ignore)
+            .is_some());
+        assert!(directory_hints
+            .hints_for(&root.join("packages/web/index.ts"), &ignore)
+            .is_none());
+    }
+
+    #[test]
+    fn test_hints_over_budget_are_referenced_not_loaded() {
+        let (dir, ignore) = setup();
+        let root = dir.path();
+        fs::write(root.join("packages/api/.goosehints"), "a".repeat(40)).unwrap();
+        fs::write(root.join("packages/web/.goosehints"), "b".repeat(40)).unwrap();
+
+        let directory_hints = hints(root, 15);
+        let api = directory_hints
+            .hints_for(&root.join("packages/api/index.ts"), &ignore)
+            .unwrap();
+        assert!(api.contains(&"a".repeat(40)));
+
+        let web = directory_hints
+            .hints_for(&root.join("packages/web/index.ts"), &ignore)
+            .unwrap();
+        assert!(!web.contains(&"b".repeat(40)));
+        assert!(web.contains("token budget exhausted"));
+
+        assert!(hints(root, 0)
+            .hints_for(&root.join("packages/api/index.ts"), &ignore)
+            .is_none());
+    }
+}
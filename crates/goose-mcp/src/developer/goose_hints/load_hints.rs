@@ -9,7 +9,7 @@ use crate::developer::goose_hints::import_files::read_referenced_files;
 
 pub const GOOSE_HINTS_FILENAME: &str = ".goosehints";
 
-fn find_git_root(start_dir: &Path) -> Option<&Path> {
+pub(crate) fn find_git_root(start_dir: &Path) -> Option<&Path> {
     let mut check_dir = start_dir;
 
     loop {
@@ -1,2 +1,3 @@
+pub mod directory_hints;
 mod import_files;
 pub mod load_hints;
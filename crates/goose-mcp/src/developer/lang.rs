@@ -9,6 +9,7 @@ pub fn get_language_identifier(path: &Path) -> &'static str {
         Some("py") => "python",
         Some("js") => "javascript",
         Some("ts") => "typescript",
+        Some("tsx") => "tsx",
         Some("json") => "json",
         Some("toml") => "toml",
         Some("yaml") | Some("yml") => "yaml",
@@ -24,6 +25,7 @@ pub fn get_language_identifier(path: &Path) -> &'static str {
         Some("java") => "java",
         Some("cpp") | Some("cc") | Some("cxx") => "cpp",
         Some("c") => "c",
+        Some("cs") => "csharp",
         Some("h") | Some("hpp") => "cpp",
         Some("rb") => "ruby",
         Some("php") => "php",
@@ -0,0 +1,343 @@
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    path::Path,
+    process::Stdio,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, Command},
+    sync::{oneshot, Mutex, Notify},
+};
+
+use super::{path_to_uri, LspServerConfig};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+type PendingRequests = Arc<std::sync::Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>;
+
+/// A running language server for one workspace, spoken to over stdio with JSON-RPC.
+pub struct LspClient {
+    // Held so the server is killed when the client is dropped
+    _child: Child,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending: PendingRequests,
+    diagnostics: Arc<std::sync::Mutex<HashMap<String, Vec<Value>>>>,
+    diagnostics_changed: Arc<Notify>,
+    open_documents: Mutex<HashMap<String, (i32, String)>>,
+    next_id: AtomicI64,
+    exited: Arc<AtomicBool>,
+}
+
+impl LspClient {
+    /// Spawn the server and complete the `initialize` handshake.
+    pub async fn start(config: &LspServerConfig, root: &Path) -> Result<Self, String> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                format!(
+                    "Failed to start language server '{}': {}",
+                    config.command, e
+                )
+            })?;
+
+        let stdin = Arc::new(Mutex::new(child.stdin.take().ok_or("No stdin")?));
+        let stdout = child.stdout.take().ok_or("No stdout")?;
+
+        let client = Self {
+            _child: child,
+            stdin: stdin.clone(),
+            pending: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            diagnostics: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            diagnostics_changed: Arc::new(Notify::new()),
+            open_documents: Mutex::new(HashMap::new()),
+            next_id: AtomicI64::new(1),
+            exited: Arc::new(AtomicBool::new(false)),
+        };
+
+        tokio::spawn(read_loop(
+            BufReader::new(stdout),
+            stdin,
+            client.pending.clone(),
+            client.diagnostics.clone(),
+            client.diagnostics_changed.clone(),
+            client.exited.clone(),
+        ));
+
+        let root_uri = path_to_uri(root);
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": "workspace" }],
+                    "initializationOptions": config.initialization_options,
+                    "capabilities": {
+                        "textDocument": {
+                            "synchronization": { "didSave": false },
+                            "definition": { "linkSupport": true },
+                            "references": {},
+                            "hover": { "contentFormat": ["markdown", "plaintext"] },
+                            "rename": { "prepareSupport": false },
+                            "publishDiagnostics": { "relatedInformation": false }
+                        },
+                        "workspace": {
+                            "workspaceEdit": { "documentChanges": true },
+                            "configuration": true,
+                            "workspaceFolders": true
+                        }
+                    }
+                }),
+            )
+            .await?;
+        client.notify("initialized", json!({})).await?;
+
+        Ok(client)
+    }
+
+    /// False once the server has closed its stdout, e.g. because it crashed.
+    pub fn is_alive(&self) -> bool {
+        !self.exited.load(Ordering::SeqCst)
+    }
+
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(id, tx);
+
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_message(&mut *self.stdin.lock().await, &message)
+            .await
+            .map_err(|e| format!("Failed to write to language server: {}", e))?;
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err("Language server exited".to_string()),
+            Err(_) => {
+                self.pending
+                    .lock()
+                    .unwrap_or_else(|p| p.into_inner())
+                    .remove(&id);
+                Err(format!("Language server timed out on {}", method))
+            }
+        }
+    }
+
+    pub async fn notify(&self, method: &str, params: Value) -> Result<(), String> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut *self.stdin.lock().await, &message)
+            .await
+            .map_err(|e| format!("Failed to write to language server: {}", e))
+    }
+
+    /// Send the current file contents to the server, opening the document on first use.
+    /// Files edited on disk since the last sync are resent in full.
+    pub async fn sync_document(&self, path: &Path, language_id: &str) -> Result<String, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        let uri = path_to_uri(path);
+
+        let mut open = self.open_documents.lock().await;
+        if open.get(&uri).is_none_or(|(_, synced)| *synced != text) {
+            // Diagnostics for the old contents are stale from here on
+            self.diagnostics
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .remove(&uri);
+        }
+        match open.get_mut(&uri) {
+            Some((_, synced)) if *synced == text => {}
+            Some((version, synced)) => {
+                *version += 1;
+                *synced = text.clone();
+                self.notify(
+                    "textDocument/didChange",
+                    json!({
+                        "textDocument": { "uri": uri, "version": *version },
+                        "contentChanges": [{ "text": text }]
+                    }),
+                )
+                .await?;
+            }
+            None => {
+                open.insert(uri.clone(), (1, text.clone()));
+                self.notify(
+                    "textDocument/didOpen",
+                    json!({
+                        "textDocument": {
+                            "uri": uri,
+                            "languageId": language_id,
+                            "version": 1,
+                            "text": text
+                        }
+                    }),
+                )
+                .await?;
+            }
+        }
+        Ok(text)
+    }
+
+    /// Diagnostics last published for `uri`, waiting up to `wait` for the first batch.
+    pub async fn diagnostics(&self, uri: &str, wait: Duration) -> Option<Vec<Value>> {
+        let deadline = tokio::time::Instant::now() + wait;
+        loop {
+            let notified = self.diagnostics_changed.notified();
+            let found = self
+                .diagnostics
+                .lock()
+                .unwrap_or_else(|p| p.into_inner())
+                .get(uri)
+                .cloned();
+            if found.is_some() {
+                return found;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return None;
+            }
+        }
+    }
+}
+
+async fn read_loop<R: AsyncBufRead + Unpin>(
+    mut reader: R,
+    stdin: Arc<Mutex<ChildStdin>>,
+    pending: PendingRequests,
+    diagnostics: Arc<std::sync::Mutex<HashMap<String, Vec<Value>>>>,
+    diagnostics_changed: Arc<Notify>,
+    exited: Arc<AtomicBool>,
+) {
+    while let Ok(Some(message)) = read_message(&mut reader).await {
+        let method = message.get("method").and_then(Value::as_str);
+        match (message.get("id"), method) {
+            // Response to one of our requests
+            (Some(id), None) => {
+                let Some(id) = id.as_i64() else { continue };
+                let sender = pending
+                    .lock()
+                    .unwrap_or_else(|p| p.into_inner())
+                    .remove(&id);
+                if let Some(sender) = sender {
+                    let result = match message.get("error") {
+                        Some(error) => Err(error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("Unknown language server error")
+                            .to_string()),
+                        None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                    };
+                    let _ = sender.send(result);
+                }
+            }
+            // Request from the server; answer with defaults so it does not stall
+            (Some(id), Some(method)) => {
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message
+                            .pointer("/params/items")
+                            .and_then(Value::as_array)
+                            .map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                let _ = write_message(&mut *stdin.lock().await, &response).await;
+            }
+            (None, Some("textDocument/publishDiagnostics")) => {
+                if let Some(uri) = message.pointer("/params/uri").and_then(Value::as_str) {
+                    let items = message
+                        .pointer("/params/diagnostics")
+                        .and_then(Value::as_array)
+                        .cloned()
+                        .unwrap_or_default();
+                    diagnostics
+                        .lock()
+                        .unwrap_or_else(|p| p.into_inner())
+                        .insert(uri.to_string(), items);
+                    diagnostics_changed.notify_waiters();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The server is gone; fail everything still waiting on it
+    exited.store(true, Ordering::SeqCst);
+    pending.lock().unwrap_or_else(|p| p.into_inner()).clear();
+}
+
+pub(crate) async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Value,
+) -> std::io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    writer
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .await?;
+    writer.write_all(&body).await?;
+    writer.flush().await
+}
+
+/// Read one `Content-Length` framed message. Returns `None` at end of stream.
+pub(crate) async fn read_message<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let length = content_length.ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "Missing Content-Length")
+    })?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_message_framing_round_trip() {
+        let mut buffer = Vec::new();
+        let first = json!({ "jsonrpc": "2.0", "id": 1, "result": { "ok": "ünïcode" } });
+        let second = json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} });
+        write_message(&mut buffer, &first).await.unwrap();
+        write_message(&mut buffer, &second).await.unwrap();
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        assert_eq!(read_message(&mut reader).await.unwrap(), Some(first));
+        assert_eq!(read_message(&mut reader).await.unwrap(), Some(second));
+        assert_eq!(read_message(&mut reader).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,188 @@
+use serde_json::Value;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use super::uri_to_path;
+
+/// A single `TextEdit`: replace the text between two 0-indexed positions, with
+/// characters counted in UTF-16 code units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+    pub new_text: String,
+}
+
+/// Collect the text edits of a `WorkspaceEdit` per file, from either `changes` or
+/// `documentChanges`. Resource operations (create/rename/delete) are not supported.
+pub fn collect_workspace_edits(edit: &Value) -> Result<BTreeMap<PathBuf, Vec<TextEdit>>, String> {
+    let mut files: BTreeMap<PathBuf, Vec<TextEdit>> = BTreeMap::new();
+
+    if let Some(document_changes) = edit.get("documentChanges").and_then(Value::as_array) {
+        for change in document_changes {
+            if change.get("kind").is_some() {
+                return Err(
+                    "The language server requested file operations, which are not supported"
+                        .to_string(),
+                );
+            }
+            let uri = change
+                .pointer("/textDocument/uri")
+                .and_then(Value::as_str)
+                .ok_or("Malformed documentChanges entry")?;
+            add_edits(&mut files, uri, change.get("edits"))?;
+        }
+    } else if let Some(changes) = edit.get("changes").and_then(Value::as_object) {
+        for (uri, edits) in changes {
+            add_edits(&mut files, uri, Some(edits))?;
+        }
+    }
+
+    Ok(files)
+}
+
+fn add_edits(
+    files: &mut BTreeMap<PathBuf, Vec<TextEdit>>,
+    uri: &str,
+    edits: Option<&Value>,
+) -> Result<(), String> {
+    let path = uri_to_path(uri).ok_or_else(|| format!("Unsupported document URI '{}'", uri))?;
+    let entry = files.entry(path).or_default();
+    for edit in edits.and_then(Value::as_array).into_iter().flatten() {
+        let position = |pointer: &str| -> Result<(u32, u32), String> {
+            let line = edit.pointer(&format!("/range/{}/line", pointer));
+            let character = edit.pointer(&format!("/range/{}/character", pointer));
+            match (
+                line.and_then(Value::as_u64),
+                character.and_then(Value::as_u64),
+            ) {
+                (Some(line), Some(character)) => Ok((line as u32, character as u32)),
+                _ => Err("Malformed text edit range".to_string()),
+            }
+        };
+        entry.push(TextEdit {
+            start: position("start")?,
+            end: position("end")?,
+            new_text: edit
+                .get("newText")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Byte offset of an LSP position in `text`, clamped to the end of its line.
+fn byte_offset(text: &str, (line, character): (u32, u32)) -> Result<usize, String> {
+    let mut offset = 0;
+    for _ in 0..line {
+        let rest = &text[offset..];
+        let newline = rest.find('\n').ok_or_else(|| {
+            format!(
+                "Edit position line {} is past the end of the file",
+                line + 1
+            )
+        })?;
+        offset += newline + 1;
+    }
+
+    let mut units = 0;
+    for (i, c) in text[offset..].char_indices() {
+        if units >= character as usize || c == '\n' {
+            return Ok(offset + i);
+        }
+        units += c.len_utf16();
+    }
+    Ok(text.len())
+}
+
+/// Apply `edits` to `text`. Edits must not overlap; they are applied back to front so
+/// earlier positions stay valid.
+pub fn apply_text_edits(text: &str, edits: &[TextEdit]) -> Result<String, String> {
+    let mut ranges = edits
+        .iter()
+        .map(|edit| {
+            Ok((
+                byte_offset(text, edit.start)?,
+                byte_offset(text, edit.end)?,
+                edit.new_text.as_str(),
+            ))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    ranges.sort_by_key(|(start, end, _)| (*start, *end));
+
+    if ranges.windows(2).any(|pair| pair[0].1 > pair[1].0) {
+        return Err("The language server returned overlapping edits".to_string());
+    }
+
+    let mut result = text.to_string();
+    for (start, end, new_text) in ranges.into_iter().rev() {
+        result.replace_range(start..end, new_text);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+        TextEdit {
+            start,
+            end,
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_text_edits_back_to_front() {
+        let text = "fn old() {}\nfn main() { old(); let é = \"😀\"; old(); }\n";
+        let edits = vec![
+            edit((0, 3), (0, 6), "new"),
+            edit((1, 12), (1, 15), "new"),
+            // after a two-unit emoji
+            edit((1, 33), (1, 36), "new"),
+        ];
+        assert_eq!(
+            apply_text_edits(text, &edits).unwrap(),
+            "fn new() {}\nfn main() { new(); let é = \"😀\"; new(); }\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_text_edits_rejects_overlap() {
+        let edits = vec![edit((0, 0), (0, 5), "a"), edit((0, 3), (0, 6), "b")];
+        assert!(apply_text_edits("abcdefgh", &edits).is_err());
+    }
+
+    #[test]
+    fn test_collect_workspace_edits_formats() {
+        let range =
+            json!({ "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 3 } });
+        let changes = json!({
+            "changes": { "file:///tmp/a.rs": [{ "range": range, "newText": "new" }] }
+        });
+        let document_changes = json!({
+            "documentChanges": [{
+                "textDocument": { "uri": "file:///tmp/a.rs", "version": 1 },
+                "edits": [{ "range": range, "newText": "new" }]
+            }]
+        });
+
+        for edit in [changes, document_changes] {
+            let files = collect_workspace_edits(&edit).unwrap();
+            assert_eq!(
+                files.get(&PathBuf::from("/tmp/a.rs")).unwrap(),
+                &vec![TextEdit {
+                    start: (0, 0),
+                    end: (0, 3),
+                    new_text: "new".to_string()
+                }]
+            );
+        }
+
+        let rename_file = json!({ "documentChanges": [{ "kind": "rename", "oldUri": "file:///a", "newUri": "file:///b" }] });
+        assert!(collect_workspace_edits(&rename_file).is_err());
+    }
+}
@@ -0,0 +1,505 @@
+mod client;
+pub mod edits;
+
+use rmcp::schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::Mutex;
+
+use crate::developer::lang;
+
+pub use client::LspClient;
+
+/// Config key holding the language servers to launch, keyed by language
+/// (`rust`, `python`, `go`, ...), e.g.
+///
+/// ```yaml
+/// GOOSE_LSP_SERVERS:
+///   rust:
+///     command: rust-analyzer
+///   python:
+///     command: pyright-langserver
+///     args: ["--stdio"]
+/// ```
+pub const LSP_SERVERS_CONFIG_KEY: &str = "GOOSE_LSP_SERVERS";
+
+/// How long to wait for a server to publish diagnostics for a freshly opened file
+const DIAGNOSTICS_WAIT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Files or directories marking the workspace root; the nearest ancestor of the file
+    /// containing one is used, or the current directory if none does
+    #[serde(default = "default_root_markers")]
+    pub root_markers: Vec<String>,
+    #[serde(default)]
+    pub initialization_options: Option<Value>,
+}
+
+fn default_root_markers() -> Vec<String> {
+    vec![".git".to_string()]
+}
+
+/// Parameters shared by the position-based code intelligence tools
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SymbolPositionParams {
+    /// Path of the file containing the symbol
+    pub path: String,
+    /// 1-indexed line of the symbol
+    pub line: u32,
+    /// 1-indexed column of the symbol; if omitted, `symbol` is looked up on the line
+    pub column: Option<u32>,
+    /// Name of the symbol on that line. Also used to search the call graph when no
+    /// language server is configured.
+    pub symbol: Option<String>,
+}
+
+/// Parameters for the diagnostics tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiagnosticsParams {
+    /// Path of the file to check
+    pub path: String,
+}
+
+/// Parameters for the rename_symbol tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RenameSymbolParams {
+    #[serde(flatten)]
+    pub position: SymbolPositionParams,
+    /// The new name for the symbol
+    pub new_name: String,
+}
+
+type LspClients = HashMap<(String, Vec<String>, PathBuf), Arc<LspClient>>;
+
+/// Launches and caches one language server per (server command line, workspace root), so
+/// languages configured with the same server share it.
+#[derive(Clone, Default)]
+pub struct LspManager {
+    servers: HashMap<String, LspServerConfig>,
+    clients: Arc<Mutex<LspClients>>,
+}
+
+impl LspManager {
+    pub fn new(servers: HashMap<String, LspServerConfig>) -> Self {
+        Self {
+            servers,
+            clients: Arc::default(),
+        }
+    }
+
+    /// Read the server table from goose's config. Missing or invalid config means no servers.
+    pub fn from_config() -> Self {
+        let servers = goose::config::Config::global()
+            .get_param::<HashMap<String, LspServerConfig>>(LSP_SERVERS_CONFIG_KEY)
+            .unwrap_or_default();
+        Self::new(servers)
+    }
+
+    /// The running server for `path`'s language and workspace, started on first use.
+    /// Returns `None` when no server is configured for the language.
+    pub async fn client_for(&self, path: &Path) -> Result<Option<Arc<LspClient>>, String> {
+        let language = lang::get_language_identifier(path);
+        // TSX files are usually served by the TypeScript server
+        let config = match (self.servers.get(language), language) {
+            (None, "tsx") => self.servers.get("typescript"),
+            (config, _) => config,
+        };
+        let Some(config) = config else {
+            return Ok(None);
+        };
+        let root = workspace_root(path, &config.root_markers);
+
+        let mut clients = self.clients.lock().await;
+        let key = (config.command.clone(), config.args.clone(), root.clone());
+        if let Some(client) = clients.get(&key) {
+            if client.is_alive() {
+                return Ok(Some(client.clone()));
+            }
+            tracing::warn!(
+                "Language server '{}' for {} exited, restarting it",
+                config.command,
+                root.display()
+            );
+            clients.remove(&key);
+        }
+
+        tracing::info!(
+            "Starting language server '{}' for {}",
+            config.command,
+            root.display()
+        );
+        let client = Arc::new(LspClient::start(config, &root).await?);
+        clients.insert(key, client.clone());
+        Ok(Some(client))
+    }
+
+    /// Open `path` on its server and build the `textDocument/position` params for the
+    /// symbol described by `params`.
+    pub async fn position_request(
+        &self,
+        client: &LspClient,
+        path: &Path,
+        params: &SymbolPositionParams,
+    ) -> Result<Value, String> {
+        let text = client.sync_document(path, lsp_language_id(path)).await?;
+        let (line, character) = lsp_position(&text, params)?;
+        Ok(json!({
+            "textDocument": { "uri": path_to_uri(path) },
+            "position": { "line": line, "character": character }
+        }))
+    }
+
+    pub async fn diagnostics(&self, client: &LspClient, path: &Path) -> Result<String, String> {
+        client.sync_document(path, lsp_language_id(path)).await?;
+        let Some(diagnostics) = client
+            .diagnostics(&path_to_uri(path), DIAGNOSTICS_WAIT)
+            .await
+        else {
+            return Ok(format!(
+                "The language server published no diagnostics for {} yet",
+                path.display()
+            ));
+        };
+        Ok(format_diagnostics(path, &diagnostics))
+    }
+}
+
+/// The LSP `languageId` for a file, which differs from goose's identifier for TSX.
+fn lsp_language_id(path: &Path) -> &'static str {
+    match lang::get_language_identifier(path) {
+        "tsx" => "typescriptreact",
+        language => language,
+    }
+}
+
+/// The nearest ancestor of `path` containing one of `markers`, or the current directory.
+fn workspace_root(path: &Path, markers: &[String]) -> PathBuf {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| markers.iter().any(|marker| dir.join(marker).exists()))
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+pub fn path_to_uri(path: &Path) -> String {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|_| format!("file://{}", path.display()))
+}
+
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    url::Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// Convert the 1-indexed line/column (or symbol name) in `params` into a 0-indexed LSP
+/// position, whose character offset counts UTF-16 code units.
+pub fn lsp_position(text: &str, params: &SymbolPositionParams) -> Result<(u32, u32), String> {
+    let line_index = params
+        .line
+        .checked_sub(1)
+        .ok_or("Line numbers start at 1")?;
+    let line = text
+        .lines()
+        .nth(line_index as usize)
+        .ok_or_else(|| format!("Line {} is past the end of the file", params.line))?;
+
+    let char_index = match (params.column, &params.symbol) {
+        (Some(column), _) => column.saturating_sub(1) as usize,
+        (None, Some(symbol)) => {
+            let byte_index = find_identifier(line, symbol)
+                .ok_or_else(|| format!("'{}' does not appear on line {}", symbol, params.line))?;
+            line[..byte_index].chars().count()
+        }
+        (None, None) => return Err("Either column or symbol is required".to_string()),
+    };
+
+    let character = line
+        .chars()
+        .take(char_index)
+        .map(char::len_utf16)
+        .sum::<usize>();
+    Ok((line_index, character as u32))
+}
+
+/// Byte offset of the first occurrence of `symbol` on `line` that is not part of a
+/// longer identifier.
+fn find_identifier(line: &str, symbol: &str) -> Option<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(symbol).map(|(i, _)| i).find(|&i| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + symbol.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// The identifier under the given position, for the call graph fallback.
+pub fn identifier_at(text: &str, params: &SymbolPositionParams) -> Option<String> {
+    if let Some(symbol) = &params.symbol {
+        return Some(symbol.clone());
+    }
+    let line = text.lines().nth(params.line.checked_sub(1)? as usize)?;
+    let column = params.column?.checked_sub(1)? as usize;
+    let chars: Vec<char> = line.chars().collect();
+    let is_ident = |c: &char| c.is_alphanumeric() || *c == '_';
+    if !chars.get(column).is_some_and(is_ident) {
+        return None;
+    }
+    let start = chars[..column]
+        .iter()
+        .rposition(|c| !is_ident(c))
+        .map_or(0, |i| i + 1);
+    let end = chars[column..]
+        .iter()
+        .position(|c| !is_ident(c))
+        .map_or(chars.len(), |i| column + i);
+    Some(chars[start..end].iter().collect())
+}
+
+/// Render a definition or references result (`Location`, `Location[]` or
+/// `LocationLink[]`) as `path:line:column` lines with the source line.
+pub fn format_locations(result: &Value) -> String {
+    let locations: Vec<&Value> = match result {
+        Value::Array(items) => items.iter().collect(),
+        Value::Null => Vec::new(),
+        single => vec![single],
+    };
+
+    let mut output = String::new();
+    for location in locations {
+        let uri = location
+            .get("uri")
+            .or_else(|| location.get("targetUri"))
+            .and_then(Value::as_str);
+        let range = location
+            .get("range")
+            .or_else(|| location.get("targetSelectionRange"));
+        let (Some(path), Some(range)) = (uri.and_then(uri_to_path), range) else {
+            continue;
+        };
+        let line = range
+            .pointer("/start/line")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let character = range
+            .pointer("/start/character")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let source = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| {
+                text.lines()
+                    .nth(line as usize)
+                    .map(|l| l.trim().to_string())
+            })
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{}:{}:{}  {}\n",
+            path.display(),
+            line + 1,
+            character + 1,
+            source
+        ));
+    }
+    output
+}
+
+/// Flatten the `contents` of a hover result, which may be a string, `MarkedString`,
+/// `MarkedString[]` or `MarkupContent`.
+pub fn format_hover(result: &Value) -> Option<String> {
+    fn text(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Object(map) => {
+                let value = map.get("value")?.as_str()?;
+                match map.get("language").and_then(Value::as_str) {
+                    Some(language) => Some(format!("```{}\n{}\n```", language, value)),
+                    None => Some(value.to_string()),
+                }
+            }
+            Value::Array(items) => {
+                let parts: Vec<String> = items.iter().filter_map(text).collect();
+                (!parts.is_empty()).then(|| parts.join("\n\n"))
+            }
+            _ => None,
+        }
+    }
+    text(result.get("contents")?).filter(|s| !s.trim().is_empty())
+}
+
+fn format_diagnostics(path: &Path, diagnostics: &[Value]) -> String {
+    if diagnostics.is_empty() {
+        return format!("No diagnostics for {}", path.display());
+    }
+    let mut output = format!(
+        "{} diagnostics for {}:\n",
+        diagnostics.len(),
+        path.display()
+    );
+    for diagnostic in diagnostics {
+        let severity = match diagnostic.get("severity").and_then(Value::as_u64) {
+            Some(1) => "error",
+            Some(2) => "warning",
+            Some(3) => "info",
+            Some(4) => "hint",
+            _ => "diagnostic",
+        };
+        let line = diagnostic
+            .pointer("/range/start/line")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let character = diagnostic
+            .pointer("/range/start/character")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let message = diagnostic
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "{}:{}: {}: {}",
+            line + 1,
+            character + 1,
+            severity,
+            message
+        ));
+        if let Some(source) = diagnostic.get("source").and_then(Value::as_str) {
+            output.push_str(&format!(" ({})", source));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: u32, column: Option<u32>, symbol: Option<&str>) -> SymbolPositionParams {
+        SymbolPositionParams {
+            path: String::new(),
+            line,
+            column,
+            symbol: symbol.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_lsp_position_counts_utf16_units() {
+        let text = "fn main() {\n    let café = \"😀\"; render(café);\n}\n";
+        assert_eq!(lsp_position(text, &at(1, Some(4), None)).unwrap(), (0, 3));
+        // "render" comes after a two-unit emoji
+        assert_eq!(
+            lsp_position(text, &at(2, None, Some("render"))).unwrap(),
+            (1, 21)
+        );
+        assert!(lsp_position(text, &at(2, None, Some("missing"))).is_err());
+        assert!(lsp_position(text, &at(9, Some(1), None)).is_err());
+    }
+
+    #[test]
+    fn test_symbol_lookup_skips_partial_matches() {
+        assert_eq!(find_identifier("let run_all = run();", "run"), Some(14));
+        assert_eq!(
+            identifier_at("    helper_fn(x)", &at(1, Some(8), None)).as_deref(),
+            Some("helper_fn")
+        );
+        assert_eq!(identifier_at("a + b", &at(1, Some(2), None)), None);
+    }
+
+    #[test]
+    fn test_format_hover_variants() {
+        let markup = json!({ "contents": { "kind": "markdown", "value": "fn main()" } });
+        assert_eq!(format_hover(&markup).as_deref(), Some("fn main()"));
+
+        let marked = json!({ "contents": [{ "language": "rust", "value": "i32" }, "docs"] });
+        assert_eq!(
+            format_hover(&marked).as_deref(),
+            Some("```rust\ni32\n```\n\ndocs")
+        );
+        assert_eq!(format_hover(&json!({ "contents": "" })), None);
+    }
+
+    #[test]
+    fn test_workspace_root_uses_markers() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        std::fs::write(project.join("Cargo.toml"), "").unwrap();
+
+        let root = workspace_root(
+            &project.join("src").join("main.rs"),
+            &["Cargo.toml".to_string()],
+        );
+        assert_eq!(root, project);
+    }
+
+    /// `cat` echoes our requests back, which the client answers and then reads as the
+    /// responses, so it stands in for a server that accepts every request.
+    fn echo_server(lifetime: Option<&str>) -> LspServerConfig {
+        let (command, args) = match lifetime {
+            Some(seconds) => ("timeout", vec![seconds.to_string(), "cat".to_string()]),
+            None => ("cat", Vec::new()),
+        };
+        LspServerConfig {
+            command: command.to_string(),
+            args,
+            root_markers: default_root_markers(),
+            initialization_options: None,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_client_for_restarts_exited_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        let manager = LspManager::new(HashMap::from([(
+            "rust".to_string(),
+            echo_server(Some("1")),
+        )]));
+
+        let first = manager.client_for(&path).await.unwrap().unwrap();
+        assert!(Arc::ptr_eq(
+            &first,
+            &manager.client_for(&path).await.unwrap().unwrap()
+        ));
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!first.is_alive());
+        let second = manager.client_for(&path).await.unwrap().unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert!(second.is_alive());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_client_for_shares_server_across_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LspManager::new(HashMap::from([
+            ("typescript".to_string(), echo_server(None)),
+            ("tsx".to_string(), echo_server(None)),
+        ]));
+
+        let ts = manager
+            .client_for(&dir.path().join("app.ts"))
+            .await
+            .unwrap()
+            .unwrap();
+        let tsx = manager
+            .client_for(&dir.path().join("view.tsx"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(Arc::ptr_eq(&ts, &tsx));
+    }
+}
@@ -33,14 +33,34 @@ use tokio_util::sync::CancellationToken;
 
 use super::analyze::{types::AnalyzeParams, CodeAnalyzer};
 use super::editor_models::{create_editor_model, EditorModel};
+use super::goose_hints::directory_hints::{
+    DirectoryHints, DEFAULT_DIRECTORY_HINTS_TOKEN_BUDGET, DIRECTORY_HINTS_TOKEN_BUDGET_KEY,
+};
 use super::goose_hints::load_hints::{load_hint_files, GOOSE_HINTS_FILENAME};
+use super::lsp::{
+    edits::{apply_text_edits, collect_workspace_edits},
+    format_hover, format_locations, identifier_at, DiagnosticsParams, LspManager,
+    RenameSymbolParams, SymbolPositionParams, LSP_SERVERS_CONFIG_KEY,
+};
 use super::shell::{
     configure_shell_command, expand_path, get_shell_config, is_absolute_path, kill_process_group,
 };
 use super::text_editor::{
-    text_editor_insert, text_editor_replace, text_editor_undo, text_editor_view, text_editor_write,
+    save_file_history, text_editor_insert, text_editor_replace, text_editor_undo, text_editor_view,
+    text_editor_write,
 };
 
+/// Directory depth searched by the call graph fallback of the code intelligence tools
+const CALL_GRAPH_FALLBACK_DEPTH: u32 = 5;
+
+/// What the call graph fallback looks up for a symbol
+#[derive(Clone, Copy)]
+enum SymbolLookup {
+    Definition,
+    References,
+    Hover,
+}
+
 /// Parameters for the screen_capture tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ScreenCaptureParams {
@@ -167,6 +187,27 @@ fn load_prompt_files() -> HashMap<String, Prompt> {
     prompts
 }
 
+/// Names of the hints files to load, from `CONTEXT_FILE_NAMES` or the defaults
+fn hints_filenames() -> Vec<String> {
+    std::env::var("CONTEXT_FILE_NAMES")
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_else(|| vec!["AGENTS.md".to_string(), GOOSE_HINTS_FILENAME.to_string()])
+}
+
+fn lsp_error(message: String) -> ErrorData {
+    ErrorData::new(ErrorCode::INTERNAL_ERROR, message, None)
+}
+
+fn text_result(output: String, empty_message: &str) -> CallToolResult {
+    let output = if output.trim().is_empty() {
+        empty_message.to_string()
+    } else {
+        output
+    };
+    CallToolResult::success(vec![Content::text(output)])
+}
+
 /// Developer MCP Server using official RMCP SDK
 #[derive(Clone)]
 pub struct DeveloperServer {
@@ -176,6 +217,8 @@ pub struct DeveloperServer {
     editor_model: Option<EditorModel>,
     prompts: HashMap<String, Prompt>,
     code_analyzer: CodeAnalyzer,
+    lsp: LspManager,
+    directory_hints: Arc<DirectoryHints>,
     #[cfg(test)]
     pub running_processes: Arc<RwLock<HashMap<String, CancellationToken>>>,
     #[cfg(not(test))]
@@ -246,10 +289,7 @@ impl ServerHandler for DeveloperServer {
             }
         };
 
-        let hints_filenames: Vec<String> = std::env::var("CONTEXT_FILE_NAMES")
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or_else(|| vec!["AGENTS.md".to_string(), GOOSE_HINTS_FILENAME.to_string()]);
+        let hints_filenames = hints_filenames();
 
         // Build ignore patterns for file reference processing
         let ignore_patterns = Self::build_ignore_patterns(&cwd);
@@ -558,6 +598,11 @@ impl DeveloperServer {
         // Initialize editor model for AI-powered code editing
         let editor_model = create_editor_model();
 
+        let hints_token_budget = goose::config::Config::global()
+            .get_param::<usize>(DIRECTORY_HINTS_TOKEN_BUDGET_KEY)
+            .unwrap_or(DEFAULT_DIRECTORY_HINTS_TOKEN_BUDGET);
+        let directory_hints = DirectoryHints::new(&cwd, hints_filenames(), hints_token_budget);
+
         Self {
             tool_router: Self::tool_router(),
             file_history: Arc::new(Mutex::new(HashMap::new())),
@@ -565,6 +610,8 @@ impl DeveloperServer {
             editor_model,
             prompts: load_prompt_files(),
             code_analyzer: CodeAnalyzer::new(),
+            lsp: LspManager::from_config(),
+            directory_hints: Arc::new(directory_hints),
             running_processes: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -741,7 +788,7 @@ impl DeveloperServer {
             ));
         }
 
-        match params.command.as_str() {
+        let mut content = match params.command.as_str() {
             "view" => {
                 let view_range = params.view_range.as_ref().and_then(|vr| {
                     if vr.len() == 2 {
@@ -750,8 +797,7 @@ impl DeveloperServer {
                         None
                     }
                 });
-                let content = text_editor_view(&path, view_range).await?;
-                Ok(CallToolResult::success(content))
+                text_editor_view(&path, view_range).await?
             }
             "write" => {
                 let file_text = params.file_text.ok_or_else(|| {
@@ -761,14 +807,13 @@ impl DeveloperServer {
                         None,
                     )
                 })?;
-                let content = text_editor_write(&path, &file_text).await?;
-                Ok(CallToolResult::success(content))
+                text_editor_write(&path, &file_text).await?
             }
             "str_replace" => {
                 // Check if diff parameter is provided
                 if let Some(ref diff) = params.diff {
                     // When diff is provided, old_str and new_str are not required
-                    let content = text_editor_replace(
+                    text_editor_replace(
                         &path,
                         "", // old_str not used with diff
                         "", // new_str not used with diff
@@ -776,8 +821,7 @@ impl DeveloperServer {
                         &self.editor_model,
                         &self.file_history,
                     )
-                    .await?;
-                    Ok(CallToolResult::success(content))
+                    .await?
                 } else {
                     // Traditional str_replace with old_str and new_str
                     let old_str = params.old_str.ok_or_else(|| {
@@ -794,7 +838,7 @@ impl DeveloperServer {
                             None,
                         )
                     })?;
-                    let content = text_editor_replace(
+                    text_editor_replace(
                         &path,
                         &old_str,
                         &new_str,
@@ -802,8 +846,7 @@ impl DeveloperServer {
                         &self.editor_model,
                         &self.file_history,
                     )
-                    .await?;
-                    Ok(CallToolResult::success(content))
+                    .await?
                 }
             }
             "insert" => {
@@ -821,21 +864,24 @@ impl DeveloperServer {
                         None,
                     )
                 })?;
-                let content =
-                    text_editor_insert(&path, insert_line as i64, &new_str, &self.file_history)
-                        .await?;
-                Ok(CallToolResult::success(content))
+                text_editor_insert(&path, insert_line as i64, &new_str, &self.file_history).await?
             }
-            "undo_edit" => {
-                let content = text_editor_undo(&path, &self.file_history).await?;
-                Ok(CallToolResult::success(content))
+            "undo_edit" => text_editor_undo(&path, &self.file_history).await?,
+            _ => {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("Unknown command '{}'", params.command),
+                    None,
+                ))
             }
-            _ => Err(ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Unknown command '{}'", params.command),
-                None,
-            )),
+        };
+
+        // Hints from subdirectories are injected the first time a file under them is touched
+        if let Some(hints) = self.directory_hints.hints_for(&path, &self.ignore_patterns) {
+            content.push(Content::text(hints).with_audience(vec![Role::Assistant]));
         }
+
+        Ok(CallToolResult::success(content))
     }
 
     /// Execute a command in the shell.
@@ -1120,6 +1166,197 @@ impl DeveloperServer {
             .analyze(params, path, &self.ignore_patterns)
     }
 
+    /// Jump to where the symbol at a position is defined.
+    ///
+    /// Uses the language server configured for the file's language, or the tree-sitter
+    /// call graph (matching by name) when there is none.
+    #[tool(
+        name = "goto_definition",
+        description = "Find where the symbol at a position is defined. Give the file path, the 1-indexed line and either the column or the symbol name on that line. Uses a language server when one is configured, otherwise a name-based call graph."
+    )]
+    pub async fn goto_definition(
+        &self,
+        params: Parameters<SymbolPositionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let path = self.resolve_path(&params.path)?;
+        self.check_not_ignored(&path)?;
+        let Some(client) = self.lsp.client_for(&path).await.map_err(lsp_error)? else {
+            return self.call_graph_lookup(&path, &params, SymbolLookup::Definition);
+        };
+
+        let request = self
+            .lsp
+            .position_request(&client, &path, &params)
+            .await
+            .map_err(lsp_error)?;
+        let result = client
+            .request("textDocument/definition", request)
+            .await
+            .map_err(lsp_error)?;
+        Ok(text_result(
+            format_locations(&result),
+            "No definition found",
+        ))
+    }
+
+    /// List every reference to the symbol at a position, including its declaration.
+    #[tool(
+        name = "find_references",
+        description = "Find all references to the symbol at a position, including its declaration. Give the file path, the 1-indexed line and either the column or the symbol name on that line. Uses a language server when one is configured, otherwise a name-based call graph."
+    )]
+    pub async fn find_references(
+        &self,
+        params: Parameters<SymbolPositionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let path = self.resolve_path(&params.path)?;
+        self.check_not_ignored(&path)?;
+        let Some(client) = self.lsp.client_for(&path).await.map_err(lsp_error)? else {
+            return self.call_graph_lookup(&path, &params, SymbolLookup::References);
+        };
+
+        let mut request = self
+            .lsp
+            .position_request(&client, &path, &params)
+            .await
+            .map_err(lsp_error)?;
+        request["context"] = serde_json::json!({ "includeDeclaration": true });
+        let result = client
+            .request("textDocument/references", request)
+            .await
+            .map_err(lsp_error)?;
+        Ok(text_result(
+            format_locations(&result),
+            "No references found",
+        ))
+    }
+
+    /// Show the type signature and documentation of the symbol at a position.
+    #[tool(
+        name = "hover",
+        description = "Show the type, signature and documentation of the symbol at a position. Give the file path, the 1-indexed line and either the column or the symbol name on that line. Without a language server only the definition lines are shown."
+    )]
+    pub async fn hover(
+        &self,
+        params: Parameters<SymbolPositionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let path = self.resolve_path(&params.path)?;
+        self.check_not_ignored(&path)?;
+        let Some(client) = self.lsp.client_for(&path).await.map_err(lsp_error)? else {
+            return self.call_graph_lookup(&path, &params, SymbolLookup::Hover);
+        };
+
+        let request = self
+            .lsp
+            .position_request(&client, &path, &params)
+            .await
+            .map_err(lsp_error)?;
+        let result = client
+            .request("textDocument/hover", request)
+            .await
+            .map_err(lsp_error)?;
+        Ok(text_result(
+            format_hover(&result).unwrap_or_default(),
+            "No hover information available",
+        ))
+    }
+
+    /// Report the language server's errors and warnings for a file.
+    #[tool(
+        name = "diagnostics",
+        description = "Report compiler and linter errors and warnings for a file from its language server. Requires a language server to be configured for the file's language."
+    )]
+    pub async fn diagnostics(
+        &self,
+        params: Parameters<DiagnosticsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let path = self.resolve_path(&params.0.path)?;
+        self.check_not_ignored(&path)?;
+        let client = self.require_lsp_client(&path).await?;
+        let output = self
+            .lsp
+            .diagnostics(&client, &path)
+            .await
+            .map_err(lsp_error)?;
+        Ok(CallToolResult::success(vec![
+            Content::text(output.clone()).with_audience(vec![Role::Assistant]),
+            Content::text(output)
+                .with_audience(vec![Role::User])
+                .with_priority(0.0),
+        ]))
+    }
+
+    /// Rename a symbol across the workspace using the language server's rename edits.
+    /// Every changed file is saved to the edit history, so `undo_edit` reverts it.
+    #[tool(
+        name = "rename_symbol",
+        description = "Rename the symbol at a position everywhere it is used, via the language server. Give the file path, the 1-indexed line, either the column or the symbol name, and new_name. Changed files can be reverted with text_editor undo_edit. Requires a language server to be configured for the file's language."
+    )]
+    pub async fn rename_symbol(
+        &self,
+        params: Parameters<RenameSymbolParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let path = self.resolve_path(&params.position.path)?;
+        self.check_not_ignored(&path)?;
+        let client = self.require_lsp_client(&path).await?;
+
+        let mut request = self
+            .lsp
+            .position_request(&client, &path, &params.position)
+            .await
+            .map_err(lsp_error)?;
+        request["newName"] = serde_json::Value::String(params.new_name.clone());
+        let result = client
+            .request("textDocument/rename", request)
+            .await
+            .map_err(lsp_error)?;
+        let files = collect_workspace_edits(&result).map_err(lsp_error)?;
+        if files.is_empty() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "The language server found nothing to rename at that position".to_string(),
+                None,
+            ));
+        }
+
+        // The server decides which files change, so check each one before touching any
+        for file in files.keys() {
+            self.check_lsp_edit(file)?;
+        }
+
+        // Compute every new file before writing any, so a bad edit leaves the tree untouched
+        let mut updated = Vec::with_capacity(files.len());
+        for (file, edits) in &files {
+            let text = std::fs::read_to_string(file)
+                .map_err(|e| lsp_error(format!("Failed to read '{}': {}", file.display(), e)))?;
+            let new_text = apply_text_edits(&text, edits)
+                .map_err(|e| lsp_error(format!("{}: {}", file.display(), e)))?;
+            updated.push((file, edits.len(), new_text));
+        }
+
+        let mut summary = format!(
+            "Renamed to '{}' in {} file(s):\n",
+            params.new_name,
+            updated.len()
+        );
+        for (file, count, new_text) in updated {
+            save_file_history(file, &self.file_history)?;
+            std::fs::write(file, new_text)
+                .map_err(|e| lsp_error(format!("Failed to write '{}': {}", file.display(), e)))?;
+            summary.push_str(&format!("{} ({} edits)\n", file.display(), count));
+        }
+
+        Ok(CallToolResult::success(vec![
+            Content::text(summary.clone()).with_audience(vec![Role::Assistant]),
+            Content::text(summary)
+                .with_audience(vec![Role::User])
+                .with_priority(0.0),
+        ]))
+    }
+
     /// Process an image file from disk.
     ///
     /// The image will be:
@@ -1241,6 +1478,126 @@ impl DeveloperServer {
     }
 
     // Helper method to resolve and validate file paths
+    async fn require_lsp_client(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<super::lsp::LspClient>, ErrorData> {
+        self.lsp
+            .client_for(path)
+            .await
+            .map_err(lsp_error)?
+            .ok_or_else(|| {
+                ErrorData::new(
+                    ErrorCode::INVALID_REQUEST,
+                    format!(
+                        "No language server is configured for '{}'. Add one to {} in the goose config.",
+                        path.display(),
+                        LSP_SERVERS_CONFIG_KEY
+                    ),
+                    None,
+                )
+            })
+    }
+
+    /// Answer a symbol query from the tree-sitter call graph. Symbols are matched by
+    /// name only, so overloads and same-named symbols in other modules are merged.
+    fn call_graph_lookup(
+        &self,
+        path: &Path,
+        params: &SymbolPositionParams,
+        lookup: SymbolLookup,
+    ) -> Result<CallToolResult, ErrorData> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("Failed to read '{}': {}", path.display(), e),
+                None,
+            )
+        })?;
+        let symbol = identifier_at(&text, params).ok_or_else(|| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "No identifier at line {} of '{}'; pass the symbol name",
+                    params.line,
+                    path.display()
+                ),
+                None,
+            )
+        })?;
+
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let root = if path.starts_with(&cwd) {
+            cwd
+        } else {
+            path.parent().unwrap_or(path).to_path_buf()
+        };
+        let graph = self.code_analyzer.call_graph(
+            &root,
+            CALL_GRAPH_FALLBACK_DEPTH,
+            &self.ignore_patterns,
+        )?;
+
+        let source_line = |file: &Path, line: usize| {
+            std::fs::read_to_string(file)
+                .ok()
+                .and_then(|text| {
+                    text.lines()
+                        .nth(line.saturating_sub(1))
+                        .map(|l| l.trim().to_string())
+                })
+                .unwrap_or_default()
+        };
+        let definitions = graph.definitions.get(&symbol).cloned().unwrap_or_default();
+
+        let mut output = String::new();
+        match lookup {
+            SymbolLookup::Definition => {
+                for (file, line) in &definitions {
+                    output.push_str(&format!(
+                        "{}:{}  {}\n",
+                        file.display(),
+                        line,
+                        source_line(file, *line)
+                    ));
+                }
+            }
+            SymbolLookup::References => {
+                for (file, line) in &definitions {
+                    output.push_str(&format!("{}:{}  (definition)\n", file.display(), line));
+                }
+                for (file, line, caller) in graph.references(&symbol) {
+                    let context = match caller.as_str() {
+                        "<module>" => "module level".to_string(),
+                        "<reference>" => "type reference".to_string(),
+                        caller => format!("in {}", caller),
+                    };
+                    output.push_str(&format!("{}:{}  ({})\n", file.display(), line, context));
+                }
+            }
+            SymbolLookup::Hover => {
+                for (file, line) in &definitions {
+                    output.push_str(&format!(
+                        "{} ({}:{})\n```\n{}\n```\n",
+                        symbol,
+                        file.display(),
+                        line,
+                        source_line(file, *line)
+                    ));
+                }
+            }
+        }
+
+        if output.is_empty() {
+            output = format!("No matches for '{}' in the call graph", symbol);
+        }
+        let output = format!(
+            "No language server is configured for this file type ({}); results come from the call graph and match '{}' by name.\n\n{}",
+            LSP_SERVERS_CONFIG_KEY, symbol, output
+        );
+        Ok(CallToolResult::success(vec![Content::text(output)]))
+    }
+
     fn resolve_path(&self, path_str: &str) -> Result<PathBuf, ErrorData> {
         let cwd = std::env::current_dir().expect("should have a current working dir");
         let expanded = expand_path(path_str);
@@ -1290,6 +1647,45 @@ impl DeveloperServer {
         self.ignore_patterns.matched(path, false).is_ignore()
     }
 
+    // Helper method to refuse paths restricted by .gooseignore
+    fn check_not_ignored(&self, path: &Path) -> Result<(), ErrorData> {
+        if self.is_ignored(path) {
+            return Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!(
+                    "Access to '{}' is restricted by .gooseignore",
+                    path.display()
+                ),
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    // Helper method to check a file a language server wants to edit: it must be inside
+    // the working directory and not restricted by .gooseignore
+    fn check_lsp_edit(&self, file: &Path) -> Result<(), ErrorData> {
+        let cwd = std::env::current_dir().expect("should have a current working dir");
+        let relative = cwd
+            .canonicalize()
+            .ok()
+            .zip(file.canonicalize().ok())
+            .and_then(|(root, file)| file.strip_prefix(root).ok().map(Path::to_path_buf))
+            .ok_or_else(|| {
+                ErrorData::new(
+                    ErrorCode::INVALID_REQUEST,
+                    format!(
+                        "The language server tried to edit '{}', which is outside the working directory {}",
+                        file.display(),
+                        cwd.display()
+                    ),
+                    None,
+                )
+            })?;
+        self.check_not_ignored(file)?;
+        self.check_not_ignored(&cwd.join(relative))
+    }
+
     // Only returns true when 100% certain (checks /proc/1/cgroup for container markers)
     fn is_definitely_container() -> bool {
         let Ok(content) = std::fs::read_to_string("/proc/1/cgroup") else {
@@ -3863,4 +4259,127 @@ Additional instructions here.
             cleanup_test_service(running_service, peer);
         });
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_code_intelligence_falls_back_to_call_graph() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::env::set_current_dir(&temp_dir).unwrap();
+        let mut server = create_test_server();
+        server.lsp = LspManager::default();
+
+        let file = temp_dir.path().join("main.rs");
+        fs::write(
+            &file,
+            "fn helper() -> u32 {\n    1\n}\n\nfn main() {\n    let x = helper();\n}\n",
+        )
+        .unwrap();
+        let position = SymbolPositionParams {
+            path: file.to_str().unwrap().to_string(),
+            line: 6,
+            column: None,
+            symbol: Some("helper".to_string()),
+        };
+
+        let result = server
+            .goto_definition(Parameters(position.clone()))
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("main.rs:1  fn helper() -> u32 {"), "{}", text);
+
+        let result = server
+            .find_references(Parameters(position.clone()))
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("main.rs:6  (in main)"), "{}", text);
+
+        let err = server
+            .diagnostics(Parameters(DiagnosticsParams {
+                path: position.path.clone(),
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.message.contains(LSP_SERVERS_CONFIG_KEY));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_code_intelligence_respects_ignore_patterns() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::env::set_current_dir(&temp_dir).unwrap();
+        fs::write(".gooseignore", "secret.rs").unwrap();
+        let mut server = create_test_server();
+        server.lsp = LspManager::default();
+
+        let secret = temp_dir.path().join("secret.rs");
+        fs::write(&secret, "fn key() -> u32 {\n    1\n}\n").unwrap();
+        let err = server
+            .goto_definition(Parameters(SymbolPositionParams {
+                path: secret.to_str().unwrap().to_string(),
+                line: 1,
+                column: None,
+                symbol: Some("key".to_string()),
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.message.contains(".gooseignore"), "{}", err.message);
+
+        let outside_dir = tempfile::tempdir().unwrap();
+        let outside = outside_dir.path().join("lib.rs");
+        fs::write(&outside, "fn key() {}\n").unwrap();
+        let err = server.check_lsp_edit(&outside).unwrap_err();
+        assert!(err.message.contains("outside the working directory"));
+
+        assert!(server.check_lsp_edit(&secret).is_err());
+        let main = temp_dir.path().join("main.rs");
+        fs::write(&main, "fn main() {}\n").unwrap();
+        assert!(server.check_lsp_edit(&main).is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_text_editor_injects_directory_hints_once() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::env::set_current_dir(&temp_dir).unwrap();
+        let package_dir = temp_dir.path().join("packages").join("api");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(package_dir.join(".goosehints"), "API package uses axum").unwrap();
+        fs::write(package_dir.join("main.rs"), "fn main() {}").unwrap();
+        let server = create_test_server();
+
+        let view = |path: &Path| {
+            Parameters(TextEditorParams {
+                path: path.to_str().unwrap().to_string(),
+                command: "view".to_string(),
+                view_range: None,
+                file_text: None,
+                old_str: None,
+                new_str: None,
+                insert_line: None,
+                diff: None,
+            })
+        };
+        let texts = |result: CallToolResult| -> String {
+            result
+                .content
+                .iter()
+                .filter_map(|c| c.as_text().map(|t| t.text.clone()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let first = server
+            .text_editor(view(&package_dir.join("main.rs")))
+            .await
+            .unwrap();
+        assert!(texts(first).contains("API package uses axum"));
+
+        let second = server
+            .text_editor(view(&package_dir.join("main.rs")))
+            .await
+            .unwrap();
+        assert!(!texts(second).contains("API package uses axum"));
+    }
 }
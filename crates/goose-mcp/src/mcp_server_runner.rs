@@ -1,5 +1,6 @@
 use crate::{
-    AutoVisualiserRouter, ComputerControllerServer, DeveloperServer, MemoryServer, TutorialServer,
+    AutoVisualiserRouter, ComputerControllerServer, DataServer, DeveloperServer, MemoryServer,
+    TutorialServer,
 };
 use anyhow::{anyhow, Result};
 use rmcp::{transport::stdio, ServiceExt};
@@ -20,6 +21,7 @@ pub async fn run_mcp_server(name: &str) -> Result<()> {
     match name {
         "autovisualiser" => serve_and_wait(AutoVisualiserRouter::new()).await,
         "computercontroller" => serve_and_wait(ComputerControllerServer::new()).await,
+        "data" => serve_and_wait(DataServer::new()).await,
         "developer" => serve_and_wait(DeveloperServer::new()).await,
         "memory" => serve_and_wait(MemoryServer::new()).await,
         "tutorial" => serve_and_wait(TutorialServer::new()).await,
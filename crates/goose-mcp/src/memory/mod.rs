@@ -1,3 +1,5 @@
+pub mod store;
+
 use etcetera::{choose_app_strategy, AppStrategy};
 use indoc::formatdoc;
 use rmcp::{
@@ -11,11 +13,16 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
-    fs,
-    io::{self, Read, Write},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
 };
+use store::{Memory, MemoryQuery, MemoryStore, MemoryUpdate};
+
+/// How many memories are written into the instructions at startup unless
+/// `GOOSE_MEMORY_STARTUP_LIMIT` says otherwise. The rest stay reachable through search.
+const DEFAULT_STARTUP_MEMORY_LIMIT: usize = 20;
+const DEFAULT_SEARCH_LIMIT: usize = 10;
 
 /// Parameters for the remember_memory tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -55,11 +62,45 @@ pub struct RemoveSpecificMemoryParams {
     /// The category containing the memory
     pub category: String,
     /// The content of the memory to remove
+    #[serde(default)]
     pub memory_content: String,
+    /// The id of the memory to remove; takes precedence over memory_content
+    pub id: Option<String>,
     /// Whether to remove from global or local storage
     pub is_global: bool,
 }
 
+/// Parameters for the search_memories tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SearchMemoriesParams {
+    /// Keywords to look for in memory content, categories and tags
+    pub query: String,
+    /// Only return memories carrying all of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only return memories from this category
+    pub category: Option<String>,
+    /// Search only global (true) or only local (false) memories; both when omitted
+    pub is_global: Option<bool>,
+    /// Maximum number of results (default 10)
+    pub limit: Option<usize>,
+}
+
+/// Parameters for the update_memory tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateMemoryParams {
+    /// The id of the memory to update
+    pub id: String,
+    /// New content for the memory
+    pub data: Option<String>,
+    /// Move the memory to this category
+    pub category: Option<String>,
+    /// Replace the memory's tags
+    pub tags: Option<Vec<String>>,
+    /// Whether the memory is stored globally or locally
+    pub is_global: bool,
+}
+
 /// Memory MCP Server using official RMCP SDK
 #[derive(Clone)]
 pub struct MemoryServer {
@@ -85,7 +126,8 @@ impl MemoryServer {
              1. Store information in categories with optional tags for context-based retrieval.
              2. Search memories by content or specific tags to find relevant information.
              3. List all available memory categories for easy navigation.
-             4. Remove entire categories of memories when they are no longer needed.
+             4. Update individual memories by id when information changes.
+             5. Remove entire categories of memories when they are no longer needed.
              When to call memory tools:
              - These are examples where the assistant should proactively call the memory tool because the user is providing recurring preferences, project details, or workflow habits that they may expect to be remembered.
              - Preferred Development Tools & Conventions
@@ -146,6 +188,10 @@ impl MemoryServer {
              Assistant: "I'll store this in the 'github' category. Any specific tags to add? Suggestions: #comments #gh"
              Retrieving Memories:
              To access stored information, utilize the memory retrieval protocols:
+             - **Search by Relevance** (preferred):
+               - Ranks memories by keyword matches, tags and how recently they were updated.
+               - Use: `search_memories(query="code formatting", tags=["formatting"])`
+               - Omit `is_global` to search both local and global memories.
              - **Search by Category**:
                - Provides all memories within the specified context.
                - Use: `retrieve_memories(category="development", is_global=False)`
@@ -160,10 +206,14 @@ impl MemoryServer {
               - Use: `remove_memory_category(category="development", is_global=False)`
               - Note: If you want to remove all local memories, use `remove_memory_category(category="*", is_global=False)`
               - Note: If you want to remove all global memories, use `remove_memory_category(category="*", is_global=True)`
+            - **Remove or Update by Id**:
+              - Every memory has an id, shown next to it in search and retrieval results.
+              - Use: `remove_specific_memory(category="development", id="1a2b3c4d", is_global=False)`
+              - Use: `update_memory(id="1a2b3c4d", data="We use ruff for formatting", is_global=False)`
             The Protocol is:
              1. Confirm what kind of information the user seeks by category or keyword.
              2. Suggest categories or relevant tags based on the user's request.
-             3. Use the retrieve function to access relevant memory entries.
+             3. Use search_memories, or the retrieve function for a whole category, to access relevant memory entries.
              4. Present a summary of findings, offering detailed exploration upon request.
              Example Interaction for Retrieving Information:
              User: "What configuration do we use for code formatting?"
@@ -182,19 +232,11 @@ impl MemoryServer {
             "#};
 
         // Check for .goose/memory in current directory
-        let local_memory_dir = std::env::var("GOOSE_WORKING_DIR")
+        let working_dir = std::env::var("GOOSE_WORKING_DIR")
             .map(PathBuf::from)
-            .unwrap_or_else(|_| std::env::current_dir().unwrap())
-            .join(".goose")
-            .join("memory");
-
-        // choose_app_strategy().config_dir()
-        // - macOS/Linux: ~/.config/goose/memory/
-        // - Windows:     ~\AppData\Roaming\Block\goose\config\memory
-        // if it fails, fall back to `.config/goose/memory` (relative to the current dir)
-        let global_memory_dir = choose_app_strategy(crate::APP_STRATEGY.clone())
-            .map(|strategy| strategy.in_config_dir("memory"))
-            .unwrap_or_else(|_| PathBuf::from(".config/goose/memory"));
+            .unwrap_or_else(|_| std::env::current_dir().unwrap());
+        let local_memory_dir = local_memory_dir(&working_dir);
+        let global_memory_dir = global_memory_dir();
 
         let mut memory_router = Self {
             tool_router: Self::tool_router(),
@@ -203,45 +245,20 @@ impl MemoryServer {
             local_memory_dir,
         };
 
-        let retrieved_global_memories = memory_router.retrieve_all(true);
-        let retrieved_local_memories = memory_router.retrieve_all(false);
-
         let mut updated_instructions = instructions;
 
         let memories_follow_up_instructions = formatdoc! {r#"
-            **Here are the user's currently saved memories:**
+            **Here are the user's most recently updated memories:**
             Please keep this information in mind when answering future questions.
             Do not bring up memories unless relevant.
             Note: if the user has not saved any memories, this section will be empty.
             Note: if the user removes a memory that was previously loaded into the system, please remove it from the system instructions.
+            Note: older memories are not listed here; use search_memories when a question may depend on them.
             "#};
 
         updated_instructions.push_str("\n\n");
         updated_instructions.push_str(&memories_follow_up_instructions);
-
-        if let Ok(global_memories) = retrieved_global_memories {
-            if !global_memories.is_empty() {
-                updated_instructions.push_str("\n\nGlobal Memories:\n");
-                for (category, memories) in global_memories {
-                    updated_instructions.push_str(&format!("\nCategory: {}\n", category));
-                    for memory in memories {
-                        updated_instructions.push_str(&format!("- {}\n", memory));
-                    }
-                }
-            }
-        }
-
-        if let Ok(local_memories) = retrieved_local_memories {
-            if !local_memories.is_empty() {
-                updated_instructions.push_str("\n\nLocal Memories:\n");
-                for (category, memories) in local_memories {
-                    updated_instructions.push_str(&format!("\nCategory: {}\n", category));
-                    for memory in memories {
-                        updated_instructions.push_str(&format!("- {}\n", memory));
-                    }
-                }
-            }
-        }
+        updated_instructions.push_str(&memory_router.startup_memories(startup_memory_limit()));
 
         memory_router.set_instructions(updated_instructions);
 
@@ -257,36 +274,70 @@ impl MemoryServer {
         &self.instructions
     }
 
-    fn get_memory_file(&self, category: &str, is_global: bool) -> PathBuf {
+    fn memory_dir(&self, is_global: bool) -> &PathBuf {
         // Defaults to local memory if no is_global flag is provided
-        let base_dir = if is_global {
+        if is_global {
             &self.global_memory_dir
         } else {
             &self.local_memory_dir
-        };
-        base_dir.join(format!("{}.txt", category))
+        }
     }
 
-    pub fn retrieve_all(&self, is_global: bool) -> io::Result<HashMap<String, Vec<String>>> {
-        let base_dir = if is_global {
-            &self.global_memory_dir
-        } else {
-            &self.local_memory_dir
-        };
-        let mut memories = HashMap::new();
-        if base_dir.exists() {
-            for entry in fs::read_dir(base_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_file() {
-                    let category = entry.file_name().to_string_lossy().replace(".txt", "");
-                    let category_memories = self.retrieve(&category, is_global)?;
-                    memories.insert(
-                        category,
-                        category_memories.into_iter().flat_map(|(_, v)| v).collect(),
-                    );
+    fn open_store(&self, is_global: bool) -> io::Result<MemoryStore> {
+        MemoryStore::open(self.memory_dir(is_global))
+    }
+
+    /// Render the `limit` most recently updated memories across both scopes for the
+    /// server instructions, noting how many were left out.
+    fn startup_memories(&self, limit: usize) -> String {
+        let mut memories: Vec<(bool, Memory)> = Vec::new();
+        for is_global in [true, false] {
+            if let Ok(store) = self.open_store(is_global) {
+                memories.extend(store.memories().iter().cloned().map(|m| (is_global, m)));
+            }
+        }
+        memories.sort_by(|a, b| b.1.updated_at.cmp(&a.1.updated_at));
+        let omitted = memories.len().saturating_sub(limit);
+        memories.truncate(limit);
+
+        let mut section = String::new();
+        for (is_global, heading) in [(true, "Global Memories"), (false, "Local Memories")] {
+            let mut by_category: BTreeMap<&str, Vec<&Memory>> = BTreeMap::new();
+            for (_, memory) in memories.iter().filter(|(g, _)| *g == is_global) {
+                by_category
+                    .entry(memory.category.as_str())
+                    .or_default()
+                    .push(memory);
+            }
+            if by_category.is_empty() {
+                continue;
+            }
+            section.push_str(&format!("\n\n{}:\n", heading));
+            for (category, memories) in by_category {
+                section.push_str(&format!("\nCategory: {}\n", category));
+                for memory in memories {
+                    section.push_str(&format!("- {} (id: {})\n", memory.content, memory.id));
                 }
             }
         }
+
+        if omitted > 0 {
+            section.push_str(&format!(
+                "\n{} older memories are not shown; use search_memories to find them.\n",
+                omitted
+            ));
+        }
+        section
+    }
+
+    pub fn retrieve_all(&self, is_global: bool) -> io::Result<HashMap<String, Vec<String>>> {
+        let mut memories: HashMap<String, Vec<String>> = HashMap::new();
+        for memory in self.open_store(is_global)?.memories() {
+            memories
+                .entry(memory.category.clone())
+                .or_default()
+                .push(memory.content.clone());
+        }
         Ok(memories)
     }
 
@@ -297,59 +348,32 @@ impl MemoryServer {
         data: &str,
         tags: &[&str],
         is_global: bool,
-    ) -> io::Result<()> {
-        let memory_file_path = self.get_memory_file(category, is_global);
-
-        if let Some(parent) = memory_file_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let mut file = fs::OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open(&memory_file_path)?;
-        if !tags.is_empty() {
-            writeln!(file, "# {}", tags.join(" "))?;
-        }
-        writeln!(file, "{}\n", data)?;
-
-        Ok(())
+    ) -> io::Result<Memory> {
+        self.open_store(is_global)?.add(category, data, tags)
     }
 
+    /// Memories in `category`, grouped by their space-joined tags ("untagged" for none).
     pub fn retrieve(
         &self,
         category: &str,
         is_global: bool,
     ) -> io::Result<HashMap<String, Vec<String>>> {
-        let memory_file_path = self.get_memory_file(category, is_global);
-        if !memory_file_path.exists() {
-            return Ok(HashMap::new());
-        }
-
-        let mut file = fs::File::open(memory_file_path)?;
-        let mut content = String::new();
-        file.read_to_string(&mut content)?;
-
-        let mut memories = HashMap::new();
-        for entry in content.split("\n\n") {
-            let mut lines = entry.lines();
-            if let Some(first_line) = lines.next() {
-                if let Some(stripped) = first_line.strip_prefix('#') {
-                    let tags = stripped
-                        .split_whitespace()
-                        .map(String::from)
-                        .collect::<Vec<_>>();
-                    memories.insert(tags.join(" "), lines.map(String::from).collect());
-                } else {
-                    let entry_data: Vec<String> = std::iter::once(first_line.to_string())
-                        .chain(lines.map(String::from))
-                        .collect();
-                    memories
-                        .entry("untagged".to_string())
-                        .or_insert_with(Vec::new)
-                        .extend(entry_data);
-                }
-            }
+        let mut memories: HashMap<String, Vec<String>> = HashMap::new();
+        for memory in self
+            .open_store(is_global)?
+            .memories()
+            .iter()
+            .filter(|m| m.category == category)
+        {
+            let key = if memory.tags.is_empty() {
+                "untagged".to_string()
+            } else {
+                memory.tags.join(" ")
+            };
+            memories
+                .entry(key)
+                .or_default()
+                .push(memory.content.clone());
         }
 
         Ok(memories)
@@ -361,34 +385,15 @@ impl MemoryServer {
         memory_content: &str,
         is_global: bool,
     ) -> io::Result<()> {
-        let memory_file_path = self.get_memory_file(category, is_global);
-        if !memory_file_path.exists() {
-            return Ok(());
-        }
-
-        let mut file = fs::File::open(&memory_file_path)?;
-        let mut content = String::new();
-        file.read_to_string(&mut content)?;
-
-        let memories: Vec<&str> = content.split("\n\n").collect();
-        let new_content: Vec<String> = memories
-            .into_iter()
-            .filter(|entry| !entry.contains(memory_content))
-            .map(|s| s.to_string())
-            .collect();
-
-        fs::write(memory_file_path, new_content.join("\n\n"))?;
-
-        Ok(())
+        self.open_store(is_global)?
+            .remove_matching(category, memory_content)
+            .map(|_| ())
     }
 
     pub fn clear_memory(&self, category: &str, is_global: bool) -> io::Result<()> {
-        let memory_file_path = self.get_memory_file(category, is_global);
-        if memory_file_path.exists() {
-            fs::remove_file(memory_file_path)?;
-        }
-
-        Ok(())
+        self.open_store(is_global)?
+            .remove_category(category)
+            .map(|_| ())
     }
 
     pub fn clear_all_global_or_local_memories(&self, is_global: bool) -> io::Result<()> {
@@ -423,18 +428,19 @@ impl MemoryServer {
         }
 
         let tags: Vec<&str> = params.tags.iter().map(|s| s.as_str()).collect();
-        self.remember(
-            "context",
-            &params.category,
-            &params.data,
-            &tags,
-            params.is_global,
-        )
-        .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        let memory = self
+            .remember(
+                "context",
+                &params.category,
+                &params.data,
+                &tags,
+                params.is_global,
+            )
+            .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
-            "Stored memory in category: {}",
-            params.category
+            "Stored memory {} in category: {}",
+            memory.id, params.category
         ))]))
     }
 
@@ -449,16 +455,115 @@ impl MemoryServer {
     ) -> Result<CallToolResult, ErrorData> {
         let params = params.0;
 
-        let memories = if params.category == "*" {
-            self.retrieve_all(params.is_global)
-        } else {
-            self.retrieve(&params.category, params.is_global)
+        let store = self
+            .open_store(params.is_global)
+            .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        let memories: Vec<&Memory> = store
+            .memories()
+            .iter()
+            .filter(|m| params.category == "*" || m.category == params.category)
+            .collect();
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Retrieved memories:\n{}",
+            format_memories(memories.into_iter().map(|m| (params.is_global, m)))
+        ))]))
+    }
+
+    /// Searches memories by keywords and tags, ranked by relevance and recency
+    #[tool(
+        name = "search_memories",
+        description = "Searches memories by keywords and tags, ranked by relevance and recency"
+    )]
+    pub async fn search_memories(
+        &self,
+        params: Parameters<SearchMemoriesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let scopes = match params.is_global {
+            Some(is_global) => vec![is_global],
+            None => vec![true, false],
+        };
+
+        let stores = scopes
+            .into_iter()
+            .map(|is_global| self.open_store(is_global).map(|store| (is_global, store)))
+            .collect::<io::Result<Vec<_>>>()
+            .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+        let query = MemoryQuery {
+            text: &params.query,
+            tags: &params.tags,
+            category: params.category.as_deref(),
+        };
+        let mut results: Vec<(f64, bool, &Memory)> = stores
+            .iter()
+            .flat_map(|(is_global, store)| {
+                store
+                    .search(&query)
+                    .into_iter()
+                    .map(move |(score, memory)| (score, *is_global, memory))
+            })
+            .collect();
+        results.sort_by(|a, b| b.0.total_cmp(&a.0));
+        results.truncate(params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+
+        if results.is_empty() {
+            return Ok(CallToolResult::success(vec![Content::text(
+                "No matching memories found",
+            )]));
         }
-        .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
-            "Retrieved memories: {:?}",
-            memories
+            "Found {} memories:\n{}",
+            results.len(),
+            format_memories(results.into_iter().map(|(_, g, m)| (g, m)))
+        ))]))
+    }
+
+    /// Updates the content, category or tags of a memory by id
+    #[tool(
+        name = "update_memory",
+        description = "Updates the content, category or tags of a memory by id"
+    )]
+    pub async fn update_memory(
+        &self,
+        params: Parameters<UpdateMemoryParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+
+        if params.data.as_deref().is_some_and(str::is_empty) {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "Data must not be empty when updating a memory".to_string(),
+                None,
+            ));
+        }
+
+        let updated = self
+            .open_store(params.is_global)
+            .and_then(|mut store| {
+                store.update(
+                    &params.id,
+                    MemoryUpdate {
+                        category: params.category,
+                        content: params.data,
+                        tags: params.tags,
+                    },
+                )
+            })
+            .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?
+            .ok_or_else(|| {
+                ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("No memory with id {}", params.id),
+                    None,
+                )
+            })?;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Updated memory {} in category: {}",
+            updated.id, updated.category
         ))]))
     }
 
@@ -500,12 +605,32 @@ impl MemoryServer {
     ) -> Result<CallToolResult, ErrorData> {
         let params = params.0;
 
-        self.remove_specific_memory_internal(
-            &params.category,
-            &params.memory_content,
-            params.is_global,
-        )
-        .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        if let Some(id) = &params.id {
+            let removed = self
+                .open_store(params.is_global)
+                .and_then(|mut store| store.remove(id))
+                .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+            if !removed {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("No memory with id {}", id),
+                    None,
+                ));
+            }
+        } else if params.memory_content.is_empty() {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                "Either id or memory_content is required".to_string(),
+                None,
+            ));
+        } else {
+            self.remove_specific_memory_internal(
+                &params.category,
+                &params.memory_content,
+                params.is_global,
+            )
+            .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+        }
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Removed specific memory from category: {}",
@@ -514,6 +639,50 @@ impl MemoryServer {
     }
 }
 
+/// Directory holding the project memories for `working_dir`.
+pub fn local_memory_dir(working_dir: &Path) -> PathBuf {
+    working_dir.join(".goose").join("memory")
+}
+
+/// Directory holding the user-wide memories.
+pub fn global_memory_dir() -> PathBuf {
+    // choose_app_strategy().config_dir()
+    // - macOS/Linux: ~/.config/goose/memory/
+    // - Windows:     ~\AppData\Roaming\Block\goose\config\memory
+    // if it fails, fall back to `.config/goose/memory` (relative to the current dir)
+    choose_app_strategy(crate::APP_STRATEGY.clone())
+        .map(|strategy| strategy.in_config_dir("memory"))
+        .unwrap_or_else(|_| PathBuf::from(".config/goose/memory"))
+}
+
+fn startup_memory_limit() -> usize {
+    std::env::var("GOOSE_MEMORY_STARTUP_LIMIT")
+        .ok()
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_STARTUP_MEMORY_LIMIT)
+}
+
+fn format_memories<'a>(memories: impl Iterator<Item = (bool, &'a Memory)>) -> String {
+    let mut output = String::new();
+    for (is_global, memory) in memories {
+        output.push_str(&format!(
+            "- [{}] ({}, {}) {}",
+            memory.id,
+            if is_global { "global" } else { "local" },
+            memory.category,
+            memory.content
+        ));
+        if !memory.tags.is_empty() {
+            output.push_str(&format!(" #{}", memory.tags.join(" #")));
+        }
+        output.push_str(&format!(
+            " (updated {})\n",
+            memory.updated_at.format("%Y-%m-%d")
+        ));
+    }
+    output
+}
+
 #[tool_handler(router = self.tool_router)]
 impl ServerHandler for MemoryServer {
     fn get_info(&self) -> ServerInfo {
@@ -652,7 +821,10 @@ mod tests {
             .unwrap();
 
         assert!(router.local_memory_dir.exists());
-        assert!(router.local_memory_dir.join("category.txt").exists());
+        assert!(router
+            .local_memory_dir
+            .join(store::MEMORY_STORE_FILE)
+            .exists());
     }
 
     #[test]
@@ -692,4 +864,32 @@ mod tests {
             .any(|v| v.iter().any(|content| content.contains("keep_this")));
         assert!(has_kept);
     }
+
+    #[test]
+    fn test_startup_memories_are_capped() {
+        let temp_dir = tempdir().unwrap();
+        let memory_base = temp_dir.path().join("startup_test");
+
+        let router = MemoryServer {
+            tool_router: ToolRouter::new(),
+            instructions: String::new(),
+            global_memory_dir: memory_base.join("global"),
+            local_memory_dir: memory_base.join("local"),
+        };
+
+        for i in 0..5 {
+            router
+                .remember("context", "notes", &format!("note {}", i), &[], i % 2 == 0)
+                .unwrap();
+        }
+
+        let section = router.startup_memories(2);
+        assert_eq!(section.matches("- note").count(), 2);
+        assert!(section.contains("3 older memories are not shown"));
+
+        let everything = router.startup_memories(DEFAULT_STARTUP_MEMORY_LIMIT);
+        assert!(everything.contains("Global Memories:"));
+        assert!(everything.contains("Local Memories:"));
+        assert!(!everything.contains("older memories"));
+    }
 }
@@ -0,0 +1,721 @@
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    ops::Bound,
+    path::{Path, PathBuf},
+};
+
+pub const MEMORY_STORE_FILE: &str = "memories.json";
+
+/// Lock file held while a process loads, changes and saves the store
+const MEMORY_LOCK_FILE: &str = "memories.json.lock";
+
+/// Format version written by this build. Bump it and add a step to `MemoryStore::upgrade`
+/// whenever the layout of `memories.json` changes.
+pub const MEMORY_STORE_VERSION: u32 = 1;
+
+/// Where the pre-store `<category>.txt` files are moved once they have been imported
+pub const LEGACY_BACKUP_DIR: &str = "legacy";
+
+/// Memories untouched for this many days get half the recency boost of fresh ones
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Memory {
+    pub id: String,
+    pub category: String,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Memory {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryUpdate {
+    pub category: Option<String>,
+    pub content: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryQuery<'a> {
+    pub text: &'a str,
+    pub tags: &'a [String],
+    pub category: Option<&'a str>,
+}
+
+/// The on-disk layout of `memories.json`, also used as the export format so an
+/// exported file can be imported on another machine or dropped in as a store.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MemoryFile {
+    /// Missing in stores written before versioning was introduced, which are format 0
+    #[serde(default)]
+    pub version: u32,
+    pub memories: Vec<Memory>,
+}
+
+impl MemoryFile {
+    pub fn new(memories: Vec<Memory>) -> Self {
+        Self {
+            version: MEMORY_STORE_VERSION,
+            memories,
+        }
+    }
+
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let file: MemoryFile = serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if file.version > MEMORY_STORE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "memory format {} was written by a newer goose (this build reads up to {})",
+                    file.version, MEMORY_STORE_VERSION
+                ),
+            ));
+        }
+        Ok(file)
+    }
+
+    pub fn to_json(&self) -> io::Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct PruneOptions<'a> {
+    /// Remove memories last updated before this time
+    pub updated_before: Option<DateTime<Utc>>,
+    /// Remove all but the most recently updated copy of memories with identical content
+    pub duplicates: bool,
+    /// Only consider memories in this category
+    pub category: Option<&'a str>,
+}
+
+/// All memories of one scope (global or local), kept in a single JSON file alongside
+/// an inverted index from lowercase terms to memory positions.
+///
+/// The store is loaded per operation rather than held open, so several goose processes
+/// can share a memory directory without seeing stale data. Changes reload the file under
+/// an exclusive lock before saving, so concurrent sessions never drop each other's writes.
+pub struct MemoryStore {
+    dir: PathBuf,
+    memories: Vec<Memory>,
+    index: BTreeMap<String, HashSet<usize>>,
+}
+
+impl MemoryStore {
+    /// Load the store in `dir` without changing anything on disk. A directory without a
+    /// store file is read from the old `<category>.txt` files; the first change to it
+    /// imports them and moves them to [`LEGACY_BACKUP_DIR`].
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let mut store = Self {
+            dir: dir.to_path_buf(),
+            memories: Vec::new(),
+            index: BTreeMap::new(),
+        };
+        // Read under the lock so a concurrent migration never moves the legacy files
+        // mid-read. A directory that does not exist yet has nothing to read.
+        if dir.is_dir() {
+            let _lock = lock_store(dir)?;
+            store.memories = store.load()?.0;
+        }
+        store.rebuild_index();
+        Ok(store)
+    }
+
+    pub fn memories(&self) -> &[Memory] {
+        &self.memories
+    }
+
+    pub fn add(&mut self, category: &str, content: &str, tags: &[&str]) -> io::Result<Memory> {
+        let now = Utc::now();
+        let memory = Memory {
+            id: new_memory_id(),
+            category: category.to_string(),
+            content: content.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.modify(|memories| memories.push(memory.clone()))?;
+        Ok(memory)
+    }
+
+    pub fn update(&mut self, id: &str, update: MemoryUpdate) -> io::Result<Option<Memory>> {
+        self.modify(|memories| {
+            let memory = memories.iter_mut().find(|m| m.id == id)?;
+            if let Some(category) = update.category {
+                memory.category = category;
+            }
+            if let Some(content) = update.content {
+                memory.content = content;
+            }
+            if let Some(tags) = update.tags {
+                memory.tags = tags;
+            }
+            memory.updated_at = Utc::now();
+            Some(memory.clone())
+        })
+    }
+
+    pub fn remove(&mut self, id: &str) -> io::Result<bool> {
+        self.remove_where(|m| m.id == id).map(|removed| removed > 0)
+    }
+
+    /// Remove every memory in `category` whose content contains `needle`.
+    pub fn remove_matching(&mut self, category: &str, needle: &str) -> io::Result<usize> {
+        self.remove_where(|m| m.category == category && m.content.contains(needle))
+    }
+
+    pub fn remove_category(&mut self, category: &str) -> io::Result<usize> {
+        self.remove_where(|m| m.category == category)
+    }
+
+    fn remove_where(&mut self, predicate: impl Fn(&Memory) -> bool) -> io::Result<usize> {
+        self.modify(|memories| {
+            let before = memories.len();
+            memories.retain(|m| !predicate(m));
+            before - memories.len()
+        })
+    }
+
+    /// Merge `memories` into the store. A memory whose id already exists replaces the
+    /// stored copy only if it was updated more recently; a memory with the same category
+    /// and content as an existing one is skipped.
+    pub fn import(&mut self, memories: Vec<Memory>) -> io::Result<ImportSummary> {
+        self.modify(|stored| {
+            let mut summary = ImportSummary::default();
+            for memory in memories {
+                if let Some(existing) = stored.iter_mut().find(|m| m.id == memory.id) {
+                    if memory.updated_at > existing.updated_at {
+                        *existing = memory;
+                        summary.updated += 1;
+                    } else {
+                        summary.skipped += 1;
+                    }
+                } else if stored
+                    .iter()
+                    .any(|m| m.category == memory.category && m.content == memory.content)
+                {
+                    summary.skipped += 1;
+                } else {
+                    stored.push(memory);
+                    summary.added += 1;
+                }
+            }
+            summary
+        })
+    }
+
+    /// Remove the memories selected by `options` and return them. With `dry_run` the
+    /// store is left untouched.
+    pub fn prune(&mut self, options: &PruneOptions, dry_run: bool) -> io::Result<Vec<Memory>> {
+        let mut newest_first: Vec<&Memory> = self.memories.iter().collect();
+        newest_first.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+        let mut seen = HashSet::new();
+        let mut pruned_ids = HashSet::new();
+        for memory in newest_first {
+            if options.category.is_some_and(|c| memory.category != c) {
+                continue;
+            }
+            let stale = options
+                .updated_before
+                .is_some_and(|cutoff| memory.updated_at < cutoff);
+            let duplicate = options.duplicates
+                && !seen.insert((memory.category.as_str(), memory.content.trim()));
+            if stale || duplicate {
+                pruned_ids.insert(memory.id.clone());
+            }
+        }
+
+        let pruned: Vec<Memory> = self
+            .memories
+            .iter()
+            .filter(|m| pruned_ids.contains(&m.id))
+            .cloned()
+            .collect();
+        if !dry_run {
+            self.remove_where(|m| pruned_ids.contains(&m.id))?;
+        }
+        Ok(pruned)
+    }
+
+    /// Rank memories against `query`. Terms score by rarity (exact matches count double
+    /// prefix matches, tag matches get a bonus) and the total is boosted for recently
+    /// updated memories. With no query text, matching memories are ranked by recency.
+    pub fn search(&self, query: &MemoryQuery) -> Vec<(f64, &Memory)> {
+        let terms: BTreeSet<String> = tokenize(query.text).into_iter().collect();
+        let total = self.memories.len().max(1) as f64;
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        if terms.is_empty() {
+            for position in 0..self.memories.len() {
+                scores.insert(position, 1.0);
+            }
+        }
+
+        for term in &terms {
+            // The index is sorted, so the keys starting with the term follow it directly
+            let matches = self
+                .index
+                .range::<str, _>((Bound::Included(term.as_str()), Bound::Unbounded))
+                .take_while(|(key, _)| key.starts_with(term.as_str()));
+            for (key, positions) in matches {
+                let weight = if key == term { 1.0 } else { 0.5 };
+                let idf = (1.0 + total / positions.len() as f64).ln();
+                for &position in positions {
+                    *scores.entry(position).or_default() += weight * idf;
+                }
+            }
+        }
+        if !terms.is_empty() {
+            for (position, memory) in self.memories.iter().enumerate() {
+                let tags: HashSet<String> = memory.tags.iter().map(|t| t.to_lowercase()).collect();
+                let tag_matches = tags.iter().filter(|tag| terms.contains(*tag)).count();
+                if tag_matches > 0 {
+                    *scores.entry(position).or_default() += tag_matches as f64;
+                }
+            }
+        }
+
+        let now = Utc::now();
+        let mut results: Vec<(f64, &Memory)> = scores
+            .into_iter()
+            .map(|(position, score)| (score, &self.memories[position]))
+            .filter(|(_, memory)| query.category.is_none_or(|c| memory.category == c))
+            .filter(|(_, memory)| query.tags.iter().all(|tag| memory.has_tag(tag)))
+            .map(|(score, memory)| {
+                let age_days = (now - memory.updated_at).num_seconds().max(0) as f64 / 86_400.0;
+                let recency = 0.5f64.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+                (score * (1.0 + recency), memory)
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then_with(|| b.1.updated_at.cmp(&a.1.updated_at))
+        });
+        results
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index.clear();
+        for (position, memory) in self.memories.iter().enumerate() {
+            let text = std::iter::once(memory.content.as_str())
+                .chain(std::iter::once(memory.category.as_str()))
+                .chain(memory.tags.iter().map(String::as_str));
+            for term in text.flat_map(tokenize) {
+                self.index.entry(term).or_default().insert(position);
+            }
+        }
+    }
+
+    /// The memories on disk and the format they were read from: the store's version, or
+    /// `None` for the legacy `.txt` files. Callers must hold the store's lock.
+    fn load(&self) -> io::Result<(Vec<Memory>, Option<u32>)> {
+        let store_path = self.dir.join(MEMORY_STORE_FILE);
+        if store_path.exists() {
+            let file = MemoryFile::from_json(&fs::read_to_string(&store_path)?)?;
+            Ok((file.memories, Some(file.version)))
+        } else {
+            Ok((read_legacy_memories(&self.dir)?, None))
+        }
+    }
+
+    /// Bring an older store up to [`MEMORY_STORE_VERSION`] once it is being written
+    /// anyway. Callers must hold the store's lock.
+    fn upgrade(&mut self, from: Option<u32>) -> io::Result<()> {
+        match from {
+            // Format 0 imported the .txt files but left them in place
+            None | Some(0) => archive_legacy_files(&self.dir),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Apply `change` to the memories currently on disk and save them if anything
+    /// changed, holding the store's lock from the load through the save.
+    fn modify<T>(&mut self, change: impl FnOnce(&mut Vec<Memory>) -> T) -> io::Result<T> {
+        fs::create_dir_all(&self.dir)?;
+        let _lock = lock_store(&self.dir)?;
+
+        let (memories, version) = self.load()?;
+        self.memories = memories;
+        let outdated = match version {
+            Some(version) => version < MEMORY_STORE_VERSION,
+            None => !self.memories.is_empty(),
+        };
+        let before = self.memories.clone();
+        let result = change(&mut self.memories);
+        if outdated || self.memories != before {
+            // Save before archiving so a failure never leaves memories only in the backup
+            self.save()?;
+            if outdated {
+                self.upgrade(version)?;
+            }
+        } else {
+            self.rebuild_index();
+        }
+        Ok(result)
+    }
+
+    /// Callers must hold the store's lock.
+    fn save(&mut self) -> io::Result<()> {
+        self.rebuild_index();
+        fs::create_dir_all(&self.dir)?;
+        let json = MemoryFile::new(self.memories.clone()).to_json()?;
+
+        // Write to a temporary file first so a crash never leaves a truncated store
+        let mut tmp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        tmp.write_all(json.as_bytes())?;
+        tmp.as_file().sync_all()?;
+        tmp.persist(self.dir.join(MEMORY_STORE_FILE))
+            .map(|_| ())
+            .map_err(|e| e.error)
+    }
+}
+
+/// Take the exclusive lock on the store in `dir`, released when the file is dropped.
+fn lock_store(dir: &Path) -> io::Result<File> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(MEMORY_LOCK_FILE))?;
+    file.lock_exclusive()?;
+    Ok(file)
+}
+
+fn new_memory_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| term.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn legacy_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if dir.exists() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("txt") {
+                files.push(path);
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn archive_legacy_files(dir: &Path) -> io::Result<()> {
+    let files = legacy_files(dir)?;
+    if files.is_empty() {
+        return Ok(());
+    }
+    let backup_dir = dir.join(LEGACY_BACKUP_DIR);
+    fs::create_dir_all(&backup_dir)?;
+    for path in files {
+        if let Some(name) = path.file_name() {
+            fs::rename(&path, backup_dir.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse the pre-store format: one `<category>.txt` per category holding blank-line
+/// separated entries, each optionally starting with a `# tag1 tag2` line.
+fn read_legacy_memories(dir: &Path) -> io::Result<Vec<Memory>> {
+    let mut memories = Vec::new();
+    for path in legacy_files(dir)? {
+        let Some(category) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let modified: DateTime<Utc> = fs::metadata(&path)?
+            .modified()
+            .map(DateTime::from)
+            .unwrap_or_else(|_| Utc::now());
+
+        for block in fs::read_to_string(&path)?.split("\n\n") {
+            let mut lines = block.lines().peekable();
+            let tags = match lines.peek() {
+                Some(first) if first.starts_with('#') => {
+                    let tags = first[1..].split_whitespace().map(String::from).collect();
+                    lines.next();
+                    tags
+                }
+                _ => Vec::new(),
+            };
+            let content = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+            if content.is_empty() {
+                continue;
+            }
+            memories.push(Memory {
+                id: new_memory_id(),
+                category: category.clone(),
+                content,
+                tags,
+                created_at: modified,
+                updated_at: modified,
+            });
+        }
+    }
+
+    Ok(memories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_search_ranks_relevant_memories_first() {
+        let dir = tempdir().unwrap();
+        let mut store = MemoryStore::open(dir.path()).unwrap();
+        store
+            .add(
+                "development",
+                "We format Python with black",
+                &["formatting"],
+            )
+            .unwrap();
+        store
+            .add("personal", "My name is Alex", &["identity"])
+            .unwrap();
+        store
+            .add("development", "Run tests with cargo nextest", &["testing"])
+            .unwrap();
+
+        let results = store.search(&MemoryQuery {
+            text: "python formatter",
+            ..Default::default()
+        });
+        assert_eq!(results[0].1.content, "We format Python with black");
+        assert!(results.iter().all(|(_, m)| m.content != "My name is Alex"));
+
+        // Terms match the indexed words they are a prefix of
+        let prefixed = store.search(&MemoryQuery {
+            text: "nex nex",
+            ..Default::default()
+        });
+        assert_eq!(prefixed.len(), 1);
+        assert_eq!(prefixed[0].1.content, "Run tests with cargo nextest");
+
+        let tagged = store.search(&MemoryQuery {
+            tags: &["testing".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].1.content, "Run tests with cargo nextest");
+    }
+
+    #[test]
+    fn test_update_and_remove_by_id() {
+        let dir = tempdir().unwrap();
+        let mut store = MemoryStore::open(dir.path()).unwrap();
+        let memory = store.add("tools", "use rg", &[]).unwrap();
+
+        let updated = store
+            .update(
+                &memory.id,
+                MemoryUpdate {
+                    content: Some("use ripgrep".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.content, "use ripgrep");
+        assert_eq!(updated.created_at, memory.created_at);
+
+        let reopened = MemoryStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.memories()[0].content, "use ripgrep");
+
+        let mut store = reopened;
+        assert!(store.remove(&memory.id).unwrap());
+        assert!(!store.remove(&memory.id).unwrap());
+        assert!(store.memories().is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_stores_keep_each_others_writes() {
+        let dir = tempdir().unwrap();
+        let mut first = MemoryStore::open(dir.path()).unwrap();
+        let mut second = MemoryStore::open(dir.path()).unwrap();
+
+        first.add("tools", "use rg", &[]).unwrap();
+        second.add("tools", "use fd", &[]).unwrap();
+        assert_eq!(second.memories().len(), 2);
+
+        let reopened = MemoryStore::open(dir.path()).unwrap();
+        let mut contents: Vec<&str> = reopened
+            .memories()
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        contents.sort();
+        assert_eq!(contents, vec!["use fd", "use rg"]);
+    }
+
+    #[test]
+    fn test_imports_legacy_text_files() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("development.txt"),
+            "# formatting tools\nuse black\n\nuntagged entry\n\n",
+        )
+        .unwrap();
+
+        // Reading leaves the legacy files alone
+        let mut store = MemoryStore::open(dir.path()).unwrap();
+        assert_eq!(store.memories().len(), 2);
+        assert!(!dir.path().join(MEMORY_STORE_FILE).exists());
+        assert!(dir.path().join("development.txt").exists());
+
+        // The first write imports them, even when it changes nothing
+        assert!(!store.remove("missing").unwrap());
+        assert!(dir.path().join(MEMORY_STORE_FILE).exists());
+        assert!(!dir.path().join("development.txt").exists());
+        assert!(dir
+            .path()
+            .join(LEGACY_BACKUP_DIR)
+            .join("development.txt")
+            .exists());
+
+        // The migration only runs once
+        let mut reopened = MemoryStore::open(dir.path()).unwrap();
+        assert!(!reopened.remove("missing").unwrap());
+        assert_eq!(reopened.memories().len(), 2);
+
+        let formatted = store
+            .memories()
+            .iter()
+            .find(|m| m.content == "use black")
+            .unwrap();
+        assert_eq!(formatted.category, "development");
+        assert_eq!(formatted.tags, vec!["formatting", "tools"]);
+    }
+
+    #[test]
+    fn test_upgrades_unversioned_store() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("notes.txt"), "already imported\n\n").unwrap();
+        fs::write(
+            dir.path().join(MEMORY_STORE_FILE),
+            r#"{"memories":[{"id":"abcd1234","category":"notes","content":"already imported","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+
+        let mut store = MemoryStore::open(dir.path()).unwrap();
+        assert_eq!(store.memories().len(), 1);
+        assert!(dir.path().join("notes.txt").exists());
+
+        store.add("notes", "new entry", &[]).unwrap();
+        assert_eq!(store.memories().len(), 2);
+        assert!(!dir.path().join("notes.txt").exists());
+
+        let saved =
+            MemoryFile::from_json(&fs::read_to_string(dir.path().join(MEMORY_STORE_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(saved.version, MEMORY_STORE_VERSION);
+
+        let newer = r#"{"version":999,"memories":[]}"#;
+        assert!(MemoryFile::from_json(newer).is_err());
+    }
+
+    #[test]
+    fn test_import_merges_by_id_and_content() {
+        let source_dir = tempdir().unwrap();
+        let mut source = MemoryStore::open(source_dir.path()).unwrap();
+        let shared = source.add("team", "deploy with make release", &[]).unwrap();
+        source.add("team", "on-call rotates weekly", &[]).unwrap();
+        let exported = MemoryFile::new(source.memories().to_vec())
+            .to_json()
+            .unwrap();
+
+        let target_dir = tempdir().unwrap();
+        let mut target = MemoryStore::open(target_dir.path()).unwrap();
+        target.add("team", "on-call rotates weekly", &[]).unwrap();
+
+        let file = MemoryFile::from_json(&exported).unwrap();
+        let summary = target.import(file.memories).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 1,
+                updated: 0,
+                skipped: 1
+            }
+        );
+
+        let mut changed = shared.clone();
+        changed.content = "deploy with make release-prod".to_string();
+        changed.updated_at = shared.updated_at + chrono::Duration::seconds(1);
+        let summary = target.import(vec![changed, shared]).unwrap();
+        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(target.memories().len(), 2);
+    }
+
+    fn memory(id: &str, category: &str, content: &str, days_old: i64) -> Memory {
+        let updated_at = Utc::now() - chrono::Duration::days(days_old);
+        Memory {
+            id: id.to_string(),
+            category: category.to_string(),
+            content: content.to_string(),
+            tags: Vec::new(),
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_prune_stale_and_duplicate_memories() {
+        let dir = tempdir().unwrap();
+        let mut store = MemoryStore::open(dir.path()).unwrap();
+        store
+            .import(vec![
+                memory("old", "notes", "old fact", 90),
+                memory("dup-old", "notes", "repeated", 5),
+                memory("dup-new", "notes", "repeated ", 1),
+                memory("other", "other", "repeated", 200),
+            ])
+            .unwrap();
+
+        let options = PruneOptions {
+            updated_before: Some(Utc::now() - chrono::Duration::days(30)),
+            duplicates: true,
+            category: Some("notes"),
+        };
+        let preview = store.prune(&options, true).unwrap();
+        assert_eq!(preview.len(), 2);
+        assert_eq!(store.memories().len(), 4);
+
+        store.prune(&options, false).unwrap();
+        let remaining: Vec<&str> = store.memories().iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(remaining, vec!["dup-new", "other"]);
+    }
+}
@@ -1,25 +1,510 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
 use axum::{
-    extract::{Request, State},
-    http::StatusCode,
+    extract::{FromRequestParts, Request, State},
+    http::{request::Parts, StatusCode},
     middleware::Next,
     response::Response,
 };
+use goose::session::SessionManager;
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// The caller a request was authenticated as.
+///
+/// `Admin` is whoever holds the shared `GOOSE_SERVER__SECRET_KEY` (the desktop app, or a
+/// single-user goosed) and sees everything. `User` is a named account from the users file
+/// or a verified JWT, and only sees the sessions, schedules and recipes it owns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthUser {
+    Admin,
+    User(String),
+}
+
+impl AuthUser {
+    pub fn is_admin(&self) -> bool {
+        matches!(self, AuthUser::Admin)
+    }
+
+    /// Owner to record on things this caller creates.
+    pub fn owner(&self) -> Option<String> {
+        match self {
+            AuthUser::Admin => None,
+            AuthUser::User(name) => Some(name.clone()),
+        }
+    }
+
+    pub fn can_access(&self, owner: Option<&str>) -> bool {
+        match self {
+            AuthUser::Admin => true,
+            AuthUser::User(name) => owner == Some(name.as_str()),
+        }
+    }
+}
+
+/// Requests that never went through [`check_token`] are rejected rather than
+/// trusted, so a route mounted without the middleware fails closed.
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UsersFile {
+    #[serde(default)]
+    users: Vec<UserEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserEntry {
+    name: String,
+    /// Hex encoded SHA-256 of the user's API token
+    token_sha256: String,
+    #[serde(default)]
+    admin: bool,
+}
+
+struct JwtVerifier {
+    keys: JwkSet,
+    issuer: Option<String>,
+    audience: Option<String>,
+    user_claim: String,
+}
+
+impl JwtVerifier {
+    fn verify(&self, token: &str) -> Result<String> {
+        let header = decode_header(token)?;
+        let jwk = match &header.kid {
+            Some(kid) => self
+                .keys
+                .find(kid)
+                .ok_or_else(|| anyhow!("no key with kid '{}' in JWKS", kid))?,
+            None if self.keys.keys.len() == 1 => &self.keys.keys[0],
+            None => return Err(anyhow!("token has no kid and JWKS holds several keys")),
+        };
+        let key = DecodingKey::from_jwk(jwk)?;
+
+        // The token header is untrusted, so the key decides which algorithms are acceptable
+        let algorithms = key_algorithms(jwk)?;
+        if !algorithms.contains(&header.alg) {
+            return Err(anyhow!(
+                "token algorithm {:?} does not match its key",
+                header.alg
+            ));
+        }
+        let mut validation = Validation::new(header.alg);
+        validation.algorithms = algorithms;
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let claims =
+            decode::<serde_json::Map<String, serde_json::Value>>(token, &key, &validation)?.claims;
+        claims
+            .get(&self.user_claim)
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("token has no '{}' claim", self.user_claim))
+    }
+}
+
+/// Algorithms a JWK may verify: its `alg` if it names one, otherwise the ones that fit its key type.
+fn key_algorithms(jwk: &Jwk) -> Result<Vec<Algorithm>> {
+    if let Some(alg) = jwk.common.key_algorithm {
+        let alg = Algorithm::from_str(&alg.to_string())
+            .map_err(|_| anyhow!("JWKS key uses unsupported algorithm {}", alg))?;
+        return Ok(vec![alg]);
+    }
+    Ok(match &jwk.algorithm {
+        AlgorithmParameters::RSA(_) => vec![
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+        ],
+        AlgorithmParameters::EllipticCurve(params) => match params.curve {
+            EllipticCurve::P256 => vec![Algorithm::ES256],
+            EllipticCurve::P384 => vec![Algorithm::ES384],
+            _ => return Err(anyhow!("JWKS key uses an unsupported curve")),
+        },
+        AlgorithmParameters::OctetKeyPair(_) => vec![Algorithm::EdDSA],
+        AlgorithmParameters::OctetKey(_) => {
+            vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512]
+        }
+    })
+}
+
+/// How goosed authenticates callers, read from `GOOSE_SERVER__*` environment variables.
+pub struct AuthConfig {
+    secret_key: String,
+    /// SHA-256 token hash -> user
+    users: HashMap<String, AuthUser>,
+    jwt: Option<JwtVerifier>,
+}
+
+impl AuthConfig {
+    pub fn new(secret_key: impl Into<String>) -> Self {
+        Self {
+            secret_key: secret_key.into(),
+            users: HashMap::new(),
+            jwt: None,
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let secret_key =
+            std::env::var("GOOSE_SERVER__SECRET_KEY").unwrap_or_else(|_| "test".to_string());
+        let mut config = Self::new(secret_key);
+
+        if let Ok(path) = std::env::var("GOOSE_SERVER__USERS_FILE") {
+            config = config.with_users_file(Path::new(&path))?;
+        }
+
+        if let Ok(path) = std::env::var("GOOSE_SERVER__JWKS_FILE") {
+            let user_claim =
+                std::env::var("GOOSE_SERVER__JWT_USER_CLAIM").unwrap_or_else(|_| "sub".to_string());
+            config = config.with_jwks_file(
+                Path::new(&path),
+                std::env::var("GOOSE_SERVER__JWT_ISSUER").ok(),
+                std::env::var("GOOSE_SERVER__JWT_AUDIENCE").ok(),
+                user_claim,
+            )?;
+        }
+
+        Ok(config)
+    }
+
+    pub fn with_users_file(mut self, path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read users file {}", path.display()))?;
+        let file: UsersFile = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse users file {}", path.display()))?;
+
+        for entry in file.users {
+            let user = if entry.admin {
+                AuthUser::Admin
+            } else {
+                AuthUser::User(entry.name)
+            };
+            self.users
+                .insert(entry.token_sha256.trim().to_lowercase(), user);
+        }
+        Ok(self)
+    }
+
+    pub fn with_jwks_file(
+        mut self,
+        path: &Path,
+        issuer: Option<String>,
+        audience: Option<String>,
+        user_claim: String,
+    ) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read JWKS file {}", path.display()))?;
+        let keys: JwkSet = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JWKS file {}", path.display()))?;
+
+        self.jwt = Some(JwtVerifier {
+            keys,
+            issuer,
+            audience,
+            user_claim,
+        });
+        Ok(self)
+    }
+
+    pub fn authenticate(&self, token: &str) -> Option<AuthUser> {
+        if token == self.secret_key {
+            return Some(AuthUser::Admin);
+        }
+
+        if let Some(user) = self.users.get(&hash_token(token)) {
+            return Some(user.clone());
+        }
+
+        let jwt = self.jwt.as_ref()?;
+        match jwt.verify(token) {
+            Ok(name) => Some(AuthUser::User(name)),
+            Err(e) => {
+                tracing::debug!("Rejected bearer token: {}", e);
+                None
+            }
+        }
+    }
+}
+
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn request_token(request: &Request) -> Option<&str> {
+    let headers = request.headers();
+    headers
+        .get("X-Secret-Key")
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+}
 
 pub async fn check_token(
-    State(state): State<String>,
-    request: Request,
+    State(state): State<Arc<AuthConfig>>,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     if request.uri().path() == "/status" {
         return Ok(next.run(request).await);
     }
-    let secret_key = request
-        .headers()
-        .get("X-Secret-Key")
-        .and_then(|value| value.to_str().ok());
 
-    match secret_key {
-        Some(key) if key == state => Ok(next.run(request).await),
-        _ => Err(StatusCode::UNAUTHORIZED),
+    let user = request_token(&request)
+        .and_then(|token| state.authenticate(token))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    request.extensions_mut().insert(user);
+    Ok(next.run(request).await)
+}
+
+/// Guard for routes that change server-wide state such as provider config.
+pub async fn require_admin(
+    user: AuthUser,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if !user.is_admin() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(request).await)
+}
+
+/// Fails with 404 if the session does not exist and 403 if it belongs to someone else.
+pub async fn check_session_access(user: &AuthUser, session_id: &str) -> Result<(), StatusCode> {
+    if user.is_admin() {
+        return Ok(());
+    }
+
+    let session = SessionManager::get_session(session_id, false)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    if user.can_access(session.owner.as_deref()) {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// Where a caller's own recipes live. Named users get a private directory under the
+/// shared recipe library so they can't overwrite each other's files.
+pub fn user_recipe_dir(user: &AuthUser, library_dir: PathBuf) -> PathBuf {
+    match user {
+        AuthUser::Admin => library_dir,
+        AuthUser::User(name) => library_dir.join("users").join(sanitize_user_name(name)),
+    }
+}
+
+fn sanitize_user_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '@' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use base64::Engine;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use tower::ServiceExt;
+
+    async fn whoami(user: AuthUser) -> String {
+        match user {
+            AuthUser::Admin => "admin".to_string(),
+            AuthUser::User(name) => name,
+        }
+    }
+
+    fn app(config: AuthConfig) -> Router {
+        Router::new()
+            .route("/whoami", get(whoami))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(config),
+                check_token,
+            ))
+    }
+
+    async fn call(app: Router, header: (&str, &str)) -> (StatusCode, String) {
+        let request = http::Request::builder()
+            .uri("/whoami")
+            .header(header.0, header.1)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_secret_key_and_user_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        let users_file = dir.path().join("users.yaml");
+        std::fs::write(
+            &users_file,
+            format!(
+                "users:\n  - name: alice\n    token_sha256: {}\n",
+                hash_token("alice-token")
+            ),
+        )
+        .unwrap();
+        let config = AuthConfig::new("secret")
+            .with_users_file(&users_file)
+            .unwrap();
+        let app = app(config);
+
+        assert_eq!(
+            call(app.clone(), ("X-Secret-Key", "secret")).await,
+            (StatusCode::OK, "admin".to_string())
+        );
+        assert_eq!(
+            call(app.clone(), ("Authorization", "Bearer alice-token")).await,
+            (StatusCode::OK, "alice".to_string())
+        );
+        assert_eq!(
+            call(app, ("X-Secret-Key", "wrong")).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jwt_verified_against_jwks() {
+        let secret = b"jwks-test-signing-secret";
+        let dir = tempfile::tempdir().unwrap();
+        let jwks_file = dir.path().join("jwks.json");
+        std::fs::write(
+            &jwks_file,
+            serde_json::json!({
+                "keys": [{
+                    "kty": "oct",
+                    "kid": "test-key",
+                    "alg": "HS256",
+                    "k": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret),
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let config = AuthConfig::new("secret")
+            .with_jwks_file(
+                &jwks_file,
+                Some("https://issuer.example".to_string()),
+                None,
+                "sub".to_string(),
+            )
+            .unwrap();
+        let app = app(config);
+
+        let exp = chrono::Utc::now().timestamp() + 600;
+        let sign_with = |alg: Algorithm, iss: &str| {
+            let mut header = Header::new(alg);
+            header.kid = Some("test-key".to_string());
+            encode(
+                &header,
+                &serde_json::json!({"sub": "bob", "iss": iss, "exp": exp}),
+                &EncodingKey::from_secret(secret),
+            )
+            .unwrap()
+        };
+        let sign = |iss: &str| sign_with(Algorithm::HS256, iss);
+
+        let good = format!("Bearer {}", sign("https://issuer.example"));
+        assert_eq!(
+            call(app.clone(), ("Authorization", good.as_str())).await,
+            (StatusCode::OK, "bob".to_string())
+        );
+
+        let wrong_issuer = format!("Bearer {}", sign("https://evil.example"));
+        assert_eq!(
+            call(app.clone(), ("Authorization", wrong_issuer.as_str()))
+                .await
+                .0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let wrong_algorithm = format!(
+            "Bearer {}",
+            sign_with(Algorithm::HS512, "https://issuer.example")
+        );
+        assert_eq!(
+            call(app, ("Authorization", wrong_algorithm.as_str()))
+                .await
+                .0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_auth_user_is_rejected() {
+        let app = Router::new().route("/whoami", get(whoami));
+        let request = http::Request::builder()
+            .uri("/whoami")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_access_and_recipe_dirs() {
+        let alice = AuthUser::User("alice".to_string());
+        assert!(alice.can_access(Some("alice")));
+        assert!(!alice.can_access(Some("bob")));
+        assert!(!alice.can_access(None));
+        assert!(AuthUser::Admin.can_access(Some("bob")));
+
+        let library = PathBuf::from("/recipes");
+        assert_eq!(
+            user_recipe_dir(&AuthUser::User("../evil".to_string()), library.clone()),
+            PathBuf::from("/recipes/users/_evil")
+        );
+        assert_eq!(user_recipe_dir(&AuthUser::Admin, library.clone()), library);
     }
 }
@@ -2,7 +2,8 @@ use crate::configuration;
 use crate::state;
 use anyhow::Result;
 use axum::middleware;
-use goose_server::auth::check_token;
+use goose_server::auth::{check_token, AuthConfig};
+use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
 
@@ -42,8 +43,7 @@ pub async fn run() -> Result<()> {
         );
     }
 
-    let secret_key =
-        std::env::var("GOOSE_SERVER__SECRET_KEY").unwrap_or_else(|_| "test".to_string());
+    let auth_config = Arc::new(AuthConfig::from_env()?);
 
     let app_state = state::AppState::new().await?;
 
@@ -53,10 +53,7 @@ pub async fn run() -> Result<()> {
         .allow_headers(Any);
 
     let app = crate::routes::configure(app_state)
-        .layer(middleware::from_fn_with_state(
-            secret_key.clone(),
-            check_token,
-        ))
+        .layer(middleware::from_fn_with_state(auth_config, check_token))
         .layer(cors);
 
     let listener = tokio::net::TcpListener::bind(settings.socket_addr()).await?;
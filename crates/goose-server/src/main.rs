@@ -6,6 +6,9 @@ mod openapi;
 mod routes;
 mod state;
 
+// Routes share the library's auth types, so the extractors see what its middleware inserted
+use goose_server::auth;
+
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -326,6 +326,11 @@ derive_utoipa!(Icon as IconSchema);
     paths(
         super::routes::status::status,
         super::routes::status::diagnostics,
+        super::routes::commands::list_commands,
+        super::routes::permissions::list_tool_permissions,
+        super::routes::permissions::revoke_tool_permission,
+        super::routes::permissions::get_permission_suggestions,
+        super::routes::permissions::accept_permission_suggestion,
         super::routes::config_management::backup_config,
         super::routes::config_management::recover_config,
         super::routes::config_management::validate_config,
@@ -360,6 +365,7 @@ derive_utoipa!(Icon as IconSchema);
         super::routes::session::update_session_name,
         super::routes::session::delete_session,
         super::routes::session::export_session,
+        super::routes::session::get_session_plan,
         super::routes::session::import_session,
         super::routes::session::update_session_user_recipe_values,
         super::routes::schedule::create_schedule,
@@ -384,6 +390,8 @@ derive_utoipa!(Icon as IconSchema);
         super::routes::setup::start_tetrate_setup,
     ),
     components(schemas(
+        super::routes::status::StatusResponse,
+        goose::execution::manager::AgentPoolStats,
         super::routes::config_management::UpsertConfigQuery,
         super::routes::config_management::ConfigKeyQuery,
         super::routes::config_management::ConfigResponse,
@@ -448,6 +456,11 @@ derive_utoipa!(Icon as IconSchema);
         Conversation,
         IconSchema,
         goose::session::extension_data::ExtensionData,
+        goose::session::export::SessionExportFormat,
+        goose::session::Plan,
+        goose::session::PlanStatus,
+        goose::session::PlanStep,
+        goose::session::PlanStepStatus,
         super::routes::schedule::CreateScheduleRequest,
         super::routes::schedule::UpdateScheduleRequest,
         super::routes::schedule::KillJobResponse,
@@ -483,6 +496,17 @@ derive_utoipa!(Icon as IconSchema);
         goose::recipe::Response,
         goose::recipe::SubRecipe,
         goose::agents::types::RetryConfig,
+        goose::agents::budget::Budget,
+        super::routes::commands::ListCommandsQuery,
+        super::routes::commands::ListCommandsResponse,
+        super::routes::commands::CommandSummary,
+        super::routes::permissions::PermissionSuggestionsQuery,
+        super::routes::permissions::PermissionSuggestionsResponse,
+        goose::permission::PermissionSuggestion,
+        super::routes::permissions::ToolPermissionsResponse,
+        goose::permission::ToolPermissionSummary,
+        goose::permission::permission_store::ToolPermissionRecord,
+        goose::config::permission::PermissionRule,
         goose::agents::types::SuccessCheck,
         super::routes::agent::UpdateProviderRequest,
         super::routes::agent::GetToolsQuery,
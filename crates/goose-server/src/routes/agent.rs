@@ -1,3 +1,4 @@
+use crate::auth::{check_session_access, AuthUser};
 use crate::routes::errors::ErrorResponse;
 use crate::routes::recipe_utils::{
     apply_recipe_to_agent, build_recipe_with_parameter_values, load_recipe_by_id, validate_recipe,
@@ -14,7 +15,7 @@ use goose::config::PermissionManager;
 use goose::agents::ExtensionConfig;
 use goose::config::{Config, GooseMode};
 use goose::model::ModelConfig;
-use goose::prompt_template::render_global_file;
+use goose::prompt_template::render_session_file;
 use goose::providers::{create, create_with_named_model};
 use goose::recipe::Recipe;
 use goose::recipe_deeplink;
@@ -97,6 +98,7 @@ pub struct RemoveExtensionRequest {
 )]
 async fn start_agent(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(payload): Json<StartAgentRequest>,
 ) -> Result<Json<Session>, ErrorResponse> {
     let StartAgentRequest {
@@ -118,7 +120,7 @@ async fn start_agent(
             }
         }
     } else if let Some(id) = recipe_id {
-        match load_recipe_by_id(state.as_ref(), &id).await {
+        match load_recipe_by_id(state.as_ref(), &user, &id).await {
             Ok(recipe) => Some(recipe),
             Err(err) => return Err(err),
         }
@@ -149,6 +151,18 @@ async fn start_agent(
                 }
             })?;
 
+    if let Some(owner) = user.owner() {
+        SessionManager::update_session(&session.id)
+            .owner(Some(owner.clone()))
+            .apply()
+            .await
+            .map_err(|err| {
+                error!("Failed to set session owner: {}", err);
+                ErrorResponse::internal(format!("Failed to set session owner: {}", err))
+            })?;
+        session.owner = Some(owner);
+    }
+
     if let Some(recipe) = original_recipe {
         SessionManager::update_session(&session.id)
             .recipe(Some(recipe))
@@ -189,8 +203,15 @@ async fn start_agent(
 )]
 async fn resume_agent(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(payload): Json<ResumeAgentRequest>,
 ) -> Result<Json<Session>, ErrorResponse> {
+    check_session_access(&user, &payload.session_id)
+        .await
+        .map_err(|status| ErrorResponse {
+            message: format!("Cannot access session {}", payload.session_id),
+            status,
+        })?;
     let session = SessionManager::get_session(&payload.session_id, true)
         .await
         .map_err(|err| {
@@ -281,8 +302,15 @@ async fn resume_agent(
 )]
 async fn update_from_session(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(payload): Json<UpdateFromSessionRequest>,
 ) -> Result<StatusCode, ErrorResponse> {
+    check_session_access(&user, &payload.session_id)
+        .await
+        .map_err(|status| ErrorResponse {
+            message: format!("Cannot access session {}", payload.session_id),
+            status,
+        })?;
     let agent = state
         .get_agent_for_route(payload.session_id.clone())
         .await
@@ -297,8 +325,8 @@ async fn update_from_session(
             status: StatusCode::INTERNAL_SERVER_ERROR,
         })?;
     let context: HashMap<&str, Value> = HashMap::new();
-    let desktop_prompt =
-        render_global_file("desktop_prompt.md", &context).expect("Prompt should render");
+    let desktop_prompt = render_session_file(&session.working_dir, "desktop_prompt.md", &context)
+        .expect("Prompt should render");
     let mut update_prompt = desktop_prompt;
     if let Some(recipe) = session.recipe {
         match build_recipe_with_parameter_values(
@@ -308,7 +336,9 @@ async fn update_from_session(
         .await
         {
             Ok(Some(recipe)) => {
-                if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, true).await {
+                if let Some(prompt) =
+                    apply_recipe_to_agent(&agent, &recipe, true, &session.working_dir).await
+                {
                     update_prompt = prompt;
                 }
             }
@@ -344,8 +374,10 @@ async fn update_from_session(
 )]
 async fn get_tools(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Query(query): Query<GetToolsQuery>,
 ) -> Result<Json<Vec<ToolInfo>>, StatusCode> {
+    check_session_access(&user, &query.session_id).await?;
     let config = Config::global();
     let goose_mode = config.get_goose_mode().unwrap_or(GooseMode::Auto);
     let agent = state.get_agent_for_route(query.session_id).await?;
@@ -398,8 +430,10 @@ async fn get_tools(
 )]
 async fn update_agent_provider(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(payload): Json<UpdateProviderRequest>,
 ) -> Result<StatusCode, StatusCode> {
+    check_session_access(&user, &payload.session_id).await?;
     let agent = state
         .get_agent_for_route(payload.session_id.clone())
         .await?;
@@ -444,8 +478,10 @@ async fn update_agent_provider(
 )]
 async fn update_router_tool_selector(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(payload): Json<UpdateRouterToolSelectorRequest>,
 ) -> Result<Json<String>, StatusCode> {
+    check_session_access(&user, &payload.session_id).await?;
     let agent = state.get_agent_for_route(payload.session_id).await?;
     agent
         .update_router_tool_selector(None, Some(true))
@@ -473,8 +509,15 @@ async fn update_router_tool_selector(
 )]
 async fn agent_add_extension(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<AddExtensionRequest>,
 ) -> Result<StatusCode, ErrorResponse> {
+    check_session_access(&user, &request.session_id)
+        .await
+        .map_err(|status| ErrorResponse {
+            message: format!("Cannot access session {}", request.session_id),
+            status,
+        })?;
     if cfg!(target_os = "windows") {
         if let ExtensionConfig::Stdio { cmd, .. } = &request.config {
             if cmd.ends_with("npx.cmd") || cmd.ends_with("npx") {
@@ -536,8 +579,15 @@ async fn agent_add_extension(
 )]
 async fn agent_remove_extension(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<RemoveExtensionRequest>,
 ) -> Result<StatusCode, ErrorResponse> {
+    check_session_access(&user, &request.session_id)
+        .await
+        .map_err(|status| ErrorResponse {
+            message: format!("Cannot access session {}", request.session_id),
+            status,
+        })?;
     let agent = state.get_agent(request.session_id).await?;
     agent.remove_extension(&request.name).await?;
     Ok(StatusCode::OK)
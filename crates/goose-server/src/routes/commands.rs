@@ -0,0 +1,86 @@
+use crate::auth::{check_session_access, AuthUser};
+use crate::state::AppState;
+use axum::extract::Query;
+use axum::routing::get;
+use axum::{http::StatusCode, Json, Router};
+use goose::session::SessionManager;
+use goose::slash_commands::{discover_commands, SlashCommand};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct ListCommandsQuery {
+    /// Include the commands of this session's project; otherwise only user commands
+    /// and those of the server's working directory are listed
+    session_id: Option<String>,
+}
+
+/// What a client needs to offer a command. The prompt, extensions, settings and
+/// file path stay on the server, since extensions can carry environment values.
+#[derive(Serialize, ToSchema)]
+pub struct CommandSummary {
+    /// Name without the leading slash
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Whether the command uses what follows it as `{{args}}`
+    takes_args: bool,
+}
+
+impl From<SlashCommand> for CommandSummary {
+    fn from(command: SlashCommand) -> Self {
+        Self {
+            takes_args: command.uses_args().unwrap_or(false),
+            name: command.name,
+            description: command.description,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListCommandsResponse {
+    commands: Vec<CommandSummary>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/commands",
+    params(ListCommandsQuery),
+    responses(
+        (status = 200, description = "Slash commands defined in prompt files", body = ListCommandsResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Session belongs to another user"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Commands"
+)]
+async fn list_commands(
+    user: AuthUser,
+    Query(query): Query<ListCommandsQuery>,
+) -> Result<Json<ListCommandsResponse>, StatusCode> {
+    let working_dir = match &query.session_id {
+        Some(session_id) => {
+            check_session_access(&user, session_id).await?;
+            SessionManager::get_session(session_id, false)
+                .await
+                .map_err(|_| StatusCode::NOT_FOUND)?
+                .working_dir
+        }
+        None => std::env::current_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    };
+
+    Ok(Json(ListCommandsResponse {
+        commands: discover_commands(&working_dir)
+            .into_iter()
+            .map(CommandSummary::from)
+            .collect(),
+    }))
+}
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/commands", get(list_commands))
+        .with_state(state)
+}
@@ -1,5 +1,7 @@
+use crate::auth::require_admin;
 use crate::routes::utils::check_provider_configured;
 use crate::state::AppState;
+use axum::middleware;
 use axum::routing::put;
 use axum::{
     extract::Path,
@@ -735,21 +737,15 @@ pub async fn update_custom_provider(
 }
 
 pub fn routes(state: Arc<AppState>) -> Router {
-    Router::new()
-        .route("/config", get(read_all_config))
+    // Config is shared by every user of the server, so only the admin may change it
+    let admin_routes = Router::new()
         .route("/config/upsert", post(upsert_config))
         .route("/config/remove", post(remove_config))
-        .route("/config/read", post(read_config))
-        .route("/config/extensions", get(get_extensions))
         .route("/config/extensions", post(add_extension))
         .route("/config/extensions/{name}", delete(remove_extension))
-        .route("/config/providers", get(providers))
-        .route("/config/providers/{name}/models", get(get_provider_models))
-        .route("/config/pricing", post(get_pricing))
         .route("/config/init", post(init_config))
         .route("/config/backup", post(backup_config))
         .route("/config/recover", post(recover_config))
-        .route("/config/validate", get(validate_config))
         .route("/config/permissions", post(upsert_permissions))
         .route("/config/custom-providers", post(create_custom_provider))
         .route(
@@ -757,7 +753,18 @@ pub fn routes(state: Arc<AppState>) -> Router {
             delete(remove_custom_provider),
         )
         .route("/config/custom-providers/{id}", put(update_custom_provider))
+        .route_layer(middleware::from_fn(require_admin));
+
+    Router::new()
+        .route("/config", get(read_all_config))
+        .route("/config/read", post(read_config))
+        .route("/config/extensions", get(get_extensions))
+        .route("/config/providers", get(providers))
+        .route("/config/providers/{name}/models", get(get_provider_models))
+        .route("/config/pricing", post(get_pricing))
+        .route("/config/validate", get(validate_config))
         .route("/config/custom-providers/{id}", get(get_custom_provider))
+        .merge(admin_routes)
         .with_state(state)
 }
 
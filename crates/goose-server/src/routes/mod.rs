@@ -1,7 +1,9 @@
 pub mod agent;
 pub mod audio;
+pub mod commands;
 pub mod config_management;
 pub mod errors;
+pub mod permissions;
 pub mod recipe;
 pub mod recipe_utils;
 pub mod reply;
@@ -18,11 +20,13 @@ use axum::Router;
 // Function to configure all routes
 pub fn configure(state: Arc<crate::state::AppState>) -> Router {
     Router::new()
-        .merge(status::routes())
+        .merge(status::routes(state.clone()))
         .merge(reply::routes(state.clone()))
         .merge(agent::routes(state.clone()))
         .merge(audio::routes(state.clone()))
+        .merge(commands::routes(state.clone()))
         .merge(config_management::routes(state.clone()))
+        .merge(permissions::routes(state.clone()))
         .merge(recipe::routes(state.clone()))
         .merge(session::routes(state.clone()))
         .merge(schedule::routes(state.clone()))
@@ -0,0 +1,134 @@
+use crate::auth::require_admin;
+use crate::state::AppState;
+use axum::extract::{Path, Query};
+use axum::middleware;
+use axum::routing::{delete, get, post};
+use axum::{http::StatusCode, Json, Router};
+use goose::config::PermissionManager;
+use goose::permission::permission_store::ToolPermissionRecord;
+use goose::permission::permission_suggestions::DEFAULT_MIN_APPROVALS;
+use goose::permission::{
+    suggest_permissions, summarize_tool_permissions, PermissionSuggestion, ToolPermissionStore,
+    ToolPermissionSummary,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct ToolPermissionsResponse {
+    permissions: Vec<ToolPermissionSummary>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/permissions",
+    responses(
+        (status = 200, description = "Stored permission levels, allow rules and recorded decisions for each tool", body = ToolPermissionsResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Only the admin may read stored permissions"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Permissions"
+)]
+async fn list_tool_permissions() -> Result<Json<ToolPermissionsResponse>, StatusCode> {
+    let store = ToolPermissionStore::load().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let permissions = summarize_tool_permissions(&store, &PermissionManager::default());
+    Ok(Json(ToolPermissionsResponse { permissions }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/permissions/{tool}",
+    params(
+        ("tool" = String, Path, description = "Name of the tool, e.g. developer__shell")
+    ),
+    responses(
+        (status = 200, description = "Permission level, allow rules and recorded decisions removed", body = String),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Only the admin may change permissions"),
+        (status = 404, description = "No permissions stored for the tool"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Permissions"
+)]
+async fn revoke_tool_permission(Path(tool): Path<String>) -> Result<Json<String>, StatusCode> {
+    let removed_level = PermissionManager::default().remove_tool(&tool);
+    let removed_decisions = ToolPermissionStore::load()
+        .and_then(|mut store| store.remove_tool(&tool))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !removed_level && removed_decisions == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(format!("Revoked permissions for {}", tool)))
+}
+
+#[derive(Deserialize, ToSchema, utoipa::IntoParams)]
+pub struct PermissionSuggestionsQuery {
+    /// Approvals of similar calls needed before a rule is suggested
+    min_approvals: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PermissionSuggestionsResponse {
+    suggestions: Vec<PermissionSuggestion>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/permissions/suggestions",
+    params(PermissionSuggestionsQuery),
+    responses(
+        (status = 200, description = "Allow rules learned from approved tool calls", body = PermissionSuggestionsResponse),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Only the admin may read stored permissions"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Permissions"
+)]
+async fn get_permission_suggestions(
+    Query(query): Query<PermissionSuggestionsQuery>,
+) -> Result<Json<PermissionSuggestionsResponse>, StatusCode> {
+    let store = ToolPermissionStore::load().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let records: Vec<ToolPermissionRecord> = store.records().cloned().collect();
+    let suggestions = suggest_permissions(
+        &records,
+        &PermissionManager::default(),
+        query.min_approvals.unwrap_or(DEFAULT_MIN_APPROVALS),
+    );
+    Ok(Json(PermissionSuggestionsResponse { suggestions }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/permissions/suggestions/accept",
+    request_body = PermissionSuggestion,
+    responses(
+        (status = 200, description = "Suggestion saved as a user permission", body = String),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Only the admin may change permissions")
+    ),
+    tag = "Permissions"
+)]
+async fn accept_permission_suggestion(
+    Json(suggestion): Json<PermissionSuggestion>,
+) -> Result<Json<String>, StatusCode> {
+    let mut permission_manager = PermissionManager::default();
+    suggestion.accept(&mut permission_manager);
+    Ok(Json(format!("Saved permission for {}", suggestion.tool)))
+}
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    // Permissions are shared by every user of the server and recorded decisions carry the
+    // arguments of everyone's tool calls, so only the admin may read or change them
+    Router::new()
+        .route("/permissions", get(list_tool_permissions))
+        .route("/permissions/{tool}", delete(revoke_tool_permission))
+        .route("/permissions/suggestions", get(get_permission_suggestions))
+        .route(
+            "/permissions/suggestions/accept",
+            post(accept_permission_suggestion),
+        )
+        .route_layer(middleware::from_fn(require_admin))
+        .with_state(state)
+}
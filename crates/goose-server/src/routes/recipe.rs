@@ -36,10 +36,11 @@ fn clean_data_error(err: &axum::extract::rejection::JsonDataError) -> String {
         .unwrap_or_else(|| message.to_string())
 }
 
+use crate::auth::{check_session_access, AuthUser};
 use crate::routes::errors::ErrorResponse;
 use crate::routes::recipe_utils::{
-    get_all_recipes_manifests, get_recipe_file_path_by_id, short_id_from_path, validate_recipe,
-    RecipeValidationError,
+    get_all_recipes_manifests, get_recipe_file_path_by_id, recipe_save_dir, recipe_writable_by,
+    short_id_from_path, validate_recipe, RecipeValidationError,
 };
 use crate::state::AppState;
 
@@ -146,12 +147,14 @@ pub struct ListRecipeResponse {
 )]
 async fn create_recipe(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<CreateRecipeRequest>,
 ) -> Result<Json<CreateRecipeResponse>, StatusCode> {
     tracing::info!(
         "Recipe creation request received for session_id: {}",
         request.session_id
     );
+    check_session_access(&user, &request.session_id).await?;
 
     let session = match SessionManager::get_session(&request.session_id, true).await {
         Ok(session) => session,
@@ -280,8 +283,9 @@ async fn scan_recipe(
 )]
 async fn list_recipes(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
 ) -> Result<Json<ListRecipeResponse>, StatusCode> {
-    let recipe_manifest_with_paths = get_all_recipes_manifests().unwrap_or_default();
+    let recipe_manifest_with_paths = get_all_recipes_manifests(&user).unwrap_or_default();
     let mut recipe_file_hash_map = HashMap::new();
     let recipe_manifest_responses = recipe_manifest_with_paths
         .iter()
@@ -317,13 +321,18 @@ async fn list_recipes(
 )]
 async fn delete_recipe(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<DeleteRecipeRequest>,
 ) -> StatusCode {
-    let file_path = match get_recipe_file_path_by_id(state.as_ref(), &request.id).await {
+    let file_path = match get_recipe_file_path_by_id(state.as_ref(), &user, &request.id).await {
         Ok(path) => path,
         Err(err) => return err.status,
     };
 
+    if !recipe_writable_by(&user, &file_path) {
+        return StatusCode::FORBIDDEN;
+    }
+
     if fs::remove_file(file_path).is_err() {
         return StatusCode::INTERNAL_SERVER_ERROR;
     }
@@ -346,6 +355,7 @@ async fn delete_recipe(
 )]
 async fn save_recipe(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     payload: Result<Json<Value>, JsonRejection>,
 ) -> Result<Json<SaveRecipeResponse>, ErrorResponse> {
     let Json(raw_json) = payload.map_err(json_rejection_to_error_response)?;
@@ -353,11 +363,20 @@ async fn save_recipe(
     ensure_recipe_valid(&request.recipe)?;
 
     let file_path = match request.id.as_ref() {
-        Some(id) => Some(get_recipe_file_path_by_id(state.as_ref(), id).await?),
+        Some(id) => Some(get_recipe_file_path_by_id(state.as_ref(), &user, id).await?),
         None => None,
     };
 
-    match local_recipes::save_recipe_to_file(request.recipe, file_path.clone()) {
+    if let Some(path) = &file_path {
+        if !recipe_writable_by(&user, path) {
+            return Err(ErrorResponse {
+                message: "Recipe belongs to another user".to_string(),
+                status: StatusCode::FORBIDDEN,
+            });
+        }
+    }
+
+    match local_recipes::save_recipe_to_dir(request.recipe, file_path, &recipe_save_dir(&user)) {
         Ok(save_file_path) => Ok(Json(SaveRecipeResponse {
             id: short_id_from_path(&save_file_path.display().to_string()),
         })),
@@ -2,18 +2,21 @@ use std::collections::HashMap;
 use std::fs;
 use std::hash::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
 use axum::http::StatusCode;
 
+use crate::auth::{user_recipe_dir, AuthUser};
 use crate::routes::errors::ErrorResponse;
 use crate::state::AppState;
 use goose::agents::Agent;
-use goose::prompt_template::render_global_file;
+use goose::prompt_template::render_session_file;
 use goose::recipe::build_recipe::{build_recipe_from_template, RecipeError};
-use goose::recipe::local_recipes::{get_recipe_library_dir, list_local_recipes};
+use goose::recipe::local_recipes::{
+    get_recipe_library_dir, list_local_recipes, scan_directory_for_recipes,
+};
 use goose::recipe::validate_recipe::validate_recipe_template_from_content;
 use goose::recipe::Recipe;
 use serde_json::Value;
@@ -39,8 +42,27 @@ pub fn short_id_from_path(path: &str) -> String {
     format!("{:016x}", h)
 }
 
-pub fn get_all_recipes_manifests() -> Result<Vec<RecipeManifestWithPath>> {
-    let recipes_with_path = list_local_recipes()?;
+/// Directory new recipes saved by `user` go to.
+pub fn recipe_save_dir(user: &AuthUser) -> PathBuf {
+    user_recipe_dir(user, get_recipe_library_dir(true))
+}
+
+/// Shared recipes are visible to everyone; a named user's own directory only to them.
+pub fn recipe_visible_to(user: &AuthUser, path: &Path) -> bool {
+    let users_dir = get_recipe_library_dir(true).join("users");
+    user.is_admin() || !path.starts_with(users_dir) || path.starts_with(recipe_save_dir(user))
+}
+
+/// Named users may only change recipes in their own directory.
+pub fn recipe_writable_by(user: &AuthUser, path: &Path) -> bool {
+    user.is_admin() || path.starts_with(recipe_save_dir(user))
+}
+
+pub fn get_all_recipes_manifests(user: &AuthUser) -> Result<Vec<RecipeManifestWithPath>> {
+    let mut recipes_with_path = list_local_recipes()?;
+    if !user.is_admin() {
+        recipes_with_path.extend(scan_directory_for_recipes(&recipe_save_dir(user))?);
+    }
     let mut recipe_manifests_with_path = Vec::new();
     for (file_path, recipe) in recipes_with_path {
         let Ok(last_modified) = fs::metadata(file_path.clone())
@@ -86,6 +108,7 @@ pub fn validate_recipe(recipe: &Recipe) -> Result<(), RecipeValidationError> {
 
 pub async fn get_recipe_file_path_by_id(
     state: &AppState,
+    user: &AuthUser,
     id: &str,
 ) -> Result<PathBuf, ErrorResponse> {
     let cached_path = {
@@ -93,11 +116,11 @@ pub async fn get_recipe_file_path_by_id(
         map.get(id).cloned()
     };
 
-    if let Some(path) = cached_path {
+    if let Some(path) = cached_path.filter(|path| recipe_visible_to(user, path)) {
         return Ok(path);
     }
 
-    let recipe_manifest_with_paths = get_all_recipes_manifests().unwrap_or_default();
+    let recipe_manifest_with_paths = get_all_recipes_manifests(user).unwrap_or_default();
     let mut recipe_file_hash_map = HashMap::new();
     let mut resolved_path: Option<PathBuf> = None;
 
@@ -119,8 +142,12 @@ pub async fn get_recipe_file_path_by_id(
     })
 }
 
-pub async fn load_recipe_by_id(state: &AppState, id: &str) -> Result<Recipe, ErrorResponse> {
-    let path = get_recipe_file_path_by_id(state, id).await?;
+pub async fn load_recipe_by_id(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<Recipe, ErrorResponse> {
+    let path = get_recipe_file_path_by_id(state, user, id).await?;
 
     Recipe::from_file_path(&path).map_err(|err| ErrorResponse {
         message: format!("Failed to load recipe: {}", err),
@@ -155,6 +182,7 @@ pub async fn apply_recipe_to_agent(
     agent: &Arc<Agent>,
     recipe: &Recipe,
     include_final_output_tool: bool,
+    working_dir: &Path,
 ) -> Option<String> {
     if let Some(sub_recipes) = &recipe.sub_recipes {
         agent.add_sub_recipes(sub_recipes.clone()).await;
@@ -169,6 +197,7 @@ pub async fn apply_recipe_to_agent(
     recipe.instructions.as_ref().map(|instructions| {
         let mut context: HashMap<&str, Value> = HashMap::new();
         context.insert("recipe_instructions", Value::String(instructions.clone()));
-        render_global_file("desktop_recipe_instruction.md", &context).expect("Prompt should render")
+        render_session_file(working_dir, "desktop_recipe_instruction.md", &context)
+            .expect("Prompt should render")
     })
 }
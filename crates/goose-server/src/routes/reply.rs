@@ -1,3 +1,4 @@
+use crate::auth::{check_session_access, AuthUser};
 use crate::state::AppState;
 use axum::{
     extract::{DefaultBodyLimit, State},
@@ -182,8 +183,11 @@ async fn stream_event(
 )]
 pub async fn reply(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<ChatRequest>,
 ) -> Result<SseResponse, StatusCode> {
+    check_session_access(&user, &request.session_id).await?;
+
     let session_start = std::time::Instant::now();
 
     tracing::info!(
@@ -260,6 +264,7 @@ pub async fn reply(
             schedule_id: session.schedule_id.clone(),
             max_turns: None,
             retry_config: None,
+            budget: None,
         };
 
         let user_message = match messages.last() {
@@ -355,6 +360,10 @@ pub async fn reply(
                         Ok(Some(Ok(AgentEvent::ModelChange { model, mode }))) => {
                             stream_event(MessageEvent::ModelChange { model, mode }, &tx, &cancel_token).await;
                         }
+                        Ok(Some(Ok(AgentEvent::BudgetExceeded(limit)))) => {
+                            // The agent already sent an assistant message explaining the stop
+                            tracing::info!("Reply stopped at budget limit: {}", limit);
+                        }
                         Ok(Some(Ok(AgentEvent::McpNotification((request_id, n))))) => {
                             stream_event(MessageEvent::Notification{
                                 request_id: request_id.clone(),
@@ -469,13 +478,20 @@ fn default_principal_type() -> PrincipalType {
     responses(
         (status = 200, description = "Permission action is confirmed", body = Value),
         (status = 401, description = "Unauthorized - invalid secret key"),
+        (status = 403, description = "Only admins can always allow a tool"),
         (status = 500, description = "Internal server error")
     )
 )]
 pub async fn confirm_permission(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(request): Json<PermissionConfirmationRequest>,
 ) -> Result<Json<Value>, StatusCode> {
+    // Always allow is stored for every user of this goosed, so only admins may grant it
+    if request.action == "always_allow" && !user.is_admin() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    check_session_access(&user, &request.session_id).await?;
     let agent = state.get_agent_for_route(request.session_id).await?;
     let permission = match request.action.as_str() {
         "always_allow" => Permission::AlwaysAllow,
@@ -512,7 +528,8 @@ mod tests {
 
     mod integration_tests {
         use super::*;
-        use axum::{body::Body, http::Request};
+        use crate::auth::{check_token, hash_token, AuthConfig};
+        use axum::{body::Body, http::Request, middleware, Extension};
         use goose::conversation::message::Message;
         use tower::ServiceExt;
 
@@ -520,7 +537,7 @@ mod tests {
         async fn test_reply_endpoint() {
             let state = AppState::new().await.unwrap();
 
-            let app = routes(state);
+            let app = routes(state).layer(Extension(AuthUser::Admin));
 
             let request = Request::builder()
                 .uri("/reply")
@@ -542,5 +559,44 @@ mod tests {
 
             assert_eq!(response.status(), StatusCode::OK);
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn test_non_admin_cannot_always_allow() {
+            let dir = tempfile::tempdir().unwrap();
+            let users_file = dir.path().join("users.yaml");
+            std::fs::write(
+                &users_file,
+                format!(
+                    "users:\n  - name: alice\n    token_sha256: {}\n",
+                    hash_token("alice-token")
+                ),
+            )
+            .unwrap();
+            let auth = AuthConfig::new("test-secret")
+                .with_users_file(&users_file)
+                .unwrap();
+            let state = AppState::new().await.unwrap();
+            let app =
+                routes(state).layer(middleware::from_fn_with_state(Arc::new(auth), check_token));
+
+            let request = Request::builder()
+                .uri("/confirm")
+                .method("POST")
+                .header("content-type", "application/json")
+                .header("Authorization", "Bearer alice-token")
+                .body(Body::from(
+                    serde_json::json!({
+                        "id": "tool-request",
+                        "action": "always_allow",
+                        "session_id": "someone-elses-session",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+
+            let response = app.oneshot(request).await.unwrap();
+
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        }
     }
 }
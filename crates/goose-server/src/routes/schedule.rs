@@ -8,7 +8,9 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::auth::AuthUser;
 use crate::state::AppState;
+use goose::agents::budget::Budget;
 use goose::scheduler::ScheduledJob;
 
 #[derive(Deserialize, Serialize, utoipa::ToSchema)]
@@ -18,6 +20,8 @@ pub struct CreateScheduleRequest {
     cron: String,
     #[serde(default)]
     execution_mode: Option<String>, // "foreground" or "background"
+    #[serde(default)]
+    budget: Option<Budget>,
 }
 
 #[derive(Deserialize, Serialize, utoipa::ToSchema)]
@@ -95,6 +99,7 @@ pub struct SessionDisplayInfo {
 #[axum::debug_handler]
 async fn create_schedule(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(req): Json<CreateScheduleRequest>,
 ) -> Result<Json<ScheduledJob>, StatusCode> {
     let scheduler = state
@@ -115,6 +120,8 @@ async fn create_schedule(
         paused: false,
         current_session_id: None,
         process_start_time: None,
+        owner: user.owner(),
+        budget: req.budget,
     };
     scheduler
         .add_scheduled_job(job.clone())
@@ -144,6 +151,7 @@ async fn create_schedule(
 #[axum::debug_handler]
 async fn list_schedules(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
 ) -> Result<Json<ListSchedulesResponse>, StatusCode> {
     let scheduler = state
         .scheduler()
@@ -151,10 +159,16 @@ async fn list_schedules(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     tracing::info!("Server: Calling scheduler.list_scheduled_jobs()");
-    let jobs = scheduler.list_scheduled_jobs().await.map_err(|e| {
-        eprintln!("Error listing schedules: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let jobs = scheduler
+        .list_scheduled_jobs()
+        .await
+        .map_err(|e| {
+            eprintln!("Error listing schedules: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .into_iter()
+        .filter(|job| user.can_access(job.owner.as_deref()))
+        .collect();
     Ok(Json(ListSchedulesResponse { jobs }))
 }
 
@@ -174,8 +188,11 @@ async fn list_schedules(
 #[axum::debug_handler]
 async fn delete_schedule(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -206,8 +223,11 @@ async fn delete_schedule(
 #[axum::debug_handler]
 async fn run_now_handler(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<RunNowResponse>, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -299,9 +319,12 @@ async fn run_now_handler(
 #[axum::debug_handler]
 async fn sessions_handler(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(schedule_id_param): Path<String>, // Renamed to avoid confusion with session_id
     Query(query_params): Query<SessionsQuery>,
 ) -> Result<Json<Vec<SessionDisplayInfo>>, StatusCode> {
+    check_schedule_access(&state, &user, &schedule_id_param).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -358,8 +381,11 @@ async fn sessions_handler(
 #[axum::debug_handler]
 async fn pause_schedule(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -392,8 +418,11 @@ async fn pause_schedule(
 #[axum::debug_handler]
 async fn unpause_schedule(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -427,9 +456,12 @@ async fn unpause_schedule(
 #[axum::debug_handler]
 async fn update_schedule(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
     Json(req): Json<UpdateScheduleRequest>,
 ) -> Result<Json<ScheduledJob>, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -472,8 +504,11 @@ async fn update_schedule(
 #[axum::debug_handler]
 pub async fn kill_running_job(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<KillJobResponse>, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -509,8 +544,11 @@ pub async fn kill_running_job(
 #[axum::debug_handler]
 pub async fn inspect_running_job(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(id): Path<String>,
 ) -> Result<Json<InspectJobResponse>, StatusCode> {
+    check_schedule_access(&state, &user, &id).await?;
+
     let scheduler = state
         .scheduler()
         .await
@@ -543,6 +581,32 @@ pub async fn inspect_running_job(
     }
 }
 
+/// Named users may only see and drive the schedules they created.
+async fn check_schedule_access(
+    state: &AppState,
+    user: &AuthUser,
+    id: &str,
+) -> Result<(), StatusCode> {
+    if user.is_admin() {
+        return Ok(());
+    }
+
+    let scheduler = state
+        .scheduler()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let jobs = scheduler
+        .list_scheduled_jobs()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match jobs.iter().find(|job| job.id == id) {
+        Some(job) if user.can_access(job.owner.as_deref()) => Ok(()),
+        Some(_) => Err(StatusCode::FORBIDDEN),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
 pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/schedule/create", post(create_schedule))
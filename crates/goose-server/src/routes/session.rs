@@ -1,19 +1,23 @@
+use crate::auth::{check_session_access, AuthUser};
 use crate::routes::errors::ErrorResponse;
 use crate::routes::recipe_utils::{apply_recipe_to_agent, build_recipe_with_parameter_values};
 use crate::state::AppState;
 use axum::extract::State;
 use axum::routing::post;
 use axum::{
-    extract::Path,
+    extract::{Path, Query},
     http::StatusCode,
     routing::{delete, get, put},
     Json, Router,
 };
 use goose::recipe::Recipe;
+use goose::session::export::{self, SessionExportFormat};
+use goose::session::plan::load_plan;
 use goose::session::session_manager::SessionInsights;
-use goose::session::{Session, SessionManager};
+use goose::session::{Plan, Session, SessionManager};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use utoipa::ToSchema;
 
@@ -47,6 +51,17 @@ pub struct UpdateSessionUserRecipeValuesResponse {
 #[serde(rename_all = "camelCase")]
 pub struct ImportSessionRequest {
     json: String,
+    // `json` for a goose session export, `jsonl` for an OpenAI chat-completions transcript
+    #[serde(default)]
+    format: SessionExportFormat,
+    /// Working directory for sessions imported from a transcript, which carries none
+    working_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ExportSessionQuery {
+    #[serde(default)]
+    format: SessionExportFormat,
 }
 
 const MAX_NAME_LENGTH: usize = 200;
@@ -64,10 +79,13 @@ const MAX_NAME_LENGTH: usize = 200;
     ),
     tag = "Session Management"
 )]
-async fn list_sessions() -> Result<Json<SessionListResponse>, StatusCode> {
+async fn list_sessions(user: AuthUser) -> Result<Json<SessionListResponse>, StatusCode> {
     let sessions = SessionManager::list_sessions()
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .filter(|session| user.can_access(session.owner.as_deref()))
+        .collect();
 
     Ok(Json(SessionListResponse { sessions }))
 }
@@ -81,6 +99,7 @@ async fn list_sessions() -> Result<Json<SessionListResponse>, StatusCode> {
     responses(
         (status = 200, description = "Session history retrieved successfully", body = Session),
         (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Session belongs to another user"),
         (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -89,13 +108,48 @@ async fn list_sessions() -> Result<Json<SessionListResponse>, StatusCode> {
     ),
     tag = "Session Management"
 )]
-async fn get_session(Path(session_id): Path<String>) -> Result<Json<Session>, StatusCode> {
+async fn get_session(
+    user: AuthUser,
+    Path(session_id): Path<String>,
+) -> Result<Json<Session>, StatusCode> {
+    check_session_access(&user, &session_id).await?;
     let session = SessionManager::get_session(&session_id, true)
         .await
         .map_err(|_| StatusCode::NOT_FOUND)?;
 
     Ok(Json(session))
 }
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}/plan",
+    params(
+        ("session_id" = String, Path, description = "Unique identifier for the session")
+    ),
+    responses(
+        (status = 200, description = "Plan made with /plan in the session, with the status of each step", body = Plan),
+        (status = 401, description = "Unauthorized - Invalid or missing API key"),
+        (status = 403, description = "Session belongs to another user"),
+        (status = 404, description = "Session not found or has no plan"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    tag = "Session Management"
+)]
+async fn get_session_plan(
+    user: AuthUser,
+    Path(session_id): Path<String>,
+) -> Result<Json<Plan>, StatusCode> {
+    check_session_access(&user, &session_id).await?;
+    let plan = load_plan(&session_id)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(plan))
+}
+
 #[utoipa::path(
     get,
     path = "/sessions/insights",
@@ -109,8 +163,8 @@ async fn get_session(Path(session_id): Path<String>) -> Result<Json<Session>, St
     ),
     tag = "Session Management"
 )]
-async fn get_session_insights() -> Result<Json<SessionInsights>, StatusCode> {
-    let insights = SessionManager::get_insights()
+async fn get_session_insights(user: AuthUser) -> Result<Json<SessionInsights>, StatusCode> {
+    let insights = SessionManager::get_insights(user.owner().as_deref())
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Json(insights))
@@ -136,9 +190,11 @@ async fn get_session_insights() -> Result<Json<SessionInsights>, StatusCode> {
     tag = "Session Management"
 )]
 async fn update_session_name(
+    user: AuthUser,
     Path(session_id): Path<String>,
     Json(request): Json<UpdateSessionNameRequest>,
 ) -> Result<StatusCode, StatusCode> {
+    check_session_access(&user, &session_id).await?;
     let name = request.name.trim();
     if name.is_empty() {
         return Err(StatusCode::BAD_REQUEST);
@@ -177,9 +233,17 @@ async fn update_session_name(
 // Update session user recipe parameter values
 async fn update_session_user_recipe_values(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Path(session_id): Path<String>,
     Json(request): Json<UpdateSessionUserRecipeValuesRequest>,
 ) -> Result<Json<UpdateSessionUserRecipeValuesResponse>, ErrorResponse> {
+    check_session_access(&user, &session_id)
+        .await
+        .map_err(|status| ErrorResponse {
+            message: format!("Cannot access session {}", session_id),
+            status,
+        })?;
+
     SessionManager::update_session(&session_id)
         .user_recipe_values(Some(request.user_recipe_values))
         .apply()
@@ -210,7 +274,9 @@ async fn update_session_user_recipe_values(
                     message: format!("Failed to get agent: {}", status),
                     status,
                 })?;
-            if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, false).await {
+            if let Some(prompt) =
+                apply_recipe_to_agent(&agent, &recipe, false, &session.working_dir).await
+            {
                 agent.extend_system_prompt(prompt).await;
             }
             Ok(Json(UpdateSessionUserRecipeValuesResponse { recipe }))
@@ -243,7 +309,12 @@ async fn update_session_user_recipe_values(
     ),
     tag = "Session Management"
 )]
-async fn delete_session(Path(session_id): Path<String>) -> Result<StatusCode, StatusCode> {
+async fn delete_session(
+    user: AuthUser,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    check_session_access(&user, &session_id).await?;
+
     SessionManager::delete_session(&session_id)
         .await
         .map_err(|e| {
@@ -261,10 +332,12 @@ async fn delete_session(Path(session_id): Path<String>) -> Result<StatusCode, St
     get,
     path = "/sessions/{session_id}/export",
     params(
-        ("session_id" = String, Path, description = "Unique identifier for the session")
+        ("session_id" = String, Path, description = "Unique identifier for the session"),
+        ("format" = Option<SessionExportFormat>, Query, description = "Export format, json by default")
     ),
     responses(
         (status = 200, description = "Session exported successfully", body = String),
+        (status = 400, description = "Session cannot be exported in the requested format"),
         (status = 401, description = "Unauthorized - Invalid or missing API key"),
         (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
@@ -274,10 +347,24 @@ async fn delete_session(Path(session_id): Path<String>) -> Result<StatusCode, St
     ),
     tag = "Session Management"
 )]
-async fn export_session(Path(session_id): Path<String>) -> Result<Json<String>, StatusCode> {
-    let exported = SessionManager::export_session(&session_id)
-        .await
-        .map_err(|_| StatusCode::NOT_FOUND)?;
+async fn export_session(
+    user: AuthUser,
+    Path(session_id): Path<String>,
+    Query(query): Query<ExportSessionQuery>,
+) -> Result<Json<String>, StatusCode> {
+    check_session_access(&user, &session_id).await?;
+
+    let exported = match query.format {
+        SessionExportFormat::Json => SessionManager::export_session(&session_id)
+            .await
+            .map_err(|_| StatusCode::NOT_FOUND)?,
+        format => {
+            let session = SessionManager::get_session(&session_id, true)
+                .await
+                .map_err(|_| StatusCode::NOT_FOUND)?;
+            export::export_session(&session, format).map_err(|_| StatusCode::BAD_REQUEST)?
+        }
+    };
 
     Ok(Json(exported))
 }
@@ -289,7 +376,7 @@ async fn export_session(Path(session_id): Path<String>) -> Result<Json<String>,
     responses(
         (status = 200, description = "Session imported successfully", body = Session),
         (status = 401, description = "Unauthorized - Invalid or missing API key"),
-        (status = 400, description = "Bad request - Invalid JSON"),
+        (status = 400, description = "Bad request - Invalid JSON or transcript"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -298,11 +385,43 @@ async fn export_session(Path(session_id): Path<String>) -> Result<Json<String>,
     tag = "Session Management"
 )]
 async fn import_session(
+    user: AuthUser,
     Json(request): Json<ImportSessionRequest>,
 ) -> Result<Json<Session>, StatusCode> {
-    let session = SessionManager::import_session(&request.json)
+    let mut session = match request.format {
+        SessionExportFormat::Json => SessionManager::import_session(&request.json)
+            .await
+            .map_err(|_| StatusCode::BAD_REQUEST)?,
+        SessionExportFormat::Jsonl => {
+            let working_dir = request
+                .working_dir
+                .map(PathBuf::from)
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap_or_default();
+            // A transcript file may hold several conversations; the first one is returned
+            // and the rest show up in the session list
+            let sessions = SessionManager::import_openai_jsonl(&request.json, working_dir)
+                .await
+                .map_err(|_| StatusCode::BAD_REQUEST)?;
+            for other in sessions.iter().skip(1) {
+                SessionManager::update_session(&other.id)
+                    .owner(user.owner())
+                    .apply()
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+            sessions.into_iter().next().ok_or(StatusCode::BAD_REQUEST)?
+        }
+        SessionExportFormat::Html => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    // Imported sessions always belong to the importer, whatever the file says
+    SessionManager::update_session(&session.id)
+        .owner(user.owner())
+        .apply()
         .await
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    session.owner = user.owner();
 
     Ok(Json(session))
 }
@@ -313,6 +432,7 @@ pub fn routes(state: Arc<AppState>) -> Router {
         .route("/sessions/{session_id}", get(get_session))
         .route("/sessions/{session_id}", delete(delete_session))
         .route("/sessions/{session_id}/export", get(export_session))
+        .route("/sessions/{session_id}/plan", get(get_session_plan))
         .route("/sessions/import", post(import_session))
         .route("/sessions/insights", get(get_session_insights))
         .route("/sessions/{session_id}/name", put(update_session_name))
@@ -1,5 +1,6 @@
+use crate::auth::require_admin;
 use crate::state::AppState;
-use axum::{http::StatusCode, routing::post, Json, Router};
+use axum::{http::StatusCode, middleware, routing::post, Json, Router};
 use goose::config::signup_openrouter::OpenRouterAuth;
 use goose::config::signup_tetrate::{configure_tetrate, TetrateAuth};
 use goose::config::{configure_openrouter, Config};
@@ -17,6 +18,7 @@ pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/handle_openrouter", post(start_openrouter_setup))
         .route("/handle_tetrate", post(start_tetrate_setup))
+        .route_layer(middleware::from_fn(require_admin))
         .with_state(state)
 }
 
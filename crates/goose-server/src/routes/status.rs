@@ -1,21 +1,41 @@
+use crate::auth::require_admin;
+use crate::state::AppState;
 use axum::body::Body;
+use axum::extract::State;
 use axum::http::HeaderValue;
 use axum::response::IntoResponse;
-use axum::{extract::Path, http::StatusCode, routing::get, Router};
+use axum::{extract::Path, http::StatusCode, middleware, routing::get, Json, Router};
+use goose::execution::manager::AgentPoolStats;
 use goose::session::generate_diagnostics;
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusResponse {
+    /// Always "ok" when the server is up
+    status: String,
+    /// Current state of the in-memory agent pool
+    agent_pool: AgentPoolStats,
+}
 
 #[utoipa::path(get, path = "/status",
     responses(
-        (status = 200, description = "ok", body = String),
+        (status = 200, description = "Server is up", body = StatusResponse),
     )
 )]
-async fn status() -> String {
-    "ok".to_string()
+async fn status(State(state): State<Arc<AppState>>) -> Json<StatusResponse> {
+    Json(StatusResponse {
+        status: "ok".to_string(),
+        agent_pool: state.agent_manager.pool_stats().await,
+    })
 }
 
 #[utoipa::path(get, path = "/diagnostics/{session_id}",
     responses(
         (status = 200, description = "Diagnostics zip file", content_type = "application/zip", body = Vec<u8>),
+        (status = 403, description = "Only the admin may download diagnostics"),
         (status = 500, description = "Failed to generate diagnostics"),
     )
 )]
@@ -39,8 +59,15 @@ async fn diagnostics(Path(session_id): Path<String>) -> impl IntoResponse {
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
-pub fn routes() -> Router {
+
+pub fn routes(state: Arc<AppState>) -> Router {
+    // Diagnostics bundle server-wide logs and config alongside the session
+    let admin_routes = Router::new()
+        .route("/diagnostics/{session_id}", get(diagnostics))
+        .route_layer(middleware::from_fn(require_admin));
+
     Router::new()
         .route("/status", get(status))
-        .route("/diagnostics/{session_id}", get(diagnostics))
+        .merge(admin_routes)
+        .with_state(state)
 }
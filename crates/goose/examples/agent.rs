@@ -47,6 +47,7 @@ async fn main() {
         schedule_id: None,
         max_turns: None,
         retry_config: None,
+        budget: None,
     };
 
     let user_message = Message::user()
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -8,10 +9,13 @@ use futures::stream::BoxStream;
 use futures::{stream, FutureExt, Stream, StreamExt, TryStreamExt};
 use uuid::Uuid;
 
+use crate::agents::approval::ApprovalBroker;
+use crate::agents::budget::{is_past_deadline, wait_for_deadline, Budget, BudgetLimit};
 use crate::agents::extension::{ExtensionConfig, ExtensionError, ExtensionResult, ToolInfo};
 use crate::agents::extension_manager::{get_parameter_names, ExtensionManager};
 use crate::agents::extension_manager_extension::MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE;
 use crate::agents::final_output_tool::{FINAL_OUTPUT_CONTINUATION_MESSAGE, FINAL_OUTPUT_TOOL_NAME};
+use crate::agents::hooks::{HookEvent, HookInput, HookManager};
 use crate::agents::platform_tools::PLATFORM_MANAGE_SCHEDULE_TOOL_NAME;
 use crate::agents::prompt_manager::PromptManager;
 use crate::agents::recipe_tools::dynamic_task_tools::{
@@ -38,6 +42,7 @@ use crate::permission::permission_judge::PermissionCheckResult;
 use crate::permission::PermissionConfirmation;
 use crate::providers::base::Provider;
 use crate::providers::errors::ProviderError;
+use crate::providers::pricing::initialize_pricing_cache;
 use crate::recipe::{Author, Recipe, Response, Settings, SubRecipe};
 use crate::scheduler_trait::SchedulerTrait;
 use crate::security::security_inspector::SecurityInspector;
@@ -104,14 +109,21 @@ pub struct Agent {
     pub(super) retry_manager: RetryManager,
     pub(super) tool_inspection_manager: ToolInspectionManager,
     pub(super) autopilot: Mutex<AutoPilot>,
+    pub(super) hooks: Arc<HookManager>,
+    pub(super) approval_broker: Mutex<Option<Arc<ApprovalBroker>>>,
 }
 
 #[derive(Clone, Debug)]
 pub enum AgentEvent {
     Message(Message),
     McpNotification((String, ServerNotification)),
-    ModelChange { model: String, mode: String },
+    ModelChange {
+        model: String,
+        mode: String,
+    },
     HistoryReplaced(Conversation),
+    /// The reply stopped because a budget limit was reached
+    BudgetExceeded(BudgetLimit),
 }
 
 impl Default for Agent {
@@ -179,6 +191,8 @@ impl Agent {
             retry_manager: RetryManager::new(),
             tool_inspection_manager: Self::create_default_tool_inspection_manager(),
             autopilot: Mutex::new(AutoPilot::new()),
+            hooks: Arc::new(HookManager::new()),
+            approval_broker: Mutex::new(None),
         }
     }
 
@@ -385,7 +399,61 @@ impl Agent {
         sub_recipe_manager.add_sub_recipe_tools(sub_recipes);
     }
 
-    /// Dispatch a single tool call to the appropriate client
+    /// Run the `pre_tool_use` hooks for a batch of tool requests before they are
+    /// inspected, so arguments a hook rewrites go through the same permission checks
+    /// and approval as the model's own. Blocked calls get their error response here
+    /// and are left out of the returned requests.
+    async fn run_pre_tool_hooks(
+        &self,
+        requests: Vec<ToolRequest>,
+        message_tool_response: Arc<Mutex<Message>>,
+        session: &Session,
+    ) -> Vec<ToolRequest> {
+        if !HookManager::has_hooks(HookEvent::PreToolUse) {
+            return requests;
+        }
+
+        let mut allowed = Vec::with_capacity(requests.len());
+        let mut contexts = HashMap::new();
+        for mut request in requests {
+            let Ok(tool_call) = request.tool_call.as_mut() else {
+                allowed.push(request);
+                continue;
+            };
+            let outcome = self
+                .hooks
+                .run(
+                    HookInput::new(HookEvent::PreToolUse, &session.id, &session.working_dir)
+                        .with_tool(&tool_call.name, tool_call.arguments.as_ref()),
+                )
+                .await;
+            if let Some(reason) = outcome.blocked {
+                let mut response = message_tool_response.lock().await;
+                *response = response.clone().with_tool_response(
+                    request.id.clone(),
+                    Err(ErrorData::new(
+                        ErrorCode::INVALID_REQUEST,
+                        format!("A pre_tool_use hook blocked this tool call: {}", reason),
+                        None,
+                    )),
+                );
+                continue;
+            }
+            if let Some(arguments) = outcome.tool_input {
+                tool_call.arguments = Some(arguments);
+            }
+            if !outcome.context.is_empty() {
+                contexts.insert(request.id.clone(), outcome.context);
+            }
+            allowed.push(request);
+        }
+        self.hooks.set_pre_tool_context(contexts).await;
+        allowed
+    }
+
+    /// Dispatch a single tool call to the appropriate client, running the
+    /// `post_tool_use` hooks after it. The `pre_tool_use` hooks already ran in
+    /// [`Self::run_pre_tool_hooks`] before the call was inspected.
     #[instrument(skip(self, tool_call, request_id), fields(input, output))]
     pub async fn dispatch_tool_call(
         &self,
@@ -393,6 +461,68 @@ impl Agent {
         request_id: String,
         cancellation_token: Option<CancellationToken>,
         session: &Session,
+    ) -> (String, Result<ToolCallResult, ErrorData>) {
+        let pre_tool_context = self.hooks.take_pre_tool_context(&request_id).await;
+        let post_tool = HookManager::has_hooks(HookEvent::PostToolUse).then(|| {
+            HookInput::new(HookEvent::PostToolUse, &session.id, &session.working_dir)
+                .with_tool(&tool_call.name, tool_call.arguments.as_ref())
+        });
+
+        let (request_id, result) = self
+            .route_tool_call(tool_call, request_id, cancellation_token, session)
+            .await;
+        if pre_tool_context.is_empty() && post_tool.is_none() {
+            return (request_id, result);
+        }
+        (
+            request_id,
+            result.map(|result| self.with_tool_hooks(result, pre_tool_context, post_tool)),
+        )
+    }
+
+    /// Append hook output to a successful tool result so the model sees it, running
+    /// the `post_tool_use` hooks once the tool finishes.
+    fn with_tool_hooks(
+        &self,
+        result: ToolCallResult,
+        pre_tool_context: Vec<String>,
+        post_tool: Option<HookInput>,
+    ) -> ToolCallResult {
+        let hooks = self.hooks.clone();
+        let tool_result = result.result;
+        ToolCallResult {
+            notification_stream: result.notification_stream,
+            result: Box::new(Box::pin(async move {
+                let output = tool_result.await;
+                let mut context = pre_tool_context;
+                if let Some(input) = post_tool {
+                    let input = match &output {
+                        Ok(content) => input.with_tool_output(
+                            content
+                                .iter()
+                                .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            false,
+                        ),
+                        Err(e) => input.with_tool_output(e.message.to_string(), true),
+                    };
+                    context.extend(hooks.run(input).await.context);
+                }
+                output.map(|mut content| {
+                    content.extend(context.into_iter().map(Content::text));
+                    content
+                })
+            })),
+        }
+    }
+
+    async fn route_tool_call(
+        &self,
+        tool_call: CallToolRequestParam,
+        request_id: String,
+        cancellation_token: Option<CancellationToken>,
+        session: &Session,
     ) -> (String, Result<ToolCallResult, ErrorData>) {
         if tool_call.name == PLATFORM_MANAGE_SCHEDULE_TOOL_NAME {
             let arguments = tool_call
@@ -534,7 +664,10 @@ impl Agent {
         } else if tool_call.name == ROUTER_LLM_SEARCH_TOOL_NAME {
             match self
                 .tool_route_manager
-                .dispatch_route_search_tool(tool_call.arguments.unwrap_or_default())
+                .dispatch_route_search_tool(
+                    tool_call.arguments.unwrap_or_default(),
+                    &session.working_dir,
+                )
                 .await
             {
                 Ok(tool_result) => tool_result,
@@ -716,6 +849,16 @@ impl Agent {
         self.extension_manager.get_extension_configs().await
     }
 
+    /// Let `broker` answer tool confirmations instead of waiting for
+    /// [`Agent::handle_confirmation`], e.g. when there is no one to ask
+    pub async fn set_approval_broker(&self, broker: Option<ApprovalBroker>) {
+        *self.approval_broker.lock().await = broker.map(Arc::new);
+    }
+
+    pub async fn approval_broker(&self) -> Option<Arc<ApprovalBroker>> {
+        self.approval_broker.lock().await.clone()
+    }
+
     /// Handle a confirmation response for a tool request
     pub async fn handle_confirmation(
         &self,
@@ -756,7 +899,25 @@ impl Agent {
 
         let conversation_to_compact = conversation.clone();
 
+        if self.hooks.mark_session_started(&session.id).await {
+            let session_start = self
+                .hooks
+                .run(HookInput::new(
+                    HookEvent::SessionStart,
+                    &session.id,
+                    &session.working_dir,
+                ))
+                .await;
+            for context in session_start.context {
+                self.extend_system_prompt(context).await;
+            }
+        }
+
         Ok(Box::pin(async_stream::try_stream! {
+            for notice in self.hook_notices().await {
+                yield AgentEvent::Message(notice);
+            }
+
             let final_conversation = if !needs_auto_compact {
                 conversation
             } else {
@@ -787,10 +948,16 @@ impl Agent {
                     )
                 );
 
-                match crate::context_mgmt::compact_messages(self, &conversation_to_compact, false).await {
+                let trigger = if is_manual_compact { "manual" } else { "auto" };
+                let hook_context = self.run_pre_compact_hooks(&session, trigger).await;
+                for notice in self.hook_notices().await {
+                    yield AgentEvent::Message(notice);
+                }
+
+                match crate::context_mgmt::compact_messages(self, &session.working_dir, &conversation_to_compact, false, &hook_context).await {
                     Ok((compacted_conversation, summarization_usage)) => {
                         SessionManager::replace_conversation(&session_config.id, &compacted_conversation).await?;
-                        Self::update_session_metrics(&session_config, &summarization_usage, true).await?;
+                        self.update_session_metrics(&session_config, &summarization_usage, true).await?;
 
                         yield AgentEvent::HistoryReplaced(compacted_conversation.clone());
 
@@ -842,6 +1009,25 @@ impl Agent {
         let reply_span = tracing::Span::current();
         self.reset_retry_attempts().await;
 
+        let recipe_budget = session
+            .recipe
+            .as_ref()
+            .and_then(|recipe| recipe.settings.as_ref())
+            .and_then(|settings| settings.budget.as_ref());
+        let budget = Budget::from_config()
+            .merge(recipe_budget)
+            .merge(session_config.budget.as_ref());
+        if budget.max_cost_usd.is_some() {
+            if let Err(e) = initialize_pricing_cache().await {
+                warn!(
+                    "Failed to initialize pricing cache for the cost budget: {}",
+                    e
+                );
+            }
+        }
+        let reply_started = std::time::Instant::now();
+        let deadline = budget.deadline(reply_started);
+
         let provider = self.provider().await?;
         let session_id = session_config.id.clone();
         tokio::spawn(async move {
@@ -854,6 +1040,8 @@ impl Agent {
             let _ = reply_span.enter();
             let mut turns_taken = 0u32;
             let max_turns = session_config.max_turns.unwrap_or(DEFAULT_MAX_TURNS);
+            let mut budget_warnings = HashSet::new();
+            let mut warned_unknown_cost = false;
 
             loop {
                 if is_token_cancelled(&cancel_token) {
@@ -870,6 +1058,46 @@ impl Agent {
                     }
                 }
 
+                if !budget.is_unlimited() {
+                    let usage = self
+                        .budget_usage(&session_config.id, &budget, reply_started)
+                        .await?;
+                    if budget.max_cost_usd.is_some()
+                        && usage.cost_usd.is_none()
+                        && !warned_unknown_cost
+                    {
+                        warned_unknown_cost = true;
+                        yield AgentEvent::Message(
+                            Message::assistant().with_system_notification(
+                                SystemNotificationType::InlineMessage,
+                                "No pricing data for this model, so the cost budget is not enforced.",
+                            )
+                        );
+                    }
+
+                    let check = budget.check(&usage);
+                    if let Some(limit) = check.exceeded {
+                        yield AgentEvent::Message(
+                            Message::assistant().with_text(format!(
+                                "I've stopped because this session reached its budget: {}. Raise the limit to continue.",
+                                limit
+                            ))
+                        );
+                        yield AgentEvent::BudgetExceeded(limit);
+                        break;
+                    }
+                    for warning in check.warnings {
+                        if budget_warnings.insert(warning.kind) {
+                            yield AgentEvent::Message(
+                                Message::assistant().with_system_notification(
+                                    SystemNotificationType::InlineMessage,
+                                    format!("Approaching the session budget: {} used.", warning),
+                                )
+                            );
+                        }
+                    }
+                }
+
                 turns_taken += 1;
                 if turns_taken > max_turns {
                     yield AgentEvent::Message(
@@ -893,13 +1121,23 @@ impl Agent {
                     }
                 }
 
-                let mut stream = Self::stream_response_from_provider(
-                    self.provider().await?,
-                    &system_prompt,
-                    conversation.messages(),
-                    &tools,
-                    &toolshim_tools,
-                ).await?;
+                // Provider calls and tools stop at the deadline, and the budget check at
+                // the top of the loop then ends the reply
+                let provider = self.provider().await?;
+                let stream = tokio::select! {
+                    stream = Self::stream_response_from_provider(
+                        provider,
+                        &system_prompt,
+                        conversation.messages(),
+                        &tools,
+                        &toolshim_tools,
+                    ) => Some(stream),
+                    _ = wait_for_deadline(deadline) => None,
+                };
+                let Some(stream) = stream else {
+                    continue;
+                };
+                let mut stream = stream?.take_until(Box::pin(wait_for_deadline(deadline)));
 
                 let mut no_tools_called = true;
                 let mut messages_to_add = Conversation::default();
@@ -935,7 +1173,7 @@ impl Agent {
                             }
 
                             if let Some(ref usage) = usage {
-                                Self::update_session_metrics(&session_config, usage, false).await?;
+                                self.update_session_metrics(&session_config, usage, false).await?;
                             }
 
                             if let Some(response) = response {
@@ -981,6 +1219,12 @@ impl Agent {
                                         );
                                     }
                                 } else {
+                                    let remaining_requests = self.run_pre_tool_hooks(
+                                        remaining_requests,
+                                        message_tool_response.clone(),
+                                        &session,
+                                    ).await;
+
                                     // Run all tool inspectors (security, repetition, permission, etc.)
                                     let inspection_results = self.tool_inspection_manager
                                         .inspect_tools(
@@ -1043,6 +1287,10 @@ impl Agent {
                                         futures_lock.drain(..).collect::<Vec<_>>()
                                     };
 
+                                    let pending_request_ids: Vec<String> = tool_futures
+                                        .iter()
+                                        .map(|(request_id, _)| request_id.clone())
+                                        .collect();
                                     let with_id = tool_futures
                                         .into_iter()
                                         .map(|(request_id, stream)| {
@@ -1050,8 +1298,10 @@ impl Agent {
                                         })
                                         .collect::<Vec<_>>();
 
-                                    let mut combined = stream::select_all(with_id);
+                                    let mut combined = stream::select_all(with_id)
+                                        .take_until(Box::pin(wait_for_deadline(deadline)));
                                     let mut all_install_successful = true;
+                                    let mut finished_request_ids = HashSet::new();
 
                                     while let Some((request_id, item)) = combined.next().await {
                                         if is_token_cancelled(&cancel_token) {
@@ -1064,6 +1314,7 @@ impl Agent {
                                                 {
                                                     all_install_successful = false;
                                                 }
+                                                finished_request_ids.insert(request_id.clone());
                                                 let mut response = message_tool_response.lock().await;
                                                 *response =
                                                     response.clone().with_tool_response(request_id, output);
@@ -1076,6 +1327,23 @@ impl Agent {
                                         }
                                     }
 
+                                    if is_past_deadline(deadline) {
+                                        let mut response = message_tool_response.lock().await;
+                                        for request_id in pending_request_ids
+                                            .into_iter()
+                                            .filter(|request_id| !finished_request_ids.contains(request_id))
+                                        {
+                                            *response = response.clone().with_tool_response(
+                                                request_id,
+                                                Err(ErrorData::new(
+                                                    ErrorCode::INTERNAL_ERROR,
+                                                    "Stopped because the reply ran out of its wall-clock budget".to_string(),
+                                                    None,
+                                                )),
+                                            );
+                                        }
+                                    }
+
                                     if all_install_successful && !enable_extension_request_ids.is_empty() {
                                         if let Err(e) = self.save_extension_state(&session_config).await {
                                             warn!("Failed to save extension state after runtime changes: {}", e);
@@ -1086,6 +1354,9 @@ impl Agent {
 
                                 let final_message_tool_resp = message_tool_response.lock().await.clone();
                                 yield AgentEvent::Message(final_message_tool_resp.clone());
+                                for notice in self.hook_notices().await {
+                                    yield AgentEvent::Message(notice);
+                                }
 
                                 no_tools_called = false;
                                 messages_to_add.push(final_message_tool_resp);
@@ -1105,10 +1376,15 @@ impl Agent {
                                 )
                             );
 
-                            match crate::context_mgmt::compact_messages(self, &conversation, true).await {
+                            let hook_context = self.run_pre_compact_hooks(&session, "context_limit").await;
+                            for notice in self.hook_notices().await {
+                                yield AgentEvent::Message(notice);
+                            }
+
+                            match crate::context_mgmt::compact_messages(self, &session.working_dir, &conversation, true, &hook_context).await {
                                 Ok((compacted_conversation, usage)) => {
                                     SessionManager::replace_conversation(&session_config.id, &compacted_conversation).await?;
-                                    Self::update_session_metrics(&session_config, &usage, true).await?;
+                                    self.update_session_metrics(&session_config, &usage, true).await?;
                                     conversation = compacted_conversation;
                                     did_recovery_compact_this_iteration = true;
                                     yield AgentEvent::HistoryReplaced(conversation.clone());
@@ -1140,7 +1416,7 @@ impl Agent {
                     (tools, toolshim_tools, system_prompt) = self.prepare_tools_and_prompt().await?;
                 }
                 let mut exit_chat = false;
-                if no_tools_called {
+                if no_tools_called && !is_past_deadline(deadline) {
                     if let Some(final_output_tool) = self.final_output_tool.lock().await.as_ref() {
                         if final_output_tool.final_output.is_none() {
                             warn!("Final output tool has not been called yet. Continuing agent loop.");
@@ -1190,6 +1466,54 @@ impl Agent {
         }))
     }
 
+    /// Run the `session_end` hooks for a session this agent has replied in. Failures
+    /// are only logged since there is no reply stream left to report them on.
+    pub async fn end_session(&self, session_id: &str) {
+        if !self.hooks.mark_session_ended(session_id).await {
+            return;
+        }
+        let working_dir = match SessionManager::get_session(session_id, false).await {
+            Ok(session) => session.working_dir,
+            Err(e) => {
+                warn!(
+                    "Failed to load session {} for session_end hooks: {}",
+                    session_id, e
+                );
+                return;
+            }
+        };
+        self.hooks
+            .run(HookInput::new(
+                HookEvent::SessionEnd,
+                session_id,
+                &working_dir,
+            ))
+            .await;
+        self.hooks.take_notices().await;
+    }
+
+    async fn run_pre_compact_hooks(&self, session: &Session, trigger: &str) -> Vec<String> {
+        self.hooks
+            .run(
+                HookInput::new(HookEvent::PreCompact, &session.id, &session.working_dir)
+                    .with_trigger(trigger),
+            )
+            .await
+            .context
+    }
+
+    async fn hook_notices(&self) -> Vec<Message> {
+        self.hooks
+            .take_notices()
+            .await
+            .into_iter()
+            .map(|notice| {
+                Message::assistant()
+                    .with_system_notification(SystemNotificationType::InlineMessage, notice)
+            })
+            .collect()
+    }
+
     pub async fn extend_system_prompt(&self, instruction: String) {
         let mut prompt_manager = self.prompt_manager.lock().await;
         prompt_manager.add_system_prompt_extra(instruction);
@@ -1256,6 +1580,17 @@ impl Agent {
         Err(anyhow!("Prompt '{}' not found", name))
     }
 
+    /// The working directory of the session this agent serves, or the process's when it
+    /// is not bound to one.
+    async fn working_dir(&self) -> PathBuf {
+        if let Some(session_id) = self.extension_manager.get_context().await.session_id {
+            if let Ok(session) = SessionManager::get_session(&session_id, false).await {
+                return session.working_dir;
+            }
+        }
+        std::env::current_dir().unwrap_or_default()
+    }
+
     pub async fn get_plan_prompt(&self) -> Result<String> {
         let tools = self.extension_manager.get_prefixed_tools(None).await?;
         let tools_info = tools
@@ -1273,7 +1608,10 @@ impl Agent {
             })
             .collect();
 
-        let plan_prompt = self.extension_manager.get_planning_prompt(tools_info).await;
+        let plan_prompt = self
+            .extension_manager
+            .get_planning_prompt(tools_info, &self.working_dir().await)
+            .await;
 
         Ok(plan_prompt)
     }
@@ -1301,6 +1639,7 @@ impl Agent {
         let model_name = &model_config.model_name;
         tracing::debug!("Using model: {}", model_name);
 
+        let working_dir = self.working_dir().await;
         let prompt_manager = self.prompt_manager.lock().await;
         let system_prompt = prompt_manager
             .builder(model_name)
@@ -1309,7 +1648,7 @@ impl Agent {
             .with_extension_and_tool_counts(extension_count, tool_count)
             .build();
 
-        let recipe_prompt = prompt_manager.get_recipe_prompt().await;
+        let recipe_prompt = prompt_manager.get_recipe_prompt(&working_dir).await;
         let tools = self
             .extension_manager
             .get_prefixed_tools(None)
@@ -1451,6 +1790,7 @@ impl Agent {
             goose_provider: Some(provider_name.clone()),
             goose_model: Some(model_name.clone()),
             temperature: Some(model_config.temperature.unwrap_or(0.0)),
+            budget: None,
         };
 
         tracing::debug!(
@@ -0,0 +1,389 @@
+//! Answers tool confirmations when nobody is at the terminal.
+//!
+//! In `approve` and `smart_approve` modes a headless `goose run` cannot prompt for
+//! permission, so it hands each confirmation to the broker set in
+//! `GOOSE_APPROVAL_BROKER`:
+//!
+//! - `command:<shell command>` runs the command with the request as JSON on stdin.
+//!   Exit code 0 allows the call and 1 denies it; anything else is no answer.
+//! - `unix:<path>` connects to a socket another process listens on, writes the
+//!   request as one line of JSON and reads the answer as one line of JSON.
+//! - `http://...` or `https://...` POSTs the request and reads the answer from
+//!   the response body.
+//! - `allow` or `deny` answers every request without asking anyone.
+//!
+//! An answer is `{"decision": "allow"}` or `{"decision": "deny", "reason": "..."}`.
+//! When the broker fails or takes longer than `GOOSE_APPROVAL_TIMEOUT` seconds, the
+//! request gets `GOOSE_APPROVAL_DEFAULT`, which is `deny` unless set.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::agents::hooks::run_shell_command;
+use crate::config::Config;
+use crate::permission::Permission;
+
+pub const GOOSE_APPROVAL_BROKER: &str = "GOOSE_APPROVAL_BROKER";
+pub const GOOSE_APPROVAL_TIMEOUT: &str = "GOOSE_APPROVAL_TIMEOUT";
+pub const GOOSE_APPROVAL_DEFAULT: &str = "GOOSE_APPROVAL_DEFAULT";
+pub const DEFAULT_APPROVAL_TIMEOUT_SECONDS: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Allow,
+    Deny,
+}
+
+impl From<ApprovalDecision> for Permission {
+    fn from(decision: ApprovalDecision) -> Self {
+        match decision {
+            ApprovalDecision::Allow => Permission::AllowOnce,
+            ApprovalDecision::Deny => Permission::DenyOnce,
+        }
+    }
+}
+
+impl fmt::Display for ApprovalDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalDecision::Allow => write!(f, "allow"),
+            ApprovalDecision::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// What a broker is asked to decide, sent to it as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub session_id: String,
+    pub request_id: String,
+    pub tool_name: String,
+    pub arguments: Map<String, Value>,
+    /// Why the security inspector flagged this call, if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    pub working_dir: PathBuf,
+}
+
+/// What a socket or HTTP broker sends back
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalAnswer {
+    pub decision: ApprovalDecision,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApprovalBackend {
+    /// Every request gets this decision
+    Fixed(ApprovalDecision),
+    /// Shell command that decides by its exit code
+    Command(String),
+    /// Unix socket answered by another process
+    Socket(PathBuf),
+    /// HTTP endpoint answered by another process
+    Http(String),
+}
+
+impl FromStr for ApprovalBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if let Some(command) = value.strip_prefix("command:") {
+            if command.trim().is_empty() {
+                return Err(anyhow!("'command:' needs a command to run"));
+            }
+            return Ok(Self::Command(command.trim().to_string()));
+        }
+        if let Some(path) = value.strip_prefix("unix:") {
+            if path.trim().is_empty() {
+                return Err(anyhow!("'unix:' needs a socket path"));
+            }
+            return Ok(Self::Socket(PathBuf::from(path.trim())));
+        }
+        if value.starts_with("http://") || value.starts_with("https://") {
+            return Ok(Self::Http(value.to_string()));
+        }
+        match value {
+            "allow" => Ok(Self::Fixed(ApprovalDecision::Allow)),
+            "deny" => Ok(Self::Fixed(ApprovalDecision::Deny)),
+            _ => Err(anyhow!(
+                "Unknown approval broker '{}'; expected command:<cmd>, unix:<path>, an http(s) URL, allow or deny",
+                value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApprovalBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApprovalBackend::Fixed(decision) => write!(f, "{}", decision),
+            ApprovalBackend::Command(command) => write!(f, "command '{}'", command),
+            ApprovalBackend::Socket(path) => write!(f, "socket {}", path.display()),
+            ApprovalBackend::Http(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalBroker {
+    pub backend: ApprovalBackend,
+    pub timeout: Duration,
+    /// Used when the backend fails, times out or has no answer
+    pub default: ApprovalDecision,
+}
+
+impl ApprovalBroker {
+    pub fn new(backend: ApprovalBackend) -> Self {
+        Self {
+            backend,
+            timeout: Duration::from_secs(DEFAULT_APPROVAL_TIMEOUT_SECONDS),
+            default: ApprovalDecision::Deny,
+        }
+    }
+
+    /// The broker set in the config, or None when there is none
+    pub fn from_config() -> Result<Option<Self>> {
+        let config = Config::global();
+        let Ok(backend) = config.get_param::<String>(GOOSE_APPROVAL_BROKER) else {
+            return Ok(None);
+        };
+        let mut broker = Self::new(backend.parse()?);
+        if let Ok(seconds) = config.get_param::<u64>(GOOSE_APPROVAL_TIMEOUT) {
+            broker.timeout = Duration::from_secs(seconds);
+        }
+        if let Ok(default) = config.get_param::<ApprovalDecision>(GOOSE_APPROVAL_DEFAULT) {
+            broker.default = default;
+        }
+        Ok(Some(broker))
+    }
+
+    pub async fn decide(&self, request: &ApprovalRequest) -> ApprovalDecision {
+        let answer = tokio::time::timeout(self.timeout, self.ask(request))
+            .await
+            .map_err(|_| anyhow!("no answer after {:?}", self.timeout))
+            .and_then(|answer| answer);
+
+        match answer {
+            Ok(Some(answer)) => {
+                info!(
+                    "Approval broker answered {} for {}{}",
+                    answer.decision,
+                    request.tool_name,
+                    answer
+                        .reason
+                        .map(|reason| format!(": {}", reason))
+                        .unwrap_or_default()
+                );
+                answer.decision
+            }
+            Ok(None) => self.default,
+            Err(e) => {
+                warn!(
+                    "Approval broker {} failed for {}, using {}: {}",
+                    self.backend, request.tool_name, self.default, e
+                );
+                self.default
+            }
+        }
+    }
+
+    async fn ask(&self, request: &ApprovalRequest) -> Result<Option<ApprovalAnswer>> {
+        match &self.backend {
+            ApprovalBackend::Fixed(decision) => Ok(Some(ApprovalAnswer {
+                decision: *decision,
+                reason: None,
+            })),
+            ApprovalBackend::Command(command) => ask_command(command, request).await,
+            ApprovalBackend::Socket(path) => ask_socket(path, request).await.map(Some),
+            ApprovalBackend::Http(url) => ask_http(url, request).await.map(Some),
+        }
+    }
+}
+
+async fn ask_command(command: &str, request: &ApprovalRequest) -> Result<Option<ApprovalAnswer>> {
+    let payload = serde_json::to_vec(request)?;
+    let output = run_shell_command(
+        command,
+        &request.working_dir,
+        &[
+            ("GOOSE_SESSION_ID", &request.session_id),
+            ("GOOSE_TOOL_NAME", &request.tool_name),
+        ],
+        &payload,
+    )
+    .await?;
+    let reason = Some(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    let decision = match output.status.code() {
+        Some(0) => ApprovalDecision::Allow,
+        Some(1) => ApprovalDecision::Deny,
+        _ => {
+            warn!(
+                "Approval command '{}' exited with {}, using the default",
+                command, output.status
+            );
+            return Ok(None);
+        }
+    };
+    Ok(Some(ApprovalAnswer { decision, reason }))
+}
+
+#[cfg(unix)]
+async fn ask_socket(path: &std::path::Path, request: &ApprovalRequest) -> Result<ApprovalAnswer> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    let mut stream = UnixStream::connect(path).await?;
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+
+    let mut answer = String::new();
+    BufReader::new(stream).read_line(&mut answer).await?;
+    if answer.trim().is_empty() {
+        return Err(anyhow!("the socket closed without an answer"));
+    }
+    Ok(serde_json::from_str(answer.trim())?)
+}
+
+#[cfg(not(unix))]
+async fn ask_socket(_path: &std::path::Path, _request: &ApprovalRequest) -> Result<ApprovalAnswer> {
+    Err(anyhow!(
+        "Unix socket brokers are not supported on this platform"
+    ))
+}
+
+async fn ask_http(url: &str, request: &ApprovalRequest) -> Result<ApprovalAnswer> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .json(request)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(dir: &std::path::Path, tool_name: &str) -> ApprovalRequest {
+        ApprovalRequest {
+            session_id: "session".to_string(),
+            request_id: "call-1".to_string(),
+            tool_name: tool_name.to_string(),
+            arguments: Map::new(),
+            warning: None,
+            working_dir: dir.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!(
+            "command: ./approve.sh".parse::<ApprovalBackend>().unwrap(),
+            ApprovalBackend::Command("./approve.sh".to_string())
+        );
+        assert_eq!(
+            "unix:/tmp/goose.sock".parse::<ApprovalBackend>().unwrap(),
+            ApprovalBackend::Socket(PathBuf::from("/tmp/goose.sock"))
+        );
+        assert_eq!(
+            "http://localhost:8080/approve"
+                .parse::<ApprovalBackend>()
+                .unwrap(),
+            ApprovalBackend::Http("http://localhost:8080/approve".to_string())
+        );
+        assert_eq!(
+            "deny".parse::<ApprovalBackend>().unwrap(),
+            ApprovalBackend::Fixed(ApprovalDecision::Deny)
+        );
+        assert!("maybe".parse::<ApprovalBackend>().is_err());
+        assert!("command:".parse::<ApprovalBackend>().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_decides_by_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let broker = ApprovalBroker::new(ApprovalBackend::Command(
+            r#"case "$GOOSE_TOOL_NAME" in developer__shell) exit 1;; *__read*) exit 0;; *) exit 3;; esac"#
+                .to_string(),
+        ));
+        assert_eq!(
+            broker
+                .decide(&request(dir.path(), "developer__shell"))
+                .await,
+            ApprovalDecision::Deny
+        );
+        assert_eq!(
+            broker
+                .decide(&request(dir.path(), "files__read_file"))
+                .await,
+            ApprovalDecision::Allow
+        );
+
+        let broker = ApprovalBroker {
+            default: ApprovalDecision::Allow,
+            ..broker
+        };
+        assert_eq!(
+            broker.decide(&request(dir.path(), "other__tool")).await,
+            ApprovalDecision::Allow
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_answer_and_timeout() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+        use tokio::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("approve.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            let request: ApprovalRequest = serde_json::from_str(&line).unwrap();
+            assert_eq!(request.tool_name, "developer__shell");
+            reader
+                .into_inner()
+                .write_all(b"{\"decision\": \"allow\", \"reason\": \"looks fine\"}\n")
+                .await
+                .unwrap();
+            // Accept the next request but never answer it.
+            let _pending = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+
+        let broker = ApprovalBroker {
+            timeout: Duration::from_millis(200),
+            ..ApprovalBroker::new(ApprovalBackend::Socket(path))
+        };
+        assert_eq!(
+            broker
+                .decide(&request(dir.path(), "developer__shell"))
+                .await,
+            ApprovalDecision::Allow
+        );
+        assert_eq!(
+            broker
+                .decide(&request(dir.path(), "developer__shell"))
+                .await,
+            ApprovalDecision::Deny
+        );
+    }
+}
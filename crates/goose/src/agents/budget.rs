@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+use crate::config::Config;
+
+pub const GOOSE_MAX_COST_USD: &str = "GOOSE_MAX_COST_USD";
+pub const GOOSE_MAX_INPUT_TOKENS: &str = "GOOSE_MAX_INPUT_TOKENS";
+pub const GOOSE_MAX_OUTPUT_TOKENS: &str = "GOOSE_MAX_OUTPUT_TOKENS";
+pub const GOOSE_MAX_DURATION_SECONDS: &str = "GOOSE_MAX_DURATION_SECONDS";
+pub const GOOSE_BUDGET_WARNING_THRESHOLD: &str = "GOOSE_BUDGET_WARNING_THRESHOLD";
+
+/// Fraction of a limit at which a warning is shown
+pub const DEFAULT_BUDGET_WARNING_THRESHOLD: f64 = 0.8;
+
+/// Limits on what a session may spend. Cost and tokens are counted over the whole
+/// session; duration is wall-clock time for a single reply, including provider
+/// calls and tools that are still running when it runs out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Budget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration_seconds: Option<u64>,
+    /// Fraction of any limit at which to warn (default 0.8)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning_threshold: Option<f64>,
+}
+
+impl Budget {
+    /// The global budget from config or `GOOSE_MAX_*` environment variables
+    pub fn from_config() -> Self {
+        let config = Config::global();
+        Self {
+            max_cost_usd: config.get_param(GOOSE_MAX_COST_USD).ok(),
+            max_input_tokens: config.get_param(GOOSE_MAX_INPUT_TOKENS).ok(),
+            max_output_tokens: config.get_param(GOOSE_MAX_OUTPUT_TOKENS).ok(),
+            max_duration_seconds: config.get_param(GOOSE_MAX_DURATION_SECONDS).ok(),
+            warning_threshold: config.get_param(GOOSE_BUDGET_WARNING_THRESHOLD).ok(),
+        }
+    }
+
+    /// Every limit set in `overrides` replaces the one in `self`
+    pub fn merge(self, overrides: Option<&Budget>) -> Self {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        Self {
+            max_cost_usd: overrides.max_cost_usd.or(self.max_cost_usd),
+            max_input_tokens: overrides.max_input_tokens.or(self.max_input_tokens),
+            max_output_tokens: overrides.max_output_tokens.or(self.max_output_tokens),
+            max_duration_seconds: overrides.max_duration_seconds.or(self.max_duration_seconds),
+            warning_threshold: overrides.warning_threshold.or(self.warning_threshold),
+        }
+    }
+
+    /// When a reply started at `started` has to stop, if there is a duration limit
+    pub fn deadline(&self, started: Instant) -> Option<Instant> {
+        self.max_duration_seconds
+            .map(|seconds| started + Duration::from_secs(seconds))
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_cost_usd.is_none()
+            && self.max_input_tokens.is_none()
+            && self.max_output_tokens.is_none()
+            && self.max_duration_seconds.is_none()
+    }
+
+    /// The first limit reached, if any, and every limit past the warning threshold.
+    /// A cost limit is skipped when the cost is unknown.
+    pub fn check(&self, usage: &BudgetUsage) -> BudgetCheck {
+        let threshold = self
+            .warning_threshold
+            .unwrap_or(DEFAULT_BUDGET_WARNING_THRESHOLD);
+        let limits = [
+            (BudgetKind::CostUsd, self.max_cost_usd, usage.cost_usd),
+            (
+                BudgetKind::InputTokens,
+                self.max_input_tokens.map(|v| v as f64),
+                Some(usage.input_tokens as f64),
+            ),
+            (
+                BudgetKind::OutputTokens,
+                self.max_output_tokens.map(|v| v as f64),
+                Some(usage.output_tokens as f64),
+            ),
+            (
+                BudgetKind::DurationSeconds,
+                self.max_duration_seconds.map(|v| v as f64),
+                Some(usage.elapsed.as_secs_f64()),
+            ),
+        ];
+
+        let mut check = BudgetCheck::default();
+        for (kind, max, used) in limits {
+            let (Some(max), Some(used)) = (max, used) else {
+                continue;
+            };
+            let limit = BudgetLimit { kind, used, max };
+            if used >= max {
+                check.exceeded.get_or_insert(limit);
+            } else if used >= max * threshold {
+                check.warnings.push(limit);
+            }
+        }
+        check
+    }
+}
+
+/// Resolves at `deadline`, or never when there is none
+pub async fn wait_for_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+pub fn is_past_deadline(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    CostUsd,
+    InputTokens,
+    OutputTokens,
+    DurationSeconds,
+}
+
+/// How much of one limit has been used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLimit {
+    pub kind: BudgetKind,
+    pub used: f64,
+    pub max: f64,
+}
+
+impl fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            BudgetKind::CostUsd => write!(f, "cost ${:.2} of ${:.2}", self.used, self.max),
+            BudgetKind::InputTokens => {
+                write!(f, "{} of {} input tokens", self.used, self.max)
+            }
+            BudgetKind::OutputTokens => {
+                write!(f, "{} of {} output tokens", self.used, self.max)
+            }
+            BudgetKind::DurationSeconds => {
+                write!(f, "{}s of {}s wall-clock time", self.used.round(), self.max)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BudgetUsage {
+    /// Unknown when there is no pricing data for the model
+    pub cost_usd: Option<f64>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BudgetCheck {
+    pub exceeded: Option<BudgetLimit>,
+    pub warnings: Vec<BudgetLimit>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_prefers_overrides() {
+        let global = Budget {
+            max_cost_usd: Some(5.0),
+            max_input_tokens: Some(1_000_000),
+            ..Default::default()
+        };
+        let recipe = Budget {
+            max_cost_usd: Some(1.0),
+            max_duration_seconds: Some(600),
+            ..Default::default()
+        };
+
+        let merged = global.clone().merge(Some(&recipe));
+        assert_eq!(merged.max_cost_usd, Some(1.0));
+        assert_eq!(merged.max_input_tokens, Some(1_000_000));
+        assert_eq!(merged.max_duration_seconds, Some(600));
+        assert_eq!(global.clone().merge(None), global);
+        assert!(Budget::default().is_unlimited());
+    }
+
+    #[test]
+    fn test_check_warns_then_stops() {
+        let budget = Budget {
+            max_cost_usd: Some(1.0),
+            max_output_tokens: Some(1000),
+            max_duration_seconds: Some(60),
+            ..Default::default()
+        };
+
+        let usage = BudgetUsage {
+            cost_usd: Some(0.5),
+            input_tokens: 50_000,
+            output_tokens: 850,
+            elapsed: Duration::from_secs(10),
+        };
+        let check = budget.check(&usage);
+        assert_eq!(check.exceeded, None);
+        assert_eq!(check.warnings.len(), 1);
+        assert_eq!(check.warnings[0].kind, BudgetKind::OutputTokens);
+        assert_eq!(check.warnings[0].to_string(), "850 of 1000 output tokens");
+
+        let usage = BudgetUsage {
+            cost_usd: Some(1.25),
+            elapsed: Duration::from_secs(61),
+            ..usage
+        };
+        let check = budget.check(&usage);
+        let exceeded = check.exceeded.unwrap();
+        assert_eq!(exceeded.kind, BudgetKind::CostUsd);
+        assert_eq!(exceeded.to_string(), "cost $1.25 of $1.00");
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let started = Instant::now();
+        assert_eq!(Budget::default().deadline(started), None);
+        assert!(!is_past_deadline(None));
+
+        let budget = Budget {
+            max_duration_seconds: Some(0),
+            ..Default::default()
+        };
+        let deadline = budget.deadline(started);
+        assert_eq!(deadline, Some(started));
+        wait_for_deadline(deadline).await;
+        assert!(is_past_deadline(deadline));
+    }
+
+    #[test]
+    fn test_unknown_cost_is_not_enforced() {
+        let budget = Budget {
+            max_cost_usd: Some(0.01),
+            ..Default::default()
+        };
+        assert_eq!(
+            budget.check(&BudgetUsage::default()),
+            BudgetCheck::default()
+        );
+    }
+}
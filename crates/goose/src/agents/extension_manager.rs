@@ -13,6 +13,7 @@ use rmcp::transport::{
 };
 use std::collections::HashMap;
 use std::option::Option;
+use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
@@ -672,11 +673,16 @@ impl ExtensionManager {
     }
 
     /// Get the extension prompt including client instructions
-    pub async fn get_planning_prompt(&self, tools_info: Vec<ToolInfo>) -> String {
+    pub async fn get_planning_prompt(
+        &self,
+        tools_info: Vec<ToolInfo>,
+        working_dir: &Path,
+    ) -> String {
         let mut context: HashMap<&str, Value> = HashMap::new();
         context.insert("tools", serde_json::to_value(tools_info).unwrap());
 
-        prompt_template::render_global_file("plan.md", &context).expect("Prompt should render")
+        prompt_template::render_session_file(working_dir, "plan.md", &context)
+            .expect("Prompt should render")
     }
 
     /// Find and return a reference to the appropriate client for a tool call
@@ -0,0 +1,561 @@
+//! User-configured commands that run at fixed points of the agent lifecycle.
+//!
+//! Hooks live under the `hooks` key of the user's `config.yaml`. A project's
+//! `.goose/config.yaml` cannot set them, since opening a cloned repository must
+//! never run its commands:
+//!
+//! ```yaml
+//! hooks:
+//!   pre_tool_use:
+//!     - matcher: "developer__shell"
+//!       command: ./scripts/check-shell.sh
+//!   post_tool_use:
+//!     - matcher: "developer__text_editor"
+//!       command: cargo fmt
+//!       timeout: 120
+//!   session_start:
+//!     - command: git status --short
+//! ```
+//!
+//! Each command receives a JSON description of the event on stdin. Plain stdout is
+//! handed to the model as extra context. Stdout that is a JSON object is read as a
+//! [`HookResponse`], which lets `pre_tool_use` hooks block a call or rewrite its
+//! arguments. A command that fails or times out never stops the agent; the failure
+//! is reported to the user instead.
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::config::{Config, ConfigError};
+
+pub const HOOKS_CONFIG_KEY: &str = "hooks";
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    PreToolUse,
+    PostToolUse,
+    SessionStart,
+    SessionEnd,
+    PreCompact,
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HookEvent::PreToolUse => "pre_tool_use",
+            HookEvent::PostToolUse => "post_tool_use",
+            HookEvent::SessionStart => "session_start",
+            HookEvent::SessionEnd => "session_end",
+            HookEvent::PreCompact => "pre_compact",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookCommand {
+    /// Shell command, run with `sh -c` (`cmd /C` on Windows) in the session's working directory
+    pub command: String,
+    /// Regex that must match the whole tool name; tool hooks without one run for every tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    /// Seconds before the command is killed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl HookCommand {
+    fn matches(&self, tool_name: Option<&str>) -> Result<bool> {
+        match (&self.matcher, tool_name) {
+            (Some(matcher), Some(tool_name)) => {
+                let regex = Regex::new(&format!("^(?:{})$", matcher))
+                    .map_err(|e| anyhow!("invalid matcher '{}': {}", matcher, e))?;
+                Ok(regex.is_match(tool_name))
+            }
+            _ => Ok(true),
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    pub pre_tool_use: Vec<HookCommand>,
+    pub post_tool_use: Vec<HookCommand>,
+    pub session_start: Vec<HookCommand>,
+    pub session_end: Vec<HookCommand>,
+    pub pre_compact: Vec<HookCommand>,
+}
+
+impl HooksConfig {
+    /// Hooks from the user config. A malformed `hooks` entry
+    /// disables hooks rather than failing the session.
+    pub fn load() -> Self {
+        match Config::global().get_param::<HooksConfig>(HOOKS_CONFIG_KEY) {
+            Ok(config) => config,
+            Err(ConfigError::NotFound(_)) => Self::default(),
+            Err(e) => {
+                warn!("Ignoring invalid {} config: {}", HOOKS_CONFIG_KEY, e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn commands(&self, event: HookEvent) -> &[HookCommand] {
+        match event {
+            HookEvent::PreToolUse => &self.pre_tool_use,
+            HookEvent::PostToolUse => &self.post_tool_use,
+            HookEvent::SessionStart => &self.session_start,
+            HookEvent::SessionEnd => &self.session_end,
+            HookEvent::PreCompact => &self.pre_compact,
+        }
+    }
+
+    /// Run every hook registered for the event, in order. For `pre_tool_use`, a
+    /// rewritten `tool_input` is what later hooks see, and the first block wins.
+    pub async fn run(&self, mut input: HookInput) -> HookOutcome {
+        let mut outcome = HookOutcome::default();
+
+        for hook in self.commands(input.event) {
+            match hook.matches(input.tool_name.as_deref()) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    outcome.failures.push(failure_message(input.event, hook, e));
+                    continue;
+                }
+            }
+
+            let stdout = match run_command(hook, &input).await {
+                Ok(stdout) => stdout,
+                Err(e) => {
+                    outcome.failures.push(failure_message(input.event, hook, e));
+                    continue;
+                }
+            };
+
+            let response = HookResponse::parse(&stdout);
+            if let Some(context) = response.context {
+                outcome.context.push(context);
+            }
+            if input.event != HookEvent::PreToolUse {
+                continue;
+            }
+            if response.decision == Some(HookDecision::Block) {
+                outcome.blocked = Some(
+                    response
+                        .reason
+                        .unwrap_or_else(|| format!("blocked by hook `{}`", hook.command)),
+                );
+                break;
+            }
+            if let Some(tool_input) = response.tool_input {
+                input.tool_input = Some(Value::Object(tool_input.clone()));
+                outcome.tool_input = Some(tool_input);
+            }
+        }
+
+        outcome
+    }
+}
+
+/// What a hook command is told about the event, as JSON on stdin
+#[derive(Debug, Clone, Serialize)]
+pub struct HookInput {
+    pub event: HookEvent,
+    pub session_id: String,
+    pub working_dir: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_input: Option<Value>,
+    /// Text content of the tool result, or the error message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
+    /// Why compaction is happening: `manual`, `auto` or `context_limit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<String>,
+}
+
+impl HookInput {
+    pub fn new(event: HookEvent, session_id: &str, working_dir: &Path) -> Self {
+        Self {
+            event,
+            session_id: session_id.to_string(),
+            working_dir: working_dir.to_path_buf(),
+            tool_name: None,
+            tool_input: None,
+            tool_output: None,
+            is_error: None,
+            trigger: None,
+        }
+    }
+
+    pub fn with_tool(mut self, name: &str, arguments: Option<&Map<String, Value>>) -> Self {
+        self.tool_name = Some(name.to_string());
+        self.tool_input = Some(Value::Object(arguments.cloned().unwrap_or_default()));
+        self
+    }
+
+    pub fn with_tool_output(mut self, output: String, is_error: bool) -> Self {
+        self.tool_output = Some(output);
+        self.is_error = Some(is_error);
+        self
+    }
+
+    pub fn with_trigger(mut self, trigger: &str) -> Self {
+        self.trigger = Some(trigger.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookDecision {
+    Allow,
+    Block,
+}
+
+/// Structured hook output, used when stdout is a JSON object
+#[derive(Debug, Default, Deserialize)]
+pub struct HookResponse {
+    pub decision: Option<HookDecision>,
+    pub reason: Option<String>,
+    /// Replacement tool arguments (`pre_tool_use` only)
+    pub tool_input: Option<Map<String, Value>>,
+    /// Text to hand to the model
+    pub context: Option<String>,
+}
+
+impl HookResponse {
+    fn parse(stdout: &str) -> Self {
+        let stdout = stdout.trim();
+        if stdout.is_empty() {
+            return Self::default();
+        }
+        if stdout.starts_with('{') {
+            if let Ok(response) = serde_json::from_str::<HookResponse>(stdout) {
+                return response;
+            }
+        }
+        Self {
+            context: Some(stdout.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct HookOutcome {
+    /// Reason a `pre_tool_use` hook gave for refusing the call
+    pub blocked: Option<String>,
+    /// Arguments to call the tool with instead of the model's
+    pub tool_input: Option<Map<String, Value>>,
+    /// Hook output for the model, one entry per hook that printed something
+    pub context: Vec<String>,
+    /// Hooks that could not run or exited unsuccessfully, for the user
+    pub failures: Vec<String>,
+}
+
+fn failure_message(event: HookEvent, hook: &HookCommand, error: anyhow::Error) -> String {
+    format!("{} hook `{}` failed: {}", event, hook.command, error)
+}
+
+async fn run_command(hook: &HookCommand, input: &HookInput) -> Result<String> {
+    let timeout = hook.timeout();
+    debug!(
+        "Running {} hook with timeout {:?}: {}",
+        input.event, timeout, hook.command
+    );
+
+    let payload = serde_json::to_vec(input)?;
+    let output = tokio::time::timeout(
+        timeout,
+        run_shell_command(
+            &hook.command,
+            &input.working_dir,
+            &[
+                ("GOOSE_HOOK_EVENT", &input.event.to_string()),
+                ("GOOSE_SESSION_ID", &input.session_id),
+            ],
+            &payload,
+        ),
+    )
+    .await
+    .map_err(|_| anyhow!("timed out after {:?}", timeout))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("exited with {}: {}", output.status, stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run `command` with the platform shell in `working_dir`, writing `payload` to its
+/// stdin and collecting its output. The command is killed if the future is dropped.
+pub(crate) async fn run_shell_command(
+    command: &str,
+    working_dir: &Path,
+    envs: &[(&str, &str)],
+    payload: &[u8],
+) -> Result<Output> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    if working_dir.is_dir() {
+        cmd.current_dir(working_dir);
+    }
+    cmd.env("GOOSE_TERMINAL", "1")
+        .envs(envs.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that never reads stdin closes the pipe early; that is not an error.
+        let _ = stdin.write_all(payload).await;
+    }
+    Ok(child.wait_with_output().await?)
+}
+
+/// Runs hooks for an agent and holds the failures until the reply loop can show them.
+#[derive(Debug, Default)]
+pub struct HookManager {
+    notices: Mutex<Vec<String>>,
+    started_sessions: Mutex<HashSet<String>>,
+    /// Output of the `pre_tool_use` hooks by tool request id, appended to the tool
+    /// result once the call is dispatched
+    pre_tool_context: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl HookManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn has_hooks(event: HookEvent) -> bool {
+        !HooksConfig::load().commands(event).is_empty()
+    }
+
+    pub async fn run(&self, input: HookInput) -> HookOutcome {
+        let config = HooksConfig::load();
+        if config.commands(input.event).is_empty() {
+            return HookOutcome::default();
+        }
+
+        let outcome = config.run(input).await;
+        if !outcome.failures.is_empty() {
+            for failure in &outcome.failures {
+                warn!("{}", failure);
+            }
+            self.notices
+                .lock()
+                .await
+                .extend(outcome.failures.iter().cloned());
+        }
+        outcome
+    }
+
+    /// Failures recorded since the last call
+    pub async fn take_notices(&self) -> Vec<String> {
+        std::mem::take(&mut *self.notices.lock().await)
+    }
+
+    /// True the first time this agent sees the session, so `session_start` runs once
+    pub async fn mark_session_started(&self, session_id: &str) -> bool {
+        self.started_sessions
+            .lock()
+            .await
+            .insert(session_id.to_string())
+    }
+
+    pub async fn mark_session_ended(&self, session_id: &str) -> bool {
+        self.started_sessions.lock().await.remove(session_id)
+    }
+
+    /// Replace the context held for the previous batch of tool requests, so output
+    /// for calls that were declined does not pile up
+    pub async fn set_pre_tool_context(&self, contexts: HashMap<String, Vec<String>>) {
+        *self.pre_tool_context.lock().await = contexts;
+    }
+
+    pub async fn take_pre_tool_context(&self, request_id: &str) -> Vec<String> {
+        self.pre_tool_context
+            .lock()
+            .await
+            .remove(request_id)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hook(command: &str, matcher: Option<&str>) -> HookCommand {
+        HookCommand {
+            command: command.to_string(),
+            matcher: matcher.map(str::to_string),
+            timeout: None,
+        }
+    }
+
+    fn tool_input(dir: &Path, name: &str, arguments: Value) -> HookInput {
+        HookInput::new(HookEvent::PreToolUse, "20250101_1", dir)
+            .with_tool(name, arguments.as_object())
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: HooksConfig = serde_yaml::from_str(
+            "post_tool_use:\n  - matcher: developer__text_editor\n    command: cargo fmt\n    timeout: 5\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.post_tool_use,
+            vec![HookCommand {
+                command: "cargo fmt".to_string(),
+                matcher: Some("developer__text_editor".to_string()),
+                timeout: Some(5),
+            }]
+        );
+        assert!(config.pre_tool_use.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hook_reads_input_and_returns_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = HooksConfig {
+            pre_tool_use: vec![
+                hook("grep -o '\"tool_name\":\"[a-z_]*\"'", None),
+                hook("echo never", Some("other__.*")),
+                hook("pwd", Some("developer__.*")),
+            ],
+            ..Default::default()
+        };
+
+        let outcome = config
+            .run(tool_input(
+                dir.path(),
+                "developer__shell",
+                json!({"command": "ls"}),
+            ))
+            .await;
+
+        assert_eq!(outcome.blocked, None);
+        assert_eq!(outcome.failures, Vec::<String>::new());
+        assert_eq!(outcome.context.len(), 2);
+        assert_eq!(outcome.context[0], "\"tool_name\":\"developer__shell\"");
+        assert!(outcome.context[1].ends_with(dir.path().file_name().unwrap().to_str().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_pre_tool_hook_rewrites_and_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let rewrite = hook(
+            r#"echo '{"tool_input": {"command": "ls -la"}, "context": "added -la"}'"#,
+            None,
+        );
+        let block_rm = hook(
+            r#"grep -q 'rm -rf' && echo '{"decision": "block", "reason": "no rm -rf"}' || true"#,
+            None,
+        );
+
+        let config = HooksConfig {
+            pre_tool_use: vec![rewrite.clone(), block_rm.clone()],
+            ..Default::default()
+        };
+        let outcome = config
+            .run(tool_input(
+                dir.path(),
+                "developer__shell",
+                json!({"command": "ls"}),
+            ))
+            .await;
+        assert_eq!(outcome.blocked, None);
+        assert_eq!(
+            outcome.tool_input,
+            json!({"command": "ls -la"}).as_object().cloned()
+        );
+        assert_eq!(outcome.context, vec!["added -la".to_string()]);
+
+        let config = HooksConfig {
+            pre_tool_use: vec![block_rm, rewrite],
+            ..Default::default()
+        };
+        let outcome = config
+            .run(tool_input(
+                dir.path(),
+                "developer__shell",
+                json!({"command": "rm -rf /"}),
+            ))
+            .await;
+        assert_eq!(outcome.blocked, Some("no rm -rf".to_string()));
+        assert_eq!(outcome.tool_input, None);
+    }
+
+    #[tokio::test]
+    async fn test_failing_hooks_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = HooksConfig {
+            post_tool_use: vec![
+                hook("echo broken >&2; exit 3", None),
+                HookCommand {
+                    timeout: Some(1),
+                    ..hook("sleep 5", None)
+                },
+                hook("true", Some("(")),
+                hook("echo formatted", None),
+            ],
+            ..Default::default()
+        };
+
+        let input = HookInput::new(HookEvent::PostToolUse, "20250101_1", dir.path())
+            .with_tool("developer__text_editor", None)
+            .with_tool_output("ok".to_string(), false);
+        let outcome = config.run(input).await;
+
+        assert_eq!(outcome.failures.len(), 3);
+        assert!(outcome.failures[0].contains("broken"));
+        assert!(outcome.failures[1].contains("timed out"));
+        assert!(outcome.failures[2].contains("invalid matcher"));
+        assert_eq!(outcome.context, vec!["formatted".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_session_started_once() {
+        let manager = HookManager::new();
+        assert!(manager.mark_session_started("a").await);
+        assert!(!manager.mark_session_started("a").await);
+        assert!(manager.mark_session_ended("a").await);
+        assert!(manager.take_notices().await.is_empty());
+    }
+}
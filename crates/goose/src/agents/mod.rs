@@ -1,10 +1,13 @@
 mod agent;
+pub mod approval;
+pub mod budget;
 pub(crate) mod chatrecall_extension;
 pub mod extension;
 pub mod extension_malware_check;
 pub mod extension_manager;
 pub mod extension_manager_extension;
 pub mod final_output_tool;
+pub mod hooks;
 mod large_response_handler;
 pub mod mcp_client;
 pub mod model_selector;
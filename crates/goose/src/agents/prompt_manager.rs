@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 
 use crate::agents::extension::ExtensionInfo;
 use crate::agents::recipe_tools::dynamic_task_tools::should_enabled_subagents;
@@ -204,9 +205,9 @@ impl PromptManager {
         }
     }
 
-    pub async fn get_recipe_prompt(&self) -> String {
+    pub async fn get_recipe_prompt(&self, working_dir: &Path) -> String {
         let context: HashMap<&str, Value> = HashMap::new();
-        prompt_template::render_global_file("recipe.md", &context)
+        prompt_template::render_session_file(working_dir, "recipe.md", &context)
             .unwrap_or_else(|_| "The recipe prompt is busted. Tell the user.".to_string())
     }
 }
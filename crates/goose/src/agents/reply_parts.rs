@@ -1,15 +1,18 @@
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_stream::try_stream;
 use futures::stream::StreamExt;
 use tracing::debug;
 
 use super::super::agents::Agent;
+use crate::agents::budget::{Budget, BudgetUsage};
 use crate::conversation::message::{Message, MessageContent, ToolRequest};
 use crate::conversation::Conversation;
 use crate::providers::base::{stream_from_single_message, MessageStream, Provider, ProviderUsage};
 use crate::providers::errors::ProviderError;
+use crate::providers::pricing::estimate_cost_usd;
 use crate::providers::toolshim::{
     augment_message_with_tool_calls, convert_tool_messages_to_text,
     modify_system_prompt_for_tool_json, OllamaInterpreter,
@@ -252,7 +255,33 @@ impl Agent {
         (frontend_requests, other_requests, filtered_message)
     }
 
+    /// What the session has used so far against its budget. The cost is the one
+    /// accumulated per provider call, and only reported when there is a cost limit.
+    pub(crate) async fn budget_usage(
+        &self,
+        session_id: &str,
+        budget: &Budget,
+        reply_started: Instant,
+    ) -> Result<BudgetUsage> {
+        let session = SessionManager::get_session(session_id, false).await?;
+        let input_tokens = session.accumulated_input_tokens.unwrap_or(0).max(0) as u64;
+        let output_tokens = session.accumulated_output_tokens.unwrap_or(0).max(0) as u64;
+
+        Ok(BudgetUsage {
+            cost_usd: budget.max_cost_usd.and_then(|_| {
+                // Nothing has been spent before the first call, priced or not
+                session
+                    .accumulated_cost_usd
+                    .or((input_tokens == 0 && output_tokens == 0).then_some(0.0))
+            }),
+            input_tokens,
+            output_tokens,
+            elapsed: reply_started.elapsed(),
+        })
+    }
+
     pub(crate) async fn update_session_metrics(
+        &self,
         session_config: &crate::agents::types::SessionConfig,
         usage: &ProviderUsage,
         is_compaction_usage: bool,
@@ -260,6 +289,21 @@ impl Agent {
         let session_id = session_config.id.as_str();
         let session = SessionManager::get_session(session_id, false).await?;
 
+        // Price each call at the model that made it, so switching models mid-session
+        // does not reprice what was already spent
+        let provider = self.provider().await?;
+        let cost = estimate_cost_usd(
+            provider.get_name(),
+            &usage.model,
+            usage.usage.input_tokens.unwrap_or(0).max(0) as usize,
+            usage.usage.output_tokens.unwrap_or(0).max(0) as usize,
+        )
+        .await;
+        let accumulated_cost = match (session.accumulated_cost_usd, cost) {
+            (Some(total), Some(cost)) => Some(total + cost),
+            (total, cost) => total.or(cost),
+        };
+
         let accumulate = |a: Option<i32>, b: Option<i32>| -> Option<i32> {
             match (a, b) {
                 (Some(x), Some(y)) => Some(x + y),
@@ -294,6 +338,7 @@ impl Agent {
             .accumulated_total_tokens(accumulated_total)
             .accumulated_input_tokens(accumulated_input)
             .accumulated_output_tokens(accumulated_output)
+            .accumulated_cost_usd(accumulated_cost)
             .apply()
             .await?;
 
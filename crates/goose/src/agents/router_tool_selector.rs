@@ -7,11 +7,12 @@ use serde::Serialize;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::conversation::message::Message;
-use crate::prompt_template::render_global_file;
+use crate::prompt_template::render_session_file;
 use crate::providers::base::Provider;
 
 #[derive(Serialize)]
@@ -22,7 +23,11 @@ struct ToolSelectorContext {
 
 #[async_trait]
 pub trait RouterToolSelector: Send + Sync {
-    async fn select_tools(&self, params: JsonObject) -> Result<Vec<Content>, ErrorData>;
+    async fn select_tools(
+        &self,
+        params: JsonObject,
+        working_dir: &Path,
+    ) -> Result<Vec<Content>, ErrorData>;
     async fn index_tools(&self, tools: &[Tool], extension_name: &str) -> Result<(), ErrorData>;
     async fn remove_tool(&self, tool_name: &str) -> Result<(), ErrorData>;
     async fn record_tool_call(&self, tool_name: &str) -> Result<(), ErrorData>;
@@ -47,7 +52,11 @@ impl LLMToolSelector {
 
 #[async_trait]
 impl RouterToolSelector for LLMToolSelector {
-    async fn select_tools(&self, params: JsonObject) -> Result<Vec<Content>, ErrorData> {
+    async fn select_tools(
+        &self,
+        params: JsonObject,
+        working_dir: &Path,
+    ) -> Result<Vec<Content>, ErrorData> {
         let query = params
             .get("query")
             .and_then(|v| v.as_str())
@@ -84,8 +93,8 @@ impl RouterToolSelector for LLMToolSelector {
                 query: query.to_string(),
             };
 
-            let user_prompt =
-                render_global_file("router_tool_selector.md", &context).map_err(|e| ErrorData {
+            let user_prompt = render_session_file(working_dir, "router_tool_selector.md", &context)
+                .map_err(|e| ErrorData {
                     code: ErrorCode::INTERNAL_ERROR,
                     message: Cow::from(format!("Failed to render prompt template: {}", e)),
                     data: None,
@@ -186,6 +186,8 @@ impl Agent {
             paused: false,
             current_session_id: None,
             process_start_time: None,
+            owner: None,
+            budget: None,
         };
 
         match scheduler.add_scheduled_job(job).await {
@@ -138,6 +138,7 @@ fn get_agent_messages(
             schedule_id: None,
             max_turns: task_config.max_turns.map(|v| v as u32),
             retry_config: None,
+            budget: None,
         };
 
         let mut stream = crate::session_context::with_session_id(Some(session.id.clone()), async {
@@ -148,7 +149,9 @@ fn get_agent_messages(
         while let Some(message_result) = stream.next().await {
             match message_result {
                 Ok(AgentEvent::Message(msg)) => conversation.push(msg),
-                Ok(AgentEvent::McpNotification(_)) | Ok(AgentEvent::ModelChange { .. }) => {}
+                Ok(AgentEvent::McpNotification(_))
+                | Ok(AgentEvent::ModelChange { .. })
+                | Ok(AgentEvent::BudgetExceeded(_)) => {}
                 Ok(AgentEvent::HistoryReplaced(updated_conversation)) => {
                     conversation = updated_conversation;
                 }
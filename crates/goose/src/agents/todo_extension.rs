@@ -1,6 +1,7 @@
 use crate::agents::extension::PlatformExtensionContext;
 use crate::agents::mcp_client::{Error, McpClientTrait};
 use crate::session::extension_data::ExtensionState;
+use crate::session::plan::{Plan, PlanStatus};
 use crate::session::{extension_data, SessionManager};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -121,6 +122,16 @@ impl TodoClient {
         if let Some(session_id) = &self.context.session_id {
             match SessionManager::get_session(session_id, false).await {
                 Ok(mut session) => {
+                    // A plan being executed is tracked through the todo list
+                    if let Some(mut plan) = Plan::from_extension_data(&session.extension_data) {
+                        if plan.status == PlanStatus::Executing
+                            && plan.sync_from_todo(&content)
+                            && plan.to_extension_data(&mut session.extension_data).is_err()
+                        {
+                            return Err("Failed to serialize plan state".to_string());
+                        }
+                    }
+
                     let todo_state = extension_data::TodoState::new(content);
                     if todo_state
                         .to_extension_data(&mut session.extension_data)
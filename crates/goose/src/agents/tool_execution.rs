@@ -9,7 +9,9 @@ use tokio_util::sync::CancellationToken;
 
 use crate::config::permission::PermissionLevel;
 use crate::mcp_utils::ToolResult;
-use crate::permission::Permission;
+use crate::permission::permission_confirmation::PrincipalType;
+use crate::permission::permission_store::DECISION_RETENTION;
+use crate::permission::{Permission, PermissionConfirmation, ToolPermissionStore};
 use rmcp::model::{Content, ServerNotification};
 
 // ToolCallResult combines the result of a tool call with an optional notification stream that
@@ -29,6 +31,7 @@ impl From<ToolResult<Vec<Content>>> for ToolCallResult {
 }
 
 use super::agent::{tool_stream, ToolStream};
+use super::approval::ApprovalRequest;
 use crate::agents::Agent;
 use crate::conversation::message::{Message, ToolRequest};
 use crate::session::Session;
@@ -75,10 +78,25 @@ impl Agent {
                         request.id.clone(),
                         tool_call.name.to_string().clone(),
                         tool_call.arguments.clone().unwrap_or_default(),
-                        security_message,
+                        security_message.clone(),
                     );
                     yield confirmation;
 
+                    if let Some(broker) = self.approval_broker().await {
+                        let decision = broker.decide(&ApprovalRequest {
+                            session_id: session.id.clone(),
+                            request_id: request.id.clone(),
+                            tool_name: tool_call.name.to_string(),
+                            arguments: tool_call.arguments.clone().unwrap_or_default(),
+                            warning: security_message,
+                            working_dir: session.working_dir.clone(),
+                        }).await;
+                        self.handle_confirmation(request.id.clone(), PermissionConfirmation {
+                            principal_type: PrincipalType::Tool,
+                            permission: decision.into(),
+                        }).await;
+                    }
+
                     let mut rx = self.confirmation_rx.lock().await;
                     while let Some((req_id, confirmation)) = rx.recv().await {
                         if req_id == request.id {
@@ -93,6 +111,16 @@ impl Agent {
                                 );
                             }
 
+                            // Keep the decision so `goose permissions suggest` can learn from it
+                            if confirmation.permission != Permission::Cancel {
+                                let allowed = matches!(confirmation.permission, Permission::AllowOnce | Permission::AlwaysAllow);
+                                if let Err(e) = ToolPermissionStore::load()
+                                    .and_then(|mut store| store.record_permission(request, allowed, Some(DECISION_RETENTION)))
+                                {
+                                    tracing::warn!("Failed to record permission decision for {}: {}", tool_call.name, e);
+                                }
+                            }
+
                             if confirmation.permission == Permission::AllowOnce || confirmation.permission == Permission::AlwaysAllow {
                                 let (req_id, tool_result) = self.dispatch_tool_call(tool_call.clone(), request.id.clone(), cancellation_token.clone(), session).await;
                                 let mut futures = tool_futures.lock().await;
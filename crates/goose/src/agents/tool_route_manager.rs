@@ -8,6 +8,7 @@ use crate::conversation::message::ToolRequest;
 use crate::providers::base::Provider;
 use anyhow::{anyhow, Result};
 use rmcp::model::{ErrorCode, ErrorData, JsonObject, Tool};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::error;
@@ -52,10 +53,11 @@ impl ToolRouteManager {
     pub async fn dispatch_route_search_tool(
         &self,
         arguments: JsonObject,
+        working_dir: &Path,
     ) -> Result<ToolCallResult, ErrorData> {
         let selector = self.router_tool_selector.lock().await.clone();
         match selector.as_ref() {
-            Some(selector) => match selector.select_tools(arguments).await {
+            Some(selector) => match selector.select_tools(arguments, working_dir).await {
                 Ok(tools) => Ok(ToolCallResult::from(Ok(tools))),
                 Err(e) => Err(ErrorData::new(
                     ErrorCode::INTERNAL_ERROR,
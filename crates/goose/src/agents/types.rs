@@ -1,3 +1,4 @@
+use crate::agents::budget::Budget;
 use crate::mcp_utils::ToolResult;
 use crate::providers::base::Provider;
 use rmcp::model::{Content, Tool};
@@ -92,4 +93,7 @@ pub struct SessionConfig {
     /// Retry configuration for automated validation and recovery
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_config: Option<RetryConfig>,
+    /// Limits layered over the global budget, e.g. from a recipe or scheduled job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
 }
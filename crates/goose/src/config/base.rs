@@ -1,4 +1,5 @@
 use crate::config::paths::Paths;
+use crate::config::project;
 use crate::config::GooseMode;
 use fs2::FileExt;
 use keyring::Entry;
@@ -6,8 +7,9 @@ use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_yaml::Mapping;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fmt;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -66,7 +68,9 @@ impl From<keyring::Error> for ConfigError {
 ///
 /// Configuration values are loaded with the following precedence:
 /// 1. Environment variables (exact key match)
-/// 2. Configuration file (~/.config/goose/config.yaml by default)
+/// 2. Project configuration file (.goose/config.yaml in the working directory
+///    or one of its parents), restricted to a small set of overridable keys
+/// 3. Configuration file (~/.config/goose/config.yaml by default)
 ///
 /// Secrets are loaded with the following precedence:
 /// 1. Environment variables (exact key match)
@@ -102,10 +106,30 @@ impl From<keyring::Error> for ConfigError {
 /// For goose-specific configuration, consider prefixing with "goose_" to avoid conflicts.
 pub struct Config {
     config_path: PathBuf,
+    project_path: Option<PathBuf>,
     secrets: SecretStorage,
     guard: Mutex<()>,
 }
 
+/// The layer a configuration value was resolved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigLayer {
+    Environment,
+    Project,
+    User,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLayer::Environment => write!(f, "env"),
+            ConfigLayer::Project => write!(f, "project"),
+            ConfigLayer::User => write!(f, "user"),
+        }
+    }
+}
+
 enum SecretStorage {
     Keyring { service: String },
     File { path: PathBuf },
@@ -130,8 +154,13 @@ impl Default for Config {
                 service: KEYRING_SERVICE.to_string(),
             },
         };
+        let project_path = env::current_dir()
+            .ok()
+            .and_then(|cwd| project::find_project_config(&cwd));
+
         Config {
             config_path,
+            project_path,
             secrets,
             guard: Mutex::new(()),
         }
@@ -173,6 +202,7 @@ impl Config {
     pub fn new<P: AsRef<Path>>(config_path: P, service: &str) -> Result<Self, ConfigError> {
         Ok(Config {
             config_path: config_path.as_ref().to_path_buf(),
+            project_path: None,
             secrets: SecretStorage::Keyring {
                 service: service.to_string(),
             },
@@ -190,6 +220,7 @@ impl Config {
     ) -> Result<Self, ConfigError> {
         Ok(Config {
             config_path: config_path.as_ref().to_path_buf(),
+            project_path: None,
             secrets: SecretStorage::File {
                 path: secrets_path.as_ref().to_path_buf(),
             },
@@ -197,6 +228,12 @@ impl Config {
         })
     }
 
+    /// Layer a project configuration file over this configuration.
+    pub fn with_project_config<P: AsRef<Path>>(mut self, project_path: P) -> Self {
+        self.project_path = Some(project_path.as_ref().to_path_buf());
+        self
+    }
+
     pub fn exists(&self) -> bool {
         self.config_path.exists()
     }
@@ -209,6 +246,35 @@ impl Config {
         self.config_path.to_string_lossy().to_string()
     }
 
+    /// Path of the project configuration file in effect, if any.
+    pub fn project_path(&self) -> Option<String> {
+        self.project_path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+    }
+
+    // Load the allowed values from the project config file, given the user's
+    // values. A missing or unreadable project file never prevents goose from starting.
+    fn load_project(&self, user_values: &Mapping) -> Mapping {
+        let Some(path) = &self.project_path else {
+            return Mapping::new();
+        };
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Failed to read project config {:?}: {}", path, e);
+                return Mapping::new();
+            }
+        };
+        match parse_yaml_content(&content) {
+            Ok(values) => project::filter_project_values(values, user_values, path),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid project config {:?}: {}", path, e);
+                Mapping::new()
+            }
+        }
+    }
+
     fn load(&self) -> Result<Mapping, ConfigError> {
         if self.config_path.exists() {
             self.load_values_with_recovery()
@@ -241,6 +307,41 @@ impl Config {
         })
     }
 
+    /// All configuration values after layering, along with the layer each
+    /// value was resolved from. Environment variables are only reported for
+    /// keys that are also present in a config file.
+    pub fn all_values_with_layers(
+        &self,
+    ) -> Result<BTreeMap<String, (Value, ConfigLayer)>, ConfigError> {
+        let mut layered: BTreeMap<String, (serde_yaml::Value, ConfigLayer)> = BTreeMap::new();
+        let user_values = self.load()?;
+        let project_values = self.load_project(&user_values);
+        for (k, v) in user_values {
+            if let Some(k) = k.as_str() {
+                layered.insert(k.to_string(), (v, ConfigLayer::User));
+            }
+        }
+        for (k, v) in project_values {
+            if let Some(k) = k.as_str() {
+                let entry = layered
+                    .entry(k.to_string())
+                    .or_insert((serde_yaml::Value::Null, ConfigLayer::Project));
+                project::merge_values(&mut entry.0, v);
+                entry.1 = ConfigLayer::Project;
+            }
+        }
+
+        let mut result = BTreeMap::new();
+        for (k, (v, layer)) in layered {
+            let resolved = match env::var(k.to_uppercase()) {
+                Ok(val) => (Self::parse_env_value(&val)?, ConfigLayer::Environment),
+                Err(_) => (serde_json::to_value(v)?, layer),
+            };
+            result.insert(k, resolved);
+        }
+        Ok(result)
+    }
+
     // Helper method to create and save default config with consistent logging
     fn create_and_save_default_config(
         &self,
@@ -569,7 +670,8 @@ impl Config {
     ///
     /// This will attempt to get the value from:
     /// 1. Environment variable with the exact key name
-    /// 2. Configuration file
+    /// 2. Project configuration file, for keys a project may override
+    /// 3. Configuration file
     ///
     /// The value will be deserialized into the requested type. This works with
     /// both simple types (String, i32, etc.) and complex types that implement
@@ -582,12 +684,51 @@ impl Config {
     /// - The value cannot be deserialized into the requested type
     /// - There is an error reading the config file
     pub fn get_param<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Result<T, ConfigError> {
+        self.get_param_with_layer(key).map(|(value, _)| value)
+    }
+
+    /// Get a configuration value (non-secret) along with the layer it came from.
+    ///
+    /// Lookup order is environment, then the project config file for keys a
+    /// project may override, then the user config file. Mapping values present
+    /// in both config files are merged, with project entries taking precedence.
+    pub fn get_param_with_layer<T: for<'de> Deserialize<'de>>(
+        &self,
+        key: &str,
+    ) -> Result<(T, ConfigLayer), ConfigError> {
         let env_key = key.to_uppercase();
         if let Ok(val) = env::var(&env_key) {
             let value = Self::parse_env_value(&val)?;
-            return Ok(serde_json::from_value(value)?);
+            return Ok((serde_json::from_value(value)?, ConfigLayer::Environment));
         }
 
+        let user_values = self.load()?;
+        let user_value = user_values.get(key).cloned();
+        let project_value = if project::is_project_overridable(key) {
+            self.load_project(&user_values).get(key).cloned()
+        } else {
+            None
+        };
+
+        let (value, layer) = match (user_value, project_value) {
+            (Some(mut base), Some(overlay)) => {
+                project::merge_values(&mut base, overlay);
+                (base, ConfigLayer::Project)
+            }
+            (None, Some(overlay)) => (overlay, ConfigLayer::Project),
+            (Some(base), None) => (base, ConfigLayer::User),
+            (None, None) => return Err(ConfigError::NotFound(key.to_string())),
+        };
+        Ok((serde_yaml::from_value(value)?, layer))
+    }
+
+    /// Get a configuration value from the user config file only, ignoring
+    /// environment and project overrides. Use this when reading a value in
+    /// order to modify and write it back.
+    pub fn get_user_param<T: for<'de> Deserialize<'de>>(
+        &self,
+        key: &str,
+    ) -> Result<T, ConfigError> {
         let values = self.load()?;
         values
             .get(key)
@@ -1465,4 +1606,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_project_config_overlay() -> Result<(), ConfigError> {
+        let config_file = NamedTempFile::new().unwrap();
+        let project_file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            project_file.path(),
+            "GOOSE_AUTO_COMPACT_THRESHOLD: 0.5\nproject_only_key: nope\nextensions:\n  developer:\n    enabled: false\n  backdoor:\n    type: stdio\n    cmd: sh\n",
+        )?;
+        let config = Config::new(config_file.path(), TEST_KEYRING_SERVICE)?
+            .with_project_config(project_file.path());
+
+        config.set_param("GOOSE_AUTO_COMPACT_THRESHOLD", 0.8)?;
+        config.set_param(
+            "extensions",
+            serde_json::json!({"developer": {"enabled": true, "name": "developer"}}),
+        )?;
+
+        let (threshold, layer): (f64, ConfigLayer) =
+            config.get_param_with_layer("GOOSE_AUTO_COMPACT_THRESHOLD")?;
+        assert_eq!(threshold, 0.5);
+        assert_eq!(layer, ConfigLayer::Project);
+
+        // Project mappings merge into the user mapping
+        let extensions: Value = config.get_param("extensions")?;
+        assert_eq!(extensions["developer"]["enabled"], Value::Bool(false));
+        assert_eq!(extensions["developer"]["name"], "developer");
+        // but cannot define new extensions
+        assert!(extensions.get("backdoor").is_none());
+
+        // Keys outside the allow list are ignored
+        let result: Result<String, ConfigError> = config.get_param("project_only_key");
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+
+        // The user layer is left untouched
+        let user_threshold: f64 = config.get_user_param("GOOSE_AUTO_COMPACT_THRESHOLD")?;
+        assert_eq!(user_threshold, 0.8);
+
+        let layered = config.all_values_with_layers()?;
+        assert_eq!(layered["extensions"].1, ConfigLayer::Project);
+        assert!(!layered.contains_key("project_only_key"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_config_cannot_set_secrets() -> Result<(), ConfigError> {
+        let config_file = NamedTempFile::new().unwrap();
+        let secrets_file = NamedTempFile::new().unwrap();
+        let project_file = NamedTempFile::new().unwrap();
+        std::fs::write(project_file.path(), "PROJECT_API_KEY: from-project\n")?;
+        let config = Config::new_with_file_secrets(config_file.path(), secrets_file.path())?
+            .with_project_config(project_file.path());
+
+        let secret: Result<String, ConfigError> = config.get_secret("PROJECT_API_KEY");
+        assert!(matches!(secret, Err(ConfigError::NotFound(_))));
+        let param: Result<String, ConfigError> = config.get_param("PROJECT_API_KEY");
+        assert!(matches!(param, Err(ConfigError::NotFound(_))));
+
+        Ok(())
+    }
 }
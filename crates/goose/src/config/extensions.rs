@@ -1,4 +1,4 @@
-use super::base::Config;
+use super::base::{Config, ConfigError};
 use crate::agents::extension::PLATFORM_EXTENSIONS;
 use crate::agents::ExtensionConfig;
 use indexmap::IndexMap;
@@ -27,16 +27,25 @@ pub fn name_to_key(name: &str) -> String {
         .to_lowercase()
 }
 
+// Extensions as seen by the running session, including project overrides.
 fn get_extensions_map() -> IndexMap<String, ExtensionEntry> {
-    let raw: Mapping = Config::global()
-        .get_param(EXTENSIONS_CONFIG_KEY)
-        .unwrap_or_else(|err| {
-            warn!(
-                "Failed to load {}: {err}. Falling back to empty object.",
-                EXTENSIONS_CONFIG_KEY
-            );
-            Default::default()
-        });
+    parse_extensions_map(Config::global().get_param(EXTENSIONS_CONFIG_KEY))
+}
+
+// Extensions as stored in the user config file. Modifications start from this
+// map so that project-level overrides are never written back to the user config.
+fn get_user_extensions_map() -> IndexMap<String, ExtensionEntry> {
+    parse_extensions_map(Config::global().get_user_param(EXTENSIONS_CONFIG_KEY))
+}
+
+fn parse_extensions_map(raw: Result<Mapping, ConfigError>) -> IndexMap<String, ExtensionEntry> {
+    let raw = raw.unwrap_or_else(|err| {
+        warn!(
+            "Failed to load {}: {err}. Falling back to empty object.",
+            EXTENSIONS_CONFIG_KEY
+        );
+        Default::default()
+    });
 
     let mut extensions_map = IndexMap::with_capacity(raw.len());
     for (k, v) in raw {
@@ -92,20 +101,20 @@ pub fn get_extension_by_name(name: &str) -> Option<ExtensionConfig> {
 }
 
 pub fn set_extension(entry: ExtensionEntry) {
-    let mut extensions = get_extensions_map();
+    let mut extensions = get_user_extensions_map();
     let key = entry.config.key();
     extensions.insert(key, entry);
     save_extensions_map(extensions);
 }
 
 pub fn remove_extension(key: &str) {
-    let mut extensions = get_extensions_map();
+    let mut extensions = get_user_extensions_map();
     extensions.shift_remove(key);
     save_extensions_map(extensions);
 }
 
 pub fn set_extension_enabled(key: &str, enabled: bool) {
-    let mut extensions = get_extensions_map();
+    let mut extensions = get_user_extensions_map();
     if let Some(entry) = extensions.get_mut(key) {
         entry.enabled = enabled;
         save_extensions_map(extensions);
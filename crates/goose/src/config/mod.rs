@@ -5,12 +5,13 @@ pub mod extensions;
 pub mod goose_mode;
 pub mod paths;
 pub mod permission;
+pub mod project;
 pub mod search_path;
 pub mod signup_openrouter;
 pub mod signup_tetrate;
 
 pub use crate::agents::ExtensionConfig;
-pub use base::{Config, ConfigError};
+pub use base::{Config, ConfigError, ConfigLayer};
 pub use declarative_providers::DeclarativeProviderConfig;
 pub use experiments::ExperimentManager;
 pub use extensions::{
@@ -1,5 +1,8 @@
 use crate::config::paths::Paths;
+use crate::config::Config;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -16,10 +19,59 @@ pub enum PermissionLevel {
 
 /// Struct representing the configuration of permissions, categorized by level.
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
 pub struct PermissionConfig {
     pub always_allow: Vec<String>, // List of tools that are always allowed
     pub ask_before: Vec<String>,   // List of tools that require user consent
     pub never_allow: Vec<String>,  // List of tools that are never allowed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow_rules: Vec<PermissionRule>, // Calls allowed by their arguments
+}
+
+/// Allows calls to a tool whose string argument matches a regex, e.g. only the
+/// `developer__shell` commands that start with `cargo test`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct PermissionRule {
+    pub tool: String,
+    pub argument: String,
+    pub pattern: String,
+}
+
+impl PermissionRule {
+    /// A prefix rule: `argument` is `prefix`, optionally followed by more words that
+    /// cannot chain, pipe or substitute shell commands
+    pub fn prefix(tool: &str, argument: &str, prefix: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            argument: argument.to_string(),
+            pattern: format!(r"^{}([ \t][^;&|`$()<>\r\n]*)?$", regex::escape(prefix)),
+        }
+    }
+
+    /// An exact rule: `argument` is `value` and nothing else
+    pub fn exact(tool: &str, argument: &str, value: &str) -> Self {
+        Self {
+            tool: tool.to_string(),
+            argument: argument.to_string(),
+            pattern: format!("^{}$", regex::escape(value)),
+        }
+    }
+
+    pub fn matches(&self, tool_name: &str, arguments: &Map<String, Value>) -> bool {
+        if self.tool != tool_name {
+            return false;
+        }
+        let Some(Value::String(value)) = arguments.get(&self.argument) else {
+            return false;
+        };
+        match Regex::new(&self.pattern) {
+            Ok(regex) => regex.is_match(value),
+            Err(e) => {
+                tracing::warn!("Ignoring permission rule for {}: {}", self.tool, e);
+                false
+            }
+        }
+    }
 }
 
 /// PermissionManager manages permission configurations for various tools.
@@ -27,30 +79,30 @@ pub struct PermissionConfig {
 pub struct PermissionManager {
     config_path: PathBuf, // Path to the permission configuration file
     permission_map: HashMap<String, PermissionConfig>, // Mapping of permission names to configurations
+    project_permission: Option<PermissionConfig>, // Read-only user permissions from the project config
 }
 
 // Constants representing specific permission categories
 const USER_PERMISSION: &str = "user";
 const SMART_APPROVE_PERMISSION: &str = "smart_approve";
+// Project config key holding user permission policies for that project
+const PROJECT_PERMISSIONS_KEY: &str = "permissions";
 
 /// Implements the default constructor for `PermissionManager`.
 impl Default for PermissionManager {
     fn default() -> Self {
         let config_path = Paths::config_dir().join("permission.yaml");
 
-        // Load the existing configuration file or create an empty map if the file doesn't exist
-        let permission_map = if config_path.exists() {
-            // Load the configuration file
-            let file_contents =
-                fs::read_to_string(&config_path).expect("Failed to read permission.yaml");
-            serde_yaml::from_str(&file_contents).unwrap_or_else(|_| HashMap::new())
-        } else {
-            HashMap::new() // No config file, create an empty map
-        };
+        let permission_map = Self::load_permission_map(&config_path);
+
+        let project_permission = Config::global()
+            .get_param::<PermissionConfig>(PROJECT_PERMISSIONS_KEY)
+            .ok();
 
         PermissionManager {
             config_path,
             permission_map,
+            project_permission,
         }
     }
 }
@@ -60,30 +112,94 @@ impl PermissionManager {
     pub fn new<P: AsRef<Path>>(config_path: P) -> Self {
         let config_path = config_path.as_ref().to_path_buf();
 
-        // Load the existing configuration file or create an empty map if the file doesn't exist
-        let permission_map = if config_path.exists() {
-            // Load the configuration file
+        let permission_map = Self::load_permission_map(&config_path);
+
+        PermissionManager {
+            config_path,
+            permission_map,
+            project_permission: None,
+        }
+    }
+
+    /// Load the existing configuration file or create an empty map if the file doesn't exist
+    fn load_permission_map(config_path: &Path) -> HashMap<String, PermissionConfig> {
+        if config_path.exists() {
             let file_contents =
-                fs::read_to_string(&config_path).expect("Failed to read permission.yaml");
+                fs::read_to_string(config_path).expect("Failed to read permission.yaml");
             serde_yaml::from_str(&file_contents).unwrap_or_else(|_| HashMap::new())
         } else {
             HashMap::new() // No config file, create an empty map
-        };
+        }
+    }
 
-        PermissionManager {
-            config_path,
-            permission_map,
+    /// Re-reads permission.yaml, which other managers (a CLI command, a server route or
+    /// another agent) may have changed since this one was loaded. A file that cannot be
+    /// read or parsed, e.g. halfway through being written, leaves the current map in place.
+    pub fn reload(&mut self) {
+        if !self.config_path.exists() {
+            self.permission_map.clear();
+            return;
+        }
+        match fs::read_to_string(&self.config_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| serde_yaml::from_str(&contents).map_err(anyhow::Error::from))
+        {
+            Ok(permission_map) => self.permission_map = permission_map,
+            Err(e) => tracing::warn!(
+                "Keeping loaded permissions, failed to reload {}: {}",
+                self.config_path.display(),
+                e
+            ),
         }
     }
 
+    /// Overlay read-only user permission policies, as set in a project config.
+    /// These can only make a tool stricter than the stored user permissions.
+    pub fn with_project_permission(mut self, project_permission: PermissionConfig) -> Self {
+        self.project_permission = Some(project_permission);
+        self
+    }
+
     /// Returns a list of all the names (keys) in the permission map.
     pub fn get_permission_names(&self) -> Vec<String> {
         self.permission_map.keys().cloned().collect()
     }
 
-    /// Retrieves the user permission level for a specific tool.
+    /// Retrieves the user permission level for a specific tool. A project level
+    /// wins only when it is stricter, and never allows a tool on its own.
     pub fn get_user_permission(&self, principal_name: &str) -> Option<PermissionLevel> {
-        self.get_permission(USER_PERMISSION, principal_name)
+        let project_level = self
+            .project_permission
+            .as_ref()
+            .and_then(|config| Self::level_in(config, principal_name))
+            .filter(|level| *level != PermissionLevel::AlwaysAllow);
+        let user_level = self.get_permission(USER_PERMISSION, principal_name);
+        match (project_level, user_level) {
+            (Some(PermissionLevel::AskBefore), Some(PermissionLevel::NeverAllow)) => {
+                Some(PermissionLevel::NeverAllow)
+            }
+            (Some(project_level), _) => Some(project_level),
+            (None, user_level) => user_level,
+        }
+    }
+
+    /// Finds a stored user allow rule that covers this call.
+    /// Allow rules are never taken from a project config, which the repository controls.
+    pub fn get_user_allow_rule(
+        &self,
+        tool_name: &str,
+        arguments: &Map<String, Value>,
+    ) -> Option<&PermissionRule> {
+        self.get_user_allow_rules()
+            .find(|rule| rule.matches(tool_name, arguments))
+    }
+
+    /// Returns every stored user allow rule.
+    pub fn get_user_allow_rules(&self) -> impl Iterator<Item = &PermissionRule> {
+        self.permission_map
+            .get(USER_PERMISSION)
+            .into_iter()
+            .flat_map(|config| config.allow_rules.iter())
     }
 
     /// Retrieves the smart approve permission level for a specific tool.
@@ -99,26 +215,26 @@ impl PermissionManager {
     /// Helper function to retrieve the permission level for a specific permission category and tool.
     fn get_permission(&self, name: &str, principal_name: &str) -> Option<PermissionLevel> {
         // Check if the permission category exists in the map
-        if let Some(permission_config) = self.permission_map.get(name) {
-            // Check the permission levels for the given tool
-            if permission_config
-                .always_allow
-                .contains(&principal_name.to_string())
-            {
-                return Some(PermissionLevel::AlwaysAllow);
-            } else if permission_config
-                .ask_before
-                .contains(&principal_name.to_string())
-            {
-                return Some(PermissionLevel::AskBefore);
-            } else if permission_config
-                .never_allow
-                .contains(&principal_name.to_string())
-            {
-                return Some(PermissionLevel::NeverAllow);
-            }
+        self.permission_map
+            .get(name)
+            .and_then(|permission_config| Self::level_in(permission_config, principal_name))
+    }
+
+    /// Helper function to find the permission level of a tool within a single configuration.
+    fn level_in(
+        permission_config: &PermissionConfig,
+        principal_name: &str,
+    ) -> Option<PermissionLevel> {
+        let principal_name = principal_name.to_string();
+        if permission_config.always_allow.contains(&principal_name) {
+            Some(PermissionLevel::AlwaysAllow)
+        } else if permission_config.ask_before.contains(&principal_name) {
+            Some(PermissionLevel::AskBefore)
+        } else if permission_config.never_allow.contains(&principal_name) {
+            Some(PermissionLevel::NeverAllow)
+        } else {
+            None // Return None if no matching permission level is found
         }
-        None // Return None if no matching permission level is found
     }
 
     /// Updates the user permission level for a specific tool.
@@ -135,8 +251,22 @@ impl PermissionManager {
         self.update_permission(SMART_APPROVE_PERMISSION, principal_name, level)
     }
 
+    /// Stores an allow rule for the user, unless it already exists.
+    pub fn add_user_allow_rule(&mut self, rule: PermissionRule) {
+        self.reload();
+        let permission_config = self
+            .permission_map
+            .entry(USER_PERMISSION.to_string())
+            .or_default();
+        if !permission_config.allow_rules.contains(&rule) {
+            permission_config.allow_rules.push(rule);
+            self.save();
+        }
+    }
+
     /// Helper function to update a permission level for a specific tool in a given permission category.
     fn update_permission(&mut self, name: &str, principal_name: &str, level: PermissionLevel) {
+        self.reload();
         // Get or create a new PermissionConfig for the specified category
         let permission_config = self.permission_map.entry(name.to_string()).or_default();
 
@@ -162,14 +292,72 @@ impl PermissionManager {
                 .push(principal_name.to_string()),
         }
 
-        // Serialize the updated permission map and write it back to the config file
-        let yaml_content = serde_yaml::to_string(&self.permission_map)
-            .expect("Failed to serialize permission config");
-        fs::write(&self.config_path, yaml_content).expect("Failed to write to permission.yaml");
+        self.save();
+    }
+
+    /// Returns every tool named in a permission level or allow rule, sorted.
+    pub fn get_tool_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .permission_map
+            .values()
+            .chain(self.project_permission.iter())
+            .flat_map(|config| {
+                config
+                    .always_allow
+                    .iter()
+                    .chain(&config.ask_before)
+                    .chain(&config.never_allow)
+                    .cloned()
+                    .chain(config.allow_rules.iter().map(|rule| rule.tool.clone()))
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Removes the stored levels and allow rules of a tool. Project permissions are
+    /// left alone. Returns whether anything was removed.
+    pub fn remove_tool(&mut self, principal_name: &str) -> bool {
+        self.reload();
+        let mut removed = false;
+        for permission_config in self.permission_map.values_mut() {
+            let before = Self::entry_count(permission_config);
+            permission_config
+                .always_allow
+                .retain(|p| p != principal_name);
+            permission_config.ask_before.retain(|p| p != principal_name);
+            permission_config
+                .never_allow
+                .retain(|p| p != principal_name);
+            permission_config
+                .allow_rules
+                .retain(|rule| rule.tool != principal_name);
+            removed = removed || Self::entry_count(permission_config) != before;
+        }
+
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    /// Removes every stored level and allow rule. Project permissions are left alone.
+    pub fn clear(&mut self) {
+        self.permission_map.clear();
+        self.save();
+    }
+
+    fn entry_count(permission_config: &PermissionConfig) -> usize {
+        permission_config.always_allow.len()
+            + permission_config.ask_before.len()
+            + permission_config.never_allow.len()
+            + permission_config.allow_rules.len()
     }
 
     /// Removes all entries where the principal name starts with the given extension name.
     pub fn remove_extension(&mut self, extension_name: &str) {
+        self.reload();
         for permission_config in self.permission_map.values_mut() {
             permission_config
                 .always_allow
@@ -180,8 +368,16 @@ impl PermissionManager {
             permission_config
                 .never_allow
                 .retain(|p| !p.starts_with(extension_name));
+            permission_config
+                .allow_rules
+                .retain(|rule| !rule.tool.starts_with(extension_name));
         }
 
+        self.save();
+    }
+
+    /// Serializes the permission map and writes it back to the config file.
+    fn save(&self) {
         let yaml_content = serde_yaml::to_string(&self.permission_map)
             .expect("Failed to serialize permission config");
         fs::write(&self.config_path, yaml_content).expect("Failed to write to permission.yaml");
@@ -302,4 +498,139 @@ mod tests {
             .always_allow
             .contains(&"nonprefix__tool2".to_string()));
     }
+
+    #[test]
+    fn test_allow_rules() {
+        let mut manager = create_test_permission_manager().with_project_permission(
+            serde_yaml::from_str(
+                "allow_rules:\n  - tool: developer__shell\n    argument: command\n    pattern: ^ls",
+            )
+            .unwrap(),
+        );
+        let rule = PermissionRule::prefix("developer__shell", "command", "cargo test");
+        manager.add_user_allow_rule(rule.clone());
+        manager.add_user_allow_rule(rule.clone());
+
+        let call = |command: &str| {
+            serde_json::json!({ "command": command })
+                .as_object()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            manager.get_user_allow_rule("developer__shell", &call("cargo test -p goose")),
+            Some(&rule)
+        );
+        assert!(manager
+            .get_user_allow_rule("developer__shell", &call("cargo test"))
+            .is_some());
+        assert!(manager
+            .get_user_allow_rule("developer__shell", &call("ls -la"))
+            .is_none());
+        assert!(manager
+            .get_user_allow_rule("developer__shell", &call("cargo testing"))
+            .is_none());
+        assert!(manager
+            .get_user_allow_rule("developer__shell", &call("cargo test && rm -rf /"))
+            .is_none());
+        assert!(manager
+            .get_user_allow_rule("developer__shell", &call("cargo test\nrm -rf /"))
+            .is_none());
+        assert!(manager
+            .get_user_allow_rule("other__shell", &call("cargo test"))
+            .is_none());
+        assert_eq!(manager.get_user_allow_rules().count(), 1);
+    }
+
+    #[test]
+    fn test_remove_tool_and_clear() {
+        let mut manager = create_test_permission_manager()
+            .with_project_permission(serde_yaml::from_str("never_allow: [slack__post]").unwrap());
+        manager.update_user_permission("developer__shell", PermissionLevel::AskBefore);
+        manager.update_smart_approve_permission("developer__shell", PermissionLevel::AlwaysAllow);
+        manager.add_user_allow_rule(PermissionRule::prefix(
+            "developer__shell",
+            "command",
+            "cargo test",
+        ));
+        manager.update_user_permission("github__get_issue", PermissionLevel::AlwaysAllow);
+        assert_eq!(
+            manager.get_tool_names(),
+            vec!["developer__shell", "github__get_issue", "slack__post"]
+        );
+
+        assert!(manager.remove_tool("developer__shell"));
+        assert!(!manager.remove_tool("developer__shell"));
+        assert_eq!(manager.get_user_permission("developer__shell"), None);
+        assert_eq!(
+            manager.get_smart_approve_permission("developer__shell"),
+            None
+        );
+        assert_eq!(manager.get_user_allow_rules().count(), 0);
+
+        manager.clear();
+        assert_eq!(manager.get_tool_names(), vec!["slack__post"]);
+        assert_eq!(
+            PermissionManager::new(manager.get_config_path()).get_tool_names(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_revoke_survives_save_from_another_manager() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut agent_manager = PermissionManager::new(temp_file.path());
+        agent_manager.update_user_permission("developer__shell", PermissionLevel::AlwaysAllow);
+        agent_manager.add_user_allow_rule(PermissionRule::prefix(
+            "developer__shell",
+            "command",
+            "cargo test",
+        ));
+
+        assert!(PermissionManager::new(temp_file.path()).remove_tool("developer__shell"));
+
+        agent_manager.update_user_permission("github__get_issue", PermissionLevel::AlwaysAllow);
+        assert_eq!(agent_manager.get_user_permission("developer__shell"), None);
+        assert_eq!(agent_manager.get_user_allow_rules().count(), 0);
+
+        let reloaded = PermissionManager::new(temp_file.path());
+        assert_eq!(reloaded.get_user_permission("developer__shell"), None);
+        assert_eq!(
+            reloaded.get_user_permission("github__get_issue"),
+            Some(PermissionLevel::AlwaysAllow)
+        );
+    }
+
+    #[test]
+    fn test_project_permission_cannot_loosen() {
+        let mut manager = create_test_permission_manager().with_project_permission(
+            serde_yaml::from_str("always_allow: [developer__shell]\nask_before: [slack__post]")
+                .unwrap(),
+        );
+        manager.update_user_permission("slack__post", PermissionLevel::NeverAllow);
+
+        assert_eq!(manager.get_user_permission("developer__shell"), None);
+        assert_eq!(
+            manager.get_user_permission("slack__post"),
+            Some(PermissionLevel::NeverAllow)
+        );
+    }
+
+    #[test]
+    fn test_project_permission_takes_precedence() {
+        let mut manager = create_test_permission_manager().with_project_permission(
+            serde_yaml::from_str("never_allow: [developer__shell]").unwrap(),
+        );
+        manager.update_user_permission("developer__shell", PermissionLevel::AlwaysAllow);
+        manager.update_user_permission("tool7", PermissionLevel::AskBefore);
+
+        assert_eq!(
+            manager.get_user_permission("developer__shell"),
+            Some(PermissionLevel::NeverAllow)
+        );
+        assert_eq!(
+            manager.get_user_permission("tool7"),
+            Some(PermissionLevel::AskBefore)
+        );
+    }
 }
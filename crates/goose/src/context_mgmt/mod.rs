@@ -1,12 +1,13 @@
 use crate::conversation::message::MessageMetadata;
 use crate::conversation::message::{Message, MessageContent};
 use crate::conversation::Conversation;
-use crate::prompt_template::render_global_file;
+use crate::prompt_template::render_session_file;
 use crate::providers::base::{Provider, ProviderUsage};
 use crate::{agents::Agent, config::Config, token_counter::create_token_counter};
 use anyhow::Result;
 use rmcp::model::Role;
 use serde::Serialize;
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -25,8 +26,10 @@ struct SummarizeContext {
 ///
 /// # Arguments
 /// * `agent` - The agent to use for context management
+/// * `working_dir` - The session's working directory, used to find a project's prompt overrides
 /// * `conversation` - The current conversation history
 /// * `preserve_last_user_message` - If true and last message is not a user message, copy the most recent user message to the end
+/// * `additional_context` - Text kept alongside the summary, such as output from `pre_compact` hooks
 ///
 /// # Returns
 /// * A tuple containing:
@@ -34,8 +37,10 @@ struct SummarizeContext {
 ///   - `ProviderUsage`: Provider usage from summarization
 pub async fn compact_messages(
     agent: &Agent,
+    working_dir: &Path,
     conversation: &Conversation,
     preserve_last_user_message: bool,
+    additional_context: &[String],
 ) -> Result<(Conversation, ProviderUsage)> {
     info!("Performing message compaction");
 
@@ -99,7 +104,7 @@ pub async fn compact_messages(
 
     let provider = agent.provider().await?;
     let (summary_message, summarization_usage) =
-        do_compact(provider.clone(), messages_to_compact).await?;
+        do_compact(provider.clone(), working_dir, messages_to_compact).await?;
 
     // Create the final message list with updated visibility metadata:
     // 1. Original messages become user_visible but not agent_visible
@@ -116,7 +121,10 @@ pub async fn compact_messages(
     }
 
     // Add the summary message (agent_visible=true, user_visible=false)
-    let summary_msg = summary_message.with_metadata(MessageMetadata::agent_only());
+    let mut summary_msg = summary_message.with_metadata(MessageMetadata::agent_only());
+    for context in additional_context {
+        summary_msg = summary_msg.with_text(context);
+    }
     final_messages.push(summary_msg);
 
     // Add an assistant message to continue the conversation (agent_visible=true, user_visible=false)
@@ -198,6 +206,7 @@ pub async fn check_if_compaction_needed(
 
 async fn do_compact(
     provider: Arc<dyn Provider>,
+    working_dir: &Path,
     messages: &[Message],
 ) -> Result<(Message, ProviderUsage), anyhow::Error> {
     let agent_visible_messages: Vec<&Message> = messages
@@ -215,7 +224,7 @@ async fn do_compact(
         messages: messages_text,
     };
 
-    let system_prompt = render_global_file("summarize_oneshot.md", &context)?;
+    let system_prompt = render_session_file(working_dir, "summarize_oneshot.md", &context)?;
 
     let user_message = Message::user()
         .with_text("Please summarize the conversation history provided in the system prompt.");
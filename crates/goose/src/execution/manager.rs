@@ -1,21 +1,63 @@
 use crate::agents::extension::PlatformExtensionContext;
-use crate::agents::Agent;
+use crate::agents::{Agent, SessionConfig};
 use crate::config::paths::Paths;
+use crate::config::Config;
+use crate::providers::create_with_named_model;
 use crate::scheduler_factory::SchedulerFactory;
 use crate::scheduler_trait::SchedulerTrait;
+use crate::session::{EnabledExtensionsState, ExtensionState, SessionManager};
 use anyhow::Result;
 use lru::LruCache;
+use serde::Serialize;
 use std::num::NonZeroUsize;
-use std::sync::Arc;
-use tokio::sync::{OnceCell, RwLock};
-use tracing::{debug, info};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OnceCell, RwLock};
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
 
 const DEFAULT_MAX_SESSION: usize = 100;
+const MAX_ACTIVE_AGENTS_CONFIG_KEY: &str = "GOOSE_MAX_ACTIVE_AGENTS";
+/// Seconds an agent may sit unused before it is evicted; unset or 0 disables idle eviction
+const AGENT_IDLE_TIMEOUT_CONFIG_KEY: &str = "GOOSE_AGENT_IDLE_TIMEOUT";
+const MIN_IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+/// Evicted sessions remembered for rehydration; beyond this the oldest are forgotten and
+/// resume like a session from before a restart
+const MAX_EVICTED_SESSIONS: usize = 10_000;
 
 static AGENT_MANAGER: OnceCell<Arc<AgentManager>> = OnceCell::const_new();
 
+struct PooledAgent {
+    agent: Arc<Agent>,
+    last_used: Instant,
+}
+
+/// Point-in-time view of the agent pool, reported on the server status endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AgentPoolStats {
+    /// Number of agents currently held in memory
+    pub active_agents: usize,
+    /// Number of agents held before the least recently used idle one is evicted; agents
+    /// in use are never evicted, so the pool may briefly exceed it
+    pub capacity: usize,
+    /// Idle time after which an unused agent is evicted, if enabled
+    pub idle_timeout_secs: Option<u64>,
+    /// Sessions whose agent was evicted and will be rehydrated on next use
+    pub evicted_sessions: usize,
+    /// Agents evicted since startup, by capacity or idle time
+    pub total_evictions: u64,
+    /// Evicted sessions rehydrated since startup
+    pub total_rehydrations: u64,
+}
+
 pub struct AgentManager {
-    sessions: Arc<RwLock<LruCache<String, Arc<Agent>>>>,
+    sessions: Arc<RwLock<LruCache<String, PooledAgent>>>,
+    capacity: NonZeroUsize,
+    evicted: Mutex<LruCache<String, ()>>,
+    idle_timeout: Option<Duration>,
+    evictions: AtomicU64,
+    rehydrations: AtomicU64,
     scheduler: Arc<dyn SchedulerTrait>,
     default_provider: Arc<RwLock<Option<Arc<dyn crate::providers::base::Provider>>>>,
 }
@@ -32,7 +74,7 @@ impl AgentManager {
         }
     }
 
-    async fn new(max_sessions: Option<usize>) -> Result<Self> {
+    async fn new(max_sessions: Option<usize>, idle_timeout: Option<Duration>) -> Result<Self> {
         let schedule_file_path = Paths::data_dir().join("schedule.json");
 
         let scheduler = SchedulerFactory::create(schedule_file_path).await?;
@@ -41,7 +83,14 @@ impl AgentManager {
             .unwrap_or_else(|| NonZeroUsize::new(100).unwrap());
 
         let manager = Self {
-            sessions: Arc::new(RwLock::new(LruCache::new(capacity))),
+            sessions: Arc::new(RwLock::new(LruCache::unbounded())),
+            capacity,
+            evicted: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_EVICTED_SESSIONS).unwrap(),
+            )),
+            idle_timeout,
+            evictions: AtomicU64::new(0),
+            rehydrations: AtomicU64::new(0),
             scheduler,
             default_provider: Arc::new(RwLock::new(None)),
         };
@@ -52,13 +101,40 @@ impl AgentManager {
     pub async fn instance() -> Result<Arc<Self>> {
         AGENT_MANAGER
             .get_or_try_init(|| async {
-                let manager = Self::new(Some(DEFAULT_MAX_SESSION)).await?;
-                Ok(Arc::new(manager))
+                let config = Config::global();
+                let max_sessions = config
+                    .get_param::<usize>(MAX_ACTIVE_AGENTS_CONFIG_KEY)
+                    .unwrap_or(DEFAULT_MAX_SESSION);
+                let idle_timeout = config
+                    .get_param::<u64>(AGENT_IDLE_TIMEOUT_CONFIG_KEY)
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .map(Duration::from_secs);
+
+                let manager = Arc::new(Self::new(Some(max_sessions), idle_timeout).await?);
+                if let Some(idle_timeout) = idle_timeout {
+                    Self::spawn_idle_sweeper(Arc::downgrade(&manager), idle_timeout);
+                }
+                Ok(manager)
             })
             .await
             .cloned()
     }
 
+    fn spawn_idle_sweeper(manager: Weak<Self>, idle_timeout: Duration) {
+        let period = (idle_timeout / 4).max(MIN_IDLE_SWEEP_INTERVAL);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.evict_idle().await;
+            }
+        });
+    }
+
     pub async fn scheduler(&self) -> Result<Arc<dyn SchedulerTrait>> {
         Ok(Arc::clone(&self.scheduler))
     }
@@ -71,8 +147,9 @@ impl AgentManager {
     pub async fn get_or_create_agent(&self, session_id: String) -> Result<Arc<Agent>> {
         {
             let mut sessions = self.sessions.write().await;
-            if let Some(existing) = sessions.get(&session_id) {
-                return Ok(Arc::clone(existing));
+            if let Some(existing) = sessions.get_mut(&session_id) {
+                existing.last_used = Instant::now();
+                return Ok(Arc::clone(&existing.agent));
             }
         }
 
@@ -90,20 +167,178 @@ impl AgentManager {
             agent.update_provider(Arc::clone(provider)).await?;
         }
 
-        let mut sessions = self.sessions.write().await;
-        if let Some(existing) = sessions.get(&session_id) {
-            Ok(Arc::clone(existing))
-        } else {
-            sessions.put(session_id, agent.clone());
-            Ok(agent)
+        // Only remove the evicted marker once the agent is in the pool, so a
+        // concurrent request for the same session also rehydrates its agent.
+        if self.evicted.lock().await.contains(&session_id) {
+            self.rehydrate(&agent, &session_id).await;
+        }
+
+        let displaced = {
+            let mut sessions = self.sessions.write().await;
+            if let Some(existing) = sessions.get_mut(&session_id) {
+                existing.last_used = Instant::now();
+                return Ok(Arc::clone(&existing.agent));
+            }
+            self.evicted.lock().await.pop(&session_id);
+            let displaced = self.make_room(&mut sessions);
+            sessions.push(
+                session_id,
+                PooledAgent {
+                    agent: agent.clone(),
+                    last_used: Instant::now(),
+                },
+            );
+            displaced
+        };
+
+        for (evicted_id, evicted) in displaced {
+            self.retire(evicted_id, evicted).await;
+        }
+        Ok(agent)
+    }
+
+    // Pop least recently used agents until there is room for one more. Agents held
+    // elsewhere (e.g. by an in-flight reply) are skipped, so a pool that is entirely
+    // in use grows past its capacity and shrinks again on later inserts.
+    fn make_room(&self, sessions: &mut LruCache<String, PooledAgent>) -> Vec<(String, Arc<Agent>)> {
+        let mut displaced = Vec::new();
+        while sessions.len() >= self.capacity.get() {
+            let Some(victim) = sessions
+                .iter()
+                .rev()
+                .find(|(_, pooled)| Arc::strong_count(&pooled.agent) == 1)
+                .map(|(id, _)| id.clone())
+            else {
+                warn!(
+                    "All {} agents are in use; exceeding the pool capacity of {}",
+                    sessions.len(),
+                    self.capacity
+                );
+                break;
+            };
+            if let Some(pooled) = sessions.pop(&victim) {
+                displaced.push((victim, pooled.agent));
+            }
+        }
+        displaced
+    }
+
+    /// Evict every agent that has not been used within the idle timeout and is
+    /// not currently held elsewhere (e.g. by an in-flight reply). Returns the
+    /// number of agents evicted.
+    pub async fn evict_idle(&self) -> usize {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return 0;
+        };
+
+        let idle: Vec<(String, Arc<Agent>)> = {
+            let mut sessions = self.sessions.write().await;
+            let expired: Vec<String> = sessions
+                .iter()
+                .filter(|(_, pooled)| {
+                    pooled.last_used.elapsed() >= idle_timeout
+                        && Arc::strong_count(&pooled.agent) == 1
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            expired
+                .into_iter()
+                .filter_map(|id| sessions.pop(&id).map(|pooled| (id, pooled.agent)))
+                .collect()
+        };
+
+        let count = idle.len();
+        for (session_id, agent) in idle {
+            self.retire(session_id, agent).await;
+        }
+        count
+    }
+
+    // Save the agent's extension state so it can be rebuilt later, then remember
+    // the session so its next use rehydrates a fresh agent.
+    async fn retire(&self, session_id: String, agent: Arc<Agent>) {
+        if !agent.get_extension_configs().await.is_empty() {
+            let session_config = SessionConfig {
+                id: session_id.clone(),
+                schedule_id: None,
+                max_turns: None,
+                retry_config: None,
+                budget: None,
+            };
+            if let Err(e) = agent.save_extension_state(&session_config).await {
+                warn!(
+                    "Failed to save extension state for evicted session {}: {}",
+                    session_id, e
+                );
+            }
+        }
+
+        self.evicted.lock().await.push(session_id.clone(), ());
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+        info!("Evicted agent for session {}", session_id);
+    }
+
+    // Restore extensions and provider for a session whose agent was evicted.
+    async fn rehydrate(&self, agent: &Agent, session_id: &str) {
+        let session = match SessionManager::get_session(session_id, false).await {
+            Ok(session) => session,
+            Err(e) => {
+                warn!("Failed to load evicted session {}: {}", session_id, e);
+                return;
+            }
+        };
+
+        if let Some(state) = EnabledExtensionsState::from_extension_data(&session.extension_data) {
+            let futures = state.extensions.into_iter().map(|extension| async move {
+                if let Err(e) = agent.add_extension(extension.clone()).await {
+                    warn!(
+                        "Failed to restore extension {} for session {}: {}",
+                        extension.name(),
+                        session_id,
+                        e
+                    );
+                }
+            });
+            futures::future::join_all(futures).await;
+        }
+
+        if self.default_provider.read().await.is_none() {
+            let config = Config::global();
+            let provider = match (config.get_goose_provider(), config.get_goose_model()) {
+                (Ok(provider_name), Ok(model)) => {
+                    create_with_named_model(&provider_name, &model).await
+                }
+                _ => Err(anyhow::anyhow!("no provider and model configured")),
+            };
+            match provider {
+                Ok(provider) => {
+                    if let Err(e) = agent.update_provider(provider).await {
+                        warn!(
+                            "Failed to restore provider for session {}: {}",
+                            session_id, e
+                        );
+                    }
+                }
+                Err(e) => warn!(
+                    "Failed to restore provider for session {}: {}",
+                    session_id, e
+                ),
+            }
         }
+
+        self.rehydrations.fetch_add(1, Ordering::Relaxed);
+        info!("Rehydrated agent for session {}", session_id);
     }
 
     pub async fn remove_session(&self, session_id: &str) -> Result<()> {
-        let mut sessions = self.sessions.write().await;
-        sessions
-            .pop(session_id)
-            .ok_or_else(|| anyhow::anyhow!("Session {} not found", session_id))?;
+        let removed = {
+            let mut sessions = self.sessions.write().await;
+            self.evicted.lock().await.pop(session_id);
+            sessions
+                .pop(session_id)
+                .ok_or_else(|| anyhow::anyhow!("Session {} not found", session_id))?
+        };
+        removed.agent.end_session(session_id).await;
         info!("Removed session {}", session_id);
         Ok(())
     }
@@ -115,12 +350,24 @@ impl AgentManager {
     pub async fn session_count(&self) -> usize {
         self.sessions.read().await.len()
     }
+
+    pub async fn pool_stats(&self) -> AgentPoolStats {
+        AgentPoolStats {
+            active_agents: self.sessions.read().await.len(),
+            capacity: self.capacity.get(),
+            idle_timeout_secs: self.idle_timeout.map(|t| t.as_secs()),
+            evicted_sessions: self.evicted.lock().await.len(),
+            total_evictions: self.evictions.load(Ordering::Relaxed),
+            total_rehydrations: self.rehydrations.load(Ordering::Relaxed),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use serial_test::serial;
     use std::sync::Arc;
+    use std::time::Duration;
 
     use crate::execution::{manager::AgentManager, SessionExecutionMode};
 
@@ -342,4 +589,94 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_idle_eviction() {
+        let manager = AgentManager::new(Some(10), Some(Duration::from_millis(10)))
+            .await
+            .unwrap();
+        let session = String::from("idle-test");
+
+        let agent = manager.get_or_create_agent(session.clone()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Agents still held elsewhere are never evicted
+        assert_eq!(manager.evict_idle().await, 0);
+        assert!(manager.has_session(&session).await);
+
+        drop(agent);
+        assert_eq!(manager.evict_idle().await, 1);
+        assert!(!manager.has_session(&session).await);
+
+        let stats = manager.pool_stats().await;
+        assert_eq!(stats.active_agents, 0);
+        assert_eq!(stats.capacity, 10);
+        assert_eq!(stats.idle_timeout_secs, Some(0));
+        assert_eq!(stats.evicted_sessions, 1);
+        assert_eq!(stats.total_evictions, 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_capacity_eviction_is_tracked() {
+        let manager = AgentManager::new(Some(2), None).await.unwrap();
+
+        for i in 0..3 {
+            manager
+                .get_or_create_agent(format!("capacity-{}", i))
+                .await
+                .unwrap();
+        }
+
+        assert!(!manager.has_session("capacity-0").await);
+        let stats = manager.pool_stats().await;
+        assert_eq!(stats.active_agents, 2);
+        assert_eq!(stats.evicted_sessions, 1);
+        assert_eq!(stats.total_evictions, 1);
+        assert_eq!(manager.evict_idle().await, 0);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_capacity_eviction_skips_agents_in_use() {
+        let manager = AgentManager::new(Some(2), None).await.unwrap();
+
+        let busy = manager
+            .get_or_create_agent("busy".to_string())
+            .await
+            .unwrap();
+        manager
+            .get_or_create_agent("idle".to_string())
+            .await
+            .unwrap();
+        manager
+            .get_or_create_agent("new".to_string())
+            .await
+            .unwrap();
+
+        // The least recently used agent is held by a caller, so the idle one goes
+        assert!(manager.has_session("busy").await);
+        assert!(!manager.has_session("idle").await);
+        assert!(manager.has_session("new").await);
+
+        // With every agent in use the pool grows, then shrinks once they are released
+        let new = manager
+            .get_or_create_agent("new".to_string())
+            .await
+            .unwrap();
+        manager
+            .get_or_create_agent("extra".to_string())
+            .await
+            .unwrap();
+        assert_eq!(manager.session_count().await, 3);
+
+        drop((busy, new));
+        manager
+            .get_or_create_agent("last".to_string())
+            .await
+            .unwrap();
+        assert_eq!(manager.session_count().await, 2);
+        assert!(manager.has_session("last").await);
+    }
 }
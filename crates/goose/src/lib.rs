@@ -18,6 +18,7 @@ pub mod scheduler_trait;
 pub mod security;
 pub mod session;
 pub mod session_context;
+pub mod slash_commands;
 pub mod token_counter;
 pub mod tool_inspection;
 pub mod tool_monitor;
@@ -2,8 +2,12 @@ pub mod permission_confirmation;
 pub mod permission_inspector;
 pub mod permission_judge;
 pub mod permission_store;
+pub mod permission_suggestions;
+pub mod permission_summary;
 
 pub use permission_confirmation::{Permission, PermissionConfirmation};
 pub use permission_inspector::PermissionInspector;
 pub use permission_judge::detect_read_only_tools;
 pub use permission_store::ToolPermissionStore;
+pub use permission_suggestions::{suggest_permissions, PermissionSuggestion};
+pub use permission_summary::{summarize_tool_permissions, ToolPermissionSummary};
@@ -132,19 +132,31 @@ impl ToolInspector for PermissionInspector {
         _messages: &[Message],
     ) -> Result<Vec<InspectionResult>> {
         let mut results = Vec::new();
-        let permission_manager = self.permission_manager.lock().await;
+        let mut permission_manager = self.permission_manager.lock().await;
+        // Pick up permissions revoked or granted outside this agent since it started
+        permission_manager.reload();
         let mode = self.mode.lock().await;
 
         for request in tool_requests {
             if let Ok(tool_call) = &request.tool_call {
                 let tool_name = &tool_call.name;
+                let user_permission = permission_manager.get_user_permission(tool_name);
+                // A rule covering these arguments allows the call unless the tool is never allowed
+                let allow_rule = match (&user_permission, &tool_call.arguments) {
+                    (Some(PermissionLevel::NeverAllow), _) | (_, None) => None,
+                    (_, Some(arguments)) => {
+                        permission_manager.get_user_allow_rule(tool_name, arguments)
+                    }
+                };
 
                 let action = match *mode {
                     GooseMode::Chat => continue,
                     GooseMode::Auto => InspectionAction::Allow,
                     GooseMode::Approve | GooseMode::SmartApprove => {
                         // 1. Check user-defined permission first
-                        if let Some(level) = permission_manager.get_user_permission(tool_name) {
+                        if allow_rule.is_some() {
+                            InspectionAction::Allow
+                        } else if let Some(level) = user_permission {
                             match level {
                                 PermissionLevel::AlwaysAllow => InspectionAction::Allow,
                                 PermissionLevel::NeverAllow => InspectionAction::Deny,
@@ -176,6 +188,11 @@ impl ToolInspector for PermissionInspector {
                     InspectionAction::Allow => {
                         if *mode == GooseMode::Auto {
                             "Auto mode - all tools approved".to_string()
+                        } else if let Some(rule) = allow_rule {
+                            format!(
+                                "User rule allows this call ({} matches {})",
+                                rule.argument, rule.pattern
+                            )
                         } else if self.readonly_tools.contains(tool_name.as_ref()) {
                             "Tool marked as read-only".to_string()
                         } else if self.regular_tools.contains(tool_name.as_ref()) {
@@ -208,3 +225,87 @@ impl ToolInspector for PermissionInspector {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::permission::PermissionRule;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::object;
+    use tempfile::NamedTempFile;
+
+    fn shell_request(command: &str) -> ToolRequest {
+        ToolRequest {
+            id: "req_1".to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "developer__shell".into(),
+                arguments: Some(object!({ "command": command })),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_allow_rule_does_not_approve() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let manager = PermissionManager::new(temp_file.path()).with_project_permission(
+            serde_yaml::from_str(
+                "allow_rules:\n  - tool: developer__shell\n    argument: command\n    pattern: .*",
+            )
+            .unwrap(),
+        );
+        let manager = Arc::new(Mutex::new(manager));
+        let inspector = PermissionInspector::with_permission_manager(
+            GooseMode::Approve,
+            HashSet::new(),
+            HashSet::new(),
+            manager.clone(),
+        );
+
+        let results = inspector
+            .inspect(&[shell_request("rm -rf ~")], &[])
+            .await
+            .unwrap();
+        assert_eq!(results[0].action, InspectionAction::RequireApproval(None));
+
+        manager
+            .lock()
+            .await
+            .add_user_allow_rule(PermissionRule::prefix(
+                "developer__shell",
+                "command",
+                "cargo test",
+            ));
+        let results = inspector
+            .inspect(&[shell_request("cargo test -p goose")], &[])
+            .await
+            .unwrap();
+        assert_eq!(results[0].action, InspectionAction::Allow);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_reaches_running_inspector() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut manager = PermissionManager::new(temp_file.path());
+        manager.update_user_permission("developer__shell", PermissionLevel::AlwaysAllow);
+        let inspector = PermissionInspector::with_permission_manager(
+            GooseMode::Approve,
+            HashSet::new(),
+            HashSet::new(),
+            Arc::new(Mutex::new(manager)),
+        );
+
+        let results = inspector
+            .inspect(&[shell_request("ls")], &[])
+            .await
+            .unwrap();
+        assert_eq!(results[0].action, InspectionAction::Allow);
+
+        PermissionManager::new(temp_file.path()).remove_tool("developer__shell");
+
+        let results = inspector
+            .inspect(&[shell_request("ls")], &[])
+            .await
+            .unwrap();
+        assert_eq!(results[0].action, InspectionAction::RequireApproval(None));
+    }
+}
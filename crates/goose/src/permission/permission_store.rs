@@ -1,22 +1,42 @@
 use crate::config::paths::Paths;
 use crate::conversation::message::ToolRequest;
+use crate::permission::permission_suggestions::command_like_arguments;
 use anyhow::Result;
 use blake3::Hasher;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::time::Duration;
 use std::{fs::File, path::PathBuf};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// How long a decision made at a confirmation prompt is kept
+pub const DECISION_RETENTION: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+
+/// The most decisions kept for one tool; older ones are dropped first
+const MAX_RECORDS_PER_TOOL: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct ToolPermissionRecord {
-    tool_name: String,
-    allowed: bool,
-    context_hash: String, // Hash of the tool's arguments/context to differentiate similar calls
+    pub tool_name: String,
+    pub allowed: bool,
+    pub context_hash: String, // Hash of the tool's arguments/context to differentiate similar calls
     #[serde(skip_serializing_if = "Option::is_none")] // Don't serialize if None
-    readable_context: Option<String>, // Add this field
-    timestamp: i64,
-    expiry: Option<i64>, // Optional expiry timestamp
+    pub readable_context: Option<String>, // Add this field
+    pub timestamp: i64,
+    pub expiry: Option<i64>, // Optional expiry timestamp
+}
+
+impl ToolPermissionRecord {
+    /// The arguments of the recorded call, read back from its readable context
+    pub fn arguments(&self) -> Option<Map<String, Value>> {
+        let prefix = format!("Tool: {}, Args: ", self.tool_name);
+        let args = self.readable_context.as_deref()?.strip_prefix(&prefix)?;
+        serde_json::from_str::<Option<Map<String, Value>>>(args)
+            .ok()
+            .map(Option::unwrap_or_default)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +96,15 @@ impl ToolPermissionStore {
         Ok(())
     }
 
+    /// Every unexpired record, oldest first for each tool call
+    pub fn records(&self) -> impl Iterator<Item = &ToolPermissionRecord> {
+        let now = Utc::now().timestamp();
+        self.permissions
+            .values()
+            .flatten()
+            .filter(move |record| record.expiry.is_none_or(|exp| exp > now))
+    }
+
     pub fn check_permission(&self, tool_request: &ToolRequest) -> Option<bool> {
         let context_hash = self.hash_tool_context(tool_request);
         let tool_call = tool_request.tool_call.as_ref().unwrap();
@@ -100,21 +129,66 @@ impl ToolPermissionStore {
         let tool_call = tool_request.tool_call.as_ref().unwrap();
         let key = format!("{}:{}", tool_call.name, context_hash);
 
+        // Suggestions only read command-like arguments, so nothing else is kept
+        let arguments: Map<String, Value> = tool_call
+            .arguments
+            .as_ref()
+            .map(|arguments| {
+                command_like_arguments(arguments)
+                    .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
         let record = ToolPermissionRecord {
-            tool_name: tool_call.name.to_string().clone(),
+            tool_name: tool_call.name.to_string(),
             allowed,
             context_hash,
-            readable_context: Some(tool_request.to_readable_string()),
+            readable_context: Some(format!(
+                "Tool: {}, Args: {}",
+                tool_call.name,
+                serde_json::to_string(&arguments)?
+            )),
             timestamp: Utc::now().timestamp(),
             expiry: expiry_duration.map(|d| Utc::now().timestamp() + d.as_secs() as i64),
         };
 
         self.permissions.entry(key).or_default().push(record);
+        self.drop_oldest_records(&tool_call.name);
+        self.remove_expired();
 
         self.save()?;
         Ok(())
     }
 
+    /// Drop the oldest decisions for a tool until at most `MAX_RECORDS_PER_TOOL` remain
+    fn drop_oldest_records(&mut self, tool_name: &str) {
+        let count = self
+            .permissions
+            .values()
+            .flatten()
+            .filter(|record| record.tool_name == tool_name)
+            .count();
+        for _ in MAX_RECORDS_PER_TOOL..count {
+            // Records for a key share its tool and are kept oldest first
+            let Some(oldest) = self
+                .permissions
+                .iter()
+                .filter_map(|(key, records)| records.first().map(|record| (key, record)))
+                .filter(|(_, record)| record.tool_name == tool_name)
+                .min_by_key(|(_, record)| record.timestamp)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(records) = self.permissions.get_mut(&oldest) {
+                records.remove(0);
+                if records.is_empty() {
+                    self.permissions.remove(&oldest);
+                }
+            }
+        }
+    }
+
     fn hash_tool_context(&self, tool_request: &ToolRequest) -> String {
         // Create a hash of the tool's arguments to differentiate similar calls
         // This helps identify when the same tool is being used in a different context
@@ -127,19 +201,159 @@ impl ToolPermissionStore {
         hasher.finalize().to_hex().to_string()
     }
 
+    /// Forget every decision recorded for a tool, returning how many there were
+    pub fn remove_tool(&mut self, tool_name: &str) -> anyhow::Result<usize> {
+        let mut removed = 0;
+        self.permissions.retain(|_, records| {
+            let before = records.len();
+            records.retain(|record| record.tool_name != tool_name);
+            removed += before - records.len();
+            !records.is_empty()
+        });
+
+        if removed > 0 {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Forget every recorded decision, returning how many there were
+    pub fn clear(&mut self) -> anyhow::Result<usize> {
+        let removed = self.permissions.values().map(Vec::len).sum();
+        self.permissions.clear();
+        self.save()?;
+        Ok(removed)
+    }
+
     pub fn cleanup_expired(&mut self) -> anyhow::Result<()> {
+        if self.remove_expired() {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Drop expired records, returning whether there were any
+    fn remove_expired(&mut self) -> bool {
         let now = Utc::now().timestamp();
         let mut changed = false;
 
         self.permissions.retain(|_, records| {
+            let before = records.len();
             records.retain(|record| record.expiry.is_none_or(|exp| exp > now));
-            changed = changed || records.is_empty();
+            changed = changed || records.len() != before;
             !records.is_empty()
         });
+        changed
+    }
+}
 
-        if changed {
-            self.save()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::object;
+
+    fn tool_request(name: &str, command: &str) -> ToolRequest {
+        ToolRequest {
+            id: "req_1".to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: name.to_string().into(),
+                arguments: Some(object!({ "command": command })),
+            }),
         }
-        Ok(())
+    }
+
+    #[test]
+    fn test_record_and_remove_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ToolPermissionStore {
+            permissions_dir: dir.path().to_path_buf(),
+            ..ToolPermissionStore::new()
+        };
+        let test = tool_request("developer__shell", "cargo test");
+        store.record_permission(&test, true, None).unwrap();
+        store
+            .record_permission(&tool_request("developer__shell", "rm -rf /"), false, None)
+            .unwrap();
+        store
+            .record_permission(&tool_request("github__get_issue", "1"), true, None)
+            .unwrap();
+
+        assert_eq!(store.check_permission(&test), Some(true));
+        let record = store
+            .records()
+            .find(|record| record.allowed && record.tool_name == "developer__shell")
+            .unwrap();
+        assert_eq!(
+            record.arguments().unwrap().get("command"),
+            Some(&Value::String("cargo test".to_string()))
+        );
+
+        assert_eq!(store.remove_tool("developer__shell").unwrap(), 2);
+        assert_eq!(store.remove_tool("developer__shell").unwrap(), 0);
+        assert_eq!(store.check_permission(&test), None);
+        assert_eq!(store.clear().unwrap(), 1);
+        assert_eq!(store.records().count(), 0);
+    }
+
+    #[test]
+    fn test_record_keeps_only_command_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ToolPermissionStore {
+            permissions_dir: dir.path().to_path_buf(),
+            ..ToolPermissionStore::new()
+        };
+        let request = ToolRequest {
+            id: "req_1".to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "developer__shell".into(),
+                arguments: Some(object!({
+                    "command": "cargo test -p goose",
+                    "token": "secret",
+                    "timeout": 30
+                })),
+            }),
+        };
+        store
+            .record_permission(&request, true, Some(DECISION_RETENTION))
+            .unwrap();
+
+        assert_eq!(store.check_permission(&request), Some(true));
+        let record = store.records().next().unwrap();
+        assert!(record.expiry.is_some());
+        assert_eq!(
+            Value::Object(record.arguments().unwrap()),
+            serde_json::json!({ "command": "cargo test -p goose" })
+        );
+    }
+
+    #[test]
+    fn test_record_caps_decisions_per_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ToolPermissionStore {
+            permissions_dir: dir.path().to_path_buf(),
+            ..ToolPermissionStore::new()
+        };
+        let first = tool_request("developer__shell", "echo 0");
+        store.record_permission(&first, true, None).unwrap();
+        // Make the first decision the oldest, as records within a second tie
+        for record in store.permissions.values_mut().flatten() {
+            record.timestamp -= 1;
+        }
+        for i in 1..MAX_RECORDS_PER_TOOL + 5 {
+            let request = tool_request("developer__shell", &format!("echo {}", i));
+            store.record_permission(&request, true, None).unwrap();
+        }
+        store
+            .record_permission(&tool_request("github__get_issue", "1"), true, None)
+            .unwrap();
+
+        let shell = store
+            .records()
+            .filter(|record| record.tool_name == "developer__shell")
+            .count();
+        assert_eq!(shell, MAX_RECORDS_PER_TOOL);
+        assert_eq!(store.check_permission(&first), None);
+        assert_eq!(store.records().count(), MAX_RECORDS_PER_TOOL + 1);
     }
 }
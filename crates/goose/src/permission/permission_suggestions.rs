@@ -0,0 +1,305 @@
+use crate::config::permission::{PermissionLevel, PermissionRule};
+use crate::config::PermissionManager;
+use crate::permission::permission_store::ToolPermissionRecord;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+/// Approvals needed before a call pattern is suggested as a rule
+pub const DEFAULT_MIN_APPROVALS: usize = 5;
+/// Leading words of a command-like argument that a suggested rule keeps
+const PREFIX_WORDS: usize = 2;
+const MAX_EXAMPLES: usize = 3;
+
+/// A rule proposed from the approvals in the permission history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PermissionSuggestion {
+    pub tool: String,
+    /// Allow only the calls matching this rule; without one the tool is always allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<PermissionRule>,
+    /// How many recorded approvals the suggestion covers
+    pub approvals: usize,
+    /// Some of the approved argument values it covers
+    pub examples: Vec<String>,
+}
+
+impl PermissionSuggestion {
+    /// One line describing the suggestion, for showing to the user
+    pub fn describe(&self) -> String {
+        match &self.rule {
+            Some(rule) => format!(
+                "You approved {} calls like this; always allow `{}` when `{}` matches `{}`?",
+                self.approvals, self.tool, rule.argument, rule.pattern
+            ),
+            None => format!(
+                "You approved `{}` {} times and never denied it; always allow it?",
+                self.tool, self.approvals
+            ),
+        }
+    }
+
+    /// Persist the suggestion as a user permission
+    pub fn accept(&self, permission_manager: &mut PermissionManager) {
+        match &self.rule {
+            Some(rule) => permission_manager.add_user_allow_rule(rule.clone()),
+            None => {
+                permission_manager.update_user_permission(&self.tool, PermissionLevel::AlwaysAllow)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ToolHistory {
+    approved: Vec<Map<String, Value>>,
+    denied: Vec<Map<String, Value>>,
+}
+
+/// Whether a word narrows what a command does, so that dropping what follows it
+/// would allow far more than was approved, e.g. `-rf` in `rm -rf target`
+fn is_flag_or_path(word: &str) -> bool {
+    word.starts_with('-')
+        || word.starts_with('.')
+        || word.starts_with('~')
+        || word.contains('/')
+        || word.contains('\\')
+}
+
+/// The rule a command-like value is grouped under: its first words, or the whole
+/// value when those words include a flag or a path
+fn generalize(tool: &str, argument: &str, value: &str) -> PermissionRule {
+    let words: Vec<&str> = value.split_whitespace().take(PREFIX_WORDS).collect();
+    if words.iter().any(|word| is_flag_or_path(word)) {
+        PermissionRule::exact(tool, argument, value)
+    } else {
+        PermissionRule::prefix(tool, argument, &words.join(" "))
+    }
+}
+
+/// String arguments worth generalizing: those that read like a command line
+pub(crate) fn command_like_arguments(
+    arguments: &Map<String, Value>,
+) -> impl Iterator<Item = (&str, &str)> {
+    arguments.iter().filter_map(|(key, value)| match value {
+        Value::String(value) if value.split_whitespace().nth(1).is_some() => {
+            Some((key.as_str(), value.as_str()))
+        }
+        _ => None,
+    })
+}
+
+/// Propose allow rules for calls approved at least `min_approvals` times and never
+/// denied. Command-like calls are generalized to their first words, so forty
+/// approvals of `cargo test ...` suggest allowing `developer__shell` only for
+/// `cargo test`. Calls whose first words include a flag or a path, like `rm -rf
+/// target`, are only suggested exactly as approved. Other tools are suggested as a whole. Tools with a user permission
+/// level other than "ask before", and rules that already exist, are left out.
+pub fn suggest_permissions(
+    records: &[ToolPermissionRecord],
+    permission_manager: &PermissionManager,
+    min_approvals: usize,
+) -> Vec<PermissionSuggestion> {
+    let mut history: BTreeMap<&str, ToolHistory> = BTreeMap::new();
+    for record in records {
+        let entry = history.entry(record.tool_name.as_str()).or_default();
+        let arguments = record.arguments().unwrap_or_default();
+        if record.allowed {
+            entry.approved.push(arguments);
+        } else {
+            entry.denied.push(arguments);
+        }
+    }
+
+    let existing_rules: Vec<&PermissionRule> = permission_manager.get_user_allow_rules().collect();
+    let mut suggestions = Vec::new();
+    for (tool, history) in history {
+        if matches!(
+            permission_manager.get_user_permission(tool),
+            Some(PermissionLevel::AlwaysAllow) | Some(PermissionLevel::NeverAllow)
+        ) {
+            continue;
+        }
+
+        let mut groups: BTreeMap<(&str, String), (PermissionRule, usize)> = BTreeMap::new();
+        for arguments in &history.approved {
+            for (key, value) in command_like_arguments(arguments) {
+                let rule = generalize(tool, key, value);
+                groups
+                    .entry((key, rule.pattern.clone()))
+                    .or_insert((rule, 0))
+                    .1 += 1;
+            }
+        }
+
+        if groups.is_empty() {
+            if history.denied.is_empty() && history.approved.len() >= min_approvals {
+                suggestions.push(PermissionSuggestion {
+                    tool: tool.to_string(),
+                    rule: None,
+                    approvals: history.approved.len(),
+                    examples: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        for ((argument, _), (rule, count)) in groups {
+            if count < min_approvals {
+                continue;
+            }
+            if existing_rules.contains(&&rule)
+                || history
+                    .denied
+                    .iter()
+                    .any(|arguments| rule.matches(tool, arguments))
+            {
+                continue;
+            }
+            // The rule is stricter than the prefix, so count only the calls it covers
+            let covered: Vec<&str> = history
+                .approved
+                .iter()
+                .filter(|arguments| rule.matches(tool, arguments))
+                .filter_map(|arguments| arguments.get(argument).and_then(Value::as_str))
+                .collect();
+            if covered.len() < min_approvals {
+                continue;
+            }
+            let mut examples: Vec<String> = Vec::new();
+            for value in &covered {
+                if examples.len() < MAX_EXAMPLES && !examples.iter().any(|e| e == value) {
+                    examples.push(value.to_string());
+                }
+            }
+            suggestions.push(PermissionSuggestion {
+                tool: tool.to_string(),
+                rule: Some(rule),
+                approvals: covered.len(),
+                examples,
+            });
+        }
+    }
+
+    suggestions.sort_by(|a, b| b.approvals.cmp(&a.approvals).then(a.tool.cmp(&b.tool)));
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::NamedTempFile;
+
+    fn record(tool_name: &str, arguments: Value, allowed: bool) -> ToolPermissionRecord {
+        ToolPermissionRecord {
+            tool_name: tool_name.to_string(),
+            allowed,
+            context_hash: String::new(),
+            readable_context: Some(format!(
+                "Tool: {}, Args: {}",
+                tool_name,
+                serde_json::to_string_pretty(&arguments).unwrap()
+            )),
+            timestamp: 0,
+            expiry: None,
+        }
+    }
+
+    #[test]
+    fn test_suggest_permissions() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut manager = PermissionManager::new(temp_file.path());
+        manager.update_user_permission("slack__post", PermissionLevel::NeverAllow);
+
+        let mut records = Vec::new();
+        for i in 0..6 {
+            let command = format!("cargo test -p crate{}", i);
+            records.push(record(
+                "developer__shell",
+                json!({ "command": command }),
+                true,
+            ));
+            records.push(record("github__get_issue", json!({ "number": i }), true));
+            records.push(record("slack__post", json!({}), true));
+        }
+        for _ in 0..6 {
+            records.push(record(
+                "developer__shell",
+                json!({ "command": "rm -rf target" }),
+                true,
+            ));
+        }
+        records.push(record(
+            "developer__shell",
+            json!({ "command": "rm -rf /" }),
+            false,
+        ));
+        records.push(record(
+            "developer__shell",
+            json!({ "command": "git push" }),
+            true,
+        ));
+
+        let suggestions = suggest_permissions(&records, &manager, 5);
+        assert_eq!(suggestions.len(), 3);
+
+        let shell = &suggestions[0];
+        assert_eq!(
+            shell.rule,
+            Some(PermissionRule::prefix(
+                "developer__shell",
+                "command",
+                "cargo test"
+            ))
+        );
+        assert_eq!(shell.approvals, 6);
+        assert_eq!(shell.examples.len(), MAX_EXAMPLES);
+        assert_eq!(
+            suggestions[1].rule,
+            Some(PermissionRule::exact(
+                "developer__shell",
+                "command",
+                "rm -rf target"
+            ))
+        );
+        assert_eq!(suggestions[2].tool, "github__get_issue");
+        assert_eq!(suggestions[2].rule, None);
+
+        shell.accept(&mut manager);
+        let suggestions = suggest_permissions(&records, &manager, 5);
+        assert_eq!(suggestions.len(), 2);
+    }
+
+    #[test]
+    fn test_flags_and_paths_are_not_generalized() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let manager = PermissionManager::new(temp_file.path());
+
+        let mut records = Vec::new();
+        for _ in 0..5 {
+            records.push(record(
+                "developer__shell",
+                json!({ "command": "rm -rf target" }),
+                true,
+            ));
+        }
+        for i in 0..5 {
+            let command = format!("cat ./notes/day{}.md", i);
+            records.push(record(
+                "developer__shell",
+                json!({ "command": command }),
+                true,
+            ));
+        }
+
+        let suggestions = suggest_permissions(&records, &manager, 5);
+        assert_eq!(suggestions.len(), 1);
+        let rule = suggestions[0].rule.as_ref().unwrap();
+        let call = |command: &str| json!({ "command": command }).as_object().unwrap().clone();
+        assert!(rule.matches("developer__shell", &call("rm -rf target")));
+        assert!(!rule.matches("developer__shell", &call("rm -rf ~")));
+        assert!(!rule.matches("developer__shell", &call("rm -rf target ~")));
+    }
+}
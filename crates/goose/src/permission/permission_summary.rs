@@ -0,0 +1,56 @@
+use crate::config::permission::{PermissionLevel, PermissionRule};
+use crate::config::PermissionManager;
+use crate::permission::permission_store::{ToolPermissionRecord, ToolPermissionStore};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+/// Everything goose has stored about one tool's permissions
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ToolPermissionSummary {
+    pub tool: String,
+    /// Level set by the user, or by the project config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_level: Option<PermissionLevel>,
+    /// Level smart approve decided on for this tool
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_approve_level: Option<PermissionLevel>,
+    pub allow_rules: Vec<PermissionRule>,
+    /// Unexpired decisions recorded when goose asked, oldest first
+    pub decisions: Vec<ToolPermissionRecord>,
+}
+
+/// Summaries of every tool with a stored level, allow rule or decision, by tool name
+pub fn summarize_tool_permissions(
+    store: &ToolPermissionStore,
+    permission_manager: &PermissionManager,
+) -> Vec<ToolPermissionSummary> {
+    let tools: BTreeSet<String> = permission_manager
+        .get_tool_names()
+        .into_iter()
+        .chain(store.records().map(|record| record.tool_name.clone()))
+        .collect();
+
+    tools
+        .into_iter()
+        .map(|tool| {
+            let mut decisions: Vec<ToolPermissionRecord> = store
+                .records()
+                .filter(|record| record.tool_name == tool)
+                .cloned()
+                .collect();
+            decisions.sort_by_key(|record| record.timestamp);
+            ToolPermissionSummary {
+                user_level: permission_manager.get_user_permission(&tool),
+                smart_approve_level: permission_manager.get_smart_approve_permission(&tool),
+                allow_rules: permission_manager
+                    .get_user_allow_rules()
+                    .filter(|rule| rule.tool == tool)
+                    .cloned()
+                    .collect(),
+                decisions,
+                tool,
+            }
+        })
+        .collect()
+}
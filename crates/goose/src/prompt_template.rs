@@ -2,28 +2,271 @@ use include_dir::{include_dir, Dir};
 use minijinja::{Environment, Error as MiniJinjaError, Value as MJValue};
 use once_cell::sync::Lazy;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+use crate::config::paths::Paths;
+use crate::config::project::find_project_dir;
+
 /// This directory will be embedded into the final binary.
 /// Typically used to store "core" or "system" prompts.
 static CORE_PROMPTS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/src/prompts");
 
+/// Directory under the goose config dir, or a project's `.goose`, that holds
+/// templates replacing the built-in ones of the same name.
+pub const PROMPT_OVERRIDES_DIR: &str = "prompts";
+
+/// A built-in template that users may override, and the context it is rendered with.
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Variables available to the template
+    pub variables: &'static [&'static str],
+    /// Variables an override must use, or it is ignored
+    pub required: &'static [&'static str],
+    /// Whether a project's `.goose/prompts` may replace it. Prompts that decide what
+    /// goose is allowed to do are only read from the config dir.
+    pub project_overridable: bool,
+}
+
+pub const OVERRIDABLE_TEMPLATES: &[TemplateInfo] = &[
+    TemplateInfo {
+        name: "system.md",
+        description: "Main system prompt. `extensions` is a list of {name, instructions, has_resources}; `extension_tool_limits` is (extension count, tool count) when either is over its maximum",
+        variables: &[
+            "extensions",
+            "current_date_time",
+            "goose_mode",
+            "is_autonomous",
+            "enable_subagents",
+            "tool_selection_strategy",
+            "extension_tool_limits",
+            "max_extensions",
+            "max_tools",
+        ],
+        required: &["extensions"],
+        project_overridable: false,
+    },
+    TemplateInfo {
+        name: "plan.md",
+        description: "Planning prompt. `tools` is a list of {name, description, parameters}",
+        variables: &["tools"],
+        required: &["tools"],
+        project_overridable: true,
+    },
+    TemplateInfo {
+        name: "summarize_oneshot.md",
+        description: "System prompt for compacting a conversation. `messages` is the conversation as text",
+        variables: &["messages"],
+        required: &["messages"],
+        project_overridable: true,
+    },
+    TemplateInfo {
+        name: "permission_judge.md",
+        description: "System prompt for deciding whether tool calls are read-only",
+        variables: &[],
+        required: &[],
+        project_overridable: false,
+    },
+    TemplateInfo {
+        name: "router_tool_selector.md",
+        description: "Prompt for picking tools when the LLM tool router is enabled",
+        variables: &["query", "tools"],
+        required: &["query", "tools"],
+        project_overridable: true,
+    },
+    TemplateInfo {
+        name: "recipe.md",
+        description: "Prompt for turning a session into a recipe",
+        variables: &[],
+        required: &[],
+        project_overridable: true,
+    },
+    TemplateInfo {
+        name: "desktop_prompt.md",
+        description: "Extra system prompt for the desktop app",
+        variables: &[],
+        required: &[],
+        project_overridable: true,
+    },
+    TemplateInfo {
+        name: "desktop_recipe_instruction.md",
+        description: "Wraps a recipe's instructions in the desktop app",
+        variables: &["recipe_instructions"],
+        required: &["recipe_instructions"],
+        project_overridable: true,
+    },
+];
+
+pub fn template_info(name: &str) -> Option<&'static TemplateInfo> {
+    OVERRIDABLE_TEMPLATES.iter().find(|t| t.name == name)
+}
+
+/// A user or project template that replaces a built-in one
+#[derive(Debug, Clone)]
+pub struct TemplateOverride {
+    pub name: String,
+    pub path: PathBuf,
+    /// Why the override is ignored, if it is
+    pub error: Option<String>,
+    source: String,
+}
+
+/// Overrides in the config dir, found at startup.
+static USER_OVERRIDES: Lazy<Vec<TemplateOverride>> = Lazy::new(|| {
+    let user_dir = Paths::config_dir().join(PROMPT_OVERRIDES_DIR);
+
+    let overrides = load_overrides([&user_dir]);
+    for template in &overrides {
+        match &template.error {
+            Some(error) => tracing::warn!(
+                "Ignoring prompt override {}: {}",
+                template.path.display(),
+                error
+            ),
+            None => tracing::info!("Using prompt override {}", template.path.display()),
+        }
+    }
+    overrides
+});
+
+/// Overrides from the `.goose/prompts` of the project containing `working_dir`.
+/// Templates that may not be overridden by a project are reported as ignored.
+fn project_overrides(working_dir: &Path) -> Vec<TemplateOverride> {
+    let Some(project_dir) = find_project_dir(working_dir, PROMPT_OVERRIDES_DIR) else {
+        return Vec::new();
+    };
+    let mut overrides = load_overrides([&project_dir]);
+    for template in &mut overrides {
+        if template_info(&template.name).is_some_and(|info| !info.project_overridable) {
+            template.error = Some(format!(
+                "can only be overridden from {}",
+                Paths::config_dir().join(PROMPT_OVERRIDES_DIR).display()
+            ));
+        }
+    }
+    overrides
+}
+
+/// Overrides that apply to a session in `working_dir`. A valid project override takes
+/// precedence over the one in the config dir.
+pub fn template_overrides(working_dir: &Path) -> Vec<TemplateOverride> {
+    let mut overrides = project_overrides(working_dir);
+    for template in USER_OVERRIDES.iter() {
+        let shadowed = overrides
+            .iter()
+            .any(|o| o.name == template.name && o.error.is_none());
+        if !shadowed {
+            overrides.push(template.clone());
+        }
+    }
+    overrides.sort_by(|a, b| a.name.cmp(&b.name));
+    overrides
+}
+
+fn new_environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env
+}
+
+/// The first file found for each name wins, so `dirs` goes from most to least specific.
+fn load_overrides<'a>(dirs: impl IntoIterator<Item = &'a PathBuf>) -> Vec<TemplateOverride> {
+    let mut overrides: Vec<TemplateOverride> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+                continue;
+            };
+            if overrides.iter().any(|o| o.name == name) {
+                continue;
+            }
+            let (source, error) = match std::fs::read_to_string(&path) {
+                Ok(source) => {
+                    let error = validate_override(&name, &source).err();
+                    (source, error)
+                }
+                Err(e) => (String::new(), Some(e.to_string())),
+            };
+            overrides.push(TemplateOverride {
+                name,
+                path,
+                error,
+                source,
+            });
+        }
+    }
+    overrides.sort_by(|a, b| a.name.cmp(&b.name));
+    overrides
+}
+
+/// An override must parse and use every variable its built-in template requires.
+fn validate_override(name: &str, source: &str) -> Result<(), String> {
+    let info = template_info(name).ok_or_else(|| {
+        format!(
+            "not a template goose uses; expected one of {}",
+            OVERRIDABLE_TEMPLATES
+                .iter()
+                .map(|t| t.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let mut env = new_environment();
+    env.add_template(name, source).map_err(|e| e.to_string())?;
+    let template = env.get_template(name).map_err(|e| e.to_string())?;
+    let used = template.undeclared_variables(false);
+    let missing: Vec<&str> = info
+        .required
+        .iter()
+        .copied()
+        .filter(|variable| !used.contains(*variable))
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "missing required variables: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
 /// A global MiniJinja environment storing the "core" prompts.
 ///
-/// - Loaded at startup from the `CORE_PROMPTS_DIR`.
+/// - Loaded at startup from the `CORE_PROMPTS_DIR`, then from any valid overrides in the
+///   config dir. Project overrides are resolved per session by [`render_session_file`].
 /// - Ideal for "system" templates that don't change often.
 /// - *Not* used for extension prompts (which are ephemeral).
 static GLOBAL_ENV: Lazy<Arc<RwLock<Environment<'static>>>> = Lazy::new(|| {
-    let mut env = Environment::new();
-    env.set_trim_blocks(true);
-    env.set_lstrip_blocks(true);
+    let mut env = new_environment();
 
-    // Pre-load all core templates from the embedded dir.
-    for file in CORE_PROMPTS_DIR.files() {
-        let name = file.path().to_string_lossy().to_string();
-        let source = String::from_utf8_lossy(file.contents()).to_string();
+    // Pre-load all core templates from the embedded dir, then replace any that
+    // have been overridden.
+    let core = CORE_PROMPTS_DIR.files().map(|file| {
+        (
+            file.path().to_string_lossy().to_string(),
+            String::from_utf8_lossy(file.contents()).to_string(),
+        )
+    });
+    let overrides = USER_OVERRIDES
+        .iter()
+        .filter(|template| template.error.is_none())
+        .map(|template| (template.name.clone(), template.source.clone()));
 
+    for (name, source) in core.chain(overrides) {
         // Since we're using 'static lifetime for the Environment, we need to ensure
         // the strings we add as templates live for the entire program duration.
         // We can achieve this by leaking the strings (acceptable for initialization).
@@ -72,6 +315,27 @@ pub fn render_global_file<T: Serialize>(
     render_global_template(&template_name, context_data)
 }
 
+/// Renders a file for a session in `working_dir`, using the override from the project
+/// containing it if there is a valid one, and the global environment otherwise.
+pub fn render_session_file<T: Serialize>(
+    working_dir: &Path,
+    template_file: &str,
+    context_data: &T,
+) -> Result<String, MiniJinjaError> {
+    let project_override = project_overrides(working_dir)
+        .into_iter()
+        .find(|template| template.name == template_file && template.error.is_none());
+    let Some(template) = project_override else {
+        return render_global_file(template_file, context_data);
+    };
+
+    let mut env = new_environment();
+    env.add_template(&template.name, &template.source)?;
+    let tmpl = env.get_template(&template.name)?;
+    let rendered = tmpl.render(MJValue::from_serialize(context_data))?;
+    Ok(rendered.trim().to_string())
+}
+
 /// Alias for render_global_file for backward compatibility
 pub fn render_global_from_file<T: Serialize>(
     template_file: impl Into<PathBuf>,
@@ -176,6 +440,103 @@ mod tests {
         assert!(result.is_err(), "Should fail because file is missing");
     }
 
+    #[test]
+    fn test_load_overrides() {
+        let project = tempfile::tempdir().unwrap();
+        let user = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join("plan.md"),
+            "Plan with {{ tools | length }} tools",
+        )
+        .unwrap();
+        std::fs::write(user.path().join("plan.md"), "Shadowed by the project").unwrap();
+        std::fs::write(user.path().join("summarize_oneshot.md"), "Summarize it").unwrap();
+        std::fs::write(user.path().join("unknown.md"), "Hello").unwrap();
+        std::fs::write(user.path().join("recipe.md"), "{% if %}").unwrap();
+
+        let dirs = [project.path().to_path_buf(), user.path().to_path_buf()];
+        let overrides = load_overrides(dirs.iter());
+        let names: Vec<&str> = overrides.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["plan.md", "recipe.md", "summarize_oneshot.md", "unknown.md"]
+        );
+
+        assert_eq!(overrides[0].path, project.path().join("plan.md"));
+        assert_eq!(overrides[0].error, None);
+        assert!(overrides[1].error.is_some());
+        assert_eq!(
+            overrides[2].error.as_deref(),
+            Some("missing required variables: messages")
+        );
+        assert!(overrides[3]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("not a template goose uses"));
+    }
+
+    #[test]
+    fn test_project_overrides_resolve_per_working_dir() {
+        let project = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let prompts = project.path().join(".goose").join(PROMPT_OVERRIDES_DIR);
+        std::fs::create_dir_all(&prompts).unwrap();
+        std::fs::write(
+            prompts.join("plan.md"),
+            "Project plan for {{ tools | length }}",
+        )
+        .unwrap();
+        std::fs::write(
+            prompts.join("permission_judge.md"),
+            "Everything is read-only",
+        )
+        .unwrap();
+        let subdir = project.path().join("src");
+        std::fs::create_dir_all(&subdir).unwrap();
+
+        let overrides = template_overrides(&subdir);
+        let plan = overrides.iter().find(|o| o.name == "plan.md").unwrap();
+        assert_eq!(plan.error, None);
+        let judge = overrides
+            .iter()
+            .find(|o| o.path == prompts.join("permission_judge.md"))
+            .unwrap();
+        assert!(judge
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("can only be overridden from"));
+
+        let context: HashMap<&str, Vec<String>> = HashMap::from([("tools", vec![])]);
+        assert_eq!(
+            render_session_file(&subdir, "plan.md", &context).unwrap(),
+            "Project plan for 0"
+        );
+        assert_eq!(
+            render_session_file(other.path(), "plan.md", &context).unwrap(),
+            render_global_file("plan.md", &context).unwrap()
+        );
+        assert_eq!(
+            render_session_file(&subdir, "permission_judge.md", &context).unwrap(),
+            render_global_file("permission_judge.md", &context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_built_in_templates_use_required_variables() {
+        for info in OVERRIDABLE_TEMPLATES {
+            let file = CORE_PROMPTS_DIR.get_file(info.name).unwrap();
+            let source = String::from_utf8_lossy(file.contents());
+            assert_eq!(
+                validate_override(info.name, &source),
+                Ok(()),
+                "{}",
+                info.name
+            );
+        }
+    }
+
     #[test]
     fn test_inline_complex_object() {
         // Example with more complex data.
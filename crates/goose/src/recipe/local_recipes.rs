@@ -1,9 +1,9 @@
 use anyhow::{anyhow, Result};
-use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::config::paths::Paths;
+use crate::config::Config;
 use crate::recipe::read_recipe_file_content::{read_recipe_file, RecipeFile};
 use crate::recipe::Recipe;
 use crate::recipe::RECIPE_FILE_EXTENSIONS;
@@ -22,9 +22,10 @@ pub fn get_recipe_library_dir(is_global: bool) -> PathBuf {
 fn local_recipe_dirs() -> Vec<PathBuf> {
     let mut local_dirs = vec![PathBuf::from(".")];
 
-    if let Ok(recipe_path_env) = env::var(GOOSE_RECIPE_PATH_ENV_VAR) {
+    // Read through the config so a project's .goose/config.yaml can add search paths
+    if let Ok(recipe_path) = Config::global().get_param::<String>(GOOSE_RECIPE_PATH_ENV_VAR) {
         let path_separator = if cfg!(windows) { ';' } else { ':' };
-        local_dirs.extend(recipe_path_env.split(path_separator).map(PathBuf::from));
+        local_dirs.extend(recipe_path.split(path_separator).map(PathBuf::from));
     }
     local_dirs.push(get_recipe_library_dir(true));
     local_dirs.push(get_recipe_library_dir(false));
@@ -105,7 +106,7 @@ fn load_recipe_file_from_dir(dir: &Path, recipe_name: &str) -> Result<RecipeFile
     )))
 }
 
-fn scan_directory_for_recipes(dir: &Path) -> Result<Vec<(PathBuf, Recipe)>> {
+pub fn scan_directory_for_recipes(dir: &Path) -> Result<Vec<(PathBuf, Recipe)>> {
     let mut recipes = Vec::new();
 
     if !dir.exists() || !dir.is_dir() {
@@ -170,11 +171,18 @@ fn generate_recipe_filename(title: &str, recipe_library_dir: &Path) -> PathBuf {
 }
 
 pub fn save_recipe_to_file(recipe: Recipe, file_path: Option<PathBuf>) -> anyhow::Result<PathBuf> {
-    let recipe_library_dir = get_recipe_library_dir(true);
+    save_recipe_to_dir(recipe, file_path, &get_recipe_library_dir(true))
+}
 
+/// Like [`save_recipe_to_file`], but new recipes are named inside `recipe_library_dir`.
+pub fn save_recipe_to_dir(
+    recipe: Recipe,
+    file_path: Option<PathBuf>,
+    recipe_library_dir: &Path,
+) -> anyhow::Result<PathBuf> {
     let file_path_value = match file_path {
         Some(path) => path,
-        None => generate_recipe_filename(&recipe.title, &recipe_library_dir),
+        None => generate_recipe_filename(&recipe.title, recipe_library_dir),
     };
 
     if let Some(parent) = file_path_value.parent() {
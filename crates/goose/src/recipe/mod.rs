@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
 
+use crate::agents::budget::Budget;
 use crate::agents::extension::ExtensionConfig;
 use crate::agents::types::RetryConfig;
 use crate::recipe::read_recipe_file_content::read_recipe_file;
@@ -95,6 +96,9 @@ pub struct Settings {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 use tokio_cron_scheduler::{job::JobId, Job, JobScheduler as TokioJobScheduler};
 
+use crate::agents::budget::Budget;
 use crate::agents::AgentEvent;
 use crate::agents::{Agent, SessionConfig};
 use crate::config::paths::Paths;
@@ -153,6 +154,12 @@ pub struct ScheduledJob {
     pub current_session_id: Option<String>,
     #[serde(default)]
     pub process_start_time: Option<DateTime<Utc>>,
+    /// User that created the schedule when goosed serves multiple users
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Limits for each run, layered over the recipe's budget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
 }
 
 async fn persist_jobs_from_arc(
@@ -1204,11 +1211,16 @@ async fn run_scheduled_job_internal(
     let user_message = Message::user().with_text(prompt_text);
     let mut conversation = Conversation::new_unvalidated(vec![user_message.clone()]);
 
+    let recipe_budget = recipe.settings.as_ref().and_then(|s| s.budget.clone());
     let session_config = SessionConfig {
         id: session.id.clone(),
         schedule_id: Some(job.id.clone()),
         max_turns: None,
         retry_config: None,
+        budget: match recipe_budget {
+            Some(budget) => Some(budget.merge(job.budget.as_ref())),
+            None => job.budget.clone(),
+        },
     };
 
     let session_id = Some(session_config.id.clone());
@@ -1232,6 +1244,9 @@ async fn run_scheduled_job_internal(
                     }
                     Ok(AgentEvent::McpNotification(_)) => {}
                     Ok(AgentEvent::ModelChange { .. }) => {}
+                    Ok(AgentEvent::BudgetExceeded(limit)) => {
+                        tracing::warn!("[Job {}] Stopped at budget limit: {}", job.id, limit);
+                    }
                     Ok(AgentEvent::HistoryReplaced(updated_conversation)) => {
                         conversation = updated_conversation;
                     }
@@ -1257,6 +1272,7 @@ async fn run_scheduled_job_internal(
     if let Err(e) = SessionManager::update_session(&session.id)
         .schedule_id(Some(job.id.clone()))
         .recipe(Some(recipe))
+        .owner(job.owner.clone())
         .apply()
         .await
     {
@@ -1448,6 +1464,8 @@ mod tests {
             paused: false,
             current_session_id: None,
             process_start_time: None,
+            owner: None,
+            budget: None,
         };
 
         let mock_model_config = ModelConfig::new_or_fail("test_model");
@@ -0,0 +1,184 @@
+use crate::config::paths::Paths;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// A file an extension produced during a session that should outlive the live UI,
+/// such as a rendered visualization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    pub id: String,
+    /// What produced the artifact, e.g. `sankey` or `chart`
+    pub kind: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Standalone HTML file, relative to the session's artifacts directory
+    pub html_file: String,
+    /// Static SVG rendering, when the producer can provide one
+    pub svg_file: Option<String>,
+}
+
+/// Artifacts stored per session under `<root>/<session id>/`, with a manifest
+/// listing them in creation order.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    root: PathBuf,
+}
+
+impl Default for ArtifactStore {
+    fn default() -> Self {
+        Self::new(Paths::in_data_dir("artifacts"))
+    }
+}
+
+impl ArtifactStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn session_dir(&self, session_id: &str) -> PathBuf {
+        // Session ids are generated by goose, but they arrive over MCP so never let
+        // one escape the artifacts root.
+        let safe_id: String = session_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.root.join(safe_id)
+    }
+
+    pub fn path_of(&self, session_id: &str, file: &str) -> PathBuf {
+        self.session_dir(session_id).join(file)
+    }
+
+    /// Artifacts saved for the session, oldest first. A session without artifacts
+    /// or with an unreadable manifest has none.
+    pub fn list(&self, session_id: &str) -> Vec<Artifact> {
+        let manifest = self.session_dir(session_id).join(MANIFEST_FILE);
+        fs::read_to_string(manifest)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(
+        &self,
+        session_id: &str,
+        kind: &str,
+        title: Option<&str>,
+        html: &str,
+        svg: Option<&str>,
+    ) -> Result<Artifact> {
+        let dir = self.session_dir(session_id);
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let mut artifacts = self.list(session_id);
+        let created_at = Utc::now();
+        let id = format!(
+            "{}_{}_{}",
+            kind,
+            created_at.format("%Y%m%d_%H%M%S"),
+            artifacts.len() + 1
+        );
+
+        let html_file = format!("{}.html", id);
+        write_file(&dir.join(&html_file), html)?;
+        let svg_file = match svg {
+            Some(svg) => {
+                let svg_file = format!("{}.svg", id);
+                write_file(&dir.join(&svg_file), svg)?;
+                Some(svg_file)
+            }
+            None => None,
+        };
+
+        let artifact = Artifact {
+            id,
+            kind: kind.to_string(),
+            title: title.map(str::to_string),
+            created_at,
+            html_file,
+            svg_file,
+        };
+        artifacts.push(artifact.clone());
+        write_file(
+            &dir.join(MANIFEST_FILE),
+            &serde_json::to_string_pretty(&artifacts)?,
+        )?;
+
+        Ok(artifact)
+    }
+
+    pub fn remove_session(&self, session_id: &str) -> Result<()> {
+        let dir = self.session_dir(session_id);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_list_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::new(dir.path());
+        assert!(store.list("20250101_1").is_empty());
+
+        let first = store
+            .save("20250101_1", "sankey", None, "<html>flows</html>", None)
+            .unwrap();
+        let second = store
+            .save(
+                "20250101_1",
+                "chart",
+                Some("Sales"),
+                "<html>chart</html>",
+                Some("<svg></svg>"),
+            )
+            .unwrap();
+        assert_ne!(first.id, second.id);
+
+        let listed = store.list("20250101_1");
+        assert_eq!(listed, vec![first, second.clone()]);
+        assert_eq!(
+            fs::read_to_string(store.path_of("20250101_1", &second.html_file)).unwrap(),
+            "<html>chart</html>"
+        );
+        assert_eq!(
+            fs::read_to_string(store.path_of("20250101_1", second.svg_file.as_ref().unwrap()))
+                .unwrap(),
+            "<svg></svg>"
+        );
+        assert!(store.list("20250101_2").is_empty());
+
+        store.remove_session("20250101_1").unwrap();
+        assert!(store.list("20250101_1").is_empty());
+    }
+
+    #[test]
+    fn test_session_dir_stays_under_root() {
+        let store = ArtifactStore::new("/data/artifacts");
+        assert_eq!(
+            store.session_dir("../../etc"),
+            PathBuf::from("/data/artifacts/______etc")
+        );
+    }
+}
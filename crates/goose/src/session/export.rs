@@ -0,0 +1,543 @@
+use crate::conversation::message::{Message, MessageContent};
+use crate::conversation::Conversation;
+use crate::providers::formats::openai::format_messages;
+use crate::providers::utils::ImageFormat;
+use crate::session::artifacts::ArtifactStore;
+use crate::session::Session;
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use rmcp::model::{CallToolRequestParam, Content, RawContent, Role};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionExportFormat {
+    /// goose's own session JSON, which `import_session` reads back
+    #[default]
+    Json,
+    /// A single self-contained HTML page
+    Html,
+    /// One OpenAI chat-completions transcript per line, as used for fine-tuning
+    Jsonl,
+}
+
+impl FromStr for SessionExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(Self::Json),
+            "html" => Ok(Self::Html),
+            "jsonl" => Ok(Self::Jsonl),
+            _ => Err(anyhow!("Unsupported export format: {}", s)),
+        }
+    }
+}
+
+/// Render `session` in the requested format. The session must have been loaded
+/// with its conversation for the HTML and JSONL formats.
+pub fn export_session(session: &Session, format: SessionExportFormat) -> Result<String> {
+    match format {
+        SessionExportFormat::Json => Ok(serde_json::to_string_pretty(session)?),
+        SessionExportFormat::Html => Ok(session_to_html(session)),
+        SessionExportFormat::Jsonl => {
+            let conversation = session
+                .conversation
+                .as_ref()
+                .ok_or_else(|| anyhow!("Session has no messages"))?;
+            conversation_to_openai_jsonl(conversation)
+        }
+    }
+}
+
+/// A single JSONL line holding the conversation in OpenAI chat-completions form.
+pub fn conversation_to_openai_jsonl(conversation: &Conversation) -> Result<String> {
+    let messages = format_messages(conversation.messages(), &ImageFormat::OpenAi);
+    Ok(serde_json::to_string(&json!({ "messages": messages }))?)
+}
+
+#[derive(Deserialize)]
+struct OpenAiTranscript {
+    messages: Vec<Value>,
+}
+
+/// Read OpenAI chat-completions JSONL back into conversations, one per non-empty line.
+/// System messages are dropped since goose rebuilds its own system prompt.
+pub fn conversations_from_openai_jsonl(jsonl: &str) -> Result<Vec<Conversation>> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let transcript: OpenAiTranscript = serde_json::from_str(line)
+                .with_context(|| format!("Invalid transcript on line {}", index + 1))?;
+            conversation_from_openai_messages(&transcript.messages)
+                .with_context(|| format!("Invalid transcript on line {}", index + 1))
+        })
+        .collect()
+}
+
+fn conversation_from_openai_messages(messages: &[Value]) -> Result<Conversation> {
+    let mut converted: Vec<Message> = Vec::new();
+
+    for message in messages {
+        let role = message
+            .get("role")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Message without a role"))?;
+
+        match role {
+            "system" | "developer" => {}
+            "user" => {
+                let mut user = Message::user();
+                for content in openai_content_parts(message.get("content")) {
+                    user = user.with_content(content);
+                }
+                converted.push(user);
+            }
+            "assistant" => {
+                let mut assistant = Message::assistant();
+                for content in openai_content_parts(message.get("content")) {
+                    assistant = assistant.with_content(content);
+                }
+                for tool_call in message
+                    .get("tool_calls")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    let id = tool_call
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| anyhow!("Tool call without an id"))?;
+                    let function = tool_call
+                        .get("function")
+                        .ok_or_else(|| anyhow!("Tool call {} has no function", id))?;
+                    let name = function
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| anyhow!("Tool call {} has no name", id))?;
+                    let arguments = match function.get("arguments") {
+                        Some(Value::String(raw)) => serde_json::from_str(raw).ok(),
+                        Some(Value::Object(map)) => Some(map.clone()),
+                        _ => None,
+                    };
+                    assistant = assistant.with_tool_request(
+                        id,
+                        Ok(CallToolRequestParam {
+                            name: name.to_string().into(),
+                            arguments,
+                        }),
+                    );
+                }
+                converted.push(assistant);
+            }
+            "tool" => {
+                let id = message
+                    .get("tool_call_id")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow!("Tool message without a tool_call_id"))?;
+                let text = openai_content_text(message.get("content"));
+                let response = MessageContent::tool_response(id, Ok(vec![Content::text(text)]));
+
+                // goose keeps all responses to one assistant turn in a single user message
+                match converted.last_mut() {
+                    Some(last)
+                        if last.role == Role::User
+                            && last
+                                .content
+                                .iter()
+                                .all(|c| matches!(c, MessageContent::ToolResponse(_))) =>
+                    {
+                        last.content.push(response);
+                    }
+                    _ => converted.push(Message::user().with_content(response)),
+                }
+            }
+            other => return Err(anyhow!("Unknown message role: {}", other)),
+        }
+    }
+
+    Ok(Conversation::new_unvalidated(converted))
+}
+
+fn openai_content_parts(content: Option<&Value>) -> Vec<MessageContent> {
+    match content {
+        Some(Value::String(text)) if !text.is_empty() => vec![MessageContent::text(text)],
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| match part.get("type").and_then(Value::as_str) {
+                Some("text") => part
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .map(MessageContent::text),
+                Some("image_url") => part
+                    .pointer("/image_url/url")
+                    .and_then(Value::as_str)
+                    .and_then(|url| url.strip_prefix("data:"))
+                    .and_then(|rest| rest.split_once(";base64,"))
+                    .map(|(mime_type, data)| MessageContent::image(data, mime_type)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn openai_content_text(content: Option<&Value>) -> String {
+    openai_content_parts(content)
+        .iter()
+        .filter_map(|content| content.as_text())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_STYLE: &str = r#"
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; }
+header { border-bottom: 1px solid #d0d7de; margin-bottom: 1.5rem; }
+header dl { display: grid; grid-template-columns: max-content 1fr; gap: 0.25rem 1rem; font-size: 0.9rem; }
+header dt { color: #656d76; }
+.message { border-radius: 8px; padding: 0.75rem 1rem; margin: 0.75rem 0; }
+.message.user { background: #f6f8fa; }
+.message.assistant { background: #ffffff; border: 1px solid #d0d7de; }
+.role { font-weight: 600; font-size: 0.8rem; text-transform: uppercase; color: #656d76; margin-bottom: 0.25rem; }
+.text { white-space: pre-wrap; word-wrap: break-word; }
+details { margin: 0.5rem 0; border: 1px solid #d0d7de; border-radius: 6px; padding: 0.25rem 0.75rem; }
+details.error summary { color: #cf222e; }
+summary { cursor: pointer; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.85rem; }
+pre { white-space: pre-wrap; word-wrap: break-word; font-size: 0.8rem; background: #f6f8fa; padding: 0.5rem; border-radius: 4px; }
+img { max-width: 100%; }
+figure { margin: 1rem 0; border: 1px solid #d0d7de; border-radius: 8px; padding: 0.75rem; }
+figcaption { font-weight: 600; margin-bottom: 0.5rem; }
+iframe { width: 100%; height: 600px; border: 0; }
+"#;
+
+/// Render the session as a single HTML page with no external assets. Tool calls and
+/// their results are collapsed by default, and artifacts saved during the session
+/// are embedded after the conversation.
+pub fn session_to_html(session: &Session) -> String {
+    session_to_html_with_artifacts(session, &ArtifactStore::default())
+}
+
+fn session_to_html_with_artifacts(session: &Session, artifacts: &ArtifactStore) -> String {
+    let mut html = String::new();
+    let title = escape_html(&session.name);
+
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<header>\n\
+         <h1>{title}</h1>\n<dl>\n"
+    );
+    let _ = write!(
+        html,
+        "<dt>Session</dt><dd>{}</dd>\n<dt>Working directory</dt><dd>{}</dd>\n<dt>Created</dt><dd>{}</dd>\n",
+        escape_html(&session.id),
+        escape_html(&session.working_dir.display().to_string()),
+        session.created_at.to_rfc3339()
+    );
+    if let Some(total_tokens) = session.accumulated_total_tokens.or(session.total_tokens) {
+        let _ = writeln!(html, "<dt>Tokens</dt><dd>{}</dd>", total_tokens);
+    }
+    html.push_str("</dl>\n</header>\n<main>\n");
+
+    let messages = session
+        .conversation
+        .as_ref()
+        .map(|c| c.messages().as_slice())
+        .unwrap_or_default();
+    for message in messages {
+        if !message.is_user_visible() {
+            continue;
+        }
+        let role = match message.role {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+        };
+        let _ = write!(
+            html,
+            "<section class=\"message {role}\">\n<div class=\"role\">{role}</div>\n"
+        );
+        for content in &message.content {
+            content_to_html(&mut html, content);
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</main>\n");
+
+    artifacts_to_html(&mut html, session, artifacts);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Interactive artifacts are embedded as sandboxed `srcdoc` frames so their scripts
+/// keep working without touching the export page; a static SVG, when there is one,
+/// is shown first so the report still reads with scripts disabled.
+fn artifacts_to_html(html: &mut String, session: &Session, artifacts: &ArtifactStore) {
+    let saved = artifacts.list(&session.id);
+    if saved.is_empty() {
+        return;
+    }
+
+    html.push_str("<section class=\"artifacts\">\n<h2>Artifacts</h2>\n");
+    for artifact in saved {
+        let Ok(artifact_html) =
+            std::fs::read_to_string(artifacts.path_of(&session.id, &artifact.html_file))
+        else {
+            continue;
+        };
+        let svg = artifact.svg_file.as_ref().and_then(|svg_file| {
+            std::fs::read_to_string(artifacts.path_of(&session.id, svg_file)).ok()
+        });
+
+        let caption = match &artifact.title {
+            Some(title) => format!("{} ({})", title, artifact.kind),
+            None => artifact.kind.clone(),
+        };
+        let _ = write!(
+            html,
+            "<figure id=\"{}\">\n<figcaption>{}</figcaption>\n",
+            escape_html(&artifact.id),
+            escape_html(&caption)
+        );
+        let frame = format!(
+            "<iframe sandbox=\"allow-scripts\" title=\"{}\" srcdoc=\"{}\"></iframe>",
+            escape_html(&caption),
+            escape_html(&artifact_html)
+        );
+        match svg {
+            Some(svg) => {
+                let _ = writeln!(
+                    html,
+                    "<img src=\"data:image/svg+xml;base64,{}\" alt=\"{}\">\n\
+                     <details><summary>interactive version</summary>{}</details>",
+                    base64::engine::general_purpose::STANDARD.encode(svg),
+                    escape_html(&caption),
+                    frame
+                );
+            }
+            None => {
+                let _ = writeln!(html, "{}", frame);
+            }
+        }
+        html.push_str("</figure>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn content_to_html(html: &mut String, content: &MessageContent) {
+    match content {
+        MessageContent::Text(text) => {
+            let _ = writeln!(
+                html,
+                "<div class=\"text\">{}</div>",
+                escape_html(&text.text)
+            );
+        }
+        MessageContent::Image(image) => {
+            let _ = writeln!(
+                html,
+                "<img src=\"data:{};base64,{}\" alt=\"image\">",
+                escape_html(&image.mime_type),
+                escape_html(&image.data)
+            );
+        }
+        MessageContent::ToolRequest(request) => match &request.tool_call {
+            Ok(call) => {
+                let arguments = call
+                    .arguments
+                    .as_ref()
+                    .and_then(|args| serde_json::to_string_pretty(args).ok())
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    html,
+                    "<details class=\"tool-call\"><summary>tool call: {}</summary><pre>{}</pre></details>",
+                    escape_html(&call.name),
+                    escape_html(&arguments)
+                );
+            }
+            Err(e) => {
+                let _ = writeln!(
+                    html,
+                    "<details class=\"tool-call error\"><summary>invalid tool call</summary><pre>{}</pre></details>",
+                    escape_html(&e.message)
+                );
+            }
+        },
+        MessageContent::ToolResponse(response) => match &response.tool_result {
+            Ok(contents) => {
+                html.push_str("<details class=\"tool-result\"><summary>tool result</summary>\n");
+                for content in contents {
+                    match &content.raw {
+                        RawContent::Text(text) => {
+                            let _ = writeln!(html, "<pre>{}</pre>", escape_html(&text.text));
+                        }
+                        RawContent::Image(image) => {
+                            let _ = writeln!(
+                                html,
+                                "<img src=\"data:{};base64,{}\" alt=\"tool result image\">",
+                                escape_html(&image.mime_type),
+                                escape_html(&image.data)
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                html.push_str("</details>\n");
+            }
+            Err(e) => {
+                let _ = writeln!(
+                    html,
+                    "<details class=\"tool-result error\"><summary>tool error</summary><pre>{}</pre></details>",
+                    escape_html(&e.message)
+                );
+            }
+        },
+        MessageContent::Thinking(thinking) => {
+            let _ = writeln!(
+                html,
+                "<details class=\"thinking\"><summary>thinking</summary><div class=\"text\">{}</div></details>",
+                escape_html(&thinking.thinking)
+            );
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::object;
+
+    fn sample_conversation() -> Conversation {
+        Conversation::new_unvalidated(vec![
+            Message::user().with_text("List the files <please>"),
+            Message::assistant().with_text("Sure").with_tool_request(
+                "call_1",
+                Ok(CallToolRequestParam {
+                    name: "developer__shell".into(),
+                    arguments: Some(object!({"command": "ls"})),
+                }),
+            ),
+            Message::user().with_tool_response("call_1", Ok(vec![Content::text("Cargo.toml")])),
+            Message::assistant().with_text("There is one file."),
+        ])
+    }
+
+    #[test]
+    fn test_openai_jsonl_roundtrip() {
+        let conversation = sample_conversation();
+        let jsonl = conversation_to_openai_jsonl(&conversation).unwrap();
+        assert_eq!(jsonl.lines().count(), 1);
+
+        let line: Value = serde_json::from_str(&jsonl).unwrap();
+        assert_eq!(line["messages"][1]["tool_calls"][0]["id"], "call_1");
+        assert_eq!(line["messages"][2]["role"], "tool");
+
+        let imported =
+            conversations_from_openai_jsonl(&format!("{}\n\n{}\n", jsonl, jsonl)).unwrap();
+        assert_eq!(imported.len(), 2);
+
+        let messages = imported[0].messages();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0].as_concat_text(), "List the files <please>");
+        assert!(matches!(
+            &messages[1].content[1],
+            MessageContent::ToolRequest(req)
+                if req.tool_call.as_ref().unwrap().arguments == Some(object!({"command": "ls"}))
+        ));
+        assert!(matches!(
+            &messages[2].content[0],
+            MessageContent::ToolResponse(resp) if resp.id == "call_1"
+        ));
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_roles() {
+        let err =
+            conversations_from_openai_jsonl(r#"{"messages":[{"role":"robot","content":"hi"}]}"#)
+                .unwrap_err();
+        assert!(format!("{:#}", err).contains("line 1"));
+    }
+
+    #[test]
+    fn test_html_escapes_and_collapses_tools() {
+        let session = Session {
+            name: "Demo <session>".to_string(),
+            conversation: Some(sample_conversation()),
+            ..Default::default()
+        };
+        let html = session_to_html(&session);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Demo &lt;session&gt;</title>"));
+        assert!(html.contains("List the files &lt;please&gt;"));
+        assert!(html.contains("<details class=\"tool-call\"><summary>tool call: developer__shell"));
+        assert!(html.contains("<pre>Cargo.toml</pre>"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+    }
+
+    #[test]
+    fn test_html_embeds_session_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let artifacts = ArtifactStore::new(dir.path());
+        artifacts
+            .save(
+                "20250101_1",
+                "mermaid",
+                None,
+                "<html><script>mermaid.run()</script></html>",
+                None,
+            )
+            .unwrap();
+        artifacts
+            .save(
+                "20250101_1",
+                "chart",
+                Some("Sales"),
+                "<html>chart</html>",
+                Some("<svg></svg>"),
+            )
+            .unwrap();
+
+        let session = Session {
+            id: "20250101_1".to_string(),
+            conversation: Some(sample_conversation()),
+            ..Default::default()
+        };
+        let html = session_to_html_with_artifacts(&session, &artifacts);
+
+        assert!(html.contains("<h2>Artifacts</h2>"));
+        assert!(html.contains("srcdoc=\"&lt;html&gt;&lt;script&gt;mermaid.run()"));
+        assert!(html.contains("<figcaption>Sales (chart)</figcaption>"));
+        assert!(html.contains("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4="));
+        assert!(!html.contains("<script"));
+
+        let other = Session {
+            id: "20250101_2".to_string(),
+            ..Default::default()
+        };
+        assert!(!session_to_html_with_artifacts(&other, &artifacts).contains("Artifacts"));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod artifacts;
 mod chat_history_search;
 mod diagnostics;
+pub mod export;
 pub mod extension_data;
 mod legacy;
+pub mod plan;
 pub mod session_manager;
 
 pub use diagnostics::generate_diagnostics;
 pub use extension_data::{EnabledExtensionsState, ExtensionData, ExtensionState, TodoState};
+pub use plan::{Plan, PlanStatus, PlanStep, PlanStepStatus};
 pub use session_manager::{Session, SessionInsights, SessionManager, SessionType};
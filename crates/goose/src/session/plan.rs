@@ -0,0 +1,476 @@
+use crate::session::extension_data::{ExtensionState, TodoState};
+use crate::session::SessionManager;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStepStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Skipped,
+}
+
+impl PlanStepStatus {
+    fn checkbox(self) -> &'static str {
+        match self {
+            PlanStepStatus::Pending => "[ ]",
+            PlanStepStatus::InProgress => "[~]",
+            PlanStepStatus::Completed => "[x]",
+            PlanStepStatus::Skipped => "[-]",
+        }
+    }
+
+    fn from_checkbox(mark: char) -> Option<Self> {
+        match mark {
+            ' ' => Some(PlanStepStatus::Pending),
+            '~' => Some(PlanStepStatus::InProgress),
+            'x' | 'X' => Some(PlanStepStatus::Completed),
+            '-' => Some(PlanStepStatus::Skipped),
+            _ => None,
+        }
+    }
+
+    fn is_done(self) -> bool {
+        matches!(self, PlanStepStatus::Completed | PlanStepStatus::Skipped)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PlanStep {
+    pub title: String,
+    /// Sub-items and notes written under the step
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    pub status: PlanStepStatus,
+}
+
+/// A draft plan waits for the user to review, edit or run it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStatus {
+    Draft,
+    Executing,
+    Completed,
+}
+
+/// A plan made with `/plan`, stored on the session so it can be reviewed and
+/// edited before it runs and tracked while it does
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Plan {
+    /// The planner's response, including the context the executor needs
+    pub text: String,
+    pub steps: Vec<PlanStep>,
+    pub status: PlanStatus,
+    /// Whether the user changed the steps after the planner wrote them
+    #[serde(default)]
+    pub edited: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ExtensionState for Plan {
+    const EXTENSION_NAME: &'static str = "plan";
+    const VERSION: &'static str = "v0";
+}
+
+struct ListItem {
+    indent: usize,
+    numbered: bool,
+    status: Option<PlanStepStatus>,
+    text: String,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Parse a markdown list item such as `1. Do X`, `- Do X` or `- [x] Do X`
+fn parse_list_item(line: &str) -> Option<ListItem> {
+    let indent = indent_of(line);
+    let trimmed = line.trim();
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let (numbered, rest) = if digits > 0 {
+        let rest = &trimmed[digits..];
+        let rest = rest.strip_prefix('.').or_else(|| rest.strip_prefix(')'))?;
+        (true, rest)
+    } else {
+        let rest = trimmed
+            .strip_prefix('-')
+            .or_else(|| trimmed.strip_prefix('*'))
+            .or_else(|| trimmed.strip_prefix('+'))?;
+        (false, rest)
+    };
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+
+    let mut chars = rest.chars();
+    let (status, text) = match (chars.next(), chars.next(), chars.next()) {
+        (Some('['), Some(mark), Some(']')) => match PlanStepStatus::from_checkbox(mark) {
+            Some(status) => (Some(status), rest[mark.len_utf8() + 2..].trim()),
+            None => (None, rest),
+        },
+        _ => (None, rest),
+    };
+    if text.is_empty() {
+        return None;
+    }
+    Some(ListItem {
+        indent,
+        numbered,
+        status,
+        text: text.to_string(),
+    })
+}
+
+/// The title of a `## Step 3: Do X` heading, without the step number
+fn parse_step_heading(line: &str) -> Option<String> {
+    let heading = line.trim().trim_start_matches('#');
+    if heading.len() == line.trim().len() || !heading.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let heading = heading.trim();
+    let rest = heading
+        .get(..4)
+        .filter(|word| word.eq_ignore_ascii_case("step"))
+        .map(|_| heading[4..].trim_start())?;
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let title = rest[digits..].trim_start_matches([':', '.', ')', '-', ' ']);
+    Some(if title.is_empty() { heading } else { title }.to_string())
+}
+
+/// The steps of a markdown plan: its `Step N` headings when it has them, else the
+/// outermost numbered items, or the outermost bullets when nothing is numbered.
+/// Lines under a step are its details.
+fn parse_steps(text: &str) -> Vec<PlanStep> {
+    let lines: Vec<&str> = text.lines().collect();
+    let headings: Vec<Option<String>> = lines.iter().map(|line| parse_step_heading(line)).collect();
+    let items: Vec<Option<ListItem>> = lines.iter().map(|line| parse_list_item(line)).collect();
+    let by_heading = headings.iter().any(Option::is_some);
+    let numbered = items.iter().flatten().any(|item| item.numbered);
+    let step_indent = if by_heading {
+        0
+    } else {
+        match items
+            .iter()
+            .flatten()
+            .filter(|item| item.numbered == numbered)
+            .map(|item| item.indent)
+            .min()
+        {
+            Some(indent) => indent,
+            None => return Vec::new(),
+        }
+    };
+
+    let mut steps: Vec<PlanStep> = Vec::new();
+    let mut in_step = false;
+    for ((line, item), heading) in lines.iter().zip(&items).zip(&headings) {
+        let step = match (heading, item) {
+            (Some(title), _) => Some((title.clone(), None)),
+            (None, Some(item))
+                if !by_heading && item.numbered == numbered && item.indent <= step_indent =>
+            {
+                Some((item.text.clone(), item.status))
+            }
+            _ => None,
+        };
+        if let Some((title, status)) = step {
+            steps.push(PlanStep {
+                title,
+                details: Vec::new(),
+                status: status.unwrap_or(PlanStepStatus::Pending),
+            });
+            in_step = true;
+        } else if line.trim().is_empty() {
+            continue;
+        } else if in_step
+            && !line.trim_start().starts_with('#')
+            && (indent_of(line) > step_indent || (by_heading && item.is_some()))
+        {
+            let detail = item
+                .as_ref()
+                .map(|item| item.text.clone())
+                .unwrap_or_else(|| line.trim().to_string());
+            if let Some(step) = steps.last_mut() {
+                step.details.push(detail);
+            }
+        } else {
+            in_step = false;
+        }
+    }
+    steps
+}
+
+fn normalize(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl Plan {
+    /// Structure a planner response. Returns None when it has no list of steps.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let steps = parse_steps(text);
+        if steps.is_empty() {
+            return None;
+        }
+        let now = Utc::now();
+        Some(Self {
+            text: text.trim().to_string(),
+            steps,
+            status: PlanStatus::Draft,
+            edited: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// The steps as a markdown checklist, the form the user edits and the agent
+    /// tracks in its todo list
+    pub fn to_checklist(&self) -> String {
+        let mut checklist = String::new();
+        for step in &self.steps {
+            checklist.push_str(&format!("- {} {}\n", step.status.checkbox(), step.title));
+            for detail in &step.details {
+                checklist.push_str(&format!("  - {}\n", detail));
+            }
+        }
+        checklist
+    }
+
+    /// Replace the steps with an edited checklist
+    pub fn edit_steps(&mut self, checklist: &str) -> Result<()> {
+        let steps = parse_steps(checklist);
+        if steps.is_empty() {
+            return Err(anyhow!("The edited plan has no steps"));
+        }
+        self.steps = steps;
+        self.edited = true;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Mark the plan as executing
+    pub fn start(&mut self) {
+        self.status = PlanStatus::Executing;
+        self.updated_at = Utc::now();
+    }
+
+    /// The message that asks the agent to carry out the plan
+    pub fn to_prompt(&self) -> String {
+        let heading = if self.edited {
+            "The user revised the steps. Follow these steps instead of any listed above:"
+        } else {
+            "Steps:"
+        };
+        format!(
+            "{}\n\n{}\n{}\nThis checklist is already in your todo list. Keep it up to date as you work: \
+             mark a step [~] when you start it, [x] when it is done and [-] if you skip it, \
+             and keep the text of each step unchanged.",
+            self.text,
+            heading,
+            self.to_checklist()
+        )
+    }
+
+    /// Steps completed or skipped so far
+    pub fn done_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status.is_done())
+            .count()
+    }
+
+    /// Take step statuses from the agent's todo list. Items are matched to steps by
+    /// their text, or by position when the list has exactly one item per step.
+    /// Returns whether anything changed.
+    pub fn sync_from_todo(&mut self, todo: &str) -> bool {
+        let items = parse_steps(todo);
+        let by_position = items.len() == self.steps.len();
+        let mut changed = false;
+        for (index, step) in self.steps.iter_mut().enumerate() {
+            let title = normalize(&step.title);
+            let item = items
+                .iter()
+                .find(|item| normalize(&item.title) == title)
+                .or_else(|| items.get(index).filter(|_| by_position));
+            if let Some(item) = item {
+                if item.status != step.status {
+                    step.status = item.status;
+                    changed = true;
+                }
+            }
+        }
+
+        if self.status == PlanStatus::Executing && self.steps.iter().all(|s| s.status.is_done()) {
+            self.status = PlanStatus::Completed;
+            changed = true;
+        }
+        if changed {
+            self.updated_at = Utc::now();
+        }
+        changed
+    }
+}
+
+/// The plan stored on a session, if there is one
+pub async fn load_plan(session_id: &str) -> Result<Option<Plan>> {
+    let session = SessionManager::get_session(session_id, false).await?;
+    Ok(Plan::from_extension_data(&session.extension_data))
+}
+
+/// Store the plan on a session, replacing any earlier one
+pub async fn save_plan(session_id: &str, plan: &Plan) -> Result<()> {
+    let mut session = SessionManager::get_session(session_id, false).await?;
+    plan.to_extension_data(&mut session.extension_data)?;
+    SessionManager::update_session(session_id)
+        .extension_data(session.extension_data)
+        .apply()
+        .await
+}
+
+/// Mark the plan as executing and make its steps the session's todo list, where
+/// the agent tracks its progress
+pub async fn start_plan(session_id: &str, plan: &mut Plan) -> Result<()> {
+    plan.start();
+    let mut session = SessionManager::get_session(session_id, false).await?;
+    plan.to_extension_data(&mut session.extension_data)?;
+    TodoState::new(plan.to_checklist()).to_extension_data(&mut session.extension_data)?;
+    SessionManager::update_session(session_id)
+        .extension_data(session.extension_data)
+        .apply()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLANNER_RESPONSE: &str = "\
+The user wants a CLI flag for verbose output.
+
+1. **Add the flag** to `cli.rs`
+   - Use `clap`'s `ArgAction::Count`
+2. Thread it through to the logger
+  (use the output from Step 1)
+
+3) Write tests
+   * Cover `-v` and `-vv`
+
+Notes:
+- Keep the default quiet
+";
+
+    #[test]
+    fn test_parse_planner_response() {
+        let plan = Plan::from_text(PLANNER_RESPONSE).unwrap();
+        let titles: Vec<&str> = plan.steps.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "**Add the flag** to `cli.rs`",
+                "Thread it through to the logger",
+                "Write tests"
+            ]
+        );
+        assert_eq!(
+            plan.steps[0].details,
+            vec!["Use `clap`'s `ArgAction::Count`"]
+        );
+        assert_eq!(plan.steps[1].details, vec!["(use the output from Step 1)"]);
+        assert_eq!(plan.steps[2].details, vec!["Cover `-v` and `-vv`"]);
+        assert_eq!(plan.status, PlanStatus::Draft);
+
+        assert!(Plan::from_text("Which shell do you use?").is_none());
+    }
+
+    #[test]
+    fn test_parse_step_headings() {
+        let text = "\
+# Plan for Converting the CLI to a Web App
+
+## Step 1: Set up the React project
+1. Create the app
+2. Install dependencies
+   src/
+   └── components/
+## Step 2. Implement authentication
+1. Configure Okta
+
+## Step 3
+1. Write tests
+
+This plan covers the conversion.
+";
+        let plan = Plan::from_text(text).unwrap();
+        let titles: Vec<&str> = plan.steps.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Set up the React project",
+                "Implement authentication",
+                "Step 3"
+            ]
+        );
+        assert_eq!(
+            plan.steps[0].details,
+            vec![
+                "Create the app",
+                "Install dependencies",
+                "src/",
+                "└── components/"
+            ]
+        );
+        assert_eq!(plan.steps[2].details, vec!["Write tests"]);
+    }
+
+    #[test]
+    fn test_edit_steps_round_trips_checklist() {
+        let mut plan = Plan::from_text(PLANNER_RESPONSE).unwrap();
+        let checklist = plan.to_checklist();
+        assert!(checklist.starts_with("- [ ] **Add the flag** to `cli.rs`\n  - Use"));
+
+        let edited = format!("{}- [ ] Update the docs\n", checklist);
+        plan.edit_steps(&edited).unwrap();
+        assert!(plan.edited);
+        assert_eq!(plan.steps.len(), 4);
+        assert_eq!(plan.steps[2].details, vec!["Cover `-v` and `-vv`"]);
+        assert_eq!(plan.steps[3].title, "Update the docs");
+        assert!(plan.to_prompt().contains("The user revised the steps"));
+
+        assert!(plan.edit_steps("Nothing to do").is_err());
+        assert_eq!(plan.steps.len(), 4);
+    }
+
+    #[test]
+    fn test_sync_from_todo() {
+        let mut plan = Plan::from_text(PLANNER_RESPONSE).unwrap();
+        plan.start();
+
+        let todo = "- [x] **Add the flag** to `cli.rs`\n- [~] thread it through  to the logger\n- [ ] Write tests\n  - [ ] Cover -v\n";
+        assert!(plan.sync_from_todo(todo));
+        assert_eq!(plan.steps[0].status, PlanStepStatus::Completed);
+        assert_eq!(plan.steps[1].status, PlanStepStatus::InProgress);
+        assert_eq!(plan.done_steps(), 1);
+        assert!(!plan.sync_from_todo(todo));
+
+        // Reworded items still match by position
+        assert!(plan.sync_from_todo("- [x] Flag\n- [x] Logger\n- [-] Tests\n"));
+        assert_eq!(plan.done_steps(), 3);
+        assert_eq!(plan.status, PlanStatus::Completed);
+    }
+}
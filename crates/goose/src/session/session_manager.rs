@@ -3,6 +3,7 @@ use crate::conversation::message::Message;
 use crate::conversation::Conversation;
 use crate::providers::base::{Provider, MSG_COUNT_FOR_SESSION_NAME_GENERATION};
 use crate::recipe::Recipe;
+use crate::session::artifacts::ArtifactStore;
 use crate::session::extension_data::ExtensionData;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -18,7 +19,7 @@ use tokio::sync::OnceCell;
 use tracing::{info, warn};
 use utoipa::ToSchema;
 
-const CURRENT_SCHEMA_VERSION: i32 = 5;
+const CURRENT_SCHEMA_VERSION: i32 = 7;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -82,11 +83,18 @@ pub struct Session {
     pub accumulated_total_tokens: Option<i32>,
     pub accumulated_input_tokens: Option<i32>,
     pub accumulated_output_tokens: Option<i32>,
+    /// Estimated cost of every provider call in the session, priced at the model
+    /// that made it. None until a call is made with a model that has pricing data.
+    #[serde(default)]
+    pub accumulated_cost_usd: Option<f64>,
     pub schedule_id: Option<String>,
     pub recipe: Option<Recipe>,
     pub user_recipe_values: Option<HashMap<String, String>>,
     pub conversation: Option<Conversation>,
     pub message_count: usize,
+    /// User that owns the session when goosed serves multiple users
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 pub struct SessionUpdateBuilder {
@@ -102,9 +110,11 @@ pub struct SessionUpdateBuilder {
     accumulated_total_tokens: Option<Option<i32>>,
     accumulated_input_tokens: Option<Option<i32>>,
     accumulated_output_tokens: Option<Option<i32>>,
+    accumulated_cost_usd: Option<Option<f64>>,
     schedule_id: Option<Option<String>>,
     recipe: Option<Option<Recipe>>,
     user_recipe_values: Option<Option<HashMap<String, String>>>,
+    owner: Option<Option<String>>,
 }
 
 #[derive(Serialize, ToSchema, Debug)]
@@ -129,9 +139,11 @@ impl SessionUpdateBuilder {
             accumulated_total_tokens: None,
             accumulated_input_tokens: None,
             accumulated_output_tokens: None,
+            accumulated_cost_usd: None,
             schedule_id: None,
             recipe: None,
             user_recipe_values: None,
+            owner: None,
         }
     }
 
@@ -198,6 +210,11 @@ impl SessionUpdateBuilder {
         self
     }
 
+    pub fn accumulated_cost_usd(mut self, cost: Option<f64>) -> Self {
+        self.accumulated_cost_usd = Some(cost);
+        self
+    }
+
     pub fn schedule_id(mut self, schedule_id: Option<String>) -> Self {
         self.schedule_id = Some(schedule_id);
         self
@@ -216,6 +233,11 @@ impl SessionUpdateBuilder {
         self
     }
 
+    pub fn owner(mut self, owner: Option<String>) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
     pub async fn apply(self) -> Result<()> {
         SessionManager::apply_update(self).await
     }
@@ -276,8 +298,9 @@ impl SessionManager {
         Self::instance().await?.delete_session(id).await
     }
 
-    pub async fn get_insights() -> Result<SessionInsights> {
-        Self::instance().await?.get_insights().await
+    /// Totals across sessions owned by `owner`, or across every session when `owner` is None.
+    pub async fn get_insights(owner: Option<&str>) -> Result<SessionInsights> {
+        Self::instance().await?.get_insights(owner).await
     }
 
     pub async fn export_session(id: &str) -> Result<String> {
@@ -288,6 +311,27 @@ impl SessionManager {
         Self::instance().await?.import_session(json).await
     }
 
+    /// Create one session per transcript in an OpenAI chat-completions JSONL file.
+    pub async fn import_openai_jsonl(jsonl: &str, working_dir: PathBuf) -> Result<Vec<Session>> {
+        let conversations = crate::session::export::conversations_from_openai_jsonl(jsonl)?;
+        if conversations.is_empty() {
+            return Err(anyhow::anyhow!("Transcript contains no conversations"));
+        }
+
+        let mut sessions = Vec::with_capacity(conversations.len());
+        for (index, conversation) in conversations.iter().enumerate() {
+            let session = Self::create_session(
+                working_dir.clone(),
+                format!("Imported transcript {}", index + 1),
+                SessionType::User,
+            )
+            .await?;
+            Self::replace_conversation(&session.id, conversation).await?;
+            sessions.push(Self::get_session(&session.id, true).await?);
+        }
+        Ok(sessions)
+    }
+
     pub async fn maybe_update_name(id: &str, provider: Arc<dyn Provider>) -> Result<()> {
         let session = Self::get_session(id, true).await?;
 
@@ -368,11 +412,13 @@ impl Default for Session {
             accumulated_total_tokens: None,
             accumulated_input_tokens: None,
             accumulated_output_tokens: None,
+            accumulated_cost_usd: None,
             schedule_id: None,
             recipe: None,
             user_recipe_values: None,
             conversation: None,
             message_count: 0,
+            owner: None,
         }
     }
 }
@@ -411,6 +457,8 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for Session {
             .unwrap_or_else(|_| "user".to_string());
         let session_type = session_type_str.parse().unwrap_or_default();
 
+        let owner: Option<String> = row.try_get("owner").unwrap_or(None);
+
         Ok(Session {
             id: row.try_get("id")?,
             working_dir: PathBuf::from(row.try_get::<String, _>("working_dir")?),
@@ -427,11 +475,13 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for Session {
             accumulated_total_tokens: row.try_get("accumulated_total_tokens")?,
             accumulated_input_tokens: row.try_get("accumulated_input_tokens")?,
             accumulated_output_tokens: row.try_get("accumulated_output_tokens")?,
+            accumulated_cost_usd: row.try_get("accumulated_cost_usd")?,
             schedule_id: row.try_get("schedule_id")?,
             recipe,
             user_recipe_values,
             conversation: None,
             message_count: row.try_get("message_count").unwrap_or(0) as usize,
+            owner,
         })
     }
 }
@@ -516,9 +566,11 @@ impl SessionStorage {
                 accumulated_total_tokens INTEGER,
                 accumulated_input_tokens INTEGER,
                 accumulated_output_tokens INTEGER,
+                accumulated_cost_usd REAL,
                 schedule_id TEXT,
                 recipe_json TEXT,
-                user_recipe_values_json TEXT
+                user_recipe_values_json TEXT,
+                owner TEXT
             )
         "#,
         )
@@ -554,6 +606,9 @@ impl SessionStorage {
         sqlx::query("CREATE INDEX idx_sessions_type ON sessions(session_type)")
             .execute(&pool)
             .await?;
+        sqlx::query("CREATE INDEX idx_sessions_owner ON sessions(owner)")
+            .execute(&pool)
+            .await?;
 
         Ok(Self { pool })
     }
@@ -619,8 +674,8 @@ impl SessionStorage {
             id, name, user_set_name, session_type, working_dir, created_at, updated_at, extension_data,
             total_tokens, input_tokens, output_tokens,
             accumulated_total_tokens, accumulated_input_tokens, accumulated_output_tokens,
-            schedule_id, recipe_json, user_recipe_values_json
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            accumulated_cost_usd, schedule_id, recipe_json, user_recipe_values_json, owner
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
         )
             .bind(&session.id)
@@ -637,9 +692,11 @@ impl SessionStorage {
             .bind(session.accumulated_total_tokens)
             .bind(session.accumulated_input_tokens)
             .bind(session.accumulated_output_tokens)
+            .bind(session.accumulated_cost_usd)
             .bind(&session.schedule_id)
             .bind(recipe_json)
             .bind(user_recipe_values_json)
+            .bind(&session.owner)
             .execute(&self.pool)
             .await?;
 
@@ -764,6 +821,28 @@ impl SessionStorage {
                     .execute(&self.pool)
                     .await?;
             }
+            6 => {
+                sqlx::query(
+                    r#"
+                    ALTER TABLE sessions ADD COLUMN owner TEXT
+                "#,
+                )
+                .execute(&self.pool)
+                .await?;
+
+                sqlx::query("CREATE INDEX idx_sessions_owner ON sessions(owner)")
+                    .execute(&self.pool)
+                    .await?;
+            }
+            7 => {
+                sqlx::query(
+                    r#"
+                    ALTER TABLE sessions ADD COLUMN accumulated_cost_usd REAL
+                "#,
+                )
+                .execute(&self.pool)
+                .await?;
+            }
             _ => {
                 anyhow::bail!("Unknown migration version: {}", version);
             }
@@ -812,7 +891,7 @@ impl SessionStorage {
         SELECT id, working_dir, name, description, user_set_name, session_type, created_at, updated_at, extension_data,
                total_tokens, input_tokens, output_tokens,
                accumulated_total_tokens, accumulated_input_tokens, accumulated_output_tokens,
-               schedule_id, recipe_json, user_recipe_values_json
+               accumulated_cost_usd, schedule_id, recipe_json, user_recipe_values_json, owner
         FROM sessions
         WHERE id = ?
     "#,
@@ -869,9 +948,11 @@ impl SessionStorage {
             builder.accumulated_output_tokens,
             "accumulated_output_tokens"
         );
+        add_update!(builder.accumulated_cost_usd, "accumulated_cost_usd");
         add_update!(builder.schedule_id, "schedule_id");
         add_update!(builder.recipe, "recipe_json");
         add_update!(builder.user_recipe_values, "user_recipe_values_json");
+        add_update!(builder.owner, "owner");
 
         if updates.is_empty() {
             return Ok(());
@@ -915,6 +996,9 @@ impl SessionStorage {
         if let Some(aot) = builder.accumulated_output_tokens {
             q = q.bind(aot);
         }
+        if let Some(cost) = builder.accumulated_cost_usd {
+            q = q.bind(cost);
+        }
         if let Some(sid) = builder.schedule_id {
             q = q.bind(sid);
         }
@@ -928,6 +1012,9 @@ impl SessionStorage {
                 .transpose()?;
             q = q.bind(user_recipe_values_json);
         }
+        if let Some(owner) = builder.owner {
+            q = q.bind(owner);
+        }
 
         q = q.bind(&builder.session_id);
         q.execute(&self.pool).await?;
@@ -1032,7 +1119,7 @@ impl SessionStorage {
         SELECT s.id, s.working_dir, s.name, s.description, s.user_set_name, s.session_type, s.created_at, s.updated_at, s.extension_data,
                s.total_tokens, s.input_tokens, s.output_tokens,
                s.accumulated_total_tokens, s.accumulated_input_tokens, s.accumulated_output_tokens,
-               s.schedule_id, s.recipe_json, s.user_recipe_values_json,
+               s.accumulated_cost_usd, s.schedule_id, s.recipe_json, s.user_recipe_values_json, s.owner,
                COUNT(m.id) as message_count
         FROM sessions s
         INNER JOIN messages m ON s.id = m.session_id
@@ -1067,17 +1154,26 @@ impl SessionStorage {
             .execute(&self.pool)
             .await?;
 
+        if let Err(e) = ArtifactStore::default().remove_session(session_id) {
+            warn!(
+                "Failed to remove artifacts for session {}: {}",
+                session_id, e
+            );
+        }
+
         Ok(())
     }
 
-    async fn get_insights(&self) -> Result<SessionInsights> {
+    async fn get_insights(&self, owner: Option<&str>) -> Result<SessionInsights> {
         let row = sqlx::query_as::<_, (i64, Option<i64>)>(
             r#"
             SELECT COUNT(*) as total_sessions,
                    COALESCE(SUM(COALESCE(accumulated_total_tokens, total_tokens, 0)), 0) as total_tokens
             FROM sessions
+            WHERE ?1 IS NULL OR owner = ?1
             "#,
         )
+            .bind(owner)
             .fetch_one(&self.pool)
             .await?;
 
@@ -1111,6 +1207,7 @@ impl SessionStorage {
             .accumulated_total_tokens(import.accumulated_total_tokens)
             .accumulated_input_tokens(import.accumulated_input_tokens)
             .accumulated_output_tokens(import.accumulated_output_tokens)
+            .accumulated_cost_usd(import.accumulated_cost_usd)
             .schedule_id(import.schedule_id)
             .recipe(import.recipe)
             .user_recipe_values(import.user_recipe_values);
@@ -1247,7 +1344,7 @@ mod tests {
             assert!(session.name.starts_with("Updated session"));
         }
 
-        let insights = storage.get_insights().await.unwrap();
+        let insights = storage.get_insights(None).await.unwrap();
         assert_eq!(insights.total_sessions, NUM_CONCURRENT_SESSIONS as usize);
         let expected_tokens = 100 * NUM_CONCURRENT_SESSIONS * (NUM_CONCURRENT_SESSIONS - 1) / 2;
         assert_eq!(insights.total_tokens, expected_tokens as i64);
@@ -1333,6 +1430,48 @@ mod tests {
         assert_eq!(conversation.messages()[1].role, Role::Assistant);
     }
 
+    #[tokio::test]
+    async fn test_session_owner_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_owner.db");
+        let storage = Arc::new(SessionStorage::create(&db_path).await.unwrap());
+
+        let session = storage
+            .create_session(
+                PathBuf::from("/tmp/test"),
+                "Owned session".to_string(),
+                SessionType::User,
+            )
+            .await
+            .unwrap();
+        assert_eq!(session.owner, None);
+
+        storage
+            .apply_update(
+                SessionUpdateBuilder::new(session.id.clone()).owner(Some("alice".to_string())),
+            )
+            .await
+            .unwrap();
+
+        let loaded = storage.get_session(&session.id, false).await.unwrap();
+        assert_eq!(loaded.owner.as_deref(), Some("alice"));
+
+        storage
+            .create_session(
+                PathBuf::from("/tmp/test"),
+                "Unowned session".to_string(),
+                SessionType::User,
+            )
+            .await
+            .unwrap();
+        let alice = storage.get_insights(Some("alice")).await.unwrap();
+        assert_eq!(alice.total_sessions, 1);
+        let bob = storage.get_insights(Some("bob")).await.unwrap();
+        assert_eq!(bob.total_sessions, 0);
+        let all = storage.get_insights(None).await.unwrap();
+        assert_eq!(all.total_sessions, 2);
+    }
+
     #[tokio::test]
     async fn test_import_session_with_description_field() {
         const OLD_FORMAT_JSON: &str = r#"{
@@ -0,0 +1,280 @@
+//! Slash commands defined as prompt files.
+//!
+//! A command is a markdown file, optionally starting with a YAML front matter block,
+//! or a YAML file with a `prompt` field, in a project's `.goose/commands` or in
+//! `commands` under the goose config dir. The file name is the command name, so
+//! `.goose/commands/review.md` is run as `/review <args>`.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+use crate::agents::extension::ExtensionConfig;
+use crate::config::paths::Paths;
+use crate::config::project::find_project_dir;
+use crate::prompt_template::render_inline_once;
+use crate::recipe::Settings;
+
+pub const COMMANDS_DIR: &str = "commands";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSource {
+    Project,
+    User,
+}
+
+/// What a command file may set, besides the prompt body of a markdown file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommandDefinition {
+    description: Option<String>,
+    prompt: Option<String>,
+    extensions: Option<Vec<ExtensionConfig>>,
+    settings: Option<Settings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommand {
+    /// Name without the leading slash
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Prompt template; `{{args}}` is replaced with what follows the command
+    pub prompt: String,
+    /// Extensions to enable before running the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<ExtensionConfig>>,
+    /// Provider, model and temperature to use while running the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<Settings>,
+    pub source: CommandSource,
+    pub path: PathBuf,
+}
+
+impl SlashCommand {
+    pub fn load(path: &Path, source: CommandSource) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| is_valid_name(stem))
+            .ok_or_else(|| anyhow!("Command names may only use letters, digits, '-' and '_'"))?
+            .to_string();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let definition = if matches!(extension, Some("yaml") | Some("yml")) {
+            let definition: CommandDefinition = serde_yaml::from_str(&content)?;
+            if definition.prompt.is_none() {
+                return Err(anyhow!("A YAML command needs a 'prompt'"));
+            }
+            definition
+        } else {
+            let (front_matter, body) = split_front_matter(&content);
+            let mut definition: CommandDefinition = match front_matter {
+                Some(yaml) => serde_yaml::from_str(yaml)?,
+                None => CommandDefinition::default(),
+            };
+            definition.prompt = Some(body.trim().to_string());
+            definition
+        };
+
+        Ok(Self {
+            name,
+            description: definition.description,
+            prompt: definition.prompt.unwrap_or_default(),
+            extensions: definition.extensions,
+            settings: definition.settings,
+            source,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// The prompt with `{{args}}` filled in. Arguments given to a command that has
+    /// no placeholder are added after the prompt.
+    pub fn render(&self, args: &str) -> Result<String> {
+        let prompt = render_inline_once(&self.prompt, &json!({ "args": args }))?;
+        if args.trim().is_empty() || self.uses_args()? {
+            Ok(prompt)
+        } else {
+            Ok(format!("{}\n\n{}", prompt, args.trim()))
+        }
+    }
+
+    /// Whether the template reads the `args` variable, rather than just mentioning the word
+    pub fn uses_args(&self) -> Result<bool> {
+        let mut env = minijinja::Environment::new();
+        env.add_template(&self.name, &self.prompt)?;
+        Ok(env
+            .get_template(&self.name)?
+            .undeclared_variables(false)
+            .contains("args"))
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// A leading block between `---` lines, and the rest of the file
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+fn load_dir(dir: &Path, source: CommandSource) -> Vec<SlashCommand> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("md") | Some("yaml") | Some("yml")
+                )
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| match SlashCommand::load(&path, source) {
+            Ok(command) => Some(command),
+            Err(e) => {
+                tracing::warn!("Ignoring slash command {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Commands in `project_dir` and then `user_dir`. When both define a name, the
+/// project's command is used.
+pub fn load_commands(project_dir: Option<&Path>, user_dir: &Path) -> Vec<SlashCommand> {
+    let mut commands: Vec<SlashCommand> = Vec::new();
+    let dirs = project_dir
+        .map(|dir| (dir, CommandSource::Project))
+        .into_iter()
+        .chain([(user_dir, CommandSource::User)]);
+    for (dir, source) in dirs {
+        for command in load_dir(dir, source) {
+            if !commands.iter().any(|c| c.name == command.name) {
+                commands.push(command);
+            }
+        }
+    }
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+/// Commands available to a session working in `working_dir`
+pub fn discover_commands(working_dir: &Path) -> Vec<SlashCommand> {
+    let project_dir = find_project_dir(working_dir, COMMANDS_DIR);
+    load_commands(
+        project_dir.as_deref(),
+        &Paths::config_dir().join(COMMANDS_DIR),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_load_commands() {
+        let project = tempfile::tempdir().unwrap();
+        let user = tempfile::tempdir().unwrap();
+        fs::write(
+            project.path().join("review.md"),
+            "---\ndescription: Review a file\nsettings:\n  temperature: 0.1\n---\nReview {{args}} carefully.\n",
+        )
+        .unwrap();
+        fs::write(user.path().join("review.md"), "Shadowed").unwrap();
+        fs::write(
+            user.path().join("standup.yaml"),
+            "description: Write a standup\nprompt: Summarize what I did today\nextensions:\n  - type: builtin\n    name: developer\n",
+        )
+        .unwrap();
+        fs::write(user.path().join("bad name.md"), "Ignored").unwrap();
+        fs::write(user.path().join("broken.yaml"), "description: no prompt").unwrap();
+        fs::write(user.path().join("notes.txt"), "Not a command").unwrap();
+
+        let commands = load_commands(Some(project.path()), user.path());
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["review", "standup"]);
+
+        let review = &commands[0];
+        assert_eq!(review.source, CommandSource::Project);
+        assert_eq!(review.description.as_deref(), Some("Review a file"));
+        assert_eq!(review.settings.as_ref().unwrap().temperature, Some(0.1));
+        assert_eq!(
+            review.render("src/main.rs").unwrap(),
+            "Review src/main.rs carefully."
+        );
+
+        let standup = &commands[1];
+        assert_eq!(standup.source, CommandSource::User);
+        assert_eq!(standup.extensions.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            standup.render("focus on reviews").unwrap(),
+            "Summarize what I did today\n\nfocus on reviews"
+        );
+    }
+
+    #[test]
+    fn test_render_appends_args_without_placeholder() {
+        let command = SlashCommand {
+            name: "lint".to_string(),
+            description: None,
+            prompt: "Fix the lint errors, keeping the args of public functions unchanged."
+                .to_string(),
+            extensions: None,
+            settings: None,
+            source: CommandSource::Project,
+            path: PathBuf::from("lint.md"),
+        };
+        assert_eq!(
+            command.render("in src/lib.rs").unwrap(),
+            "Fix the lint errors, keeping the args of public functions unchanged.\n\nin src/lib.rs"
+        );
+
+        let command = SlashCommand {
+            prompt: "{% if args %}Fix {{ args }}{% else %}Fix everything{% endif %}".to_string(),
+            ..command
+        };
+        assert_eq!(command.render("src/lib.rs").unwrap(), "Fix src/lib.rs");
+    }
+
+    #[test]
+    fn test_split_front_matter() {
+        assert_eq!(
+            split_front_matter("---\na: 1\n---\nbody"),
+            (Some("a: 1\n"), "body")
+        );
+        assert_eq!(split_front_matter("just a prompt"), (None, "just a prompt"));
+        assert_eq!(
+            split_front_matter("---\nunterminated"),
+            (None, "---\nunterminated")
+        );
+    }
+}
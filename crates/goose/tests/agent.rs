@@ -370,6 +370,7 @@ mod tests {
                 schedule_id: None,
                 max_turns: None,
                 retry_config: None,
+                budget: None,
             };
 
             let reply_stream = agent.reply(user_message, session_config, None).await?;
@@ -394,6 +395,7 @@ mod tests {
                     }
                     Ok(AgentEvent::McpNotification(_)) => {}
                     Ok(AgentEvent::ModelChange { .. }) => {}
+                    Ok(AgentEvent::BudgetExceeded(_)) => {}
                     Ok(AgentEvent::HistoryReplaced(_updated_conversation)) => {
                         // We should update the conversation here, but we're not reading it
                     }
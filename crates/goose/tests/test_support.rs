@@ -345,6 +345,8 @@ impl ScheduleToolTestBuilder {
             paused: false,
             current_session_id: None,
             process_start_time: None,
+            owner: None,
+            budget: None,
         };
         {
             let mut jobs = self.scheduler.jobs.lock().await;
@@ -391,11 +393,13 @@ pub fn create_test_session_metadata(message_count: usize, working_dir: &str) ->
         accumulated_total_tokens: Some(100),
         accumulated_input_tokens: Some(50),
         accumulated_output_tokens: Some(50),
+        accumulated_cost_usd: None,
         extension_data: Default::default(),
         updated_at: Default::default(),
         conversation: None,
         message_count,
         user_recipe_values: None,
         session_type: Default::default(),
+        owner: None,
     }
 }
@@ -9,6 +9,33 @@ export type AddExtensionRequest = {
     session_id: string;
 };
 
+export type AgentPoolStats = {
+    /**
+     * Number of agents currently held in memory
+     */
+    active_agents: number;
+    /**
+     * Maximum number of agents held before the least recently used one is evicted
+     */
+    capacity: number;
+    /**
+     * Sessions whose agent was evicted and will be rehydrated on next use
+     */
+    evicted_sessions: number;
+    /**
+     * Idle time after which an unused agent is evicted, if enabled
+     */
+    idle_timeout_secs?: number | null;
+    /**
+     * Agents evicted since startup, by capacity or idle time
+     */
+    total_evictions: number;
+    /**
+     * Evicted sessions rehydrated since startup
+     */
+    total_rehydrations: number;
+};
+
 export type Annotations = {
     audience?: Array<Role>;
     lastModified?: string;
@@ -25,6 +52,22 @@ export type AuthorRequest = {
     metadata?: string | null;
 };
 
+/**
+ * Limits on what a session may spend. Cost and tokens are counted over the whole
+ * session; duration is wall-clock time for a single reply, including provider
+ * calls and tools that are still running when it runs out.
+ */
+export type Budget = {
+    max_cost_usd?: number | null;
+    max_duration_seconds?: number | null;
+    max_input_tokens?: number | null;
+    max_output_tokens?: number | null;
+    /**
+     * Fraction of any limit at which to warn (default 0.8)
+     */
+    warning_threshold?: number | null;
+};
+
 export type ChatRequest = {
     messages: Array<Message>;
     recipe_name?: string | null;
@@ -32,6 +75,22 @@ export type ChatRequest = {
     session_id: string;
 };
 
+/**
+ * What a client needs to offer a command. The prompt, extensions, settings and
+ * file path stay on the server, since extensions can carry environment values.
+ */
+export type CommandSummary = {
+    description?: string | null;
+    /**
+     * Name without the leading slash
+     */
+    name: string;
+    /**
+     * Whether the command uses what follows it as `{{args}}`
+     */
+    takes_args: boolean;
+};
+
 /**
  * Configuration key metadata for provider setup
  */
@@ -85,6 +144,7 @@ export type CreateRecipeResponse = {
 };
 
 export type CreateScheduleRequest = {
+    budget?: Budget | null;
     cron: string;
     execution_mode?: string | null;
     id: string;
@@ -301,7 +361,12 @@ export type ImageContent = {
 };
 
 export type ImportSessionRequest = {
+    format?: SessionExportFormat;
     json: string;
+    /**
+     * Working directory for sessions imported from a transcript, which carries none
+     */
+    workingDir?: string | null;
 };
 
 export type InspectJobResponse = {
@@ -318,6 +383,18 @@ export type KillJobResponse = {
     message: string;
 };
 
+export type ListCommandsQuery = {
+    /**
+     * Include the commands of this session's project; otherwise only user commands
+     * and those of the server's working directory are listed
+     */
+    session_id?: string | null;
+};
+
+export type ListCommandsResponse = {
+    commands: Array<CommandSummary>;
+};
+
 export type ListRecipeResponse = {
     recipe_manifest_responses: Array<RecipeManifestResponse>;
 };
@@ -456,6 +533,78 @@ export type PermissionConfirmationRequest = {
  */
 export type PermissionLevel = 'always_allow' | 'ask_before' | 'never_allow';
 
+/**
+ * Allows calls to a tool whose string argument matches a regex, e.g. only the
+ * `developer__shell` commands that start with `cargo test`.
+ */
+export type PermissionRule = {
+    argument: string;
+    pattern: string;
+    tool: string;
+};
+
+/**
+ * A rule proposed from the approvals in the permission history
+ */
+export type PermissionSuggestion = {
+    /**
+     * How many recorded approvals the suggestion covers
+     */
+    approvals: number;
+    /**
+     * Some of the approved argument values it covers
+     */
+    examples: Array<string>;
+    rule?: PermissionRule | null;
+    tool: string;
+};
+
+export type PermissionSuggestionsQuery = {
+    /**
+     * Approvals of similar calls needed before a rule is suggested
+     */
+    min_approvals?: number | null;
+};
+
+export type PermissionSuggestionsResponse = {
+    suggestions: Array<PermissionSuggestion>;
+};
+
+/**
+ * A plan made with `/plan`, stored on the session so it can be reviewed and
+ * edited before it runs and tracked while it does
+ */
+export type Plan = {
+    created_at: string;
+    /**
+     * Whether the user changed the steps after the planner wrote them
+     */
+    edited?: boolean;
+    status: PlanStatus;
+    steps: Array<PlanStep>;
+    /**
+     * The planner's response, including the context the executor needs
+     */
+    text: string;
+    updated_at: string;
+};
+
+/**
+ * A draft plan waits for the user to review, edit or run it
+ */
+export type PlanStatus = 'draft' | 'executing' | 'completed';
+
+export type PlanStep = {
+    /**
+     * Sub-items and notes written under the step
+     */
+    details?: Array<string>;
+    status: PlanStepStatus;
+    title: string;
+};
+
+export type PlanStepStatus = 'pending' | 'in_progress' | 'completed' | 'skipped';
+
 export type PrincipalType = 'Extension' | 'Tool';
 
 export type ProviderDetails = {
@@ -664,17 +813,27 @@ export type ScanRecipeResponse = {
 };
 
 export type ScheduledJob = {
+    budget?: Budget | null;
     cron: string;
     current_session_id?: string | null;
     currently_running?: boolean;
     id: string;
     last_run?: string | null;
+    /**
+     * User that created the schedule when goosed serves multiple users
+     */
+    owner?: string | null;
     paused?: boolean;
     process_start_time?: string | null;
     source: string;
 };
 
 export type Session = {
+    /**
+     * Estimated cost of every provider call in the session, priced at the model
+     * that made it. None until a call is made with a model that has pricing data.
+     */
+    accumulated_cost_usd?: number | null;
     accumulated_input_tokens?: number | null;
     accumulated_output_tokens?: number | null;
     accumulated_total_tokens?: number | null;
@@ -686,6 +845,10 @@ export type Session = {
     message_count: number;
     name: string;
     output_tokens?: number | null;
+    /**
+     * User that owns the session when goosed serves multiple users
+     */
+    owner?: string | null;
     recipe?: Recipe | null;
     schedule_id?: string | null;
     session_type?: SessionType;
@@ -713,6 +876,8 @@ export type SessionDisplayInfo = {
     workingDir: string;
 };
 
+export type SessionExportFormat = 'json' | 'html' | 'jsonl';
+
 export type SessionInsights = {
     totalSessions: number;
     totalTokens: number;
@@ -732,6 +897,7 @@ export type SessionsQuery = {
 };
 
 export type Settings = {
+    budget?: Budget | null;
     goose_model?: string | null;
     goose_provider?: string | null;
     temperature?: number | null;
@@ -749,6 +915,14 @@ export type StartAgentRequest = {
     working_dir: string;
 };
 
+export type StatusResponse = {
+    agentPool: AgentPoolStats;
+    /**
+     * Always "ok" when the server is up
+     */
+    status: string;
+};
+
 export type SubRecipe = {
     description?: string | null;
     name: string;
@@ -847,6 +1021,33 @@ export type ToolPermission = {
     tool_name: string;
 };
 
+export type ToolPermissionRecord = {
+    allowed: boolean;
+    context_hash: string;
+    expiry?: number | null;
+    readable_context?: string | null;
+    timestamp: number;
+    tool_name: string;
+};
+
+/**
+ * Everything goose has stored about one tool's permissions
+ */
+export type ToolPermissionSummary = {
+    allow_rules: Array<PermissionRule>;
+    /**
+     * Unexpired decisions recorded when goose asked, oldest first
+     */
+    decisions: Array<ToolPermissionRecord>;
+    smart_approve_level?: PermissionLevel | null;
+    tool: string;
+    user_level?: PermissionLevel | null;
+};
+
+export type ToolPermissionsResponse = {
+    permissions: Array<ToolPermissionSummary>;
+};
+
 export type ToolRequest = {
     id: string;
     toolCall: {
@@ -1173,6 +1374,47 @@ export type UpdateRouterToolSelectorResponses = {
 
 export type UpdateRouterToolSelectorResponse = UpdateRouterToolSelectorResponses[keyof UpdateRouterToolSelectorResponses];
 
+export type ListCommandsData = {
+    body?: never;
+    path?: never;
+    query?: {
+        /**
+         * Include the commands of this session's project; otherwise only user commands
+         * and those of the server's working directory are listed
+         */
+        session_id?: string | null;
+    };
+    url: '/commands';
+};
+
+export type ListCommandsErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Session belongs to another user
+     */
+    403: unknown;
+    /**
+     * Session not found
+     */
+    404: unknown;
+    /**
+     * Internal server error
+     */
+    500: unknown;
+};
+
+export type ListCommandsResponses = {
+    /**
+     * Slash commands defined in prompt files
+     */
+    200: ListCommandsResponse;
+};
+
+export type ListCommandsResponse2 = ListCommandsResponses[keyof ListCommandsResponses];
+
 export type ReadAllConfigData = {
     body?: never;
     path?: never;
@@ -1636,6 +1878,10 @@ export type ConfirmPermissionErrors = {
      * Unauthorized - invalid secret key
      */
     401: unknown;
+    /**
+     * Only admins can always allow a tool
+     */
+    403: unknown;
     /**
      * Internal server error
      */
@@ -1659,6 +1905,10 @@ export type DiagnosticsData = {
 };
 
 export type DiagnosticsErrors = {
+    /**
+     * Only the admin may download diagnostics
+     */
+    403: unknown;
     /**
      * Failed to generate diagnostics
      */
@@ -1700,6 +1950,140 @@ export type StartTetrateSetupResponses = {
 
 export type StartTetrateSetupResponse = StartTetrateSetupResponses[keyof StartTetrateSetupResponses];
 
+export type ListToolPermissionsData = {
+    body?: never;
+    path?: never;
+    query?: never;
+    url: '/permissions';
+};
+
+export type ListToolPermissionsErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Only the admin may read stored permissions
+     */
+    403: unknown;
+    /**
+     * Internal server error
+     */
+    500: unknown;
+};
+
+export type ListToolPermissionsResponses = {
+    /**
+     * Stored permission levels, allow rules and recorded decisions for each tool
+     */
+    200: ToolPermissionsResponse;
+};
+
+export type ListToolPermissionsResponse = ListToolPermissionsResponses[keyof ListToolPermissionsResponses];
+
+export type GetPermissionSuggestionsData = {
+    body?: never;
+    path?: never;
+    query?: {
+        /**
+         * Approvals of similar calls needed before a rule is suggested
+         */
+        min_approvals?: number | null;
+    };
+    url: '/permissions/suggestions';
+};
+
+export type GetPermissionSuggestionsErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Only the admin may read stored permissions
+     */
+    403: unknown;
+    /**
+     * Internal server error
+     */
+    500: unknown;
+};
+
+export type GetPermissionSuggestionsResponses = {
+    /**
+     * Allow rules learned from approved tool calls
+     */
+    200: PermissionSuggestionsResponse;
+};
+
+export type GetPermissionSuggestionsResponse = GetPermissionSuggestionsResponses[keyof GetPermissionSuggestionsResponses];
+
+export type AcceptPermissionSuggestionData = {
+    body: PermissionSuggestion;
+    path?: never;
+    query?: never;
+    url: '/permissions/suggestions/accept';
+};
+
+export type AcceptPermissionSuggestionErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Only the admin may change permissions
+     */
+    403: unknown;
+};
+
+export type AcceptPermissionSuggestionResponses = {
+    /**
+     * Suggestion saved as a user permission
+     */
+    200: string;
+};
+
+export type AcceptPermissionSuggestionResponse = AcceptPermissionSuggestionResponses[keyof AcceptPermissionSuggestionResponses];
+
+export type RevokeToolPermissionData = {
+    body?: never;
+    path: {
+        /**
+         * Name of the tool, e.g. developer__shell
+         */
+        tool: string;
+    };
+    query?: never;
+    url: '/permissions/{tool}';
+};
+
+export type RevokeToolPermissionErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Only the admin may change permissions
+     */
+    403: unknown;
+    /**
+     * No permissions stored for the tool
+     */
+    404: unknown;
+    /**
+     * Internal server error
+     */
+    500: unknown;
+};
+
+export type RevokeToolPermissionResponses = {
+    /**
+     * Permission level, allow rules and recorded decisions removed
+     */
+    200: string;
+};
+
+export type RevokeToolPermissionResponse = RevokeToolPermissionResponses[keyof RevokeToolPermissionResponses];
+
 export type CreateRecipeData = {
     body: CreateRecipeRequest;
     path?: never;
@@ -2276,7 +2660,7 @@ export type ImportSessionData = {
 
 export type ImportSessionErrors = {
     /**
-     * Bad request - Invalid JSON
+     * Bad request - Invalid JSON or transcript
      */
     400: unknown;
     /**
@@ -2403,11 +2787,20 @@ export type ExportSessionData = {
          */
         session_id: string;
     };
-    query?: never;
+    query?: {
+        /**
+         * Export format, json by default
+         */
+        format?: SessionExportFormat | null;
+    };
     url: '/sessions/{session_id}/export';
 };
 
 export type ExportSessionErrors = {
+    /**
+     * Session cannot be exported in the requested format
+     */
+    400: unknown;
     /**
      * Unauthorized - Invalid or missing API key
      */
@@ -2431,6 +2824,46 @@ export type ExportSessionResponses = {
 
 export type ExportSessionResponse = ExportSessionResponses[keyof ExportSessionResponses];
 
+export type GetSessionPlanData = {
+    body?: never;
+    path: {
+        /**
+         * Unique identifier for the session
+         */
+        session_id: string;
+    };
+    query?: never;
+    url: '/sessions/{session_id}/plan';
+};
+
+export type GetSessionPlanErrors = {
+    /**
+     * Unauthorized - Invalid or missing API key
+     */
+    401: unknown;
+    /**
+     * Session belongs to another user
+     */
+    403: unknown;
+    /**
+     * Session not found or has no plan
+     */
+    404: unknown;
+    /**
+     * Internal server error
+     */
+    500: unknown;
+};
+
+export type GetSessionPlanResponses = {
+    /**
+     * Plan made with /plan in the session, with the status of each step
+     */
+    200: Plan;
+};
+
+export type GetSessionPlanResponse = GetSessionPlanResponses[keyof GetSessionPlanResponses];
+
 export type UpdateSessionNameData = {
     body: UpdateSessionNameRequest;
     path: {
@@ -2516,9 +2949,9 @@ export type StatusData = {
 
 export type StatusResponses = {
     /**
-     * ok
+     * Server is up
      */
-    200: string;
+    200: StatusResponse;
 };
 
-export type StatusResponse = StatusResponses[keyof StatusResponses];
+export type StatusResponse2 = StatusResponses[keyof StatusResponses];